@@ -472,6 +472,18 @@ impl sc_cli::CliConfiguration for NormalizedRunCmd {
 		Ok(self.base.rpc_params.rpc_rate_limit_trust_proxy_headers)
 	}
 
+	fn rpc_rate_limit_method_costs(
+		&self,
+	) -> sc_cli::Result<std::collections::HashMap<String, std::num::NonZeroU32>> {
+		Ok(self.base.rpc_params.rpc_rate_limit_method_costs())
+	}
+
+	fn rpc_rate_limit_api_keys(
+		&self,
+	) -> sc_cli::Result<std::collections::HashMap<String, sc_service::config::RateLimitTier>> {
+		self.base.rpc_params.rpc_rate_limit_api_keys()
+	}
+
 	fn rpc_max_request_size(&self) -> sc_cli::Result<u32> {
 		self.base.rpc_max_request_size()
 	}
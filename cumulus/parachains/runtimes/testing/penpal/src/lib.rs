@@ -153,6 +153,7 @@ pub type TxExtension = (
 	frame_system::CheckGenesis<Runtime>,
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
+	pallet_revive::evm::ApplyAuthorizations<Runtime>,
 	frame_system::CheckWeight<Runtime>,
 	pallet_asset_tx_payment::ChargeAssetTxPayment<Runtime>,
 	frame_system::WeightReclaim<Runtime>,
@@ -166,7 +167,11 @@ impl EthExtra for EthExtraImpl {
 	type Config = Runtime;
 	type Extension = TxExtension;
 
-	fn get_eth_extension(nonce: u32, tip: Balance) -> Self::Extension {
+	fn get_eth_extension(
+		nonce: u32,
+		tip: Balance,
+		authorization_list: pallet_revive::evm::AuthorizationList,
+	) -> Self::Extension {
 		(
 			frame_system::AuthorizeCall::<Runtime>::new(),
 			frame_system::CheckNonZeroSender::<Runtime>::new(),
@@ -175,6 +180,7 @@ impl EthExtra for EthExtraImpl {
 			frame_system::CheckGenesis::<Runtime>::new(),
 			frame_system::CheckEra::<Runtime>::from(generic::Era::Immortal),
 			frame_system::CheckNonce::<Runtime>::from(nonce),
+			pallet_revive::evm::ApplyAuthorizations::<Runtime>::from(authorization_list),
 			frame_system::CheckWeight::<Runtime>::new(),
 			pallet_asset_tx_payment::ChargeAssetTxPayment::<Runtime>::from(tip, None),
 			frame_system::WeightReclaim::<Runtime>::new(),
@@ -291,10 +297,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: alloc::borrow::Cow::Borrowed("penpal-parachain"),
 	impl_name: alloc::borrow::Cow::Borrowed("penpal-parachain"),
 	authoring_version: 1,
-	spec_version: 1,
+	spec_version: 3,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	system_version: 1,
 };
 
@@ -859,6 +865,8 @@ impl pallet_revive::Config for Runtime {
 	type NativeToEthRatio = ConstU32<1_000_000>; // 10^(18 - 12) Eth is 10^18, Native is 10^12.
 	type EthGasEncoder = ();
 	type FindAuthor = <Runtime as pallet_authorship::Config>::FindAuthor;
+	type EnableP256Verify = ConstBool<true>;
+	type EnableBls12381 = ConstBool<true>;
 }
 
 impl pallet_sudo::Config for Runtime {
@@ -175,6 +175,7 @@ impl<NodeSpec: NodeSpecT> ManualSealNode<NodeSpec> {
 			block_import: client.clone(),
 			env: proposer,
 			client: client.clone(),
+			backend: Some(backend.clone()),
 			pool: transaction_pool.clone(),
 			select_chain,
 			commands_stream: Box::pin(manual_seal_stream),
@@ -881,6 +881,7 @@ pub fn node_config(
 		warm_up_trie_cache: None,
 		state_pruning: Some(PruningMode::ArchiveAll),
 		blocks_pruning: BlocksPruning::KeepAll,
+		extrinsic_index: false,
 		chain_spec: spec,
 		executor: ExecutorConfiguration {
 			wasm_method: WasmExecutionMethod::Compiled {
@@ -1039,9 +1040,12 @@ pub fn run_relay_chain_validator_node(
 			max_subscriptions_per_connection: config.rpc.max_subs_per_conn,
 			max_buffer_capacity_per_connection: config.rpc.message_buffer_capacity,
 			rpc_methods: config.rpc.methods,
+			method_filter: Default::default(),
 			rate_limit: config.rpc.rate_limit,
 			rate_limit_trust_proxy_headers: config.rpc.rate_limit_trust_proxy_headers,
 			rate_limit_whitelisted_ips: config.rpc.rate_limit_whitelisted_ips.clone(),
+			rate_limit_method_costs: config.rpc.rate_limit_method_costs.clone(),
+			rate_limit_api_keys: config.rpc.rate_limit_api_keys.clone(),
 			retry_random_port: true,
 			is_optional: false,
 		}]);
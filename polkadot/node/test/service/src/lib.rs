@@ -206,6 +206,7 @@ pub fn node_config(
 		warm_up_trie_cache: None,
 		state_pruning: Default::default(),
 		blocks_pruning: BlocksPruning::KeepFinalized,
+		extrinsic_index: false,
 		chain_spec: Box::new(spec),
 		executor: ExecutorConfiguration {
 			wasm_method: WasmExecutionMethod::Compiled {
@@ -229,6 +230,8 @@ pub fn node_config(
 			rate_limit: None,
 			rate_limit_whitelisted_ips: Default::default(),
 			rate_limit_trust_proxy_headers: Default::default(),
+			rate_limit_method_costs: Default::default(),
+			rate_limit_api_keys: Default::default(),
 		},
 		prometheus_config: None,
 		telemetry_endpoints: None,
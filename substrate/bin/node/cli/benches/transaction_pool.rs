@@ -66,6 +66,7 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 		warm_up_trie_cache: None,
 		state_pruning: Some(PruningMode::ArchiveAll),
 		blocks_pruning: BlocksPruning::KeepAll,
+		extrinsic_index: false,
 		chain_spec: spec,
 		executor: ExecutorConfiguration::default(),
 		rpc: RpcConfiguration {
@@ -83,6 +84,8 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 			rate_limit: None,
 			rate_limit_whitelisted_ips: Default::default(),
 			rate_limit_trust_proxy_headers: Default::default(),
+			rate_limit_method_costs: Default::default(),
+			rate_limit_api_keys: Default::default(),
 		},
 		prometheus_config: None,
 		telemetry_endpoints: None,
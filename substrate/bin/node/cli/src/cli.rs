@@ -46,6 +46,10 @@ pub struct Cli {
 	#[allow(missing_docs)]
 	#[clap(flatten)]
 	pub storage_monitor: sc_storage_monitor::StorageMonitorParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub state_check_params: sc_cli::StateCheckParams,
 }
 
 /// Possible subcommands of the main binary.
@@ -108,4 +112,13 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Export a snapshot of the database.
+	ExportSnapshot(sc_cli::ExportSnapshotCmd),
+
+	/// Initialize the database from a snapshot.
+	ImportSnapshot(sc_cli::ImportSnapshotCmd),
+
+	/// Check the integrity of the state stored in the database.
+	CheckState(sc_cli::CheckStateCmd),
 }
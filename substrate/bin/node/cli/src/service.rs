@@ -132,6 +132,7 @@ pub fn create_extrinsic(
 				best_block.saturated_into(),
 			)),
 			frame_system::CheckNonce::<kitchensink_runtime::Runtime>::from(nonce),
+			pallet_revive::evm::ApplyAuthorizations::<kitchensink_runtime::Runtime>::new(),
 			frame_system::CheckWeight::<kitchensink_runtime::Runtime>::new(),
 			pallet_skip_feeless_payment::SkipCheckIfFeeless::from(
 				pallet_asset_conversion_tx_payment::ChargeAssetTxPayment::<
@@ -155,6 +156,7 @@ pub fn create_extrinsic(
 			(),
 			(),
 			(),
+			(),
 			None,
 			(),
 		),
@@ -388,6 +390,8 @@ pub struct NewFullBase {
 	pub task_manager: TaskManager,
 	/// The client instance of the node.
 	pub client: Arc<FullClient>,
+	/// The database backend of the node.
+	pub backend: Arc<FullBackend>,
 	/// The networking service of the node.
 	pub network: Arc<dyn NetworkService>,
 	/// The syncing service of the node.
@@ -808,6 +812,7 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 	Ok(NewFullBase {
 		task_manager,
 		client,
+		backend,
 		network,
 		sync: sync_service,
 		transaction_pool,
@@ -818,30 +823,42 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 /// Builds a new service for a full client.
 pub fn new_full(config: Configuration, cli: Cli) -> Result<TaskManager, ServiceError> {
 	let mixnet_config = cli.mixnet_params.config(config.role.is_authority());
+	let state_checker_config = cli.state_check_params.config();
 	let database_path = config.database.path().map(Path::to_path_buf);
+	let fork_id = config.chain_spec.fork_id().map(ToOwned::to_owned);
+
+	let NewFullBase { task_manager, backend, network, sync, .. } =
+		match config.network.network_backend {
+			sc_network::config::NetworkBackendType::Libp2p =>
+				new_full_base::<sc_network::NetworkWorker<_, _>>(
+					config,
+					mixnet_config,
+					cli.no_hardware_benchmarks,
+					|_, _| (),
+				)?,
+			sc_network::config::NetworkBackendType::Litep2p =>
+				new_full_base::<sc_network::Litep2pNetworkBackend>(
+					config,
+					mixnet_config,
+					cli.no_hardware_benchmarks,
+					|_, _| (),
+				)?,
+		};
 
-	let task_manager = match config.network.network_backend {
-		sc_network::config::NetworkBackendType::Libp2p => {
-			let task_manager = new_full_base::<sc_network::NetworkWorker<_, _>>(
-				config,
-				mixnet_config,
-				cli.no_hardware_benchmarks,
-				|_, _| (),
-			)
-			.map(|NewFullBase { task_manager, .. }| task_manager)?;
-			task_manager
-		},
-		sc_network::config::NetworkBackendType::Litep2p => {
-			let task_manager = new_full_base::<sc_network::Litep2pNetworkBackend>(
-				config,
-				mixnet_config,
-				cli.no_hardware_benchmarks,
-				|_, _| (),
-			)
-			.map(|NewFullBase { task_manager, .. }| task_manager)?;
-			task_manager
-		},
-	};
+	if let Some(state_checker_config) = state_checker_config {
+		let state_checker = sc_service::StateChecker::new(
+			state_checker_config,
+			backend,
+			network,
+			sync,
+			fork_id.as_deref(),
+		);
+		task_manager.spawn_handle().spawn_blocking(
+			"state-checker",
+			Some("db"),
+			state_checker.run(),
+		);
+	}
 
 	if let Some(database_path) = database_path {
 		sc_storage_monitor::StorageMonitorService::try_spawn(
@@ -1061,6 +1078,7 @@ mod tests {
 				let check_genesis = frame_system::CheckGenesis::new();
 				let check_era = frame_system::CheckEra::from(Era::Immortal);
 				let check_nonce = frame_system::CheckNonce::from(index);
+				let apply_authorizations = pallet_revive::evm::ApplyAuthorizations::new();
 				let check_weight = frame_system::CheckWeight::new();
 				let tx_payment = pallet_skip_feeless_payment::SkipCheckIfFeeless::from(
 					pallet_asset_conversion_tx_payment::ChargeAssetTxPayment::from(0, None),
@@ -1075,6 +1093,7 @@ mod tests {
 					check_genesis,
 					check_era,
 					check_nonce,
+					apply_authorizations,
 					check_weight,
 					tx_payment,
 					metadata_hash,
@@ -1093,6 +1112,7 @@ mod tests {
 						(),
 						(),
 						(),
+						(),
 						None,
 						(),
 					),
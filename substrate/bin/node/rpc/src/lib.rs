@@ -190,7 +190,7 @@ where
 	)?;
 	io.merge(
 		Grandpa::new(
-			subscription_executor,
+			subscription_executor.clone(),
 			shared_authority_set.clone(),
 			shared_voter_state,
 			justification_stream,
@@ -206,7 +206,8 @@ where
 
 	io.merge(StateMigration::new(client.clone(), backend).into_rpc())?;
 	io.merge(Dev::new(client).into_rpc())?;
-	let statement_store = sc_rpc::statement::StatementStore::new(statement_store).into_rpc();
+	let statement_store =
+		sc_rpc::statement::StatementStore::new(statement_store, subscription_executor).into_rpc();
 	io.merge(statement_store)?;
 
 	if let Some(mixnet_api) = mixnet_api {
@@ -181,10 +181,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// and set impl_version to 0. If only runtime
 	// implementation changes and behavior does not, then leave spec_version as
 	// is and increment impl_version.
-	spec_version: 268,
+	spec_version: 270,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 2,
+	transaction_version: 3,
 	system_version: 1,
 };
 
@@ -1488,6 +1488,8 @@ impl pallet_revive::Config for Runtime {
 	type NativeToEthRatio = ConstU32<1_000_000>; // 10^(18 - 12) Eth is 10^18, Native is 10^12.
 	type EthGasEncoder = ();
 	type FindAuthor = <Runtime as pallet_authorship::Config>::FindAuthor;
+	type EnableP256Verify = ConstBool<true>;
+	type EnableBls12381 = ConstBool<true>;
 }
 
 impl pallet_sudo::Config for Runtime {
@@ -1546,6 +1548,7 @@ where
 			frame_system::CheckGenesis::<Runtime>::new(),
 			frame_system::CheckEra::<Runtime>::from(era),
 			frame_system::CheckNonce::<Runtime>::from(nonce),
+			pallet_revive::evm::ApplyAuthorizations::<Runtime>::new(),
 			frame_system::CheckWeight::<Runtime>::new(),
 			pallet_skip_feeless_payment::SkipCheckIfFeeless::from(
 				pallet_asset_conversion_tx_payment::ChargeAssetTxPayment::<Runtime>::from(
@@ -1605,6 +1608,7 @@ where
 			frame_system::CheckGenesis::<Runtime>::new(),
 			frame_system::CheckEra::<Runtime>::from(Era::Immortal),
 			frame_system::CheckNonce::<Runtime>::from(0),
+			pallet_revive::evm::ApplyAuthorizations::<Runtime>::new(),
 			frame_system::CheckWeight::<Runtime>::new(),
 			pallet_skip_feeless_payment::SkipCheckIfFeeless::from(
 				pallet_asset_conversion_tx_payment::ChargeAssetTxPayment::<Runtime>::from(0, None),
@@ -2293,6 +2297,7 @@ impl pallet_statement::Config for Runtime {
 	type MaxAllowedStatements = MaxAllowedStatements;
 	type MinAllowedBytes = MinAllowedBytes;
 	type MaxAllowedBytes = MaxAllowedBytes;
+	type WeightInfo = pallet_statement::weights::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {
@@ -2820,6 +2825,7 @@ pub type TxExtension = (
 	frame_system::CheckGenesis<Runtime>,
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
+	pallet_revive::evm::ApplyAuthorizations<Runtime>,
 	frame_system::CheckWeight<Runtime>,
 	pallet_skip_feeless_payment::SkipCheckIfFeeless<
 		Runtime,
@@ -2836,7 +2842,11 @@ impl EthExtra for EthExtraImpl {
 	type Config = Runtime;
 	type Extension = TxExtension;
 
-	fn get_eth_extension(nonce: u32, tip: Balance) -> Self::Extension {
+	fn get_eth_extension(
+		nonce: u32,
+		tip: Balance,
+		authorization_list: pallet_revive::evm::AuthorizationList,
+	) -> Self::Extension {
 		(
 			frame_system::AuthorizeCall::<Runtime>::new(),
 			frame_system::CheckNonZeroSender::<Runtime>::new(),
@@ -2845,6 +2855,7 @@ impl EthExtra for EthExtraImpl {
 			frame_system::CheckGenesis::<Runtime>::new(),
 			frame_system::CheckEra::from(crate::generic::Era::Immortal),
 			frame_system::CheckNonce::<Runtime>::from(nonce),
+			pallet_revive::evm::ApplyAuthorizations::<Runtime>::from(authorization_list),
 			frame_system::CheckWeight::<Runtime>::new(),
 			pallet_asset_conversion_tx_payment::ChargeAssetTxPayment::<Runtime>::from(tip, None)
 				.into(),
@@ -3031,6 +3042,7 @@ mod benches {
 		[pallet_society, Society]
 		[pallet_staking, Staking]
 		[pallet_state_trie_migration, StateTrieMigration]
+		[pallet_statement, Statement]
 		[pallet_sudo, Sudo]
 		[frame_system, SystemBench::<Runtime>]
 		[frame_system_extensions, SystemExtensionsBench::<Runtime>]
@@ -3121,6 +3133,7 @@ pallet_revive::impl_runtime_apis_plus_revive!(
 		}
 	}
 
+	#[api_version(2)]
 	impl sp_statement_store::runtime_api::ValidateStatement<Block> for Runtime {
 		fn validate_statement(
 			source: sp_statement_store::runtime_api::StatementSource,
@@ -3128,6 +3141,10 @@ pallet_revive::impl_runtime_apis_plus_revive!(
 		) -> Result<sp_statement_store::runtime_api::ValidStatement, sp_statement_store::runtime_api::InvalidStatement> {
 			Statement::validate_statement(source, statement)
 		}
+
+		fn store_limits() -> Option<sp_statement_store::runtime_api::StoreLimits> {
+			Statement::store_limits()
+		}
 	}
 
 	impl sp_offchain::OffchainWorkerApi<Block> for Runtime {
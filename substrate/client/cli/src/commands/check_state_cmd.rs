@@ -0,0 +1,122 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	error,
+	params::{BlockNumberOrHash, DatabaseParams, PruningParams, SharedParams},
+	CliConfiguration,
+};
+use clap::Parser;
+use log::{error, info};
+use sc_client_api::{backend::Backend as BackendT, HeaderBackend};
+use sp_core::hexdisplay::HexDisplay;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use std::{fmt::Debug, str::FromStr};
+
+/// Number of nodes checked between two progress reports.
+const NODES_PER_REPORT: usize = 1_000_000;
+
+/// The `check-state` command used to check the integrity of the state stored in the database.
+///
+/// Every trie node of the state of the block is read from the database and verified against its
+/// hash. Damaged nodes can be repaired from peers by running the node with `--state-check-repair`.
+#[derive(Debug, Clone, Parser)]
+pub struct CheckStateCmd {
+	/// Hash or number of the block whose state is checked.
+	/// Default is the last finalized block.
+	#[arg(long, value_name = "HASH or NUMBER")]
+	pub at: Option<BlockNumberOrHash>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl CheckStateCmd {
+	/// Run the `check-state` command
+	pub fn run<B>(&self, config: &sc_service::Configuration) -> error::Result<()>
+	where
+		B: BlockT,
+		<B::Hash as FromStr>::Err: Debug,
+		<<B::Header as HeaderT>::Number as FromStr>::Err: Debug,
+	{
+		let db_config = sc_client_db::DatabaseSettings {
+			trie_cache_maximum_size: config.trie_cache_maximum_size,
+			state_pruning: config.state_pruning.clone(),
+			source: config.database.clone(),
+			blocks_pruning: config.blocks_pruning,
+			extrinsic_index: config.extrinsic_index,
+			metrics_registry: None,
+		};
+		let backend = sc_service::new_db_backend::<B>(db_config)?;
+		let blockchain = backend.blockchain();
+		let hash = match self.at.as_ref().map(|b| b.parse::<B>()).transpose()? {
+			Some(id) => blockchain.expect_block_hash_from_id(&id)?,
+			None => blockchain.info().finalized_hash,
+		};
+
+		info!("Checking the state of {hash:?}");
+		let mut check = backend.start_state_check(hash)?;
+		while !backend.continue_state_check(&mut check, NODES_PER_REPORT)? {
+			info!(
+				"Checked {} trie nodes, {} damaged",
+				check.checked_nodes(),
+				check.damaged_nodes().len()
+			);
+		}
+
+		for node in check.damaged_nodes() {
+			let path = node.path.iter().map(|nibble| format!("{nibble:x}")).collect::<String>();
+			let trie = node.child_trie.as_ref().map_or("main trie".into(), |key| {
+				format!("child trie 0x{}", HexDisplay::from(key))
+			});
+			error!(
+				"{:?} trie node {:?} at nibble path '{path}' of the {trie}",
+				node.damage, node.hash,
+			);
+		}
+		info!("Checked {} trie nodes of the state of {hash:?}", check.checked_nodes());
+
+		match check.damaged_nodes().len() {
+			0 => Ok(()),
+			damaged => Err(format!("Found {damaged} damaged trie nodes").into()),
+		}
+	}
+}
+
+impl CliConfiguration for CheckStateCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
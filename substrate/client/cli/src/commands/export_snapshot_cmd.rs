@@ -0,0 +1,119 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	error,
+	params::{BlockNumberOrHash, DatabaseParams, PruningParams, SharedParams},
+	CliConfiguration,
+};
+use clap::Parser;
+use log::info;
+use sc_client_api::{backend::Backend as BackendT, HeaderBackend};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use std::{fmt::Debug, path::PathBuf, str::FromStr};
+
+/// The `export-snapshot` command used to export a snapshot of the database.
+///
+/// The snapshot contains the state of a finalized block and the blocks preceding it. It is
+/// written into a directory together with a manifest used to verify it on import.
+#[derive(Debug, Clone, Parser)]
+pub struct ExportSnapshotCmd {
+	/// Directory to write the snapshot to.
+	#[arg()]
+	pub output: PathBuf,
+
+	/// Hash or number of the finalized block to export.
+	/// Default is the last finalized block.
+	#[arg(long, value_name = "HASH or NUMBER")]
+	pub at: Option<BlockNumberOrHash>,
+
+	/// Number of blocks preceding the exported block to include in the snapshot.
+	#[arg(long, value_name = "COUNT", default_value_t = 256)]
+	pub blocks: u32,
+
+	/// Approximate size in bytes of a single chunk file.
+	#[arg(long, value_name = "BYTES", default_value_t = 64 * 1024 * 1024)]
+	pub chunk_size: usize,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl ExportSnapshotCmd {
+	/// Run the `export-snapshot` command
+	pub fn run<B>(&self, config: &sc_service::Configuration) -> error::Result<()>
+	where
+		B: BlockT,
+		<B::Hash as FromStr>::Err: Debug,
+		<<B::Header as HeaderT>::Number as FromStr>::Err: Debug,
+	{
+		let db_config = sc_client_db::DatabaseSettings {
+			trie_cache_maximum_size: config.trie_cache_maximum_size,
+			state_pruning: config.state_pruning.clone(),
+			source: config.database.clone(),
+			blocks_pruning: config.blocks_pruning,
+			extrinsic_index: config.extrinsic_index,
+			metrics_registry: None,
+		};
+		let backend = sc_service::new_db_backend::<B>(db_config)?;
+		let blockchain = backend.blockchain();
+		let hash = match self.at.as_ref().map(|b| b.parse::<B>()).transpose()? {
+			Some(id) => blockchain.expect_block_hash_from_id(&id)?,
+			None => blockchain.info().finalized_hash,
+		};
+
+		let manifest = sc_client_db::snapshot::export_snapshot(
+			&backend,
+			hash,
+			self.blocks,
+			self.chunk_size,
+			&self.output,
+		)?;
+		info!(
+			"Exported snapshot of block #{} ({:?}) with state root {:?} to {}",
+			manifest.number,
+			manifest.hash,
+			manifest.state_root,
+			self.output.display(),
+		);
+		Ok(())
+	}
+}
+
+impl CliConfiguration for ExportSnapshotCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
@@ -0,0 +1,91 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	error,
+	params::{DatabaseParams, PruningParams, SharedParams},
+	CliConfiguration,
+};
+use clap::Parser;
+use log::info;
+use sp_runtime::traits::Block as BlockT;
+use std::path::PathBuf;
+
+/// The `import-snapshot` command used to initialize a database from a snapshot.
+///
+/// The blocks of the snapshot are verified against its manifest before anything is written, and
+/// the state is verified before any block is imported. The database must be empty, or hold an
+/// interrupted import of the same snapshot, which is then resumed.
+#[derive(Debug, Clone, Parser)]
+pub struct ImportSnapshotCmd {
+	/// Directory containing the snapshot.
+	#[arg()]
+	pub input: PathBuf,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl ImportSnapshotCmd {
+	/// Run the `import-snapshot` command
+	pub fn run<B>(&self, config: &sc_service::Configuration) -> error::Result<()>
+	where
+		B: BlockT,
+	{
+		let db_config = sc_client_db::DatabaseSettings {
+			trie_cache_maximum_size: config.trie_cache_maximum_size,
+			state_pruning: config.state_pruning.clone(),
+			source: config.database.clone(),
+			blocks_pruning: config.blocks_pruning,
+			extrinsic_index: config.extrinsic_index,
+			metrics_registry: None,
+		};
+		let backend = sc_service::new_db_backend::<B>(db_config)?;
+
+		info!("Importing snapshot from {}", self.input.display());
+		let manifest = sc_client_db::snapshot::import_snapshot(&backend, &self.input)?;
+		info!(
+			"Imported snapshot of block #{} ({:?}) with state root {:?}",
+			manifest.number, manifest.hash, manifest.state_root,
+		);
+		Ok(())
+	}
+}
+
+impl CliConfiguration for ImportSnapshotCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
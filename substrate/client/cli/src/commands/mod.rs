@@ -21,12 +21,15 @@
 mod build_spec_cmd;
 mod chain_info_cmd;
 mod check_block_cmd;
+mod check_state_cmd;
 mod export_blocks_cmd;
 mod export_chain_spec_cmd;
+mod export_snapshot_cmd;
 mod export_state_cmd;
 mod generate;
 mod generate_node_key;
 mod import_blocks_cmd;
+mod import_snapshot_cmd;
 mod insert_key;
 mod inspect_key;
 mod inspect_node_key;
@@ -42,10 +45,11 @@ mod verify;
 
 pub use self::{
 	build_spec_cmd::BuildSpecCmd, chain_info_cmd::ChainInfoCmd, check_block_cmd::CheckBlockCmd,
-	export_blocks_cmd::ExportBlocksCmd, export_chain_spec_cmd::ExportChainSpecCmd,
+	check_state_cmd::CheckStateCmd, export_blocks_cmd::ExportBlocksCmd,
+	export_chain_spec_cmd::ExportChainSpecCmd, export_snapshot_cmd::ExportSnapshotCmd,
 	export_state_cmd::ExportStateCmd, generate::GenerateCmd,
 	generate_node_key::GenerateKeyCmdCommon, import_blocks_cmd::ImportBlocksCmd,
-	insert_key::InsertKeyCmd, inspect_key::InspectKeyCmd, inspect_node_key::InspectNodeKeyCmd,
-	key::KeySubcommand, purge_chain_cmd::PurgeChainCmd, revert_cmd::RevertCmd, run_cmd::RunCmd,
-	sign::SignCmd, vanity::VanityCmd, verify::VerifyCmd,
+	import_snapshot_cmd::ImportSnapshotCmd, insert_key::InsertKeyCmd, inspect_key::InspectKeyCmd,
+	inspect_node_key::InspectNodeKeyCmd, key::KeySubcommand, purge_chain_cmd::PurgeChainCmd,
+	revert_cmd::RevertCmd, run_cmd::RunCmd, sign::SignCmd, vanity::VanityCmd, verify::VerifyCmd,
 };
@@ -28,12 +28,13 @@ use clap::Parser;
 use regex::Regex;
 use sc_service::{
 	config::{
-		BasePath, IpNetwork, PrometheusConfig, RpcBatchRequestConfig, TransactionPoolOptions,
+		BasePath, IpNetwork, PrometheusConfig, RateLimitTier, RpcBatchRequestConfig,
+		TransactionPoolOptions,
 	},
 	ChainSpec, Role,
 };
 use sc_telemetry::TelemetryEndpoints;
-use std::num::NonZeroU32;
+use std::{collections::HashMap, num::NonZeroU32};
 
 /// The `run` command used to run a node.
 #[derive(Debug, Clone, Parser)]
@@ -330,6 +331,14 @@ impl CliConfiguration for RunCmd {
 		Ok(self.rpc_params.rpc_rate_limit_trust_proxy_headers)
 	}
 
+	fn rpc_rate_limit_method_costs(&self) -> Result<HashMap<String, NonZeroU32>> {
+		Ok(self.rpc_params.rpc_rate_limit_method_costs())
+	}
+
+	fn rpc_rate_limit_api_keys(&self) -> Result<HashMap<String, RateLimitTier>> {
+		self.rpc_params.rpc_rate_limit_api_keys()
+	}
+
 	fn transaction_pool(&self, is_dev: bool) -> Result<TransactionPoolOptions> {
 		Ok(self.pool_config.transaction_pool(is_dev))
 	}
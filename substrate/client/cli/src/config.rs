@@ -29,13 +29,13 @@ use sc_service::{
 	config::{
 		BasePath, Configuration, DatabaseSource, ExecutorConfiguration, IpNetwork, KeystoreConfig,
 		NetworkConfiguration, NodeKeyConfig, OffchainWorkerConfig, PrometheusConfig, PruningMode,
-		Role, RpcBatchRequestConfig, RpcConfiguration, RpcMethods, TelemetryEndpoints,
-		TransactionPoolOptions, WasmExecutionMethod,
+		RateLimitTier, Role, RpcBatchRequestConfig, RpcConfiguration, RpcMethods,
+		TelemetryEndpoints, TransactionPoolOptions, WasmExecutionMethod,
 	},
 	BlocksPruning, ChainSpec, TracingReceiver,
 };
 use sc_tracing::logging::LoggerBuilder;
-use std::{num::NonZeroU32, path::PathBuf};
+use std::{collections::HashMap, num::NonZeroU32, path::PathBuf};
 
 /// The maximum number of characters for a node name.
 pub(crate) const NODE_NAME_MAX_LENGTH: usize = 64;
@@ -43,6 +43,9 @@ pub(crate) const NODE_NAME_MAX_LENGTH: usize = 64;
 /// Default sub directory to store network config.
 pub(crate) const DEFAULT_NETWORK_CONFIG_PATH: &str = "network";
 
+/// Default sub directory to store the transaction pool journal.
+pub(crate) const DEFAULT_TXPOOL_JOURNAL_PATH: &str = "txpool";
+
 /// The recommended open file descriptor limit to be configured for the process.
 const RECOMMENDED_OPEN_FILE_DESCRIPTOR_LIMIT: u64 = 10_000;
 
@@ -282,6 +285,14 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			.unwrap_or_else(|| Ok(BlocksPruning::KeepFinalized))
 	}
 
+	/// Whether to maintain an index of the blocks containing each extrinsic.
+	///
+	/// By default this is retrieved from `DatabaseParams` if it is available. Otherwise its
+	/// `false`.
+	fn extrinsic_index(&self) -> Result<bool> {
+		Ok(self.database_params().map(|x| x.extrinsic_index()).unwrap_or_default())
+	}
+
 	/// Get the chain ID (string).
 	///
 	/// By default this is retrieved from `SharedParams`.
@@ -376,6 +387,16 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		Ok(false)
 	}
 
+	/// Cost of the RPC methods in rate limit units.
+	fn rpc_rate_limit_method_costs(&self) -> Result<HashMap<String, NonZeroU32>> {
+		Ok(HashMap::new())
+	}
+
+	/// RPC API keys with their rate limit tier.
+	fn rpc_rate_limit_api_keys(&self) -> Result<HashMap<String, RateLimitTier>> {
+		Ok(HashMap::new())
+	}
+
 	/// Get the prometheus configuration (`None` if disabled)
 	///
 	/// By default this is `None`.
@@ -514,6 +535,8 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		let keystore = self.keystore_config(&config_dir)?;
 		let telemetry_endpoints = self.telemetry_endpoints(&chain_spec)?;
 		let runtime_cache_size = self.runtime_cache_size()?;
+		let mut transaction_pool = self.transaction_pool(is_dev)?;
+		transaction_pool.set_default_journal_path(config_dir.join(DEFAULT_TXPOOL_JOURNAL_PATH));
 
 		let rpc_addrs: Option<Vec<sc_service::config::RpcEndpoint>> = self
 			.rpc_addr(DCV::rpc_listen_port())?
@@ -523,7 +546,7 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			impl_name: C::impl_name(),
 			impl_version: C::impl_version(),
 			tokio_handle,
-			transaction_pool: self.transaction_pool(is_dev)?,
+			transaction_pool,
 			network: self.network_config(
 				&chain_spec,
 				is_dev,
@@ -541,6 +564,7 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			warm_up_trie_cache: self.warm_up_trie_cache()?,
 			state_pruning: self.state_pruning()?,
 			blocks_pruning: self.blocks_pruning()?,
+			extrinsic_index: self.extrinsic_index()?,
 			executor: ExecutorConfiguration {
 				wasm_method: self.wasm_method()?,
 				default_heap_pages: self.default_heap_pages()?,
@@ -563,6 +587,8 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 				rate_limit: self.rpc_rate_limit()?,
 				rate_limit_whitelisted_ips: self.rpc_rate_limit_whitelisted_ips()?,
 				rate_limit_trust_proxy_headers: self.rpc_rate_limit_trust_proxy_headers()?,
+				rate_limit_method_costs: self.rpc_rate_limit_method_costs()?,
+				rate_limit_api_keys: self.rpc_rate_limit_api_keys()?,
 			},
 			prometheus_config: self
 				.prometheus_config(DCV::prometheus_listen_port(), &chain_spec)?,
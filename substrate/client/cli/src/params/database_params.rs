@@ -29,6 +29,14 @@ pub struct DatabaseParams {
 	/// Limit the memory the database cache can use.
 	#[arg(long = "db-cache", value_name = "MiB")]
 	pub database_cache_size: Option<usize>,
+
+	/// Maintain an index of the blocks containing each extrinsic, by extrinsic hash.
+	///
+	/// Enables looking up extrinsics by hash with the `archive_v1_extrinsicLocations` RPC method.
+	/// Only the blocks imported while the index is enabled are indexed, so this is best enabled
+	/// when syncing a new archive node.
+	#[arg(long)]
+	pub extrinsic_index: bool,
 }
 
 impl DatabaseParams {
@@ -41,4 +49,9 @@ impl DatabaseParams {
 	pub fn database_cache_size(&self) -> Option<usize> {
 		self.database_cache_size
 	}
+
+	/// Maintain an index of the blocks containing each extrinsic.
+	pub fn extrinsic_index(&self) -> bool {
+		self.extrinsic_index
+	}
 }
@@ -18,12 +18,13 @@
 
 use crate::{
 	arg_enums::{Cors, RpcMethods},
-	params::{IpNetwork, RpcBatchRequestConfig},
+	params::{IpNetwork, RateLimitTier, RpcBatchRequestConfig, RpcMethodFilter},
 	RPC_DEFAULT_MAX_CONNECTIONS, RPC_DEFAULT_MAX_REQUEST_SIZE_MB, RPC_DEFAULT_MAX_RESPONSE_SIZE_MB,
 	RPC_DEFAULT_MAX_SUBS_PER_CONN, RPC_DEFAULT_MESSAGE_CAPACITY_PER_CONN,
 };
 use clap::Args;
 use std::{
+	collections::HashMap,
 	net::{Ipv4Addr, Ipv6Addr, SocketAddr},
 	num::NonZeroU32,
 };
@@ -38,8 +39,13 @@ const RPC_MAX_BUF_CAP_PER_CONN: &str = "max-buffer-capacity-per-connection";
 const RPC_RATE_LIMIT: &str = "rate-limit";
 const RPC_RATE_LIMIT_TRUST_PROXY_HEADERS: &str = "rate-limit-trust-proxy-headers";
 const RPC_RATE_LIMIT_WHITELISTED_IPS: &str = "rate-limit-whitelisted-ips";
+const RPC_METHOD_COST: &str = "method-cost";
+const RPC_RATE_LIMIT_TIER: &str = "rate-limit-tier";
+const RPC_API_KEY: &str = "api-key";
 const RPC_RETRY_RANDOM_PORT: &str = "retry-random-port";
 const RPC_METHODS: &str = "methods";
+const RPC_ALLOW_METHODS: &str = "allow-methods";
+const RPC_DENY_METHODS: &str = "deny-methods";
 const RPC_OPTIONAL: &str = "optional";
 const RPC_DISABLE_BATCH: &str = "disable-batch-requests";
 const RPC_BATCH_LIMIT: &str = "max-batch-request-len";
@@ -75,6 +81,20 @@ pub struct RpcParams {
 	)]
 	pub rpc_methods: RpcMethods,
 
+	/// RPC methods to expose, all the methods of the method set are exposed if not specified.
+	///
+	/// Each pattern is either the name of a method or a prefix followed by `*`, for example
+	/// `--rpc-allow-methods 'chainHead_v1_*' 'transaction_v1_*'` only exposes the methods of the
+	/// `chainHead_v1` and `transaction_v1` namespaces.
+	#[arg(long, value_name = "PATTERN", num_args = 1..)]
+	pub rpc_allow_methods: Vec<String>,
+
+	/// RPC methods not to expose, even if allowed by `--rpc-allow-methods`.
+	///
+	/// Each pattern is either the name of a method or a prefix followed by `*`.
+	#[arg(long, value_name = "PATTERN", num_args = 1..)]
+	pub rpc_deny_methods: Vec<String>,
+
 	/// RPC rate limiting (calls/minute) for each connection.
 	///
 	/// This is disabled by default.
@@ -100,6 +120,34 @@ pub struct RpcParams {
 	#[arg(long)]
 	pub rpc_rate_limit_trust_proxy_headers: bool,
 
+	/// Cost of an RPC method in rate limit units, this can be enabled more than once.
+	///
+	/// The methods not listed cost one unit, so that the rate limits are the number of calls per
+	/// minute of such methods.
+	///
+	/// For example `--rpc-method-cost state_queryStorage=50` will make a call to
+	/// `state_queryStorage` consume as much of the rate limit as 50 calls to `system_health`.
+	#[arg(long, value_name = "METHOD=COST", value_parser = parse_method_cost)]
+	pub rpc_method_cost: Vec<(String, NonZeroU32)>,
+
+	/// Rate limit tier of API keys, this can be enabled more than once.
+	///
+	/// The rate limit is in units per minute for each API key of the tier, or `unlimited`.
+	///
+	/// For example `--rpc-rate-limit-tier premium=6000` defines the `premium` tier.
+	#[arg(long, value_name = "NAME=LIMIT", value_parser = parse_rate_limit_tier)]
+	pub rpc_rate_limit_tier: Vec<RateLimitTier>,
+
+	/// API key with its rate limit tier, this can be enabled more than once.
+	///
+	/// The calls made with an API key, supplied in the `X-Api-Key` header or in the `api_key`
+	/// query parameter, are limited by the rate limit of the tier of the key instead of
+	/// `--rpc-rate-limit`. The rate limit is shared by all the connections using the key.
+	///
+	/// For example `--rpc-api-key 7f3c9a=premium` assigns the key `7f3c9a` to the `premium` tier.
+	#[arg(long, value_name = "KEY=TIER", value_parser = parse_api_key)]
+	pub rpc_api_key: Vec<(String, String)>,
+
 	/// Set the maximum RPC request payload size for both HTTP and WS in megabytes.
 	#[arg(long, default_value_t = RPC_DEFAULT_MAX_REQUEST_SIZE_MB)]
 	pub rpc_max_request_size: u32,
@@ -139,6 +187,10 @@ pub struct RpcParams {
 	///  • cors: The CORS allowed origins, this can enabled more than once (optional)
 	///  • methods: Which RPC methods to allow, valid values are "safe", "unsafe" and "auto"
 	///    (optional)
+	///  • allow-methods: A pattern of the RPC methods to expose, either a method name or a prefix
+	///    followed by `*` such as `chainHead_v1_*`, this can be enabled more than once (optional)
+	///  • deny-methods: A pattern of the RPC methods not to expose, this can be enabled more than
+	///    once (optional)
 	///  • optional: If the listen address is optional i.e the interface is not required to be
 	///    available For example this may be useful if some platforms doesn't support ipv6
 	///    (optional)
@@ -147,13 +199,19 @@ pub struct RpcParams {
 	///  • rate-limit-whitelisted-ips: Disable rate limiting for certain ip addresses, this can be
 	/// enabled more than once (optional)  • retry-random-port: If the port is already in use,
 	/// retry with a random port (optional)
+	///  • method-cost: The cost of a method in rate limit units as `<METHOD>:<COST>`, this can be
+	///    enabled more than once (optional)
+	///  • rate-limit-tier: A rate limit tier of API keys as `<NAME>:<LIMIT>`, this can be enabled
+	///    more than once (optional)
+	///  • api-key: An API key with its rate limit tier as `<KEY>:<TIER>`, this can be enabled
+	///    more than once (optional)
 	///
 	/// Use with care, this flag is unstable and subject to change.
 	#[arg(
 		long,
 		num_args = 1..,
 		verbatim_doc_comment,
-		conflicts_with_all = &["rpc_external", "unsafe_rpc_external", "rpc_port", "rpc_cors", "rpc_rate_limit_trust_proxy_headers", "rpc_rate_limit", "rpc_rate_limit_whitelisted_ips", "rpc_message_buffer_capacity_per_connection", "rpc_disable_batch_requests", "rpc_max_subscriptions_per_connection", "rpc_max_request_size", "rpc_max_response_size"]
+		conflicts_with_all = &["rpc_external", "unsafe_rpc_external", "rpc_port", "rpc_allow_methods", "rpc_deny_methods", "rpc_cors", "rpc_rate_limit_trust_proxy_headers", "rpc_rate_limit", "rpc_rate_limit_whitelisted_ips", "rpc_method_cost", "rpc_rate_limit_tier", "rpc_api_key", "rpc_message_buffer_capacity_per_connection", "rpc_disable_batch_requests", "rpc_max_subscriptions_per_connection", "rpc_max_request_size", "rpc_max_response_size"]
 	)]
 	pub experimental_rpc_endpoint: Vec<RpcEndpoint>,
 
@@ -253,9 +311,12 @@ impl RpcParams {
 				max_connections: self.rpc_max_connections,
 				listen_addr: SocketAddr::new(std::net::IpAddr::V4(ipv4), port),
 				rpc_methods: self.rpc_methods,
+				method_filter: self.rpc_method_filter(),
 				rate_limit: self.rpc_rate_limit,
 				rate_limit_trust_proxy_headers: self.rpc_rate_limit_trust_proxy_headers,
 				rate_limit_whitelisted_ips: self.rpc_rate_limit_whitelisted_ips.clone(),
+				rate_limit_method_costs: self.rpc_rate_limit_method_costs(),
+				rate_limit_api_keys: self.rpc_rate_limit_api_keys()?,
 				max_payload_in_mb: self.rpc_max_request_size,
 				max_payload_out_mb: self.rpc_max_response_size,
 				max_subscriptions_per_connection: self.rpc_max_subscriptions_per_connection,
@@ -269,9 +330,12 @@ impl RpcParams {
 				max_connections: self.rpc_max_connections,
 				listen_addr: SocketAddr::new(std::net::IpAddr::V6(ipv6), port),
 				rpc_methods: self.rpc_methods,
+				method_filter: self.rpc_method_filter(),
 				rate_limit: self.rpc_rate_limit,
 				rate_limit_trust_proxy_headers: self.rpc_rate_limit_trust_proxy_headers,
 				rate_limit_whitelisted_ips: self.rpc_rate_limit_whitelisted_ips.clone(),
+				rate_limit_method_costs: self.rpc_rate_limit_method_costs(),
+				rate_limit_api_keys: self.rpc_rate_limit_api_keys()?,
 				max_payload_in_mb: self.rpc_max_request_size,
 				max_payload_out_mb: self.rpc_max_response_size,
 				max_subscriptions_per_connection: self.rpc_max_subscriptions_per_connection,
@@ -283,6 +347,24 @@ impl RpcParams {
 		]))
 	}
 
+	/// Returns the filter of the exposed RPC methods.
+	pub fn rpc_method_filter(&self) -> RpcMethodFilter {
+		RpcMethodFilter {
+			allow: self.rpc_allow_methods.clone(),
+			deny: self.rpc_deny_methods.clone(),
+		}
+	}
+
+	/// Returns the cost of the RPC methods in rate limit units.
+	pub fn rpc_rate_limit_method_costs(&self) -> HashMap<String, NonZeroU32> {
+		self.rpc_method_cost.iter().cloned().collect()
+	}
+
+	/// Returns the RPC API keys with their rate limit tier.
+	pub fn rpc_rate_limit_api_keys(&self) -> crate::Result<HashMap<String, RateLimitTier>> {
+		api_key_tiers(&self.rpc_rate_limit_tier, &self.rpc_api_key).map_err(crate::Error::Input)
+	}
+
 	/// Returns the configuration for batch RPC requests.
 	pub fn rpc_batch_config(&self) -> crate::Result<RpcBatchRequestConfig> {
 		let cfg = if self.rpc_disable_batch_requests {
@@ -326,6 +408,50 @@ fn rpc_interface(
 	}
 }
 
+fn parse_method_cost(s: &str) -> Result<(String, NonZeroU32), String> {
+	let (method, cost) =
+		s.split_once('=').ok_or_else(|| format!("`{s}`, expects: `METHOD=COST`"))?;
+	let cost = cost
+		.parse()
+		.map_err(|_| format!("Invalid cost `{cost}` of method `{method}`"))?;
+	Ok((method.to_string(), cost))
+}
+
+fn parse_rate_limit_tier(s: &str) -> Result<RateLimitTier, String> {
+	let (name, limit) = s.split_once('=').ok_or_else(|| format!("`{s}`, expects: `NAME=LIMIT`"))?;
+	let rate_limit = match limit {
+		"unlimited" => None,
+		limit => Some(
+			limit
+				.parse()
+				.map_err(|_| format!("Invalid rate limit `{limit}` of tier `{name}`"))?,
+		),
+	};
+	Ok(RateLimitTier { name: name.to_string(), rate_limit })
+}
+
+fn parse_api_key(s: &str) -> Result<(String, String), String> {
+	let (key, tier) = s.split_once('=').ok_or_else(|| "expects: `KEY=TIER`".to_string())?;
+	Ok((key.to_string(), tier.to_string()))
+}
+
+/// Map the API keys to their rate limit tier.
+fn api_key_tiers(
+	tiers: &[RateLimitTier],
+	api_keys: &[(String, String)],
+) -> Result<HashMap<String, RateLimitTier>, String> {
+	api_keys
+		.iter()
+		.map(|(key, name)| {
+			let tier = tiers
+				.iter()
+				.find(|tier| &tier.name == name)
+				.ok_or_else(|| format!("Unknown rate limit tier `{name}` of an API key"))?;
+			Ok((key.clone(), tier.clone()))
+		})
+		.collect()
+}
+
 /// Represent a single RPC endpoint with its configuration.
 #[derive(Debug, Clone)]
 pub struct RpcEndpoint {
@@ -349,10 +475,16 @@ pub struct RpcEndpoint {
 	pub rate_limit_trust_proxy_headers: bool,
 	/// Whitelisted IPs for rate limiting.
 	pub rate_limit_whitelisted_ips: Vec<IpNetwork>,
+	/// Cost of the methods in rate limit units.
+	pub rate_limit_method_costs: HashMap<String, NonZeroU32>,
+	/// API keys with their rate limit tier.
+	pub rate_limit_api_keys: HashMap<String, RateLimitTier>,
 	/// CORS.
 	pub cors: Option<Vec<String>>,
 	/// RPC methods to expose.
 	pub rpc_methods: RpcMethods,
+	/// Filter of the RPC methods to expose.
+	pub method_filter: RpcMethodFilter,
 	/// Whether it's an optional listening address i.e, it's ignored if it fails to bind.
 	/// For example substrate tries to bind both ipv4 and ipv6 addresses but some platforms
 	/// may not support ipv6.
@@ -373,12 +505,16 @@ impl std::str::FromStr for RpcEndpoint {
 		let mut max_buffer_capacity_per_connection = None;
 		let mut cors: Option<Vec<String>> = None;
 		let mut rpc_methods = None;
+		let mut method_filter = RpcMethodFilter::default();
 		let mut is_optional = None;
 		let mut disable_batch_requests = None;
 		let mut max_batch_request_len = None;
 		let mut rate_limit = None;
 		let mut rate_limit_trust_proxy_headers = None;
 		let mut rate_limit_whitelisted_ips = Vec::new();
+		let mut rate_limit_method_costs = HashMap::new();
+		let mut rate_limit_tiers = Vec::new();
+		let mut api_keys = Vec::new();
 		let mut retry_random_port = None;
 
 		for input in s.split(',') {
@@ -474,6 +610,23 @@ impl std::str::FromStr for RpcEndpoint {
 						.map_err(|_| invalid_value(RPC_RATE_LIMIT_WHITELISTED_IPS, &val))?;
 					rate_limit_whitelisted_ips.push(ip);
 				},
+				RPC_METHOD_COST => {
+					let (method, cost) = val
+						.split_once(':')
+						.and_then(|(method, cost)| Some((method, cost.parse().ok()?)))
+						.ok_or_else(|| invalid_value(RPC_METHOD_COST, &val))?;
+					rate_limit_method_costs.insert(method.to_string(), cost);
+				},
+				RPC_RATE_LIMIT_TIER => {
+					let tier = parse_rate_limit_tier(&val.replacen(':', "=", 1))
+						.map_err(|_| invalid_value(RPC_RATE_LIMIT_TIER, &val))?;
+					rate_limit_tiers.push(tier);
+				},
+				RPC_API_KEY => {
+					let (key, tier) =
+						val.split_once(':').ok_or_else(|| invalid_value(RPC_API_KEY, &val))?;
+					api_keys.push((key.to_string(), tier.to_string()));
+				},
 				RPC_RETRY_RANDOM_PORT => {
 					if retry_random_port.is_some() {
 						return Err(only_once_err(RPC_RETRY_RANDOM_PORT));
@@ -489,6 +642,18 @@ impl std::str::FromStr for RpcEndpoint {
 					let val = val.parse().map_err(|_| invalid_value(RPC_METHODS, &val))?;
 					rpc_methods = Some(val);
 				},
+				RPC_ALLOW_METHODS => {
+					if val.is_empty() {
+						return Err(invalid_value(RPC_ALLOW_METHODS, &val));
+					}
+					method_filter.allow.push(val.to_string());
+				},
+				RPC_DENY_METHODS => {
+					if val.is_empty() {
+						return Err(invalid_value(RPC_DENY_METHODS, &val));
+					}
+					method_filter.deny.push(val.to_string());
+				},
 				RPC_OPTIONAL => {
 					if is_optional.is_some() {
 						return Err(only_once_err(RPC_OPTIONAL));
@@ -540,9 +705,12 @@ impl std::str::FromStr for RpcEndpoint {
 			max_subscriptions_per_connection: max_subscriptions_per_connection
 				.unwrap_or(RPC_DEFAULT_MAX_SUBS_PER_CONN),
 			rpc_methods: rpc_methods.unwrap_or(RpcMethods::Auto),
+			method_filter,
 			rate_limit,
 			rate_limit_trust_proxy_headers: rate_limit_trust_proxy_headers.unwrap_or(false),
 			rate_limit_whitelisted_ips,
+			rate_limit_method_costs,
+			rate_limit_api_keys: api_key_tiers(&rate_limit_tiers, &api_keys)?,
 			is_optional: is_optional.unwrap_or(false),
 			retry_random_port: retry_random_port.unwrap_or(false),
 		})
@@ -560,9 +728,12 @@ impl Into<sc_service::config::RpcEndpoint> for RpcEndpoint {
 			max_payload_out_mb: self.max_payload_out_mb,
 			max_subscriptions_per_connection: self.max_subscriptions_per_connection,
 			rpc_methods: self.rpc_methods.into(),
+			method_filter: self.method_filter,
 			rate_limit: self.rate_limit,
 			rate_limit_trust_proxy_headers: self.rate_limit_trust_proxy_headers,
 			rate_limit_whitelisted_ips: self.rate_limit_whitelisted_ips,
+			rate_limit_method_costs: self.rate_limit_method_costs,
+			rate_limit_api_keys: self.rate_limit_api_keys,
 			cors: self.cors,
 			retry_random_port: self.retry_random_port,
 			is_optional: self.is_optional,
@@ -671,6 +842,62 @@ mod tests {
 		assert_eq!(addr.is_optional, true);
 	}
 
+	#[test]
+	fn parse_rpc_endpoint_method_filter() {
+		let endpoint = RpcEndpoint::from_str(
+			"listen-addr=0.0.0.0:9944,allow-methods=chainHead_v1_*,allow-methods=transaction_v1_*,\
+			deny-methods=transaction_v1_stop",
+		)
+		.unwrap();
+		assert_eq!(
+			endpoint.method_filter,
+			RpcMethodFilter {
+				allow: vec!["chainHead_v1_*".to_string(), "transaction_v1_*".to_string()],
+				deny: vec!["transaction_v1_stop".to_string()],
+			}
+		);
+
+		let endpoint = RpcEndpoint::from_str("listen-addr=127.0.0.1:9944").unwrap();
+		assert!(endpoint.method_filter.is_empty());
+
+		assert!(RpcEndpoint::from_str("listen-addr=127.0.0.1:9944,allow-methods=").is_err());
+	}
+
+	#[test]
+	fn parse_rpc_endpoint_rate_limit_tiers() {
+		let endpoint = RpcEndpoint::from_str(
+			"listen-addr=127.0.0.1:9944,rate-limit=10,method-cost=state_queryStorage:50,\
+			rate-limit-tier=premium:6000,rate-limit-tier=internal:unlimited,api-key=abc:premium,\
+			api-key=def:internal",
+		)
+		.unwrap();
+		assert_eq!(
+			endpoint.rate_limit_method_costs,
+			[("state_queryStorage".to_string(), NonZeroU32::new(50).unwrap())].into()
+		);
+		assert_eq!(
+			endpoint.rate_limit_api_keys,
+			[
+				(
+					"abc".to_string(),
+					RateLimitTier {
+						name: "premium".to_string(),
+						rate_limit: Some(NonZeroU32::new(6000).unwrap())
+					}
+				),
+				(
+					"def".to_string(),
+					RateLimitTier { name: "internal".to_string(), rate_limit: None }
+				),
+			]
+			.into()
+		);
+
+		assert!(RpcEndpoint::from_str("listen-addr=127.0.0.1:9944,method-cost=system_health:0")
+			.is_err());
+		assert!(RpcEndpoint::from_str("listen-addr=127.0.0.1:9944,api-key=abc:premium").is_err());
+	}
+
 	#[test]
 	fn parse_rpc_endpoint_batch_options_mutually_exclusive() {
 		assert!(RpcEndpoint::from_str(
@@ -0,0 +1,46 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+use clap::Args;
+use std::time::Duration;
+
+/// Parameters of the background check of the state.
+#[derive(Debug, Clone, Args)]
+pub struct StateCheckParams {
+	/// Periodically check the integrity of the state of the finalized block.
+	///
+	/// Every trie node of the state is read from the database and verified against its hash, with
+	/// short pauses to keep the impact on block import low. The value is the time between two
+	/// checks in minutes.
+	#[arg(long, value_name = "MINUTES", value_parser = clap::value_parser!(u64).range(1..))]
+	pub state_check: Option<u64>,
+
+	/// Repair the damaged trie nodes found by `--state-check` with state proofs requested from
+	/// peers.
+	#[arg(long, requires = "state_check")]
+	pub state_check_repair: bool,
+}
+
+impl StateCheckParams {
+	/// Returns the state checker configuration, or `None` if the check is disabled.
+	pub fn config(&self) -> Option<sc_service::StateCheckerConfig> {
+		self.state_check.map(|minutes| sc_service::StateCheckerConfig {
+			interval: Duration::from_secs(minutes * 60),
+			repair: self.state_check_repair,
+		})
+	}
+}
@@ -17,7 +17,8 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use clap::{Args, ValueEnum};
-use sc_transaction_pool::TransactionPoolOptions;
+use sc_transaction_pool::{JournalOptions, PoolFairnessLimits, TransactionPoolOptions};
+use std::{path::PathBuf, time::Duration};
 
 /// Type of transaction pool to be used
 #[derive(Debug, Clone, Copy, ValueEnum)]
@@ -56,20 +57,84 @@ pub struct TransactionPoolParams {
 	#[arg(long, value_name = "SECONDS")]
 	pub tx_ban_seconds: Option<u64>,
 
+	/// Maximum number of transactions of a single sender in the transaction pool.
+	///
+	/// Further transactions of a sender reaching the quota are rejected, unless they replace a
+	/// transaction already in the pool.
+	#[arg(long, value_name = "COUNT")]
+	pub pool_sender_limit: Option<usize>,
+
+	/// Number of ready transaction slots reserved for the priority lane.
+	///
+	/// The lane is used by locally produced transactions (e.g. by off-chain workers) and
+	/// transactions with a priority of at least `--pool-lane-priority`.
+	#[arg(long, value_name = "COUNT", default_value_t = 0)]
+	pub pool_reserved_lane: usize,
+
+	/// Minimum priority of a transaction to use the priority lane.
+	///
+	/// By default only locally produced transactions use the lane.
+	#[arg(long, value_name = "PRIORITY")]
+	pub pool_lane_priority: Option<u64>,
+
 	/// The type of transaction pool to be instantiated.
 	#[arg(long, value_enum, default_value_t = TransactionPoolType::SingleState)]
 	pub pool_type: TransactionPoolType,
+
+	/// Persist pending transactions to disk and restore them after a restart.
+	///
+	/// Restored transactions are revalidated against the best block.
+	#[arg(long)]
+	pub pool_journal: bool,
+
+	/// Directory of the transaction journal.
+	///
+	/// Defaults to `txpool` within the chain's data directory.
+	#[arg(long, value_name = "PATH", requires = "pool_journal")]
+	pub pool_journal_path: Option<PathBuf>,
+
+	/// Journal transactions from all sources.
+	///
+	/// By default transactions submitted via RPC or received from the network are journaled, along
+	/// with locally produced ones (e.g. by off-chain workers). Enable this to also journal
+	/// transactions re-submitted from retracted blocks.
+	#[arg(long, requires = "pool_journal")]
+	pub pool_journal_all: bool,
+
+	/// Transactions journaled longer ago than this are discarded.
+	#[arg(long, value_name = "SECONDS", default_value_t = 10800, requires = "pool_journal")]
+	pub pool_journal_max_age: u64,
+
+	/// Maximum number of kilobytes of the transaction journal.
+	#[arg(long, value_name = "COUNT", default_value_t = 16384, requires = "pool_journal")]
+	pub pool_journal_kbytes: usize,
 }
 
 impl TransactionPoolParams {
 	/// Fill the given `PoolConfiguration` by looking at the cli parameters.
 	pub fn transaction_pool(&self, is_dev: bool) -> TransactionPoolOptions {
-		TransactionPoolOptions::new_with_params(
+		let options = TransactionPoolOptions::new_with_params(
 			self.pool_limit,
 			self.pool_kbytes * 1024,
 			self.tx_ban_seconds,
 			self.pool_type.into(),
 			is_dev,
 		)
+		.with_fairness(PoolFairnessLimits {
+			per_sender: self.pool_sender_limit,
+			lane_reserved: self.pool_reserved_lane,
+			lane_priority: self.pool_lane_priority.unwrap_or(u64::MAX),
+		});
+
+		if !self.pool_journal {
+			return options
+		}
+
+		options.with_journal(JournalOptions {
+			path: self.pool_journal_path.clone(),
+			all_sources: self.pool_journal_all,
+			max_age: Duration::from_secs(self.pool_journal_max_age),
+			max_bytes: self.pool_journal_kbytes * 1024,
+		})
 	}
 }
@@ -255,6 +255,7 @@ mod tests {
 				warm_up_trie_cache: None,
 				state_pruning: None,
 				blocks_pruning: sc_client_db::BlocksPruning::KeepAll,
+				extrinsic_index: false,
 				chain_spec: Box::new(
 					GenericChainSpec::<NoExtension, ()>::builder(
 						Default::default(),
@@ -283,6 +284,8 @@ mod tests {
 					rate_limit: None,
 					rate_limit_whitelisted_ips: Default::default(),
 					rate_limit_trust_proxy_headers: Default::default(),
+					rate_limit_method_costs: Default::default(),
+					rate_limit_api_keys: Default::default(),
 				},
 				prometheus_config: None,
 				telemetry_endpoints: None,
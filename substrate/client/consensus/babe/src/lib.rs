@@ -1892,6 +1892,29 @@ where
 		format!("Unexpected hash lookup failure for block number: {}", revert_up_to_number),
 	))?;
 
+	revert_to(client, backend.blockchain().leaves()?, revert_up_to_hash, revert_up_to_number)
+}
+
+/// Reverts protocol aux data to the given block, including finalized blocks.
+///
+/// Epoch-changes and the weights of the blocks descending from the revert point on the branches
+/// ending with `leaves` are removed in a single write. Must be called before the blocks are
+/// reverted, as their headers are needed.
+pub fn revert_to<Block, Client>(
+	client: Arc<Client>,
+	leaves: Vec<Block::Hash>,
+	revert_up_to_hash: Block::Hash,
+	revert_up_to_number: NumberFor<Block>,
+) -> ClientResult<()>
+where
+	Block: BlockT,
+	Client: AuxStore
+		+ HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ HeaderBackend<Block>
+		+ ProvideRuntimeApi<Block>
+		+ UsageProvider<Block>,
+	Client::Api: BabeApi<Block>,
+{
 	// Revert epoch changes tree.
 
 	// This config is only used on-genesis.
@@ -1908,9 +1931,10 @@ where
 
 	// Remove block weights added after the revert point.
 
-	let mut weight_keys = HashSet::with_capacity(revertible.saturated_into());
+	let reverted = client.info().best_number - revert_up_to_number;
+	let mut weight_keys = HashSet::with_capacity(reverted.saturated_into());
 
-	let leaves = backend.blockchain().leaves()?.into_iter().filter(|&leaf| {
+	let leaves = leaves.into_iter().filter(|&leaf| {
 		sp_blockchain::tree_route(&*client, revert_up_to_hash, leaf)
 			.map(|route| route.retracted().is_empty())
 			.unwrap_or_default()
@@ -21,7 +21,10 @@ use super::Error;
 
 use sc_consensus::BlockImportParams;
 use sp_inherents::InherentData;
-use sp_runtime::{traits::Block as BlockT, Digest};
+use sp_runtime::{
+	traits::{Block as BlockT, NumberFor},
+	Digest,
+};
 
 pub mod aura;
 pub mod babe;
@@ -44,4 +47,15 @@ pub trait ConsensusDataProvider<B: BlockT>: Send + Sync {
 		inherents: &InherentData,
 		proof: Self::Proof,
 	) -> Result<(), Error>;
+
+	/// Revert the consensus data of the blocks after the block `hash`, on the branches ending with
+	/// `leaves`, before the blocks are reverted.
+	fn revert(
+		&self,
+		_hash: B::Hash,
+		_number: NumberFor<B>,
+		_leaves: Vec<B::Hash>,
+	) -> Result<(), Error> {
+		Ok(())
+	}
 }
@@ -24,7 +24,8 @@ use crate::{Error, LOG_TARGET};
 use codec::Encode;
 use sc_client_api::{AuxStore, UsageProvider};
 use sc_consensus_babe::{
-	authorship, find_pre_digest, BabeIntermediate, CompatibleDigestItem, Epoch, INTERMEDIATE_KEY,
+	authorship, aux_schema::load_epoch_changes, find_pre_digest, BabeIntermediate,
+	CompatibleDigestItem, Epoch, INTERMEDIATE_KEY,
 };
 use sc_consensus_epochs::{
 	descendent_query, EpochHeader, SharedEpochChanges, ViableEpochDescriptor,
@@ -44,7 +45,7 @@ use sp_consensus_slots::Slot;
 use sp_inherents::InherentData;
 use sp_runtime::{
 	generic::Digest,
-	traits::{Block as BlockT, Header},
+	traits::{Block as BlockT, Header, NumberFor},
 	DigestItem,
 };
 use sp_timestamp::TimestampInherentData;
@@ -315,4 +316,18 @@ where
 
 		Ok(())
 	}
+
+	fn revert(
+		&self,
+		hash: B::Hash,
+		number: NumberFor<B>,
+		leaves: Vec<B::Hash>,
+	) -> Result<(), Error> {
+		// the aux data is persisted first, the epoch changes shared with the block import are only
+		// replaced once it is written.
+		sc_consensus_babe::revert_to(self.client.clone(), leaves, hash, number)?;
+		let epoch_changes = load_epoch_changes::<B, C>(&*self.client, &self.config)?;
+		*self.epoch_changes.shared_data() = (*epoch_changes.shared_data()).clone();
+		Ok(())
+	}
 }
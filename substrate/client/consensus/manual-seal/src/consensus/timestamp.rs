@@ -20,6 +20,7 @@
 //! that expect this inherent.
 
 use crate::Error;
+use parking_lot::Mutex;
 use sc_client_api::{AuxStore, UsageProvider};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
@@ -45,11 +46,124 @@ use std::{
 ///
 /// It produces timestamp inherents that are increased by `slot_duration` whenever
 /// `provide_inherent_data` is called.
+///
+/// The time of the next block may be moved forward with a [`TimeTravel`] handle.
 pub struct SlotTimestampProvider {
 	// holds the unix millisecond timestamp for the most recent block
 	unix_millis: atomic::AtomicU64,
 	// configured slot_duration in the runtime
 	slot_duration: SlotDuration,
+	// configured epoch length in the runtime, in slots, for babe
+	epoch_length: Option<u64>,
+}
+
+/// Time jumps requested for the next block.
+#[derive(Default)]
+struct Jump {
+	// timestamp of the next block, if set
+	timestamp: Option<u64>,
+	// milliseconds to add to the time of the next block
+	millis: u64,
+	// slots to skip before the next block
+	slots: u64,
+	// epochs to skip before the next block
+	epochs: u64,
+}
+
+/// State shared by the clones of a [`TimeTravel`] handle.
+#[derive(Default)]
+struct TimeTravelState {
+	// jumps requested for the next block
+	jump: Jump,
+	// offset of the time of the blocks from the system time, for chains without slots
+	offset: i64,
+	// timestamp of the last block, for chains without slots
+	last: Option<u64>,
+}
+
+/// Handle to move the time of the next block, usually shared with the manual seal RPC.
+///
+/// The requested jumps are applied by the [`SlotTimestampProvider`] created for the next block
+/// with [`SlotTimestampProvider::with_time_travel`]. The slot of the Aura and BABE digests follows
+/// the timestamp, and the blocks after the next one keep increasing from the new time. Chains
+/// without slots use [`TimeTravel::timestamp_provider`] instead.
+#[derive(Clone, Default)]
+pub struct TimeTravel(Arc<Mutex<TimeTravelState>>);
+
+impl TimeTravel {
+	/// Set the timestamp of the next block, in milliseconds since the unix epoch.
+	pub fn set_next_timestamp(&self, timestamp: u64) {
+		self.0.lock().jump.timestamp = Some(timestamp);
+	}
+
+	/// Move the time of the next block forward by `millis` milliseconds.
+	pub fn increase_time(&self, millis: u64) {
+		let mut state = self.0.lock();
+		state.jump.millis = state.jump.millis.saturating_add(millis);
+	}
+
+	/// Skip `slots` slots before the next block.
+	pub fn advance_slots(&self, slots: u64) {
+		let mut state = self.0.lock();
+		state.jump.slots = state.jump.slots.saturating_add(slots);
+	}
+
+	/// Skip `epochs` BABE epochs before the next block.
+	pub fn advance_epochs(&self, epochs: u64) {
+		let mut state = self.0.lock();
+		state.jump.epochs = state.jump.epochs.saturating_add(epochs);
+	}
+
+	/// Create the timestamp inherent of the next block, for chains without slots.
+	///
+	/// The blocks follow the system time, moved by the jumps requested so far, and the timestamp of
+	/// each block is after the one of the previous block. Slots and epochs can't be advanced
+	/// without slots. The jumps are discarded if they can't be applied.
+	pub fn timestamp_provider(&self) -> Result<sp_timestamp::InherentDataProvider, Error> {
+		let now = unix_millis()?;
+		let mut state = self.0.lock();
+		let jump = std::mem::take(&mut state.jump);
+		if jump.slots > 0 || jump.epochs > 0 {
+			return Err(Error::StringError(
+				"Slots and epochs can only be advanced with Aura or BABE".into(),
+			))
+		}
+
+		let earliest = match state.last {
+			Some(last) => last.checked_add(1).ok_or_else(overflow)?,
+			None => 0,
+		};
+		let time = match jump.timestamp {
+			Some(timestamp) => not_before(timestamp, earliest)?,
+			None => now.checked_add_signed(state.offset).ok_or_else(overflow)?.max(earliest),
+		};
+		let time = time.checked_add(jump.millis).ok_or_else(overflow)?;
+		state.offset = i64::try_from(i128::from(time) - i128::from(now)).map_err(|_| overflow())?;
+		state.last = Some(time);
+		Ok(sp_timestamp::InherentDataProvider::new(time.into()))
+	}
+}
+
+/// Returns the system time in milliseconds since the unix epoch.
+fn unix_millis() -> Result<u64, Error> {
+	Ok(SystemTime::now()
+		.duration_since(SystemTime::UNIX_EPOCH)
+		.map_err(|err| Error::StringError(format!("{}", err)))?
+		.as_millis() as u64)
+}
+
+fn overflow() -> Error {
+	Error::StringError("The time of the next block overflows".into())
+}
+
+/// Checks that the timestamp requested for the next block isn't before `earliest`.
+fn not_before(timestamp: u64, earliest: u64) -> Result<u64, Error> {
+	if timestamp < earliest {
+		return Err(Error::StringError(format!(
+			"The timestamp of the next block can't be before {earliest}, got {timestamp}"
+		)))
+	}
+	Ok(timestamp)
 }
 
 impl SlotTimestampProvider {
@@ -60,7 +174,8 @@ impl SlotTimestampProvider {
 		C: AuxStore + HeaderBackend<B> + ProvideRuntimeApi<B> + UsageProvider<B>,
 		C::Api: BabeApi<B>,
 	{
-		let slot_duration = sc_consensus_babe::configuration(&*client)?.slot_duration();
+		let config = sc_consensus_babe::configuration(&*client)?;
+		let slot_duration = config.slot_duration();
 
 		let time = Self::with_header(&client, slot_duration, |header| {
 			let slot_number = *sc_consensus_babe::find_pre_digest::<B>(&header)
@@ -69,7 +184,11 @@ impl SlotTimestampProvider {
 			Ok(slot_number)
 		})?;
 
-		Ok(Self { unix_millis: atomic::AtomicU64::new(time), slot_duration })
+		Ok(Self {
+			unix_millis: atomic::AtomicU64::new(time),
+			slot_duration,
+			epoch_length: Some(config.epoch_length),
+		})
 	}
 
 	/// Create a new mocked time stamp provider, for aura
@@ -87,7 +206,36 @@ impl SlotTimestampProvider {
 			Ok(slot_number)
 		})?;
 
-		Ok(Self { unix_millis: atomic::AtomicU64::new(time), slot_duration })
+		Ok(Self { unix_millis: atomic::AtomicU64::new(time), slot_duration, epoch_length: None })
+	}
+
+	/// Apply the time jumps requested with `time_travel` to the next block.
+	///
+	/// The timestamp of the next block can't be set before its default time, one slot after its
+	/// parent. The jumps are discarded if they can't be applied.
+	pub fn with_time_travel(self, time_travel: &TimeTravel) -> Result<Self, Error> {
+		let jump = std::mem::take(&mut time_travel.0.lock().jump);
+		let epoch_slots = match self.epoch_length {
+			Some(epoch_length) => jump.epochs.checked_mul(epoch_length).ok_or_else(overflow)?,
+			None if jump.epochs > 0 =>
+				return Err(Error::StringError("Epochs can only be advanced with BABE".into())),
+			None => 0,
+		};
+
+		let earliest = self.unix_millis.load(atomic::Ordering::SeqCst);
+		let time = match jump.timestamp {
+			Some(timestamp) => not_before(timestamp, earliest)?,
+			None => earliest,
+		};
+		let time = jump
+			.slots
+			.checked_add(epoch_slots)
+			.and_then(|slots| slots.checked_mul(self.slot_duration.as_millis()))
+			.and_then(|millis| millis.checked_add(jump.millis))
+			.and_then(|millis| millis.checked_add(time))
+			.ok_or_else(overflow)?;
+		self.unix_millis.store(time, atomic::Ordering::SeqCst);
+		Ok(self)
 	}
 
 	fn with_header<F, C, B>(
@@ -113,10 +261,7 @@ impl SlotTimestampProvider {
 			(slot * slot_duration.as_millis() as u64) + slot_duration.as_millis() as u64
 		} else {
 			// this is the first block, use the correct time.
-			let now = SystemTime::now();
-			now.duration_since(SystemTime::UNIX_EPOCH)
-				.map_err(|err| Error::StringError(format!("{}", err)))?
-				.as_millis() as u64
+			unix_millis()?
 		};
 
 		Ok(time)
@@ -159,3 +304,91 @@ impl InherentDataProvider for SlotTimestampProvider {
 		None
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn slot_provider(epoch_length: Option<u64>) -> SlotTimestampProvider {
+		SlotTimestampProvider {
+			unix_millis: atomic::AtomicU64::new(60_000),
+			slot_duration: SlotDuration::from_millis(6_000),
+			epoch_length,
+		}
+	}
+
+	#[test]
+	fn time_travel_moves_the_next_block() {
+		let time_travel = TimeTravel::default();
+		assert_eq!(
+			slot_provider(None).with_time_travel(&time_travel).unwrap().slot(),
+			Slot::from(10)
+		);
+
+		time_travel.increase_time(12_000);
+		time_travel.advance_slots(3);
+		let provider = slot_provider(None).with_time_travel(&time_travel).unwrap();
+		assert_eq!(provider.timestamp(), sp_timestamp::Timestamp::new(90_000));
+		assert_eq!(provider.slot(), Slot::from(15));
+
+		// The jumps are only applied to the next block.
+		let provider = provider.with_time_travel(&time_travel).unwrap();
+		assert_eq!(provider.slot(), Slot::from(15));
+
+		time_travel.set_next_timestamp(600_000);
+		time_travel.advance_slots(1);
+		let provider = provider.with_time_travel(&time_travel).unwrap();
+		assert_eq!(provider.slot(), Slot::from(101));
+	}
+
+	#[test]
+	fn epochs_are_only_advanced_with_babe() {
+		let time_travel = TimeTravel::default();
+		time_travel.advance_epochs(2);
+		assert!(slot_provider(None).with_time_travel(&time_travel).is_err());
+
+		let provider = slot_provider(Some(10)).with_time_travel(&time_travel).unwrap();
+		assert_eq!(provider.slot(), Slot::from(30));
+	}
+
+	#[test]
+	fn time_travel_rejects_invalid_jumps() {
+		let time_travel = TimeTravel::default();
+		// The next block can't be before its parent.
+		time_travel.set_next_timestamp(59_999);
+		assert!(slot_provider(None).with_time_travel(&time_travel).is_err());
+		// The jumps were discarded.
+		assert_eq!(
+			slot_provider(None).with_time_travel(&time_travel).unwrap().slot(),
+			Slot::from(10)
+		);
+
+		time_travel.increase_time(u64::MAX);
+		assert!(slot_provider(None).with_time_travel(&time_travel).is_err());
+		time_travel.advance_epochs(u64::MAX);
+		assert!(slot_provider(Some(10)).with_time_travel(&time_travel).is_err());
+	}
+
+	#[test]
+	fn time_travel_without_slots() {
+		let time_travel = TimeTravel::default();
+		let timestamp = |time_travel: &TimeTravel| {
+			u64::from(time_travel.timestamp_provider().unwrap().timestamp())
+		};
+
+		let first = timestamp(&time_travel);
+		time_travel.increase_time(3_600_000);
+		let second = timestamp(&time_travel);
+		assert!(second >= first + 3_600_000);
+		// The offset is kept for the next blocks.
+		let third = timestamp(&time_travel);
+		assert!(third > second);
+
+		time_travel.set_next_timestamp(third);
+		assert!(time_travel.timestamp_provider().is_err());
+		time_travel.advance_slots(1);
+		assert!(time_travel.timestamp_provider().is_err());
+		time_travel.set_next_timestamp(third + 10);
+		assert_eq!(timestamp(&time_travel), third + 10);
+	}
+}
@@ -35,6 +35,7 @@ mod codes {
 	pub const CONSENSUS_ERROR: i32 = 14_000;
 	pub const INHERENTS_ERROR: i32 = 15_000;
 	pub const BLOCKCHAIN_ERROR: i32 = 16_000;
+	pub const UNSUPPORTED: i32 = 17_000;
 	pub const UNKNOWN_ERROR: i32 = 20_000;
 }
 
@@ -61,6 +62,9 @@ pub enum Error {
 	/// Supplied parent_hash doesn't exist in chain
 	#[error("Supplied parent_hash: {0} doesn't exist in chain")]
 	BlockNotFound(String),
+	/// The feature was not enabled when starting the engine
+	#[error("{0} is not enabled on this node")]
+	Unsupported(&'static str),
 	/// Some string error
 	#[error("{0}")]
 	StringError(String),
@@ -97,6 +101,7 @@ impl Error {
 			ConsensusError(_) => codes::CONSENSUS_ERROR,
 			InherentError(_) => codes::INHERENTS_ERROR,
 			BlockchainError(_) => codes::BLOCKCHAIN_ERROR,
+			Unsupported(_) => codes::UNSUPPORTED,
 			SendError(_) | Canceled(_) => codes::SERVER_SHUTTING_DOWN,
 			_ => codes::UNKNOWN_ERROR,
 		}
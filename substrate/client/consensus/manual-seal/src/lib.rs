@@ -39,6 +39,7 @@ use std::{marker::PhantomData, sync::Arc, time::Duration};
 
 mod error;
 mod finalize_block;
+mod revert_block;
 mod seal_block;
 
 pub mod consensus;
@@ -48,6 +49,7 @@ pub use self::{
 	consensus::ConsensusDataProvider,
 	error::Error,
 	finalize_block::{finalize_block, FinalizeBlockParams},
+	revert_block::{revert_block, RevertBlockParams},
 	rpc::{CreatedBlock, EngineCommand},
 	seal_block::{seal_block, SealBlockParams, MAX_PROPOSAL_DURATION},
 };
@@ -87,7 +89,7 @@ where
 }
 
 /// Params required to start the manual sealing authorship task.
-pub struct ManualSealParams<B: BlockT, BI, E, C: ProvideRuntimeApi<B>, TP, SC, CS, CIDP, P> {
+pub struct ManualSealParams<B: BlockT, BI, E, C: ProvideRuntimeApi<B>, CB, TP, SC, CS, CIDP, P> {
 	/// Block import instance.
 	pub block_import: BI,
 
@@ -97,6 +99,10 @@ pub struct ManualSealParams<B: BlockT, BI, E, C: ProvideRuntimeApi<B>, TP, SC, C
 	/// Client instance
 	pub client: Arc<C>,
 
+	/// Backend instance, to revert blocks with [`EngineCommand::RevertTo`]. Reverting is not
+	/// supported when `None`.
+	pub backend: Option<Arc<CB>>,
+
 	/// Shared reference to the transaction pool.
 	pub pool: Arc<TP>,
 
@@ -156,12 +162,13 @@ pub async fn run_manual_seal<B, BI, CB, E, C, TP, SC, CS, CIDP, P>(
 		mut block_import,
 		mut env,
 		client,
+		backend,
 		pool,
 		mut commands_stream,
 		select_chain,
 		consensus_data_provider,
 		create_inherent_data_providers,
-	}: ManualSealParams<B, BI, E, C, TP, SC, CS, CIDP, P>,
+	}: ManualSealParams<B, BI, E, C, CB, TP, SC, CS, CIDP, P>,
 ) where
 	B: BlockT + 'static,
 	BI: BlockImport<B, Error = sp_consensus::Error> + Send + Sync + 'static,
@@ -204,6 +211,15 @@ pub async fn run_manual_seal<B, BI, CB, E, C, TP, SC, CS, CIDP, P>(
 				})
 				.await
 			},
+			EngineCommand::RevertTo { hash, sender } =>
+				revert_block(RevertBlockParams {
+					hash,
+					sender,
+					client: client.clone(),
+					backend: backend.clone(),
+					consensus_data_provider: consensus_data_provider.as_deref(),
+				})
+				.await,
 		}
 	}
 }
@@ -246,6 +262,7 @@ pub async fn run_instant_seal<B, BI, CB, E, C, TP, SC, CIDP, P>(
 		block_import,
 		env,
 		client,
+		backend: None,
 		pool,
 		commands_stream,
 		select_chain,
@@ -296,6 +313,7 @@ pub async fn run_instant_seal_and_finalize<B, BI, CB, E, C, TP, SC, CIDP, P>(
 		block_import,
 		env,
 		client,
+		backend: None,
 		pool,
 		commands_stream,
 		select_chain,
@@ -435,6 +453,7 @@ mod tests {
 			block_import: client.clone(),
 			env,
 			client: client.clone(),
+			backend: None,
 			pool: pool.clone(),
 			commands_stream,
 			select_chain,
@@ -514,6 +533,7 @@ mod tests {
 			commands_stream,
 			env,
 			client: client.clone(),
+			backend: None,
 			pool: pool.clone(),
 			select_chain,
 			create_inherent_data_providers: |_, _| async { Ok(()) },
@@ -589,6 +609,7 @@ mod tests {
 			block_import: client.clone(),
 			env,
 			client: client.clone(),
+			backend: None,
 			pool: pool.clone(),
 			commands_stream,
 			select_chain,
@@ -673,6 +694,7 @@ mod tests {
 			block_import: client.clone(),
 			env,
 			client: client.clone(),
+			backend: None,
 			pool: pool.clone(),
 			commands_stream,
 			select_chain,
@@ -777,6 +799,7 @@ mod tests {
 			block_import: client.clone(),
 			env,
 			client: client.clone(),
+			backend: None,
 			pool: pool.clone(),
 			commands_stream,
 			select_chain,
@@ -800,4 +823,62 @@ mod tests {
 		let header = client.header(created_block.hash).unwrap().unwrap();
 		assert_eq!(header.number, 1);
 	}
+
+	#[tokio::test]
+	async fn manual_seal_create_blocks_and_revert() {
+		use rpc::{ManualSeal, ManualSealApiServer, MAX_BLOCKS_PER_CALL};
+
+		let builder = TestClientBuilder::new();
+		let backend = builder.backend();
+		let (client, select_chain) = builder.build_with_longest_chain();
+		let client = Arc::new(client);
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let genesis_hash = client.info().genesis_hash;
+		let pool_api = Arc::new(FullChainApi::new(client.clone(), None, &spawner.clone()));
+		let pool = Arc::new(BasicPool::with_revalidation_type(
+			Options::default(),
+			true.into(),
+			pool_api,
+			None,
+			RevalidationType::Full,
+			spawner.clone(),
+			0,
+			genesis_hash,
+			genesis_hash,
+		));
+		let env = ProposerFactory::new(spawner.clone(), client.clone(), pool.clone(), None, None);
+		let (sink, commands_stream) = futures::channel::mpsc::channel(1024);
+
+		// spawn the background authorship task
+		tokio::spawn(run_manual_seal(ManualSealParams {
+			block_import: client.clone(),
+			env,
+			client: client.clone(),
+			backend: Some(backend),
+			pool: pool.clone(),
+			commands_stream,
+			select_chain,
+			consensus_data_provider: None,
+			create_inherent_data_providers: |_, _| async { Ok(()) },
+		}));
+		let rpc = ManualSeal::new(sink);
+
+		let blocks = rpc.create_blocks(3, false).await.unwrap();
+		assert_eq!(blocks.len(), 3);
+		assert_eq!(client.info().best_number, 3);
+		assert_eq!(client.info().best_hash, blocks[2].hash);
+		assert!(rpc.create_blocks(MAX_BLOCKS_PER_CALL + 1, false).await.is_err());
+
+		// revert to the first block, the next blocks are built on top of it.
+		assert!(rpc.revert_to(blocks[0].hash).await.unwrap());
+		assert_eq!(client.info().best_number, 1);
+		assert_eq!(client.info().best_hash, blocks[0].hash);
+		let block = rpc.create_blocks(1, false).await.unwrap().remove(0);
+		assert_eq!(client.header(block.hash).unwrap().unwrap().number, 2);
+
+		// blocks out of the best chain can't be reverted to.
+		assert!(rpc.revert_to(blocks[2].hash).await.is_err());
+		// time travel is not enabled.
+		assert!(rpc.increase_time(1_000).is_err());
+	}
 }
@@ -0,0 +1,74 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Block revert utilities
+
+use crate::{rpc, ConsensusDataProvider, Error};
+use sc_client_api::backend::Backend as ClientBackend;
+use sp_blockchain::{Backend as _, HeaderBackend};
+use sp_runtime::traits::Block as BlockT;
+use std::sync::Arc;
+
+/// params for reverting the best chain.
+pub struct RevertBlockParams<'a, B: BlockT, C, CB, P> {
+	/// hash of the block to revert the best chain to
+	pub hash: <B as BlockT>::Hash,
+	/// sender to report errors/success to the rpc.
+	pub sender: rpc::Sender<()>,
+	/// header backend
+	pub client: Arc<C>,
+	/// backend to revert the blocks from, if reverting is enabled.
+	pub backend: Option<Arc<CB>>,
+	/// Digest provider whose consensus data is reverted with the blocks.
+	pub consensus_data_provider: Option<&'a dyn ConsensusDataProvider<B, Proof = P>>,
+}
+
+/// reverts the best chain to the block with the given hash, including finalized blocks.
+pub async fn revert_block<B, C, CB, P>(
+	RevertBlockParams { hash, mut sender, client, backend, consensus_data_provider }: RevertBlockParams<
+		'_,
+		B,
+		C,
+		CB,
+		P,
+	>,
+) where
+	B: BlockT,
+	C: HeaderBackend<B>,
+	CB: ClientBackend<B>,
+{
+	let revert = || -> Result<(), Error> {
+		let backend = backend.ok_or(Error::Unsupported("Reverting blocks"))?;
+		let number =
+			client.number(hash)?.ok_or_else(|| Error::BlockNotFound(format!("{}", hash)))?;
+		if client.hash(number)? != Some(hash) {
+			return Err(Error::StringError(format!("Block {} is not in the best chain", hash)))
+		}
+
+		// The consensus data is reverted first, as it needs the headers of the reverted blocks. It
+		// is reverted again if reverting the blocks fails and the revert is retried.
+		if let Some(digest_provider) = consensus_data_provider {
+			digest_provider.revert(hash, number, backend.blockchain().leaves()?)?;
+		}
+		let (reverted, _) = backend.revert(client.info().best_number - number, true)?;
+		log::info!("⏪ Reverted {} blocks to block: {}", reverted, hash);
+		Ok(())
+	};
+
+	rpc::send_result(&mut sender, revert())
+}
@@ -18,7 +18,7 @@
 
 //! RPC interface for the `ManualSeal` Engine.
 
-use crate::error::Error;
+use crate::{consensus::timestamp::TimeTravel, error::Error};
 use futures::{
 	channel::{mpsc, oneshot},
 	SinkExt,
@@ -31,6 +31,9 @@ use sp_runtime::EncodedJustification;
 /// Sender passed to the authorship task to report errors or successes.
 pub type Sender<T> = Option<oneshot::Sender<std::result::Result<T, Error>>>;
 
+/// Maximum number of blocks created by a single `engine_createBlocks` call.
+pub const MAX_BLOCKS_PER_CALL: u32 = 1024;
+
 /// Message sent to the background authorship task, usually by RPC.
 pub enum EngineCommand<Hash> {
 	/// Tells the engine to propose a new block
@@ -59,6 +62,14 @@ pub enum EngineCommand<Hash> {
 		/// finalization justification
 		justification: Option<EncodedJustification>,
 	},
+	/// Tells the engine to revert the best chain to the block with the supplied hash, including
+	/// finalized blocks.
+	RevertTo {
+		/// hash of the block
+		hash: Hash,
+		/// sender to report errors/success to the rpc.
+		sender: Sender<()>,
+	},
 }
 
 /// RPC trait that provides methods for interacting with the manual-seal authorship task over rpc.
@@ -80,11 +91,41 @@ pub trait ManualSealApi<Hash> {
 		hash: Hash,
 		justification: Option<EncodedJustification>,
 	) -> Result<bool, Error>;
+
+	/// Instructs the manual-seal authorship task to create `count` blocks on top of the best
+	/// block, empty if there are no transactions in the pool
+	#[method(name = "engine_createBlocks")]
+	async fn create_blocks(
+		&self,
+		count: u32,
+		finalize: bool,
+	) -> Result<Vec<CreatedBlock<Hash>>, Error>;
+
+	/// Instructs the manual-seal authorship task to revert the best chain to a block
+	#[method(name = "engine_revertTo")]
+	async fn revert_to(&self, hash: Hash) -> Result<bool, Error>;
+
+	/// Sets the timestamp of the next block, in milliseconds since the unix epoch
+	#[method(name = "engine_setNextBlockTimestamp")]
+	fn set_next_block_timestamp(&self, timestamp: u64) -> Result<bool, Error>;
+
+	/// Moves the time of the next block forward by `millis` milliseconds
+	#[method(name = "engine_increaseTime")]
+	fn increase_time(&self, millis: u64) -> Result<bool, Error>;
+
+	/// Skips `slots` slots before the next block
+	#[method(name = "engine_advanceSlots")]
+	fn advance_slots(&self, slots: u64) -> Result<bool, Error>;
+
+	/// Skips `epochs` BABE epochs before the next block
+	#[method(name = "engine_advanceEpochs")]
+	fn advance_epochs(&self, epochs: u64) -> Result<bool, Error>;
 }
 
 /// A struct that implements the [`ManualSealApiServer`].
 pub struct ManualSeal<Hash> {
 	import_block_channel: mpsc::Sender<EngineCommand<Hash>>,
+	time_travel: Option<TimeTravel>,
 }
 
 /// return type of `engine_createBlock`
@@ -101,7 +142,19 @@ pub struct CreatedBlock<Hash> {
 impl<Hash> ManualSeal<Hash> {
 	/// Create new `ManualSeal` with the given reference to the client.
 	pub fn new(import_block_channel: mpsc::Sender<EngineCommand<Hash>>) -> Self {
-		Self { import_block_channel }
+		Self { import_block_channel, time_travel: None }
+	}
+
+	/// Enable the time travel methods, moving the time of the blocks with `time_travel`.
+	///
+	/// The same handle must be applied to the
+	/// [`SlotTimestampProvider`](crate::consensus::timestamp::SlotTimestampProvider) of each block.
+	pub fn with_time_travel(self, time_travel: TimeTravel) -> Self {
+		Self { time_travel: Some(time_travel), ..self }
+	}
+
+	fn time_travel(&self) -> Result<&TimeTravel, Error> {
+		self.time_travel.as_ref().ok_or(Error::Unsupported("Time travel"))
 	}
 }
 
@@ -143,6 +196,50 @@ impl<Hash: Send + 'static> ManualSealApiServer<Hash> for ManualSeal<Hash> {
 		sink.send(command).await?;
 		receiver.await.map(|_| true).map_err(Into::into)
 	}
+
+	async fn create_blocks(
+		&self,
+		count: u32,
+		finalize: bool,
+	) -> Result<Vec<CreatedBlock<Hash>>, Error> {
+		if count > MAX_BLOCKS_PER_CALL {
+			return Err(Error::StringError(format!(
+				"Cannot create more than {MAX_BLOCKS_PER_CALL} blocks at once"
+			)))
+		}
+		let mut blocks = Vec::with_capacity(count as usize);
+		for _ in 0..count {
+			blocks.push(self.create_block(true, finalize, None).await?);
+		}
+		Ok(blocks)
+	}
+
+	async fn revert_to(&self, hash: Hash) -> Result<bool, Error> {
+		let mut sink = self.import_block_channel.clone();
+		let (sender, receiver) = oneshot::channel();
+		sink.send(EngineCommand::RevertTo { hash, sender: Some(sender) }).await?;
+		receiver.await?.map(|_| true)
+	}
+
+	fn set_next_block_timestamp(&self, timestamp: u64) -> Result<bool, Error> {
+		self.time_travel()?.set_next_timestamp(timestamp);
+		Ok(true)
+	}
+
+	fn increase_time(&self, millis: u64) -> Result<bool, Error> {
+		self.time_travel()?.increase_time(millis);
+		Ok(true)
+	}
+
+	fn advance_slots(&self, slots: u64) -> Result<bool, Error> {
+		self.time_travel()?.advance_slots(slots);
+		Ok(true)
+	}
+
+	fn advance_epochs(&self, epochs: u64) -> Result<bool, Error> {
+		self.time_travel()?.advance_epochs(epochs);
+		Ok(true)
+	}
 }
 
 /// report any errors or successes encountered by the authorship task back
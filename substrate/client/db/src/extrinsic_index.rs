@@ -0,0 +1,157 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Index of the blocks containing an extrinsic, by extrinsic hash.
+//!
+//! The index is only maintained if enabled in the [`DatabaseSettings`](crate::DatabaseSettings),
+//! for the blocks imported with a body. Entries of reverted blocks and of abandoned forks are
+//! not removed from the index, they are skipped on lookup instead.
+
+use crate::{columns, BlockchainDb, DbHash};
+use codec::{Decode, Encode};
+use sp_blockchain::{Error as ClientError, HeaderBackend, Result as ClientResult};
+use sp_database::{Database, Transaction};
+use sp_runtime::traits::{Block as BlockT, Hash, HashingFor, Header as HeaderT};
+use std::collections::{hash_map::Entry, HashMap};
+
+/// Read the indexed locations of the extrinsic `hash`.
+fn read_locations<Block: BlockT>(
+	db: &dyn Database<DbHash>,
+	hash: &Block::Hash,
+) -> ClientResult<Vec<(Block::Hash, u32)>> {
+	match db.get(columns::EXTRINSIC_INDEX, hash.as_ref()) {
+		Some(locations) => Decode::decode(&mut &locations[..]).map_err(|err| {
+			ClientError::Backend(format!("Error decoding extrinsic locations: {err}"))
+		}),
+		None => Ok(Vec::new()),
+	}
+}
+
+/// Add the extrinsics of the body of the block `hash` to the index.
+pub(crate) fn index_body<Block: BlockT>(
+	transaction: &mut Transaction<DbHash>,
+	db: &dyn Database<DbHash>,
+	hash: Block::Hash,
+	body: &[Block::Extrinsic],
+) -> ClientResult<()> {
+	// The same extrinsic may be included more than once in a block.
+	let mut updated = HashMap::new();
+	for (index, extrinsic) in body.iter().enumerate() {
+		let extrinsic_hash = HashingFor::<Block>::hash_of(extrinsic);
+		let locations = match updated.entry(extrinsic_hash) {
+			Entry::Occupied(entry) => entry.into_mut(),
+			Entry::Vacant(entry) => entry.insert(read_locations::<Block>(db, &extrinsic_hash)?),
+		};
+		let location = (hash, index as u32);
+		// The block may be imported again.
+		if !locations.contains(&location) {
+			locations.push(location);
+		}
+	}
+
+	for (extrinsic_hash, locations) in updated {
+		transaction.set_from_vec(
+			columns::EXTRINSIC_INDEX,
+			extrinsic_hash.as_ref(),
+			locations.encode(),
+		);
+	}
+	Ok(())
+}
+
+impl<Block: BlockT> BlockchainDb<Block> {
+	/// Returns the indexed locations of the extrinsic `hash` in the finalized chain and in the
+	/// unfinalized blocks.
+	pub(crate) fn indexed_extrinsic_locations(
+		&self,
+		hash: Block::Hash,
+	) -> ClientResult<Vec<(Block::Hash, u32)>> {
+		let finalized_number = self.info().finalized_number;
+		let mut locations = Vec::new();
+		for (block_hash, index) in read_locations::<Block>(&*self.db, &hash)? {
+			let Some(header) = self.header(block_hash)? else { continue };
+			let number = *header.number();
+			if number > finalized_number || self.hash(number)? == Some(block_hash) {
+				locations.push((block_hash, index));
+			}
+		}
+		Ok(locations)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		tests::{insert_block, Block, UncheckedXt},
+		Backend,
+	};
+	use sc_client_api::backend::Backend as _;
+	use sp_blockchain::Backend as _;
+	use sp_core::H256;
+	use sp_runtime::traits::{Hash, HashingFor};
+
+	#[test]
+	fn extrinsics_are_found_by_hash() {
+		let backend = Backend::<Block>::new_test_with_extrinsic_index();
+		let xt = |i: u64| UncheckedXt::new_transaction(i.into(), ());
+		let hash_of = |xt: &UncheckedXt| HashingFor::<Block>::hash_of(xt);
+
+		let genesis =
+			insert_block(&backend, 0, H256::default(), None, H256::default(), vec![], None)
+				.unwrap();
+		let block1 =
+			insert_block(&backend, 1, genesis, None, H256::default(), vec![xt(1), xt(2)], None)
+				.unwrap();
+		let block2 =
+			insert_block(&backend, 2, block1, None, H256::default(), vec![xt(2), xt(3)], None)
+				.unwrap();
+		// A fork of block 2 including the same extrinsic.
+		let fork2 = insert_block(&backend, 2, block1, None, H256::from([1; 32]), vec![xt(3)], None)
+			.unwrap();
+
+		let blockchain = backend.blockchain();
+		assert_eq!(
+			blockchain.extrinsic_locations(hash_of(&xt(1))).unwrap(),
+			Some(vec![(block1, 0)])
+		);
+		assert_eq!(
+			blockchain.extrinsic_locations(hash_of(&xt(2))).unwrap(),
+			Some(vec![(block1, 1), (block2, 0)])
+		);
+		assert_eq!(
+			blockchain.extrinsic_locations(hash_of(&xt(3))).unwrap(),
+			Some(vec![(block2, 1), (fork2, 0)])
+		);
+		assert_eq!(blockchain.extrinsic_locations(hash_of(&xt(4))).unwrap(), Some(vec![]));
+
+		// The abandoned fork is skipped once block 2 is finalized.
+		backend.finalize_block(block1, None).unwrap();
+		backend.finalize_block(block2, None).unwrap();
+		assert_eq!(
+			blockchain.extrinsic_locations(hash_of(&xt(3))).unwrap(),
+			Some(vec![(block2, 1)])
+		);
+
+		let backend = Backend::<Block>::new_test(1000, 0);
+		let block =
+			insert_block(&backend, 0, H256::default(), None, H256::default(), vec![xt(1)], None)
+				.unwrap();
+		assert_eq!(backend.blockchain().extrinsic_locations(hash_of(&xt(1))).unwrap(), None);
+		assert!(backend.blockchain().body(block).unwrap().is_some());
+	}
+}
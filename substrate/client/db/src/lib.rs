@@ -31,12 +31,17 @@
 pub mod offchain;
 
 pub mod bench;
+pub mod snapshot;
+pub mod state_check;
 
 mod children;
+mod extrinsic_index;
 mod parity_db;
 mod pinned_blocks_cache;
+mod pruning_migration;
 mod record_stats_state;
 mod stats;
+mod trie_walker;
 #[cfg(any(feature = "rocksdb", test))]
 mod upgrade;
 mod utils;
@@ -96,7 +101,7 @@ use sp_trie::{cache::SharedTrieCache, prefixed_key, MemoryDB, MerkleValue, Prefi
 use utils::BLOCK_GAP_CURRENT_VERSION;
 
 // Re-export the Database trait so that one can pass an implementation of it.
-pub use sc_state_db::PruningMode;
+pub use sc_state_db::{PruningMigration, PruningMode};
 pub use sp_database::Database;
 
 pub use bench::BenchmarkingState;
@@ -313,6 +318,10 @@ pub struct DatabaseSettings {
 	///
 	/// NOTE: only finalized blocks are subject for removal!
 	pub blocks_pruning: BlocksPruning,
+	/// Maintain an index of the blocks containing each extrinsic, by extrinsic hash.
+	///
+	/// Only the blocks imported while the index is enabled are indexed.
+	pub extrinsic_index: bool,
 
 	/// Prometheus metrics registry.
 	pub metrics_registry: Option<Registry>,
@@ -442,6 +451,8 @@ pub(crate) mod columns {
 	/// Transactions
 	pub const TRANSACTION: u32 = 11;
 	pub const BODY_INDEX: u32 = 12;
+	/// Blocks containing the extrinsics, by extrinsic hash.
+	pub const EXTRINSIC_INDEX: u32 = 13;
 }
 
 struct PendingBlock<Block: BlockT> {
@@ -491,10 +502,11 @@ pub struct BlockchainDb<Block: BlockT> {
 	header_metadata_cache: Arc<HeaderMetadataCache<Block>>,
 	header_cache: Mutex<LinkedHashMap<Block::Hash, Option<Block::Header>>>,
 	pinned_blocks_cache: Arc<RwLock<PinnedBlocksCache<Block>>>,
+	extrinsic_index: bool,
 }
 
 impl<Block: BlockT> BlockchainDb<Block> {
-	fn new(db: Arc<dyn Database<DbHash>>) -> ClientResult<Self> {
+	fn new(db: Arc<dyn Database<DbHash>>, extrinsic_index: bool) -> ClientResult<Self> {
 		let meta = read_meta::<Block>(&*db, columns::HEADER)?;
 		let leaves = LeafSet::read_from_db(&*db, columns::META, meta_keys::LEAF_PREFIX)?;
 		Ok(BlockchainDb {
@@ -504,6 +516,7 @@ impl<Block: BlockT> BlockchainDb<Block> {
 			header_metadata_cache: Arc::new(HeaderMetadataCache::default()),
 			header_cache: Default::default(),
 			pinned_blocks_cache: Arc::new(RwLock::new(PinnedBlocksCache::new())),
+			extrinsic_index,
 		})
 	}
 
@@ -758,6 +771,16 @@ impl<Block: BlockT> sc_client_api::blockchain::Backend<Block> for BlockchainDb<B
 		Ok(self.db.contains(columns::TRANSACTION, hash.as_ref()))
 	}
 
+	fn extrinsic_locations(
+		&self,
+		hash: Block::Hash,
+	) -> ClientResult<Option<Vec<(Block::Hash, u32)>>> {
+		if !self.extrinsic_index {
+			return Ok(None)
+		}
+		self.indexed_extrinsic_locations(hash).map(Some)
+	}
+
 	fn block_indexed_body(&self, hash: Block::Hash) -> ClientResult<Option<Vec<Vec<u8>>>> {
 		let body = match read_db(
 			&*self.db,
@@ -894,6 +917,12 @@ impl<Block: BlockT> BlockImportOperation<Block> {
 		self.db_updates = transaction;
 		Ok(root)
 	}
+
+	/// Marks the state of the block as available, for trie nodes written to the database outside
+	/// of the operation.
+	pub(crate) fn set_commit_state(&mut self) {
+		self.commit_state = true;
+	}
 }
 
 impl<Block: BlockT> sc_client_api::backend::BlockImportOperation<Block>
@@ -1141,7 +1170,11 @@ impl<Block: BlockT> Backend<Block> {
 				Err(as_is) => return Err(as_is.into()),
 			};
 
-		Self::from_database(db as Arc<_>, canonicalization_delay, &db_config, needs_init)
+		let db = db as Arc<_>;
+		if !needs_init {
+			Self::start_requested_pruning_migration(&db, canonicalization_delay, &db_config)?;
+		}
+		Self::from_database(db, canonicalization_delay, &db_config, needs_init)
 	}
 
 	/// Reset the shared trie cache.
@@ -1175,12 +1208,30 @@ impl<Block: BlockT> Backend<Block> {
 			state_pruning: Some(state_pruning),
 			source: DatabaseSource::Custom { db, require_create_flag: true },
 			blocks_pruning,
+			extrinsic_index: false,
 			metrics_registry: None,
 		};
 
 		Self::new(db_setting, canonicalization_delay).expect("failed to create test-db")
 	}
 
+	/// Create new memory-backed archive client backend for tests, maintaining the extrinsic index.
+	#[cfg(any(test, feature = "test-helpers"))]
+	pub fn new_test_with_extrinsic_index() -> Self {
+		let db = kvdb_memorydb::create(crate::utils::NUM_COLUMNS);
+		let db = sp_database::as_database(db);
+		let db_setting = DatabaseSettings {
+			trie_cache_maximum_size: Some(16 * 1024 * 1024),
+			state_pruning: Some(PruningMode::ArchiveAll),
+			source: DatabaseSource::Custom { db, require_create_flag: true },
+			blocks_pruning: BlocksPruning::KeepAll,
+			extrinsic_index: true,
+			metrics_registry: None,
+		};
+
+		Self::new(db_setting, 0).expect("failed to create test-db")
+	}
+
 	/// Expose the Database that is used by this backend.
 	/// The second argument is the Column that stores the State.
 	///
@@ -1232,7 +1283,7 @@ impl<Block: BlockT> Backend<Block> {
 
 		let state_pruning_used = state_db.pruning_mode();
 		let is_archive_pruning = state_pruning_used.is_archive();
-		let blockchain = BlockchainDb::new(db.clone())?;
+		let blockchain = BlockchainDb::new(db.clone(), config.extrinsic_index)?;
 
 		let storage_db =
 			StorageDb { db: db.clone(), state_db, prefix_keys: !db.supports_ref_counting() };
@@ -1528,6 +1579,14 @@ impl<Block: BlockT> Backend<Block> {
 
 			transaction.set_from_vec(columns::HEADER, &lookup_key, pending_block.header.encode());
 			if let Some(body) = pending_block.body {
+				if self.blockchain.extrinsic_index {
+					extrinsic_index::index_body::<Block>(
+						&mut transaction,
+						&*self.storage.db,
+						hash,
+						&body,
+					)?;
+				}
 				// If we have any index operations we save block in the new format with indexed
 				// extrinsic headers Otherwise we save the body as a single blob.
 				if operation.index_ops.is_empty() {
@@ -2656,7 +2715,7 @@ pub(crate) mod tests {
 	const CONS0_ENGINE_ID: ConsensusEngineId = *b"CON0";
 	const CONS1_ENGINE_ID: ConsensusEngineId = *b"CON1";
 
-	type UncheckedXt = TestXt<MockCallU64, ()>;
+	pub(crate) type UncheckedXt = TestXt<MockCallU64, ()>;
 	pub(crate) type Block = RawBlock<UncheckedXt>;
 
 	pub fn insert_header(
@@ -2813,6 +2872,7 @@ pub(crate) mod tests {
 				state_pruning: Some(PruningMode::blocks_pruning(1)),
 				source: DatabaseSource::Custom { db: backing, require_create_flag: false },
 				blocks_pruning: BlocksPruning::KeepFinalized,
+				extrinsic_index: false,
 				metrics_registry: None,
 			},
 			0,
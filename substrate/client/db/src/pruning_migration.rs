@@ -0,0 +1,394 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Migration of an archive database to a constrained state pruning mode.
+//!
+//! The archive database has no pruning journal, so the trie nodes removed by each historical block
+//! are computed by comparing the state of the block with the state of its parent. A node is
+//! removed if the trie of the block doesn't contain it at the same position.
+//!
+//! The migration starts when a node opens an archive database with a constrained pruning mode.
+//! The database is switched to the requested mode right away and the historical state is pruned in
+//! the background while the node is running.
+
+use crate::{
+	apply_state_commit,
+	trie_walker::{child_root, StoredNode, TrieWalker},
+	Backend, DatabaseSettings, DbHash, StateMetaDb,
+};
+use log::{info, warn};
+use sc_client_api::backend::Backend as _;
+use sc_state_db::{PruningMigration, PruningMode, StateDb};
+use sp_blockchain::{Error as ClientError, HeaderBackend, Result as ClientResult};
+use sp_database::{Database, Transaction};
+use sp_runtime::{
+	traits::{Block as BlockT, HashingFor, Header as HeaderT, UniqueSaturatedInto},
+	SaturatedConversion,
+};
+use sp_trie::prefixed_key;
+use std::{collections::HashMap, sync::Arc};
+
+impl<Block: BlockT> Backend<Block> {
+	/// Start the state pruning migration if the database is archive and `config` requests a
+	/// constrained pruning mode.
+	pub(crate) fn start_requested_pruning_migration(
+		db: &Arc<dyn Database<DbHash>>,
+		canonicalization_delay: u64,
+		config: &DatabaseSettings,
+	) -> ClientResult<()> {
+		let Some(PruningMode::Constrained(_)) = config.state_pruning else { return Ok(()) };
+		let stored = StateDb::<Block::Hash, Vec<u8>, StateMetaDb>::stored_pruning_mode(
+			&StateMetaDb(db.clone()),
+		)
+		.map_err(ClientError::from_state_db)?;
+		if !stored.as_ref().map_or(false, PruningMode::is_archive) {
+			return Ok(())
+		}
+
+		warn!(
+			target: "db",
+			"✂️ Switching the archive database to state pruning, the historical state will be pruned",
+		);
+		// The database is opened with the stored pruning mode to switch it.
+		let archive_config = DatabaseSettings {
+			trie_cache_maximum_size: None,
+			state_pruning: None,
+			source: config.source.clone(),
+			blocks_pruning: config.blocks_pruning,
+			extrinsic_index: config.extrinsic_index,
+			metrics_registry: None,
+		};
+		let backend =
+			Self::from_database(db.clone(), canonicalization_delay, &archive_config, false)?;
+		backend.start_state_pruning_migration()?;
+		Ok(())
+	}
+
+	/// Start migrating the archive database to the constrained state pruning mode.
+	///
+	/// Unfinalized blocks of a [`PruningMode::ArchiveAll`] database are reverted, as they are not
+	/// tracked by the non-canonical overlay. The historical state is pruned in the background by
+	/// [`Backend::prune_migrated_state`] once the database is reopened with a constrained pruning
+	/// mode, which is why the backend is consumed.
+	///
+	/// Returns the historical blocks left to prune, if any.
+	fn start_state_pruning_migration(self) -> ClientResult<Option<PruningMigration>> {
+		let mode = self.storage.state_db.pruning_mode();
+		if !mode.is_archive() {
+			return Err(ClientError::Backend(format!(
+				"State pruning migration requires an archive database, found {mode:?}",
+			)))
+		}
+
+		let info = self.blockchain.info();
+		if mode == PruningMode::ArchiveAll && info.best_number > info.finalized_number {
+			let (reverted, _) = self.revert(info.best_number - info.finalized_number, false)?;
+			info!(target: "db", "Reverted {reverted} unfinalized blocks");
+		}
+
+		let (commit, migration) =
+			StateDb::<Block::Hash, Vec<u8>, StateMetaDb>::start_pruning_migration(
+				&StateMetaDb(self.storage.db.clone()),
+				(info.finalized_hash, info.finalized_number.saturated_into()),
+			)
+			.map_err(ClientError::from_state_db)?;
+
+		let mut transaction = Transaction::new();
+		apply_state_commit(&mut transaction, commit);
+		self.storage.db.commit(transaction)?;
+
+		Ok(migration)
+	}
+
+	/// Returns the progress of the state pruning migration, if any.
+	pub fn state_pruning_migration(&self) -> Option<PruningMigration> {
+		self.storage.state_db.pruning_migration()
+	}
+
+	/// Prune the next historical block of the state pruning migration.
+	///
+	/// Returns the progress of the migration or `None` if it is complete.
+	pub fn prune_migrated_state(&self) -> ClientResult<Option<PruningMigration>> {
+		let Some(migration) = self.storage.state_db.pruning_migration() else { return Ok(None) };
+		let number = migration.next;
+
+		// The state of the historical blocks doesn't change, so the removed nodes are computed
+		// without blocking imports. Blocks retained by then are checked as well.
+		let mut checked_roots = Vec::new();
+		let removed = self.removed_nodes(number)?;
+		let removed = self.drop_retained(removed, &mut checked_roots)?;
+
+		// Imports and finalization must not change the retained state until the removed nodes are
+		// committed, so only the blocks finalized in the meantime are left to check.
+		let _import_lock = self.import_lock.write();
+		if self.storage.state_db.pruning_migration() != Some(migration) {
+			return Ok(self.storage.state_db.pruning_migration())
+		}
+		let removed = self.drop_retained(removed, &mut checked_roots)?;
+
+		let mut keys = Vec::with_capacity(removed.len());
+		for node in removed {
+			let walker = TrieWalker::new(&self.storage, node.keyspace.as_deref());
+			let (packed, last) = walker.prefix(&node.path);
+			let mut key = prefixed_key::<HashingFor<Block>>(&node.hash, (&packed[..], last));
+			self.storage.db.sanitize_key(&mut key);
+			keys.push(key);
+		}
+
+		let commit = self
+			.storage
+			.state_db
+			.prune_migrated_block(number, keys)
+			.map_err(ClientError::from_state_db)?;
+		let mut transaction = Transaction::new();
+		apply_state_commit(&mut transaction, commit);
+		self.storage.db.commit(transaction)?;
+
+		Ok(self.storage.state_db.pruning_migration())
+	}
+
+	/// Returns the state root of the canonical block `number`.
+	fn canonical_state_root(&self, number: u64) -> ClientResult<Option<Block::Hash>> {
+		let Some(hash) = self.blockchain.hash(number.unique_saturated_into())? else {
+			return Ok(None)
+		};
+		Ok(self.blockchain.header(hash)?.map(|header| *header.state_root()))
+	}
+
+	/// Returns the nodes that block `number` removed from the state of its parent.
+	fn removed_nodes(&self, number: u64) -> ClientResult<Vec<StoredNode<Block::Hash>>> {
+		// Blocks imported by warp sync or without state have nothing to prune.
+		let (Some(old), Some(new)) =
+			(self.canonical_state_root(number - 1)?, self.canonical_state_root(number)?)
+		else {
+			return Ok(Vec::new())
+		};
+		let walker = TrieWalker::new(&self.storage, None);
+		if !walker.has_root(&old)? || !walker.has_root(&new)? {
+			return Ok(Vec::new())
+		}
+
+		let mut removed = Vec::new();
+		let mut child_tries = Vec::new();
+		walker.removed(&old, &new, &mut removed, &mut child_tries)?;
+		for (child_key, old_child_root) in child_tries {
+			let new_child_root = child_root(&walker, &new, &child_key)?;
+			let child_walker = TrieWalker::new(&self.storage, Some(&child_key));
+			child_walker.removed(
+				&old_child_root,
+				&new_child_root.unwrap_or(walker.empty_root),
+				&mut removed,
+				&mut Vec::new(),
+			)?;
+		}
+		Ok(removed)
+	}
+
+	/// Drops the nodes that are part of the state of a retained block whose state root is not in
+	/// `checked_roots`, which is extended with the roots checked.
+	fn drop_retained(
+		&self,
+		removed: Vec<StoredNode<Block::Hash>>,
+		checked_roots: &mut Vec<Block::Hash>,
+	) -> ClientResult<Vec<StoredNode<Block::Hash>>> {
+		// Nodes are addressed by their hash when the database counts references, so a node
+		// removed by the block is only released once. Otherwise the same node may have been
+		// inserted again by any later block, so it is kept if it is part of the state of any
+		// retained block. Nodes of the unfinalized blocks are either part of the state of the last
+		// canonicalized block or kept by the non-canonical overlay, which is checked by
+		// `StateDb::prune_migrated_block`.
+		if !self.storage.prefix_keys {
+			return Ok(removed)
+		}
+		let Some(retained) = self.storage.state_db.retained_canonical_blocks() else {
+			return Ok(removed)
+		};
+		let mut roots = Vec::new();
+		for number in retained {
+			if let Some(root) = self.canonical_state_root(number)? {
+				if !roots.contains(&root) && !checked_roots.contains(&root) {
+					roots.push(root);
+				}
+			}
+		}
+		if roots.is_empty() {
+			return Ok(removed)
+		}
+		let walker = TrieWalker::new(&self.storage, None);
+		let mut child_roots = HashMap::new();
+		let mut kept = Vec::with_capacity(removed.len());
+		'nodes: for node in removed {
+			for root in &roots {
+				let root = match &node.keyspace {
+					None => Some(*root),
+					Some(child_key) => *child_roots
+						.entry((*root, child_key.clone()))
+						.or_insert(child_root(&walker, root, child_key)?),
+				};
+				let child_walker = TrieWalker::new(&self.storage, node.keyspace.as_deref());
+				if let Some(root) = root {
+					if child_walker.contains(&root, &node)? {
+						continue 'nodes
+					}
+				}
+			}
+			kept.push(node);
+		}
+		checked_roots.extend(roots);
+		Ok(kept)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{tests::Block, BlocksPruning, DatabaseSource};
+	use sc_client_api::backend::{BlockImportOperation, NewBlockState};
+	use sp_core::H256;
+	use sp_runtime::{testing::Header, StateVersion, Storage};
+	use sp_state_machine::Backend as _;
+
+	fn insert_block(
+		backend: &Backend<Block>,
+		number: u64,
+		parent_hash: H256,
+		changes: Vec<(Vec<u8>, Option<Vec<u8>>)>,
+	) -> H256 {
+		let mut op = backend.begin_operation().unwrap();
+		backend.begin_state_operation(&mut op, parent_hash).unwrap();
+		let (root, overlay) = op
+			.old_state
+			.storage_root(changes.iter().map(|(k, v)| (&k[..], v.as_deref())), StateVersion::V1);
+		op.update_db_storage(overlay).unwrap();
+		let header = Header {
+			number,
+			parent_hash,
+			state_root: root,
+			digest: Default::default(),
+			extrinsics_root: Default::default(),
+		};
+		op.set_block_data(header.clone(), Some(vec![]), None, None, NewBlockState::Final)
+			.unwrap();
+		backend.commit_operation(op).unwrap();
+		header.hash()
+	}
+
+	fn insert_genesis(backend: &Backend<Block>) -> H256 {
+		let mut op = backend.begin_operation().unwrap();
+		let storage = Storage {
+			top: (0u8..64).map(|i| (vec![i], vec![i; 40])).collect(),
+			children_default: Default::default(),
+		};
+		let root = op.reset_storage(storage, StateVersion::V1).unwrap();
+		let genesis = Header {
+			number: 0,
+			parent_hash: Default::default(),
+			state_root: root,
+			digest: Default::default(),
+			extrinsics_root: Default::default(),
+		};
+		op.set_block_data(genesis.clone(), Some(vec![]), None, None, NewBlockState::Final)
+			.unwrap();
+		backend.commit_operation(op).unwrap();
+		genesis.hash()
+	}
+
+	#[test]
+	fn removed_nodes_match_the_state_changes() {
+		let backend = Backend::<Block>::new_test(1000, 0);
+		let genesis = insert_genesis(&backend);
+
+		let block1 = insert_block(&backend, 1, genesis, vec![(vec![1], Some(vec![2; 40]))]);
+		insert_block(&backend, 2, block1, vec![(vec![1], Some(vec![1; 40])), (vec![63], None)]);
+
+		let state_root = |number: u64| {
+			*backend
+				.blockchain
+				.header(backend.blockchain.hash(number).unwrap().unwrap())
+				.unwrap()
+				.unwrap()
+				.state_root()
+		};
+		let walker = TrieWalker::new(&backend.storage, None);
+		let removed = |number: u64| {
+			let mut removed = Vec::new();
+			walker
+				.removed(
+					&state_root(number - 1),
+					&state_root(number),
+					&mut removed,
+					&mut Vec::new(),
+				)
+				.unwrap();
+			removed
+		};
+
+		// Block 1 replaced the value node and the nodes on the path to the changed leaf.
+		let removed_by_1 = removed(1);
+		assert!(removed_by_1.iter().any(|node| node.is_value));
+		assert!(removed_by_1.iter().any(|node| node.path.is_empty()));
+		for (number, removed) in [(1, removed_by_1), (2, removed(2))] {
+			for node in &removed {
+				assert!(walker.contains(&state_root(number - 1), node).unwrap());
+				assert!(!walker.contains(&state_root(number), node).unwrap());
+			}
+		}
+
+		// The state of the genesis block is retained, so nothing removed by block 1 is pruned.
+		let mut checked_roots = Vec::new();
+		let kept = backend.drop_retained(backend.removed_nodes(1).unwrap(), &mut checked_roots);
+		assert!(kept.unwrap().is_empty());
+		assert!(checked_roots.contains(&state_root(0)));
+	}
+
+	#[test]
+	fn migration_starts_when_archive_database_is_opened_with_state_pruning() {
+		let archive = Backend::<Block>::new_test_with_tx_storage(BlocksPruning::KeepAll, 0);
+		let mut hash = insert_genesis(&archive);
+		for number in 1..=10u8 {
+			let changes = vec![(vec![number], Some(vec![number + 1; 40]))];
+			hash = insert_block(&archive, number.into(), hash, changes);
+		}
+		let db = archive.storage.db.clone();
+		drop(archive);
+
+		let backend = Backend::<Block>::new(
+			DatabaseSettings {
+				trie_cache_maximum_size: None,
+				state_pruning: Some(PruningMode::blocks_pruning(2)),
+				source: DatabaseSource::Custom { db, require_create_flag: false },
+				blocks_pruning: BlocksPruning::KeepAll,
+				extrinsic_index: false,
+				metrics_registry: None,
+			},
+			0,
+		)
+		.unwrap();
+		assert_eq!(backend.storage.state_db.pruning_mode(), PruningMode::blocks_pruning(2));
+		assert!(backend.state_pruning_migration().is_some());
+		while backend.prune_migrated_state().unwrap().is_some() {}
+
+		assert!(backend.have_state_at(hash, 10));
+		// The root node of the genesis state was deleted from the database.
+		let genesis = backend.blockchain.hash(0).unwrap().unwrap();
+		let genesis_root = *backend.blockchain.header(genesis).unwrap().unwrap().state_root();
+		let root_node =
+			sp_state_machine::Storage::get(backend.storage.as_ref(), &genesis_root, (&[], None));
+		assert!(root_node.unwrap().is_none());
+	}
+}
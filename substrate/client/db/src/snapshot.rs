@@ -0,0 +1,688 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Database snapshots.
+//!
+//! A snapshot consists of the state at a finalized block, the genesis header and the blocks
+//! leading to the finalized block. It is stored in a directory as a set of chunk files and a
+//! manifest holding the state root and the hashes of all chunks. The state is stored as the raw
+//! trie nodes of the main trie and of all child tries, so it is imported as is, whatever state
+//! version it was built with.
+//!
+//! The blocks are verified against the manifest before anything is written to the database. The
+//! trie nodes are written one chunk at a time and the state is verified by walking the trie from
+//! the state root of the manifest before any block is imported. An interrupted import can be
+//! resumed by importing the same snapshot again.
+//!
+//! Importing a snapshot results in a database that looks like it was warp synced to the
+//! snapshot block: the genesis state is not available and the blocks between genesis and the
+//! first block of the snapshot are missing until they are downloaded by block history sync.
+
+use crate::{columns, Backend, StorageDb};
+use codec::{Decode, DecodeAll, Encode};
+use hash_db::{HashDBRef, Prefix};
+use log::info;
+use sc_client_api::{
+	backend::{Backend as _, BlockImportOperation as _, NewBlockState},
+	TrieCacheContext,
+};
+use sp_blockchain::{Backend as _, Error as ClientError, HeaderBackend, Result as ClientResult};
+use sp_core::{
+	hashing::blake2_256,
+	storage::{well_known_keys, ChildInfo},
+};
+use sp_database::Transaction;
+use sp_runtime::{
+	traits::{
+		Block as BlockT, Hash as HashT, HashingFor, Header as HeaderT, NumberFor, One, Saturating,
+		Zero,
+	},
+	Justifications, StateVersion,
+};
+use sp_state_machine::{DBValue, Storage as _};
+use sp_trie::{empty_trie_root, prefixed_key, KeySpacedDB, LayoutV1};
+use std::{fs, io, path::Path};
+use trie_db::{
+	node::{Node, Value},
+	TrieDBBuilder, TrieDBNodeIterator,
+};
+
+/// Name of the manifest file of a snapshot.
+pub const MANIFEST_FILE: &str = "manifest";
+
+/// Current version of the snapshot format.
+const SNAPSHOT_VERSION: u32 = 2;
+
+/// Entry of a state chunk: the database prefix of a trie node and its encoding.
+type StateNode = (Vec<u8>, Option<u8>, Vec<u8>);
+
+/// Manifest of a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct SnapshotManifest<Block: BlockT> {
+	/// Version of the snapshot format.
+	pub version: u32,
+	/// Hash of the genesis block.
+	pub genesis_hash: Block::Hash,
+	/// Hash of the block the state belongs to.
+	pub hash: Block::Hash,
+	/// Number of the block the state belongs to.
+	pub number: NumberFor<Block>,
+	/// State root of the block the state belongs to.
+	pub state_root: Block::Hash,
+	/// Chunks holding the trie nodes of the state.
+	pub state_chunks: Vec<SnapshotChunk>,
+	/// Chunks holding the blocks, in ascending order.
+	pub block_chunks: Vec<SnapshotChunk>,
+}
+
+/// A chunk of a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct SnapshotChunk {
+	/// Name of the file holding the chunk, relative to the snapshot directory.
+	pub name: String,
+	/// `blake2_256` hash of the file content.
+	pub hash: [u8; 32],
+}
+
+/// A block stored in a snapshot.
+#[derive(Encode, Decode)]
+struct SnapshotBlock<Block: BlockT> {
+	header: Block::Header,
+	body: Option<Vec<Block::Extrinsic>>,
+	justifications: Option<Justifications>,
+}
+
+/// Writes items into chunk files of bounded size.
+struct ChunkWriter<'a, T> {
+	dir: &'a Path,
+	kind: &'static str,
+	max_size: usize,
+	items: Vec<T>,
+	size: usize,
+	chunks: Vec<SnapshotChunk>,
+}
+
+impl<'a, T: Encode> ChunkWriter<'a, T> {
+	fn new(dir: &'a Path, kind: &'static str, max_size: usize) -> Self {
+		Self { dir, kind, max_size, items: Vec::new(), size: 0, chunks: Vec::new() }
+	}
+
+	fn push(&mut self, item: T) -> ClientResult<()> {
+		self.size += item.encoded_size();
+		self.items.push(item);
+		if self.size >= self.max_size {
+			self.flush()?;
+		}
+		Ok(())
+	}
+
+	fn flush(&mut self) -> ClientResult<()> {
+		if self.items.is_empty() {
+			return Ok(())
+		}
+
+		let data = self.items.encode();
+		let name = format!("{}-{:05}.chunk", self.kind, self.chunks.len());
+		let path = self.dir.join(&name);
+		fs::write(&path, &data).map_err(|e| io_error(&path, e))?;
+		self.chunks.push(SnapshotChunk { name, hash: blake2_256(&data) });
+		self.items.clear();
+		self.size = 0;
+		Ok(())
+	}
+
+	fn finish(mut self) -> ClientResult<Vec<SnapshotChunk>> {
+		self.flush()?;
+		Ok(self.chunks)
+	}
+}
+
+fn io_error(path: &Path, e: io::Error) -> ClientError {
+	ClientError::Backend(format!("Snapshot file {}: {e}", path.display()))
+}
+
+fn invalid_snapshot(reason: impl std::fmt::Display) -> ClientError {
+	ClientError::Backend(format!("Invalid snapshot: {reason}"))
+}
+
+/// Reads the chunk and verifies its hash against the manifest.
+fn read_chunk<T: Decode>(dir: &Path, chunk: &SnapshotChunk) -> ClientResult<Vec<T>> {
+	if Path::new(&chunk.name)
+		.file_name()
+		.map_or(true, |name| name != chunk.name.as_str())
+	{
+		return Err(invalid_snapshot(format!("invalid chunk name {:?}", chunk.name)))
+	}
+
+	let path = dir.join(&chunk.name);
+	let data = fs::read(&path).map_err(|e| io_error(&path, e))?;
+	if blake2_256(&data) != chunk.hash {
+		return Err(invalid_snapshot(format!("hash mismatch of chunk {}", chunk.name)))
+	}
+	Vec::<T>::decode_all(&mut &data[..])
+		.map_err(|e| invalid_snapshot(format!("failed to decode chunk {}: {e}", chunk.name)))
+}
+
+/// Reads and decodes the manifest of the snapshot stored in `path`.
+pub fn read_manifest<Block: BlockT>(path: &Path) -> ClientResult<SnapshotManifest<Block>> {
+	let manifest_path = path.join(MANIFEST_FILE);
+	let data = fs::read(&manifest_path).map_err(|e| io_error(&manifest_path, e))?;
+	let manifest = SnapshotManifest::<Block>::decode_all(&mut &data[..])
+		.map_err(|e| invalid_snapshot(format!("failed to decode manifest: {e}")))?;
+	if manifest.version != SNAPSHOT_VERSION {
+		return Err(invalid_snapshot(format!("unsupported version {}", manifest.version)))
+	}
+	Ok(manifest)
+}
+
+/// Read access to the trie nodes stored in the database.
+struct StateNodes<'a, Block: BlockT>(&'a StorageDb<Block>);
+
+impl<'a, Block: BlockT> HashDBRef<HashingFor<Block>, DBValue> for StateNodes<'a, Block> {
+	fn get(&self, key: &Block::Hash, prefix: Prefix) -> Option<DBValue> {
+		self.0.get(key, prefix).ok().flatten()
+	}
+
+	fn contains(&self, key: &Block::Hash, prefix: Prefix) -> bool {
+		HashDBRef::get(self, key, prefix).is_some()
+	}
+}
+
+/// Visits the trie nodes and the value nodes of the state with the given root, including the
+/// nodes of all child tries.
+///
+/// `f` is called with the database prefix and the encoding of every node stored in the database.
+/// Nodes inlined in their parent are not visited separately. Fails if a node is missing.
+fn visit_state<Block: BlockT>(
+	db: &dyn HashDBRef<HashingFor<Block>, DBValue>,
+	root: &Block::Hash,
+	mut f: impl FnMut(Prefix, &[u8]) -> ClientResult<()>,
+) -> ClientResult<()> {
+	let mut child_roots = Vec::new();
+	visit_trie::<Block>(db, root, &[], &mut f, |key, value| {
+		if let Some(child_key) = key.strip_prefix(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX)
+		{
+			let child_root = Block::Hash::decode(&mut &value[..])
+				.map_err(|e| ClientError::Backend(format!("Invalid child storage root: {e}")))?;
+			child_roots.push((child_key.to_vec(), child_root));
+		}
+		Ok(())
+	})?;
+
+	for (child_key, child_root) in child_roots {
+		let child_info = ChildInfo::new_default(&child_key);
+		let keyspace = child_info.keyspace();
+		let child_db = KeySpacedDB::new(db, keyspace);
+		visit_trie::<Block>(&child_db, &child_root, keyspace, &mut f, |_, _| Ok(()))?;
+	}
+
+	Ok(())
+}
+
+/// Visits the nodes of a single trie stored under `keyspace`, see [`visit_state`].
+///
+/// `on_value` is called with the key and the value of every entry of the trie.
+fn visit_trie<Block: BlockT>(
+	db: &dyn HashDBRef<HashingFor<Block>, DBValue>,
+	root: &Block::Hash,
+	keyspace: &[u8],
+	f: &mut impl FnMut(Prefix, &[u8]) -> ClientResult<()>,
+	mut on_value: impl FnMut(&[u8], &[u8]) -> ClientResult<()>,
+) -> ClientResult<()> {
+	// The empty trie is never stored in the database.
+	if *root == empty_trie_root::<LayoutV1<HashingFor<Block>>>() {
+		return Ok(())
+	}
+
+	let trie_error = |e| ClientError::Backend(format!("Trie error: {e}"));
+	let mut db_prefix = Vec::new();
+	let mut visit_node = |prefix: Prefix, data: &[u8]| {
+		db_prefix.clear();
+		db_prefix.extend_from_slice(keyspace);
+		db_prefix.extend_from_slice(prefix.0);
+		f((&db_prefix, prefix.1), data)
+	};
+
+	// The node encoding is the same for both state versions, `LayoutV1` decodes both.
+	let trie = TrieDBBuilder::<LayoutV1<HashingFor<Block>>>::new(db, root).build();
+	let mut nodes = TrieDBNodeIterator::new(&trie).map_err(trie_error)?;
+	while let Some(item) = nodes.next() {
+		let (mut prefix, hash, node) = item.map_err(trie_error)?;
+		if hash.is_some() {
+			visit_node(prefix.as_prefix(), node.data())?;
+		}
+
+		let value = match node.node() {
+			Node::Leaf(partial, value) => {
+				prefix.append_partial(partial.right());
+				value
+			},
+			Node::Branch(_, Some(value)) => value,
+			Node::NibbledBranch(partial, _, Some(value)) => {
+				prefix.append_partial(partial.right());
+				value
+			},
+			_ => continue,
+		};
+		let (key, _) = prefix.as_prefix();
+		match value {
+			Value::Inline(value) => on_value(key, value)?,
+			Value::Node(hash) => {
+				let value = nodes.fetch_value(hash, (key, None)).map_err(trie_error)?;
+				visit_node((key, None), &value)?;
+				on_value(key, &value)?;
+			},
+		}
+	}
+
+	Ok(())
+}
+
+/// Exports a snapshot of the finalized block `hash` into the directory `path`.
+///
+/// Besides the state at `hash`, the snapshot contains the genesis header and up to `blocks`
+/// blocks preceding `hash`. Chunk files are limited to roughly `chunk_size` bytes.
+pub fn export_snapshot<Block: BlockT>(
+	backend: &Backend<Block>,
+	hash: Block::Hash,
+	blocks: u32,
+	chunk_size: usize,
+	path: &Path,
+) -> ClientResult<SnapshotManifest<Block>> {
+	let blockchain = backend.blockchain();
+	let info = blockchain.info();
+	let header = blockchain.expect_header(hash)?;
+	let number = *header.number();
+	if number.is_zero() {
+		return Err(ClientError::Backend("Cannot export a snapshot of the genesis block".into()))
+	}
+	if number > info.finalized_number || blockchain.hash(number)? != Some(hash) {
+		return Err(ClientError::Backend(format!("Block {hash:?} is not finalized")))
+	}
+
+	fs::create_dir_all(path).map_err(|e| io_error(path, e))?;
+	let manifest_path = path.join(MANIFEST_FILE);
+	if manifest_path.exists() {
+		return Err(ClientError::Backend(format!("{} already contains a snapshot", path.display())))
+	}
+
+	info!(target: "db", "Exporting state of block #{number} ({hash:?})");
+	// Keeps the state from being pruned while its nodes are read from the database.
+	let _state = backend.state_at(hash, TrieCacheContext::Untrusted)?;
+	let mut state_chunks = ChunkWriter::<StateNode>::new(path, "state", chunk_size);
+	visit_state::<Block>(&StateNodes(&backend.storage), header.state_root(), |prefix, data| {
+		state_chunks.push((prefix.0.to_vec(), prefix.1, data.to_vec()))
+	})?;
+	let state_chunks = state_chunks.finish()?;
+
+	let first = number.saturating_sub(blocks.into()).max(One::one());
+	info!(target: "db", "Exporting blocks #{first}..=#{number}");
+	let mut block_chunks = ChunkWriter::<SnapshotBlock<Block>>::new(path, "blocks", chunk_size);
+	let mut export_block = |hash: Block::Hash| -> ClientResult<()> {
+		block_chunks.push(SnapshotBlock {
+			header: blockchain.expect_header(hash)?,
+			body: blockchain.body(hash)?,
+			justifications: blockchain.justifications(hash)?,
+		})
+	};
+	export_block(info.genesis_hash)?;
+	let mut current = first;
+	while current <= number {
+		let hash = blockchain
+			.hash(current)?
+			.ok_or_else(|| ClientError::UnknownBlock(format!("Missing block #{current}")))?;
+		export_block(hash)?;
+		current += One::one();
+	}
+	let block_chunks = block_chunks.finish()?;
+
+	let manifest = SnapshotManifest {
+		version: SNAPSHOT_VERSION,
+		genesis_hash: info.genesis_hash,
+		hash,
+		number,
+		state_root: *header.state_root(),
+		state_chunks,
+		block_chunks,
+	};
+	fs::write(&manifest_path, manifest.encode()).map_err(|e| io_error(&manifest_path, e))?;
+
+	Ok(manifest)
+}
+
+/// Imports the snapshot stored in the directory `path` into an empty database.
+///
+/// The chunk hashes, the chain of headers from the first block to the snapshot block and the
+/// block bodies are verified before anything is written. The trie nodes are then written to the
+/// database one chunk at a time and the state is verified by walking its trie before the blocks
+/// are imported.
+///
+/// A database holding an interrupted import of the same snapshot is accepted as well: the trie
+/// nodes are written again and the blocks already present are skipped.
+pub fn import_snapshot<Block: BlockT>(
+	backend: &Backend<Block>,
+	path: &Path,
+) -> ClientResult<SnapshotManifest<Block>> {
+	let manifest = read_manifest::<Block>(path)?;
+
+	let blockchain = backend.blockchain();
+	let info = blockchain.info();
+	let resumed = info.genesis_hash != Default::default();
+	if resumed && (info.genesis_hash != manifest.genesis_hash || info.finalized_state.is_some()) {
+		return Err(ClientError::Backend(
+			"Snapshots can only be imported into an empty database".into(),
+		))
+	}
+
+	let mut blocks = Vec::new();
+	for chunk in &manifest.block_chunks {
+		blocks.extend(read_chunk::<SnapshotBlock<Block>>(path, chunk)?);
+	}
+	verify_blocks(&manifest, &blocks)?;
+	for block in &blocks {
+		let hash = block.header.hash();
+		match blockchain.hash(*block.header.number())? {
+			Some(existing) if existing != hash =>
+				return Err(ClientError::Backend(format!(
+					"Block #{} of the snapshot conflicts with the database",
+					block.header.number()
+				))),
+			_ => (),
+		}
+	}
+
+	info!(target: "db", "Importing state of block #{} ({:?})", manifest.number, manifest.hash);
+	let storage = &backend.storage;
+	for chunk in &manifest.state_chunks {
+		let mut transaction = Transaction::new();
+		for (prefix, padding, data) in read_chunk::<StateNode>(path, chunk)? {
+			// Trie nodes are addressed by their hash, a node can't overwrite another one.
+			let hash = HashingFor::<Block>::hash(&data);
+			let mut key = if storage.prefix_keys {
+				prefixed_key::<HashingFor<Block>>(&hash, (&prefix, padding))
+			} else {
+				hash.as_ref().to_vec()
+			};
+			storage.db.sanitize_key(&mut key);
+			transaction.set_from_vec(columns::STATE, &key, data);
+		}
+		storage.db.commit(transaction)?;
+	}
+	visit_state::<Block>(&StateNodes(storage), &manifest.state_root, |_, _| Ok(()))
+		.map_err(|e| invalid_snapshot(format!("incomplete state: {e}")))?;
+
+	let target = blocks.pop().expect("verify_blocks ensures there are at least two blocks; qed");
+	for block in blocks {
+		if blockchain.hash(*block.header.number())?.is_some() {
+			continue
+		}
+		let mut op = backend.begin_operation()?;
+		op.set_block_data(
+			block.header,
+			block.body,
+			None,
+			block.justifications,
+			NewBlockState::Final,
+		)?;
+		backend.commit_operation(op)?;
+	}
+	// The trie nodes are already in the database, the state is only marked as available.
+	let mut op = backend.begin_operation()?;
+	op.set_block_data(
+		target.header,
+		target.body,
+		None,
+		target.justifications,
+		NewBlockState::Final,
+	)?;
+	op.set_commit_state();
+	backend.commit_operation(op)?;
+
+	if resumed {
+		info!(target: "db", "Resumed the interrupted import of the snapshot");
+	}
+	Ok(manifest)
+}
+
+/// Verifies that the blocks form a chain from genesis to the snapshot block and that their
+/// bodies match the extrinsics roots of their headers.
+fn verify_blocks<Block: BlockT>(
+	manifest: &SnapshotManifest<Block>,
+	blocks: &[SnapshotBlock<Block>],
+) -> ClientResult<()> {
+	let [genesis, rest @ ..] = blocks else { return Err(invalid_snapshot("no blocks")) };
+	if !genesis.header.number().is_zero() || genesis.header.hash() != manifest.genesis_hash {
+		return Err(invalid_snapshot("genesis block mismatch"))
+	}
+
+	let Some(target) = rest.last() else {
+		return Err(invalid_snapshot("the snapshot block is missing"))
+	};
+	if target.header.hash() != manifest.hash ||
+		*target.header.number() != manifest.number ||
+		*target.header.state_root() != manifest.state_root
+	{
+		return Err(invalid_snapshot("snapshot block mismatch"))
+	}
+
+	for pair in rest.windows(2) {
+		let (parent, block) = (&pair[0].header, &pair[1].header);
+		if *block.parent_hash() != parent.hash() || *block.number() != *parent.number() + One::one()
+		{
+			return Err(invalid_snapshot(format!(
+				"block #{} is not a child of its predecessor",
+				block.number()
+			)))
+		}
+	}
+	let first = &rest[0].header;
+	if first.number().is_zero() ||
+		(*first.number() == One::one() && *first.parent_hash() != genesis.header.hash())
+	{
+		return Err(invalid_snapshot("the first block is not a descendant of genesis"))
+	}
+
+	for block in blocks {
+		let Some(body) = &block.body else { continue };
+		let extrinsics = body.iter().map(Encode::encode).collect::<Vec<_>>();
+		// The state version of the runtime is not known, it only matters for long extrinsics.
+		let matches = [StateVersion::V0, StateVersion::V1].into_iter().any(|state_version| {
+			HashingFor::<Block>::ordered_trie_root(extrinsics.clone(), state_version) ==
+				*block.header.extrinsics_root()
+		});
+		if !matches {
+			return Err(invalid_snapshot(format!(
+				"body of block #{} does not match its extrinsics root",
+				block.header.number()
+			)))
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::{insert_header, Block, UncheckedXt};
+	use sp_core::{storage::StorageChild, H256};
+	use sp_runtime::{testing::Header, Storage};
+	use sp_state_machine::Backend as _;
+
+	fn source_backend(state_version: StateVersion) -> (Backend<Block>, H256) {
+		let backend = Backend::<Block>::new_test(1000, 0);
+
+		let mut op = backend.begin_operation().unwrap();
+		let child_info = ChildInfo::new_default(b"child");
+		let storage = Storage {
+			top: vec![(vec![1, 2, 3], vec![4, 5, 6]), (vec![7], vec![8]), (vec![11], vec![12; 64])]
+				.into_iter()
+				.collect(),
+			children_default: vec![(
+				b"child".to_vec(),
+				StorageChild {
+					data: vec![(vec![9], vec![10])].into_iter().collect(),
+					child_info: child_info.clone(),
+				},
+			)]
+			.into_iter()
+			.collect(),
+		};
+		let state_root = op.reset_storage(storage, state_version).unwrap();
+		let extrinsics_root = HashingFor::<Block>::ordered_trie_root(Vec::new(), state_version);
+		let genesis = Header {
+			number: 0,
+			parent_hash: Default::default(),
+			state_root,
+			digest: Default::default(),
+			extrinsics_root,
+		};
+		op.set_block_data(genesis.clone(), Some(vec![]), None, None, NewBlockState::Final)
+			.unwrap();
+		backend.commit_operation(op).unwrap();
+
+		let mut hash = genesis.hash();
+		for number in 1..=4 {
+			hash = insert_header(&backend, number, hash, None, extrinsics_root);
+		}
+		backend.finalize_block(hash, None).unwrap();
+
+		(backend, hash)
+	}
+
+	#[test]
+	fn snapshot_roundtrip_works() {
+		let (source, hash) = source_backend(StateVersion::V1);
+		let dir = tempfile::tempdir().unwrap();
+
+		let exported = export_snapshot(&source, hash, 2, 16, dir.path()).unwrap();
+		assert_eq!(exported.number, 4);
+		assert!(exported.state_chunks.len() > 1);
+
+		let target = Backend::<Block>::new_test(1000, 0);
+		let imported = import_snapshot(&target, dir.path()).unwrap();
+		assert_eq!(imported, exported);
+
+		let info = target.blockchain().info();
+		assert_eq!(info.genesis_hash, source.blockchain().info().genesis_hash);
+		assert_eq!(info.best_hash, hash);
+		assert_eq!(info.finalized_hash, hash);
+		assert_eq!(info.finalized_state, Some((hash, 4)));
+		assert!(target.blockchain().hash(2).unwrap().is_some());
+		assert!(target.blockchain().hash(1).unwrap().is_none());
+
+		let state = target.state_at(hash, TrieCacheContext::Untrusted).unwrap();
+		let expected = source.state_at(hash, TrieCacheContext::Untrusted).unwrap();
+		assert_eq!(state.storage_root(std::iter::empty(), StateVersion::V1).0, exported.state_root);
+		assert_eq!(state.storage(&[1, 2, 3]).unwrap(), Some(vec![4, 5, 6]));
+		assert_eq!(
+			state.child_storage(&ChildInfo::new_default(b"child"), &[9]).unwrap(),
+			expected.child_storage(&ChildInfo::new_default(b"child"), &[9]).unwrap(),
+		);
+	}
+
+	#[test]
+	fn snapshot_of_state_v0_roundtrip_works() {
+		let (source, hash) = source_backend(StateVersion::V0);
+		let dir = tempfile::tempdir().unwrap();
+		let exported = export_snapshot(&source, hash, 2, 16, dir.path()).unwrap();
+
+		let target = Backend::<Block>::new_test(1000, 0);
+		import_snapshot(&target, dir.path()).unwrap();
+
+		let state = target.state_at(hash, TrieCacheContext::Untrusted).unwrap();
+		assert_eq!(state.storage_root(std::iter::empty(), StateVersion::V0).0, exported.state_root);
+		assert_eq!(state.storage(&[11]).unwrap(), Some(vec![12; 64]));
+	}
+
+	#[test]
+	fn import_rejects_tampered_chunk() {
+		let (source, hash) = source_backend(StateVersion::V1);
+		let dir = tempfile::tempdir().unwrap();
+		let manifest = export_snapshot(&source, hash, 2, 1024, dir.path()).unwrap();
+
+		let chunk = dir.path().join(&manifest.state_chunks[0].name);
+		let mut data = fs::read(&chunk).unwrap();
+		*data.last_mut().unwrap() ^= 1;
+		fs::write(&chunk, data).unwrap();
+
+		let target = Backend::<Block>::new_test(1000, 0);
+		assert!(import_snapshot(&target, dir.path()).is_err());
+		assert_eq!(target.blockchain().info().genesis_hash, Default::default());
+	}
+
+	#[test]
+	fn import_rejects_tampered_body() {
+		let (source, hash) = source_backend(StateVersion::V1);
+		let dir = tempfile::tempdir().unwrap();
+		let mut manifest = export_snapshot(&source, hash, 2, 1024 * 1024, dir.path()).unwrap();
+
+		// Replace the body and update the hashes, so only the extrinsics root is left to catch it.
+		let chunk = manifest.block_chunks.last_mut().unwrap();
+		let mut blocks = read_chunk::<SnapshotBlock<Block>>(dir.path(), chunk).unwrap();
+		blocks.last_mut().unwrap().body = Some(vec![UncheckedXt::new_transaction(1.into(), ())]);
+		let data = blocks.encode();
+		chunk.hash = blake2_256(&data);
+		fs::write(dir.path().join(&chunk.name), data).unwrap();
+		fs::write(dir.path().join(MANIFEST_FILE), manifest.encode()).unwrap();
+
+		let target = Backend::<Block>::new_test(1000, 0);
+		let error = import_snapshot(&target, dir.path()).unwrap_err();
+		assert!(error.to_string().contains("extrinsics root"), "{error}");
+		assert_eq!(target.blockchain().info().genesis_hash, Default::default());
+	}
+
+	#[test]
+	fn interrupted_import_can_be_resumed() {
+		let (source, hash) = source_backend(StateVersion::V1);
+		let dir = tempfile::tempdir().unwrap();
+		let manifest = export_snapshot(&source, hash, 2, 16, dir.path()).unwrap();
+
+		// Import the genesis block and the first block, as an interrupted import would.
+		let target = Backend::<Block>::new_test(1000, 0);
+		let blocks = manifest
+			.block_chunks
+			.iter()
+			.flat_map(|chunk| read_chunk::<SnapshotBlock<Block>>(dir.path(), chunk).unwrap())
+			.take(2);
+		for block in blocks {
+			let mut op = target.begin_operation().unwrap();
+			op.set_block_data(block.header, block.body, None, None, NewBlockState::Final)
+				.unwrap();
+			target.commit_operation(op).unwrap();
+		}
+		assert_eq!(target.blockchain().info().genesis_hash, manifest.genesis_hash);
+
+		import_snapshot(&target, dir.path()).unwrap();
+
+		let info = target.blockchain().info();
+		assert_eq!(info.finalized_state, Some((hash, 4)));
+		let state = target.state_at(hash, TrieCacheContext::Untrusted).unwrap();
+		assert_eq!(state.storage(&[1, 2, 3]).unwrap(), Some(vec![4, 5, 6]));
+		assert!(import_snapshot(&target, dir.path()).is_err());
+	}
+
+	#[test]
+	fn import_requires_empty_database() {
+		let (source, hash) = source_backend(StateVersion::V1);
+		let dir = tempfile::tempdir().unwrap();
+		export_snapshot(&source, hash, 2, 1024, dir.path()).unwrap();
+
+		assert!(import_snapshot(&source, dir.path()).is_err());
+	}
+}
@@ -0,0 +1,314 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Integrity check of the state stored in the database.
+//!
+//! The check walks every node of the state trie of a block, including the default child tries,
+//! and reports the nodes that are missing from the database or whose content doesn't match their
+//! hash. Damaged nodes can be repaired with nodes obtained elsewhere, e.g. from the state proofs
+//! served by peers, as every node is verified against its hash before it is written.
+
+use crate::{
+	columns,
+	trie_walker::{
+		decode_hash, pack_nibbles, Children, Node, ResolveValue, StoredNode, TrieValue, TrieWalker,
+	},
+	Backend,
+};
+use sp_blockchain::{Error as ClientError, HeaderBackend, Result as ClientResult};
+use sp_core::storage::well_known_keys;
+use sp_database::Transaction;
+use sp_runtime::traits::{Block as BlockT, Hash as HashT, HashingFor, Header as HeaderT};
+use sp_trie::prefixed_key;
+use std::collections::HashMap;
+use trie_db::node::NodeHandlePlan;
+
+/// How a trie node is damaged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Damage {
+	/// The node is missing from the database.
+	Missing,
+	/// The node doesn't match its hash or can't be decoded.
+	Corrupt,
+}
+
+/// A trie node that is damaged in the database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DamagedNode<Hash> {
+	/// Storage key of the default child trie containing the node, `None` for the main trie.
+	pub child_trie: Option<Vec<u8>>,
+	/// Nibbles leading from the root of the trie to the node.
+	pub path: Vec<u8>,
+	/// Hash of the node.
+	pub hash: Hash,
+	/// Whether this is a value node.
+	pub is_value: bool,
+	/// How the node is damaged.
+	pub damage: Damage,
+}
+
+impl<Hash> DamagedNode<Hash> {
+	/// Keys to start a state request at, so that the proof in the response contains the node.
+	///
+	/// The first key is a key of the main trie, the second one a key of the child trie if the
+	/// node belongs to a child trie.
+	pub fn state_request_start(&self) -> Vec<Vec<u8>> {
+		let key = pack_nibbles(&self.path);
+		match &self.child_trie {
+			Some(child_key) =>
+				vec![[well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX, child_key].concat(), key],
+			None => vec![key],
+		}
+	}
+}
+
+/// Progress of the integrity check of the state of a block.
+///
+/// Created by [`Backend::start_state_check`] and advanced by [`Backend::continue_state_check`].
+/// The state of the block must not be pruned while the check is in progress.
+#[derive(Debug, Clone)]
+pub struct StateCheck<Hash> {
+	block: Hash,
+	pending: Vec<StoredNode<Hash>>,
+	checked: u64,
+	damaged: Vec<DamagedNode<Hash>>,
+}
+
+impl<Hash: Copy> StateCheck<Hash> {
+	/// The block whose state is checked.
+	pub fn block(&self) -> Hash {
+		self.block
+	}
+
+	/// Number of nodes checked so far.
+	pub fn checked_nodes(&self) -> u64 {
+		self.checked
+	}
+
+	/// Damaged nodes found so far.
+	pub fn damaged_nodes(&self) -> &[DamagedNode<Hash>] {
+		&self.damaged
+	}
+
+	/// Whether all nodes of the state have been checked.
+	pub fn is_complete(&self) -> bool {
+		self.pending.is_empty()
+	}
+}
+
+impl<Block: BlockT> Backend<Block> {
+	/// Start checking the integrity of the state of the block `hash`.
+	pub fn start_state_check(&self, hash: Block::Hash) -> ClientResult<StateCheck<Block::Hash>> {
+		let header = self
+			.blockchain
+			.header(hash)?
+			.ok_or_else(|| ClientError::UnknownBlock(format!("{hash:?}")))?;
+		let root = *header.state_root();
+		let walker = TrieWalker::new(&self.storage, None);
+		let pending = if root == walker.empty_root {
+			Vec::new()
+		} else {
+			vec![StoredNode { keyspace: None, path: Vec::new(), hash: root, is_value: false }]
+		};
+		Ok(StateCheck { block: hash, pending, checked: 0, damaged: Vec::new() })
+	}
+
+	/// Check up to `max_nodes` nodes of the state.
+	///
+	/// Returns `true` once the check is complete.
+	pub fn continue_state_check(
+		&self,
+		check: &mut StateCheck<Block::Hash>,
+		max_nodes: usize,
+	) -> ClientResult<bool> {
+		for _ in 0..max_nodes {
+			let Some(node) = check.pending.pop() else { break };
+			check.checked += 1;
+			let walker = TrieWalker::new(&self.storage, node.keyspace.as_deref());
+			let damaged = |damage| DamagedNode {
+				child_trie: node.keyspace.clone(),
+				path: node.path.clone(),
+				hash: node.hash,
+				is_value: node.is_value,
+				damage,
+			};
+
+			let Some(data) = walker.try_node(&node.path, &node.hash)? else {
+				check.damaged.push(damaged(Damage::Missing));
+				continue
+			};
+			if HashingFor::<Block>::hash(&data) != node.hash {
+				check.damaged.push(damaged(Damage::Corrupt));
+				continue
+			}
+			if node.is_value {
+				continue
+			}
+			let Ok(decoded) = Node::decode::<Block>(&data) else {
+				check.damaged.push(damaged(Damage::Corrupt));
+				continue
+			};
+
+			let mut full_path = node.path.clone();
+			full_path.extend_from_slice(&decoded.partial);
+			match decoded.value.map(|value| value.resolve::<Block>(&data)) {
+				Some(TrieValue::Node(hash)) => check.pending.push(StoredNode {
+					keyspace: node.keyspace.clone(),
+					path: full_path.clone(),
+					hash,
+					is_value: true,
+				}),
+				Some(TrieValue::Inline(value)) if node.keyspace.is_none() => {
+					let key = pack_nibbles(&full_path);
+					let child_key =
+						key.strip_prefix(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX);
+					if let Some(child_key) = child_key {
+						if value.len() == Block::Hash::default().as_ref().len() {
+							check.pending.push(StoredNode {
+								keyspace: Some(child_key.to_vec()),
+								path: Vec::new(),
+								hash: decode_hash::<Block>(&value),
+								is_value: false,
+							});
+						}
+					}
+				},
+				_ => {},
+			}
+
+			let mut push_child = |path: Vec<u8>, range| {
+				check.pending.push(StoredNode {
+					keyspace: node.keyspace.clone(),
+					path,
+					hash: decode_hash::<Block>(&data[range]),
+					is_value: false,
+				})
+			};
+			match decoded.children {
+				Children::None => {},
+				Children::Extension(child) =>
+					if let NodeHandlePlan::Hash(range) = child {
+						push_child(full_path, range);
+					},
+				Children::Branch(children) =>
+					for (index, child) in children.into_iter().enumerate() {
+						if let Some(NodeHandlePlan::Hash(range)) = child {
+							let mut child_path = full_path.clone();
+							child_path.push(index as u8);
+							push_child(child_path, range);
+						}
+					},
+			}
+		}
+		Ok(check.is_complete())
+	}
+
+	/// Repair damaged nodes with the given trie nodes.
+	///
+	/// Nodes that don't match the hash of any damaged node are ignored. Returns the nodes that
+	/// were repaired.
+	pub fn repair_state(
+		&self,
+		damaged: &[DamagedNode<Block::Hash>],
+		nodes: impl IntoIterator<Item = Vec<u8>>,
+	) -> ClientResult<Vec<DamagedNode<Block::Hash>>> {
+		let nodes: HashMap<_, _> =
+			nodes.into_iter().map(|node| (HashingFor::<Block>::hash(&node), node)).collect();
+
+		let mut transaction = Transaction::new();
+		let mut repaired = Vec::new();
+		for node in damaged {
+			let Some(data) = nodes.get(&node.hash) else { continue };
+			let walker = TrieWalker::new(&self.storage, node.child_trie.as_deref());
+			let (packed, last) = walker.prefix(&node.path);
+			let mut key = prefixed_key::<HashingFor<Block>>(&node.hash, (&packed[..], last));
+			self.storage.db.sanitize_key(&mut key);
+			transaction.set_from_vec(columns::STATE, &key, data.clone());
+			repaired.push(node.clone());
+		}
+		self.storage.db.commit(transaction)?;
+		Ok(repaired)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::Block;
+	use sc_client_api::backend::{Backend as _, BlockImportOperation, NewBlockState};
+	use sp_core::H256;
+	use sp_runtime::{testing::Header, StateVersion, Storage};
+
+	fn check_state(backend: &Backend<Block>, hash: H256) -> Vec<DamagedNode<H256>> {
+		let mut check = backend.start_state_check(hash).unwrap();
+		while !backend.continue_state_check(&mut check, 10).unwrap() {}
+		check.damaged_nodes().to_vec()
+	}
+
+	#[test]
+	fn damaged_nodes_are_found_and_repaired() {
+		let backend = Backend::<Block>::new_test(1000, 0);
+		let mut op = backend.begin_operation().unwrap();
+		let storage = Storage {
+			top: (0u8..64).map(|i| (vec![i], vec![i; 40])).collect(),
+			children_default: Default::default(),
+		};
+		let root = op.reset_storage(storage, StateVersion::V1).unwrap();
+		let genesis = Header {
+			number: 0,
+			parent_hash: Default::default(),
+			state_root: root,
+			digest: Default::default(),
+			extrinsics_root: Default::default(),
+		};
+		op.set_block_data(genesis.clone(), Some(vec![]), None, None, NewBlockState::Final)
+			.unwrap();
+		backend.commit_operation(op).unwrap();
+		let hash = genesis.hash();
+
+		let mut check = backend.start_state_check(hash).unwrap();
+		while !backend.continue_state_check(&mut check, 10).unwrap() {}
+		// The root, the branch nodes and a value node for each of the 64 values.
+		assert!(check.checked_nodes() > 64 * 2);
+		assert!(check.damaged_nodes().is_empty());
+
+		let mut key = prefixed_key::<HashingFor<Block>>(&root, sp_trie::EMPTY_PREFIX);
+		backend.storage.db.sanitize_key(&mut key);
+		let root_node = backend.storage.db.get(columns::STATE, &key).unwrap();
+
+		let mut transaction = Transaction::new();
+		transaction.remove(columns::STATE, &key);
+		backend.storage.db.commit(transaction).unwrap();
+		let damaged = check_state(&backend, hash);
+		assert_eq!(damaged.len(), 1);
+		assert_eq!(damaged[0].damage, Damage::Missing);
+		assert_eq!(damaged[0].state_request_start(), vec![Vec::<u8>::new()]);
+
+		let mut transaction = Transaction::new();
+		transaction.set(columns::STATE, &key, b"corrupt");
+		backend.storage.db.commit(transaction).unwrap();
+		let damaged = check_state(&backend, hash);
+		assert_eq!(damaged.len(), 1);
+		assert_eq!(damaged[0].damage, Damage::Corrupt);
+
+		let repaired =
+			backend.repair_state(&damaged, vec![b"unrelated".to_vec(), root_node]).unwrap();
+		assert_eq!(repaired, damaged);
+		assert!(check_state(&backend, hash).is_empty());
+	}
+}
@@ -0,0 +1,304 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Walking the tries stored in the state database node by node.
+//!
+//! Nodes are addressed by their position in the trie, which is what the state database keys are
+//! derived from when the database doesn't count references.
+
+use crate::StorageDb;
+use hash_db::Prefix;
+use sp_blockchain::{Error as ClientError, Result as ClientResult};
+use sp_core::storage::well_known_keys;
+use sp_runtime::traits::{Block as BlockT, HashingFor};
+use sp_trie::{empty_trie_root, LayoutV1, NodeCodec, NodePlan, ValuePlan};
+use trie_db::{node::NodeHandlePlan, NodeCodec as _};
+
+/// A node stored in the state database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct StoredNode<H> {
+	/// Keyspace of the child trie, if the node doesn't belong to the main trie.
+	pub keyspace: Option<Vec<u8>>,
+	/// Nibbles leading from the root of the trie to the node.
+	pub path: Vec<u8>,
+	/// Hash of the node.
+	pub hash: H,
+	/// Whether this is a value node.
+	pub is_value: bool,
+}
+
+/// Result of [`TrieWalker::lookup`].
+#[derive(Default)]
+pub(crate) struct Lookup<H> {
+	/// The node starting at the path.
+	pub node: Option<H>,
+	/// The value stored under the path, inline or in a value node.
+	pub value: Option<TrieValue<H>>,
+}
+
+#[derive(PartialEq, Eq)]
+pub(crate) enum TrieValue<H> {
+	Inline(Vec<u8>),
+	Node(H),
+}
+
+/// Reads the nodes of a trie stored in the state database.
+pub(crate) struct TrieWalker<'a, Block: BlockT> {
+	storage: &'a StorageDb<Block>,
+	keyspace: Option<&'a [u8]>,
+	pub empty_root: Block::Hash,
+}
+
+impl<'a, Block: BlockT> TrieWalker<'a, Block> {
+	pub fn new(storage: &'a StorageDb<Block>, keyspace: Option<&'a [u8]>) -> Self {
+		Self { storage, keyspace, empty_root: empty_trie_root::<LayoutV1<HashingFor<Block>>>() }
+	}
+
+	pub fn prefix(&self, path: &[u8]) -> (Vec<u8>, Option<u8>) {
+		let mut packed = self.keyspace.map(<[u8]>::to_vec).unwrap_or_default();
+		packed.extend(path.chunks_exact(2).map(|n| n[0] << 4 | n[1]));
+		(packed, (path.len() % 2 == 1).then(|| path[path.len() - 1] << 4))
+	}
+
+	/// Returns the node stored at `path`, or `None` if the database doesn't contain it.
+	pub fn try_node(&self, path: &[u8], hash: &Block::Hash) -> ClientResult<Option<Vec<u8>>> {
+		let (packed, last) = self.prefix(path);
+		let prefix: Prefix = (&packed[..], last);
+		sp_state_machine::Storage::get(self.storage, hash, prefix).map_err(ClientError::Backend)
+	}
+
+	fn node(&self, path: &[u8], hash: &Block::Hash) -> ClientResult<Vec<u8>> {
+		self.try_node(path, hash)?
+			.ok_or_else(|| ClientError::Backend(format!("Missing trie node {hash:?}")))
+	}
+
+	pub fn has_root(&self, root: &Block::Hash) -> ClientResult<bool> {
+		Ok(*root == self.empty_root ||
+			sp_state_machine::Storage::get(self.storage, root, (&[], None))
+				.map_err(ClientError::Backend)?
+				.is_some())
+	}
+
+	/// Returns the node starting at `target` and the value stored under `target`.
+	pub fn lookup(&self, root: &Block::Hash, target: &[u8]) -> ClientResult<Lookup<Block::Hash>> {
+		if *root == self.empty_root {
+			return Ok(Default::default())
+		}
+
+		let mut path = Vec::new();
+		let mut hash = *root;
+		loop {
+			let data = self.node(&path, &hash)?;
+			let node = Node::decode::<Block>(&data)?;
+			let node_here = path.len() == target.len();
+			let rest = &target[path.len()..];
+			if node_here || rest == &node.partial[..] {
+				let value = if rest == &node.partial[..] {
+					node.value.map(|value| value.resolve::<Block>(&data))
+				} else {
+					None
+				};
+				return Ok(Lookup { node: node_here.then_some(hash), value })
+			}
+			if !rest.starts_with(&node.partial) {
+				return Ok(Default::default())
+			}
+
+			path.extend_from_slice(&node.partial);
+			let child = match node.children {
+				Children::None => None,
+				Children::Extension(child) => Some(child),
+				Children::Branch(children) => {
+					let index = target[path.len()];
+					path.push(index);
+					children[index as usize].clone()
+				},
+			};
+			match child {
+				Some(NodeHandlePlan::Hash(range)) => hash = decode_hash::<Block>(&data[range]),
+				_ => return Ok(Default::default()),
+			}
+		}
+	}
+
+	/// Collects the nodes of the trie `old` that are not stored at the same position in the trie
+	/// `new`, along with the child tries referenced by the removed nodes.
+	pub fn removed(
+		&self,
+		old: &Block::Hash,
+		new: &Block::Hash,
+		removed: &mut Vec<StoredNode<Block::Hash>>,
+		child_tries: &mut Vec<(Vec<u8>, Block::Hash)>,
+	) -> ClientResult<()> {
+		if *old == self.empty_root {
+			return Ok(())
+		}
+
+		let mut stack = vec![(Vec::new(), *old)];
+		while let Some((path, hash)) = stack.pop() {
+			if self.lookup(new, &path)?.node == Some(hash) {
+				continue
+			}
+
+			let data = self.node(&path, &hash)?;
+			let node = Node::decode::<Block>(&data)?;
+			let mut full_path = path.clone();
+			full_path.extend_from_slice(&node.partial);
+			removed.push(StoredNode {
+				keyspace: self.keyspace.map(<[u8]>::to_vec),
+				path,
+				hash,
+				is_value: false,
+			});
+
+			match node.value.map(|value| value.resolve::<Block>(&data)) {
+				Some(TrieValue::Node(value_hash)) => {
+					if self.lookup(new, &full_path)?.value != Some(TrieValue::Node(value_hash)) {
+						removed.push(StoredNode {
+							keyspace: self.keyspace.map(<[u8]>::to_vec),
+							path: full_path.clone(),
+							hash: value_hash,
+							is_value: true,
+						});
+					}
+				},
+				Some(TrieValue::Inline(value)) if self.keyspace.is_none() => {
+					let key = pack_nibbles(&full_path);
+					if let Some(child_key) =
+						key.strip_prefix(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX)
+					{
+						if value.len() == Block::Hash::default().as_ref().len() {
+							child_tries.push((child_key.to_vec(), decode_hash::<Block>(&value)));
+						}
+					}
+				},
+				_ => {},
+			}
+
+			match node.children {
+				Children::None => {},
+				Children::Extension(child) =>
+					if let NodeHandlePlan::Hash(range) = child {
+						stack.push((full_path, decode_hash::<Block>(&data[range])));
+					},
+				Children::Branch(children) =>
+					for (index, child) in children.into_iter().enumerate() {
+						if let Some(NodeHandlePlan::Hash(range)) = child {
+							let mut child_path = full_path.clone();
+							child_path.push(index as u8);
+							stack.push((child_path, decode_hash::<Block>(&data[range])));
+						}
+					},
+			}
+		}
+		Ok(())
+	}
+
+	/// Returns `true` if the trie with the given root contains the node at its position.
+	pub fn contains(
+		&self,
+		root: &Block::Hash,
+		node: &StoredNode<Block::Hash>,
+	) -> ClientResult<bool> {
+		let lookup = self.lookup(root, &node.path)?;
+		Ok(if node.is_value {
+			lookup.value == Some(TrieValue::Node(node.hash))
+		} else {
+			lookup.node == Some(node.hash)
+		})
+	}
+}
+
+pub(crate) enum Children {
+	None,
+	Extension(NodeHandlePlan),
+	Branch([Option<NodeHandlePlan>; 16]),
+}
+
+/// The parts of a decoded node relevant to the migration.
+pub(crate) struct Node {
+	pub partial: Vec<u8>,
+	pub value: Option<ValuePlan>,
+	pub children: Children,
+}
+
+impl Node {
+	pub fn decode<Block: BlockT>(data: &[u8]) -> ClientResult<Self> {
+		let plan = NodeCodec::<HashingFor<Block>>::decode_plan(data)
+			.map_err(|e| ClientError::Backend(format!("Failed to decode trie node: {e:?}")))?;
+		let nibbles = |partial: trie_db::node::NibbleSlicePlan| {
+			let partial = partial.build(data);
+			(0..partial.len()).map(|i| partial.at(i)).collect::<Vec<_>>()
+		};
+		Ok(match plan {
+			NodePlan::Empty => Node { partial: Vec::new(), value: None, children: Children::None },
+			NodePlan::Leaf { partial, value } =>
+				Node { partial: nibbles(partial), value: Some(value), children: Children::None },
+			NodePlan::Extension { partial, child } => Node {
+				partial: nibbles(partial),
+				value: None,
+				children: Children::Extension(child),
+			},
+			NodePlan::Branch { value, children } =>
+				Node { partial: Vec::new(), value, children: Children::Branch(children) },
+			NodePlan::NibbledBranch { partial, value, children } =>
+				Node { partial: nibbles(partial), value, children: Children::Branch(children) },
+		})
+	}
+}
+
+pub(crate) trait ResolveValue {
+	fn resolve<Block: BlockT>(self, data: &[u8]) -> TrieValue<Block::Hash>;
+}
+
+impl ResolveValue for ValuePlan {
+	fn resolve<Block: BlockT>(self, data: &[u8]) -> TrieValue<Block::Hash> {
+		match self {
+			ValuePlan::Inline(range) => TrieValue::Inline(data[range].to_vec()),
+			ValuePlan::Node(range) => TrieValue::Node(decode_hash::<Block>(&data[range])),
+		}
+	}
+}
+
+pub(crate) fn decode_hash<Block: BlockT>(data: &[u8]) -> Block::Hash {
+	let mut hash = Block::Hash::default();
+	hash.as_mut().copy_from_slice(data);
+	hash
+}
+
+pub(crate) fn pack_nibbles(nibbles: &[u8]) -> Vec<u8> {
+	nibbles.chunks(2).map(|n| n[0] << 4 | n.get(1).copied().unwrap_or(0)).collect()
+}
+
+pub(crate) fn unpack_nibbles(bytes: &[u8]) -> Vec<u8> {
+	bytes.iter().flat_map(|b| [b >> 4, b & 0x0f]).collect()
+}
+
+/// Returns the root of the default child trie `child_key` in the given state.
+pub(crate) fn child_root<Block: BlockT>(
+	walker: &TrieWalker<Block>,
+	root: &Block::Hash,
+	child_key: &[u8],
+) -> ClientResult<Option<Block::Hash>> {
+	let key = [well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX, child_key].concat();
+	Ok(match walker.lookup(root, &unpack_nibbles(&key))?.value {
+		Some(TrieValue::Inline(value)) if value.len() == Block::Hash::default().as_ref().len() =>
+			Some(decode_hash::<Block>(&value)),
+		_ => None,
+	})
+}
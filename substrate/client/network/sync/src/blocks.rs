@@ -23,7 +23,7 @@ use sc_network_types::PeerId;
 use sp_runtime::traits::{Block as BlockT, NumberFor, One};
 use std::{
 	cmp,
-	collections::{BTreeMap, HashMap},
+	collections::{BTreeMap, HashMap, HashSet},
 	ops::Range,
 };
 
@@ -59,6 +59,8 @@ pub struct BlockCollection<B: BlockT> {
 	/// Downloaded blocks.
 	blocks: BTreeMap<NumberFor<B>, BlockRangeState<B>>,
 	peer_requests: HashMap<PeerId, NumberFor<B>>,
+	/// Peers whose request was released, and no longer counts as downloading its range.
+	released_requests: HashSet<PeerId>,
 	/// Block ranges downloaded and queued for import.
 	/// Maps start_hash => (start_num, end_num).
 	queued_blocks: HashMap<B::Hash, (NumberFor<B>, NumberFor<B>)>,
@@ -70,6 +72,7 @@ impl<B: BlockT> BlockCollection<B> {
 		Self {
 			blocks: BTreeMap::new(),
 			peer_requests: HashMap::new(),
+			released_requests: HashSet::new(),
 			queued_blocks: HashMap::new(),
 		}
 	}
@@ -78,6 +81,7 @@ impl<B: BlockT> BlockCollection<B> {
 	pub fn clear(&mut self) {
 		self.blocks.clear();
 		self.peer_requests.clear();
+		self.released_requests.clear();
 	}
 
 	/// Insert a set of blocks into collection.
@@ -242,7 +246,10 @@ impl<B: BlockT> BlockCollection<B> {
 
 	pub fn clear_peer_download(&mut self, who: &PeerId) {
 		if let Some(start) = self.peer_requests.remove(who) {
+			let released = self.released_requests.remove(who);
 			let remove = match self.blocks.get_mut(&start) {
+				Some(&mut BlockRangeState::Downloading { downloading, .. }) if released =>
+					downloading == 0,
 				Some(&mut BlockRangeState::Downloading { ref mut downloading, .. })
 					if *downloading > 1 =>
 				{
@@ -257,6 +264,21 @@ impl<B: BlockT> BlockCollection<B> {
 			}
 		}
 	}
+
+	/// Stop counting the download of `who` towards its range, so that the range is requested from
+	/// another peer. The blocks are still accepted if `who` eventually responds.
+	///
+	/// Returns `false` if `who` isn't downloading or its download was already released.
+	pub fn release_peer_download(&mut self, who: &PeerId) -> bool {
+		let Some(start) = self.peer_requests.get(who) else { return false };
+		if !self.released_requests.insert(*who) {
+			return false
+		}
+		if let Some(BlockRangeState::Downloading { downloading, .. }) = self.blocks.get_mut(start) {
+			*downloading = downloading.saturating_sub(1);
+		}
+		true
+	}
 }
 
 #[cfg(test)]
@@ -270,7 +292,7 @@ mod test {
 	type Block = RawBlock<TestXt<MockCallU64, ()>>;
 
 	fn is_empty(bc: &BlockCollection<Block>) -> bool {
-		bc.blocks.is_empty() && bc.peer_requests.is_empty()
+		bc.blocks.is_empty() && bc.peer_requests.is_empty() && bc.released_requests.is_empty()
 	}
 
 	fn generate_blocks(n: usize) -> Vec<message::BlockData<Block>> {
@@ -480,6 +502,36 @@ mod test {
 			Some(16..21)
 		);
 	}
+	#[test]
+	fn released_range_is_requested_from_another_peer() {
+		let mut bc = BlockCollection::new();
+		let blocks = generate_blocks(20);
+		let peer1 = PeerId::random();
+		let peer2 = PeerId::random();
+		let peer3 = PeerId::random();
+
+		assert_eq!(bc.needed_blocks(peer1, 10, 100, 0, 1, 200), Some(1..11));
+		assert!(!bc.release_peer_download(&peer2));
+		assert!(bc.release_peer_download(&peer1));
+		assert!(!bc.release_peer_download(&peer1));
+
+		// The released range is requested again.
+		assert_eq!(bc.needed_blocks(peer2, 10, 100, 0, 1, 200), Some(1..11));
+		assert_eq!(bc.needed_blocks(peer3, 10, 100, 0, 1, 200), Some(11..21));
+
+		// The response of the stalled peer doesn't drop the range downloaded by the other peer.
+		bc.clear_peer_download(&peer1);
+		assert_eq!(bc.needed_blocks(peer1, 10, 100, 0, 1, 200), Some(21..31));
+		bc.clear_peer_download(&peer2);
+		bc.insert(1, blocks[1..11].to_vec(), peer2);
+		assert_eq!(bc.ready_blocks(1).len(), 10);
+
+		// A released range nobody else downloads is dropped with the request.
+		assert!(bc.release_peer_download(&peer3));
+		bc.clear_peer_download(&peer3);
+		assert_eq!(bc.needed_blocks(peer3, 10, 100, 0, 1, 200), Some(11..21));
+	}
+
 	#[test]
 	fn downloaded_range_not_requested_from_peers_with_higher_common_number() {
 		// A peer connects with a common number falling behind our best number
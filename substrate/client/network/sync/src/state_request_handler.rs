@@ -30,11 +30,11 @@ use prost::Message;
 use sc_network_types::PeerId;
 use schnellru::{ByLength, LruMap};
 
-use sc_client_api::{BlockBackend, ProofProvider};
+use sc_client_api::{BlockBackend, CompactProof, ProofProvider};
 use sc_network::{
 	config::ProtocolId,
-	request_responses::{IncomingRequest, OutgoingResponse},
-	NetworkBackend, MAX_RESPONSE_SIZE,
+	request_responses::{IfDisconnected, IncomingRequest, OutgoingResponse, RequestFailure},
+	NetworkBackend, NetworkRequest, ProtocolName, MAX_RESPONSE_SIZE,
 };
 use sp_runtime::traits::Block as BlockT;
 
@@ -77,7 +77,10 @@ pub fn generate_protocol_config<
 }
 
 /// Generate the state protocol name from the genesis hash and fork id.
-fn generate_protocol_name<Hash: AsRef<[u8]>>(genesis_hash: Hash, fork_id: Option<&str>) -> String {
+pub fn generate_protocol_name<Hash: AsRef<[u8]>>(
+	genesis_hash: Hash,
+	fork_id: Option<&str>,
+) -> String {
 	let genesis_hash = genesis_hash.as_ref();
 	if let Some(fork_id) = fork_id {
 		format!("/{}/{}/state/2", array_bytes::bytes2hex("", genesis_hash), fork_id)
@@ -276,6 +279,38 @@ where
 	}
 }
 
+/// Request the proof of the state of `block` from `peer`, starting at the keys `start`.
+///
+/// The proof contains the trie nodes leading to the start keys and the nodes of the following
+/// keys, up to the response size limit.
+pub async fn request_state_proof<Hash: Encode, N: NetworkRequest + ?Sized>(
+	network: &N,
+	peer: PeerId,
+	protocol_name: ProtocolName,
+	block: Hash,
+	start: Vec<Vec<u8>>,
+) -> Result<CompactProof, StateRequestError> {
+	let request = StateRequest { block: block.encode(), start, no_proof: false };
+	let (response, _) = network
+		.request(peer, protocol_name, request.encode_to_vec(), None, IfDisconnected::ImmediateError)
+		.await?;
+	let response = StateResponse::decode(&response[..])?;
+	Ok(CompactProof::decode(&mut response.proof.as_ref())?)
+}
+
+/// Error returned by [`request_state_proof`].
+#[derive(Debug, thiserror::Error)]
+pub enum StateRequestError {
+	#[error("Request failed: {0}.")]
+	Request(#[from] RequestFailure),
+
+	#[error("Failed to decode response: {0}.")]
+	DecodeProto(#[from] prost::DecodeError),
+
+	#[error("Failed to decode proof: {0}.")]
+	InvalidProof(#[from] codec::Error),
+}
+
 #[derive(Debug, thiserror::Error)]
 enum HandleRequestError {
 	#[error("Failed to decode request: {0}.")]
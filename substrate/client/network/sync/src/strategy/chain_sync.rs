@@ -46,7 +46,10 @@ use crate::{
 
 use futures::{channel::oneshot, FutureExt};
 use log::{debug, error, info, trace, warn};
-use prometheus_endpoint::{register, Gauge, PrometheusError, Registry, U64};
+use prometheus_endpoint::{
+	exponential_buckets, histogram_opts, register, Counter, Gauge, Histogram, PrometheusError,
+	Registry, U64,
+};
 use prost::Message;
 use sc_client_api::{blockchain::BlockGap, BlockBackend, ProofProvider};
 use sc_consensus::{BlockImportError, BlockImportStatus, IncomingBlock};
@@ -70,10 +73,13 @@ use std::{
 	collections::{HashMap, HashSet},
 	ops::Range,
 	sync::Arc,
+	time::Instant,
 };
+use throughput::PeerThroughput;
 
 #[cfg(test)]
 mod test;
+mod throughput;
 
 /// Maximum blocks to store in the import queue.
 const MAX_IMPORTING_BLOCKS: usize = 2048;
@@ -136,6 +142,9 @@ mod rep {
 struct Metrics {
 	queued_blocks: Gauge<U64>,
 	fork_targets: Gauge<U64>,
+	peer_throughput: Histogram,
+	downloaded_blocks: Counter<U64>,
+	stalled_requests: Counter<U64>,
 }
 
 impl Metrics {
@@ -150,6 +159,30 @@ impl Metrics {
 				let g = Gauge::new("substrate_sync_fork_targets", "Number of fork sync targets")?;
 				register(g, r)?
 			},
+			peer_throughput: {
+				let h = Histogram::with_opts(histogram_opts!(
+					"substrate_sync_peer_throughput",
+					"Average number of blocks per second downloaded from the peers, observed on \
+					 each block response",
+					exponential_buckets(1.0, 2.0, 14)
+						.expect("parameters are always valid values; qed")
+				))?;
+				register(h, r)?
+			},
+			downloaded_blocks: {
+				let c = Counter::new(
+					"substrate_sync_downloaded_blocks_total",
+					"Number of blocks downloaded in response to block requests",
+				)?;
+				register(c, r)?
+			},
+			stalled_requests: {
+				let c = Counter::new(
+					"substrate_sync_stalled_block_requests_total",
+					"Number of block requests reassigned to other peers for taking too long",
+				)?;
+				register(c, r)?
+			},
 		})
 	}
 }
@@ -341,6 +374,8 @@ pub struct ChainSync<B: BlockT, Client> {
 	gap_sync: Option<GapSync<B>>,
 	/// Pending actions.
 	actions: Vec<SyncingAction<B>>,
+	/// Block download throughput of the peers.
+	throughput: PeerThroughput,
 	/// Prometheus metrics.
 	metrics: Option<Metrics>,
 }
@@ -372,6 +407,7 @@ where
 		if let Some(gap_sync) = &mut self.gap_sync {
 			gap_sync.blocks.clear_peer_download(peer_id)
 		}
+		self.throughput.remove_peer(peer_id);
 
 		if let Some(state) = self.peers.remove(peer_id) {
 			if !state.state.is_available() {
@@ -967,6 +1003,7 @@ where
 			block_downloader,
 			gap_sync: None,
 			actions: Vec::new(),
+			throughput: PeerThroughput::default(),
 			metrics: metrics_registry.and_then(|r| match Metrics::register(r) {
 				Ok(metrics) => Some(metrics),
 				Err(err) => {
@@ -1439,10 +1476,50 @@ where
 		if request.fields == BlockAttributes::JUSTIFICATION {
 			self.on_block_justification(*peer_id, block_response)
 		} else {
+			self.on_blocks_downloaded(peer_id, block_response.blocks.len());
 			self.on_block_data(peer_id, Some(request), block_response)
 		}
 	}
 
+	/// Update the throughput of the peer with the blocks it sent in response to a block request.
+	fn on_blocks_downloaded(&mut self, peer_id: &PeerId, blocks: usize) {
+		let Some(blocks_per_second) = self.throughput.on_response(peer_id, blocks, Instant::now())
+		else {
+			return
+		};
+		trace!(
+			target: LOG_TARGET,
+			"Downloading from {peer_id} at {blocks_per_second:.1} blocks/s on average",
+		);
+		if let Some(metrics) = &self.metrics {
+			metrics.peer_throughput.observe(blocks_per_second);
+			metrics.downloaded_blocks.inc_by(blocks as u64);
+		}
+	}
+
+	/// Release the ranges of the block requests taking much longer than expected from the
+	/// throughput of the peers, so that they are requested from other peers.
+	fn release_stalled_requests(&mut self) {
+		for peer_id in self.throughput.stalled(Instant::now()) {
+			let released = match self.peers.get(&peer_id).map(|peer| &peer.state) {
+				Some(PeerSyncState::DownloadingNew(_)) =>
+					self.blocks.release_peer_download(&peer_id),
+				Some(PeerSyncState::DownloadingGap(_)) => self
+					.gap_sync
+					.as_mut()
+					.map_or(false, |gap_sync| gap_sync.blocks.release_peer_download(&peer_id)),
+				_ => false,
+			};
+			if released {
+				debug!(target: LOG_TARGET, "Block request to {peer_id} stalled, requesting the range from other peers");
+				self.allowed_requests.set_all();
+				if let Some(metrics) = &self.metrics {
+					metrics.stalled_requests.inc();
+				}
+			}
+		}
+	}
+
 	/// Submit a justification response for processing.
 	#[must_use]
 	fn on_block_justification(
@@ -1806,6 +1883,7 @@ where
 
 	/// Get block requests scheduled by sync to be sent out.
 	fn block_requests(&mut self) -> Vec<(PeerId, BlockRequest<B>)> {
+		self.release_stalled_requests();
 		if self.allowed_requests.is_empty() || self.state_sync.is_some() {
 			return Vec::new();
 		}
@@ -1829,9 +1907,13 @@ where
 		let gap_sync = &mut self.gap_sync;
 		let disconnected_peers = &mut self.disconnected_peers;
 		let metrics = self.metrics.as_ref();
-		let requests = self
-			.peers
-			.iter_mut()
+		// The fastest peers are asked first, for the blocks that are needed first.
+		let mut peers = self.peers.iter_mut().collect::<Vec<_>>();
+		peers.sort_by(|(a, _), (b, _)| self.throughput.cmp_peers(a, b));
+		let throughput = &mut self.throughput;
+		let now = Instant::now();
+		let requests = peers
+			.into_iter()
 			.filter_map(move |(&id, peer)| {
 				if !peer.state.is_available() ||
 					!allowed_requests.contains(&id) ||
@@ -1871,10 +1953,11 @@ where
 					blocks,
 					attrs,
 					max_parallel,
-					max_blocks_per_request,
+					throughput.request_size(&id, max_blocks_per_request),
 					last_finalized,
 					best_queued,
 				) {
+					throughput.on_request(id, req.max.unwrap_or(max_blocks_per_request), now);
 					peer.state = PeerSyncState::DownloadingNew(range.start);
 					trace!(
 						target: LOG_TARGET,
@@ -1912,9 +1995,10 @@ where
 						attrs,
 						sync.target,
 						sync.best_queued_number,
-						max_blocks_per_request,
+						throughput.request_size(&id, max_blocks_per_request),
 					)
 				}) {
+					throughput.on_request(id, req.max.unwrap_or(max_blocks_per_request), now);
 					peer.state = PeerSyncState::DownloadingGap(range.start);
 					trace!(
 						target: LOG_TARGET,
@@ -0,0 +1,232 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Block download throughput of the peers.
+//!
+//! The throughput of a peer is measured over its block range requests and averaged, so that the
+//! ranges are sized and assigned according to how fast the peers serve them, and the requests
+//! taking much longer than expected are detected as stalled.
+
+use sc_network_types::PeerId;
+use std::{
+	cmp::Ordering,
+	collections::HashMap,
+	time::{Duration, Instant},
+};
+
+/// Weight of the last measured throughput of a peer in its average.
+const SMOOTHING: f64 = 0.3;
+
+/// Minimum number of blocks requested from a slow peer.
+const MIN_BLOCKS_PER_REQUEST: u32 = 8;
+
+/// A request of a peer whose throughput is unknown is stalled after this long.
+const DEFAULT_STALL_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A request is never stalled before this long.
+const MIN_STALL_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// A request is stalled once it takes this many times longer than expected from the throughput of
+/// the peer.
+const STALL_FACTOR: f64 = 4.0;
+
+/// A block request in flight.
+#[derive(Debug)]
+struct PendingRequest {
+	/// When the request was sent.
+	started: Instant,
+	/// Number of requested blocks.
+	blocks: u32,
+	/// Whether the request was reported as stalled.
+	stalled: bool,
+}
+
+/// Throughput of a single peer.
+#[derive(Debug, Default)]
+struct Throughput {
+	/// Average number of blocks downloaded per second.
+	blocks_per_second: Option<f64>,
+	/// The block request in flight, if any.
+	pending: Option<PendingRequest>,
+}
+
+/// Block download throughput of the peers.
+#[derive(Debug, Default)]
+pub(crate) struct PeerThroughput {
+	peers: HashMap<PeerId, Throughput>,
+}
+
+impl PeerThroughput {
+	/// Note that a request for `blocks` blocks was sent to the peer.
+	pub fn on_request(&mut self, peer_id: PeerId, blocks: u32, now: Instant) {
+		self.peers.entry(peer_id).or_default().pending =
+			Some(PendingRequest { started: now, blocks, stalled: false });
+	}
+
+	/// Note that the peer answered its pending request with `blocks` blocks.
+	///
+	/// Returns the updated throughput of the peer, or `None` if no request was pending.
+	pub fn on_response(&mut self, peer_id: &PeerId, blocks: usize, now: Instant) -> Option<f64> {
+		let throughput = self.peers.get_mut(peer_id)?;
+		let request = throughput.pending.take()?;
+		let elapsed = now.saturating_duration_since(request.started).as_secs_f64();
+		// Responses are never measured as instant, to keep the throughput finite.
+		let measured = blocks as f64 / elapsed.max(0.001);
+		let average = match throughput.blocks_per_second {
+			Some(average) => average + SMOOTHING * (measured - average),
+			None => measured,
+		};
+		throughput.blocks_per_second = Some(average);
+		Some(average)
+	}
+
+	/// Forget about the peer.
+	pub fn remove_peer(&mut self, peer_id: &PeerId) {
+		self.peers.remove(peer_id);
+	}
+
+	/// Average number of blocks per second downloaded from the peer, if measured.
+	pub fn blocks_per_second(&self, peer_id: &PeerId) -> Option<f64> {
+		self.peers.get(peer_id).and_then(|throughput| throughput.blocks_per_second)
+	}
+
+	/// Number of blocks to request from the peer.
+	///
+	/// The fastest peers are asked for `max_blocks_per_request` blocks and slower peers for
+	/// proportionally smaller ranges, so that the ranges are downloaded in about the same time.
+	pub fn request_size(&self, peer_id: &PeerId, max_blocks_per_request: u32) -> u32 {
+		let fastest = self
+			.peers
+			.values()
+			.filter_map(|throughput| throughput.blocks_per_second)
+			.fold(0.0, f64::max);
+		match self.blocks_per_second(peer_id) {
+			Some(blocks_per_second) if fastest > 0.0 => {
+				let size = (max_blocks_per_request as f64 * blocks_per_second / fastest).ceil();
+				(size as u32).clamp(
+					MIN_BLOCKS_PER_REQUEST.min(max_blocks_per_request),
+					max_blocks_per_request,
+				)
+			},
+			_ => max_blocks_per_request,
+		}
+	}
+
+	/// Order the peers from the fastest to the slowest, the peers that weren't measured yet last.
+	pub fn cmp_peers(&self, a: &PeerId, b: &PeerId) -> Ordering {
+		let a = self.blocks_per_second(a).unwrap_or_default();
+		let b = self.blocks_per_second(b).unwrap_or_default();
+		b.partial_cmp(&a).unwrap_or(Ordering::Equal)
+	}
+
+	/// Returns the peers whose pending request became stalled since the last call.
+	pub fn stalled(&mut self, now: Instant) -> Vec<PeerId> {
+		self.peers
+			.iter_mut()
+			.filter_map(|(peer_id, throughput)| {
+				let request = throughput.pending.as_mut().filter(|request| !request.stalled)?;
+				let timeout = match throughput.blocks_per_second {
+					Some(blocks_per_second) if blocks_per_second > 0.0 => Duration::from_secs_f64(
+						(STALL_FACTOR * request.blocks as f64 / blocks_per_second)
+							.min(DEFAULT_STALL_TIMEOUT.as_secs_f64()),
+					)
+					.max(MIN_STALL_TIMEOUT),
+					_ => DEFAULT_STALL_TIMEOUT,
+				};
+				if now.saturating_duration_since(request.started) < timeout {
+					return None
+				}
+				request.stalled = true;
+				Some(*peer_id)
+			})
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn throughput_is_averaged_over_responses() {
+		let mut throughput = PeerThroughput::default();
+		let peer = PeerId::random();
+		let now = Instant::now();
+
+		// Nothing was requested.
+		assert_eq!(throughput.on_response(&peer, 10, now), None);
+
+		throughput.on_request(peer, 64, now);
+		assert_eq!(throughput.on_response(&peer, 64, now + Duration::from_secs(2)), Some(32.0));
+		assert_eq!(throughput.on_response(&peer, 64, now + Duration::from_secs(2)), None);
+
+		throughput.on_request(peer, 64, now);
+		let average = throughput.on_response(&peer, 64, now + Duration::from_secs(1)).unwrap();
+		assert!(average > 32.0 && average < 64.0);
+		assert_eq!(throughput.blocks_per_second(&peer), Some(average));
+
+		throughput.remove_peer(&peer);
+		assert_eq!(throughput.blocks_per_second(&peer), None);
+	}
+
+	#[test]
+	fn fast_peers_get_larger_ranges_first() {
+		let mut throughput = PeerThroughput::default();
+		let fast = PeerId::random();
+		let slow = PeerId::random();
+		let very_slow = PeerId::random();
+		let unknown = PeerId::random();
+		let now = Instant::now();
+
+		for (peer, blocks) in [(fast, 64), (slow, 16), (very_slow, 1)] {
+			throughput.on_request(peer, 64, now);
+			throughput.on_response(&peer, blocks, now + Duration::from_secs(1));
+		}
+
+		assert_eq!(throughput.request_size(&fast, 64), 64);
+		assert_eq!(throughput.request_size(&slow, 64), 16);
+		assert_eq!(throughput.request_size(&very_slow, 64), MIN_BLOCKS_PER_REQUEST);
+		assert_eq!(throughput.request_size(&unknown, 64), 64);
+
+		let mut peers = vec![unknown, very_slow, fast, slow];
+		peers.sort_by(|a, b| throughput.cmp_peers(a, b));
+		assert_eq!(peers, vec![fast, slow, very_slow, unknown]);
+	}
+
+	#[test]
+	fn stalled_requests_are_reported_once() {
+		let mut throughput = PeerThroughput::default();
+		let fast = PeerId::random();
+		let unknown = PeerId::random();
+		let now = Instant::now();
+
+		throughput.on_request(fast, 64, now);
+		throughput.on_response(&fast, 64, now + Duration::from_secs(1));
+
+		throughput.on_request(fast, 64, now);
+		throughput.on_request(unknown, 64, now);
+		assert!(throughput.stalled(now + Duration::from_secs(2)).is_empty());
+		assert_eq!(throughput.stalled(now + Duration::from_secs(5)), vec![fast]);
+		assert!(throughput.stalled(now + Duration::from_secs(6)).is_empty());
+		assert_eq!(throughput.stalled(now + DEFAULT_STALL_TIMEOUT), vec![unknown]);
+
+		// A stalled request still counts towards the throughput once answered.
+		let average = throughput.on_response(&fast, 64, now + Duration::from_secs(8)).unwrap();
+		assert!(average < 64.0);
+	}
+}
@@ -0,0 +1,257 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Persistence of the warp and state sync progress, used to resume them after a restart.
+
+use crate::LOG_TARGET;
+use codec::{Decode, Encode};
+use log::debug;
+use sp_consensus_grandpa::{AuthorityList, SetId};
+use std::{
+	fs::{self, File, OpenOptions},
+	io::{self, BufReader, Read, Seek, SeekFrom, Write},
+	path::Path,
+};
+
+/// File storing the progress of the warp proof download.
+const WARP_PROGRESS_FILE: &str = "warp-progress";
+
+/// File storing the journal of the state sync.
+const STATE_JOURNAL_FILE: &str = "state-journal";
+
+/// Progress of the warp proof download, as of the last verified proof.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub(crate) struct WarpProgress<Hash> {
+	/// Current authority set id.
+	pub set_id: SetId,
+	/// Current authorities.
+	pub authorities: AuthorityList,
+	/// Hash of the last block proven final.
+	pub last_hash: Hash,
+}
+
+/// Load the warp proof download progress stored in `dir`, if any.
+pub(crate) fn load_warp_progress<Hash: Decode>(dir: &Path) -> Option<WarpProgress<Hash>> {
+	let encoded = fs::read(dir.join(WARP_PROGRESS_FILE)).ok()?;
+	match WarpProgress::decode(&mut &encoded[..]) {
+		Ok(progress) => Some(progress),
+		Err(e) => {
+			debug!(target: LOG_TARGET, "Ignoring invalid warp sync progress: {e}");
+			None
+		},
+	}
+}
+
+/// Store the warp proof download progress in `dir`.
+pub(crate) fn store_warp_progress<Hash: Encode>(
+	dir: &Path,
+	progress: &WarpProgress<Hash>,
+) -> io::Result<()> {
+	fs::create_dir_all(dir)?;
+	// Replace the file at once, to never leave a partially written progress behind.
+	let tmp_path = dir.join(format!("{WARP_PROGRESS_FILE}.tmp"));
+	fs::write(&tmp_path, progress.encode())?;
+	fs::rename(tmp_path, dir.join(WARP_PROGRESS_FILE))
+}
+
+/// Remove the warp and state sync progress stored in `dir`.
+pub(crate) fn clear(dir: &Path) -> io::Result<()> {
+	match fs::remove_dir_all(dir) {
+		Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+		_ => Ok(()),
+	}
+}
+
+/// Read a record prefixed with its length from `input`.
+///
+/// Returns `None` at the end of the input, including when the record is only partially written.
+fn read_record(input: &mut impl Read) -> io::Result<Option<Vec<u8>>> {
+	let mut len = [0; 4];
+	match input.read_exact(&mut len) {
+		Ok(()) => {},
+		Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+		Err(e) => return Err(e),
+	}
+
+	let len = u32::from_le_bytes(len) as u64;
+	let mut record = Vec::new();
+	if (input.by_ref().take(len).read_to_end(&mut record)? as u64) < len {
+		return Ok(None)
+	}
+	Ok(Some(record))
+}
+
+/// Journal of the responses imported by a state sync, replayed to resume it.
+///
+/// The journal starts with the hash of the target block, followed by the responses. Every record
+/// is prefixed with its length as a little endian `u32`. A record partially written on shutdown
+/// is dropped when the journal is opened.
+pub(crate) struct StateJournal {
+	file: File,
+	/// Length of the header, the record of the target block.
+	header_len: u64,
+}
+
+impl StateJournal {
+	/// Open the journal of the state sync to block `target` in `dir`, and pass the stored
+	/// responses to `replay` one at a time, without loading the whole journal in memory.
+	///
+	/// The journal is truncated before the first response rejected by `replay`, and the journal of
+	/// a state sync to another block is discarded. Returns the journal along with the number of
+	/// replayed responses.
+	pub fn open(
+		dir: &Path,
+		target: &[u8],
+		mut replay: impl FnMut(&[u8]) -> bool,
+	) -> io::Result<(Self, usize)> {
+		fs::create_dir_all(dir)?;
+		let file = OpenOptions::new()
+			.read(true)
+			.write(true)
+			.create(true)
+			.truncate(false)
+			.open(dir.join(STATE_JOURNAL_FILE))?;
+		let header_len = 4 + target.len() as u64;
+
+		// Length of the journal up to the last replayed response, `0` to discard it.
+		let mut len = 0;
+		let mut replayed = 0;
+		{
+			let mut input = BufReader::new(&file);
+			if read_record(&mut input)?.as_deref() == Some(target) {
+				len = header_len;
+				while let Some(response) = read_record(&mut input)? {
+					if !replay(&response) {
+						break
+					}
+					len += 4 + response.len() as u64;
+					replayed += 1;
+				}
+			}
+		}
+
+		let mut journal = Self { file, header_len };
+		if len == 0 {
+			journal.file.set_len(0)?;
+			journal.file.seek(SeekFrom::Start(0))?;
+			journal.write_record(target)?;
+		} else {
+			journal.truncate_at(len)?;
+		}
+		Ok((journal, replayed))
+	}
+
+	/// Append a response to the journal.
+	pub fn append(&mut self, response: &[u8]) -> io::Result<()> {
+		self.write_record(response)
+	}
+
+	/// Remove all the responses from the journal.
+	pub fn clear(&mut self) -> io::Result<()> {
+		self.truncate_at(self.header_len)
+	}
+
+	fn truncate_at(&mut self, len: u64) -> io::Result<()> {
+		self.file.set_len(len)?;
+		self.file.seek(SeekFrom::Start(len))?;
+		Ok(())
+	}
+
+	fn write_record(&mut self, record: &[u8]) -> io::Result<()> {
+		let len = u32::try_from(record.len())
+			.map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Record too large"))?;
+		self.file.write_all(&[&len.to_le_bytes()[..], record].concat())?;
+		self.file.sync_data()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::H256;
+
+	#[test]
+	fn warp_progress_is_stored_and_cleared() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("sync");
+		assert_eq!(load_warp_progress::<H256>(&path), None);
+
+		let progress =
+			WarpProgress { set_id: 3, authorities: Vec::new(), last_hash: H256::random() };
+		store_warp_progress(&path, &progress).unwrap();
+		assert_eq!(load_warp_progress(&path), Some(progress));
+
+		clear(&path).unwrap();
+		clear(&path).unwrap();
+		assert_eq!(load_warp_progress::<H256>(&path), None);
+	}
+
+	/// Open the journal, returning all the stored responses.
+	fn open_all(path: &Path, target: &[u8]) -> (StateJournal, Vec<Vec<u8>>) {
+		let mut responses = Vec::new();
+		let (journal, replayed) = StateJournal::open(path, target, |response| {
+			responses.push(response.to_vec());
+			true
+		})
+		.unwrap();
+		assert_eq!(replayed, responses.len());
+		(journal, responses)
+	}
+
+	#[test]
+	fn state_journal_is_resumed_for_the_same_target() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path();
+
+		let (mut journal, responses) = open_all(path, b"target");
+		assert!(responses.is_empty());
+		journal.append(b"first").unwrap();
+		journal.append(b"second").unwrap();
+		journal.append(b"third").unwrap();
+		drop(journal);
+
+		// A partially written response is dropped.
+		let mut file = OpenOptions::new().append(true).open(path.join(STATE_JOURNAL_FILE)).unwrap();
+		file.write_all(&[10, 0, 0, 0, 1, 2]).unwrap();
+		drop(file);
+
+		let (_, responses) = open_all(path, b"target");
+		assert_eq!(responses, vec![b"first".to_vec(), b"second".to_vec(), b"third".to_vec()]);
+
+		// The responses from the first rejected one are dropped.
+		let (mut journal, replayed) =
+			StateJournal::open(path, b"target", |response| response == b"first").unwrap();
+		assert_eq!(replayed, 1);
+		journal.append(b"fourth").unwrap();
+		drop(journal);
+
+		let (mut journal, responses) = open_all(path, b"target");
+		assert_eq!(responses, vec![b"first".to_vec(), b"fourth".to_vec()]);
+		journal.clear().unwrap();
+		drop(journal);
+		assert!(open_all(path, b"target").1.is_empty());
+
+		// The journal of another target is discarded.
+		let (mut journal, responses) = open_all(path, b"other target");
+		assert!(responses.is_empty());
+		journal.append(b"fifth").unwrap();
+		drop(journal);
+		let (_, responses) = open_all(path, b"target");
+		assert!(responses.is_empty());
+	}
+}
@@ -25,7 +25,9 @@ use crate::{
 	service::network::NetworkServiceHandle,
 	strategy::{
 		chain_sync::{ChainSync, ChainSyncMode},
+		journal,
 		state::StateStrategy,
+		state_sync::StateSync,
 		warp::{WarpSync, WarpSyncConfig},
 		StrategyKey, SyncingAction, SyncingStrategy,
 	},
@@ -41,7 +43,7 @@ use sc_network_common::sync::{message::BlockAnnounce, SyncMode};
 use sc_network_types::PeerId;
 use sp_blockchain::{Error as ClientError, HeaderBackend, HeaderMetadata};
 use sp_runtime::traits::{Block as BlockT, Header, NumberFor};
-use std::{any::Any, collections::HashMap, sync::Arc};
+use std::{any::Any, collections::HashMap, path::PathBuf, sync::Arc};
 
 /// Corresponding `ChainSync` mode.
 fn chain_sync_mode(sync_mode: SyncMode) -> ChainSyncMode {
@@ -73,6 +75,9 @@ where
 	pub state_request_protocol_name: ProtocolName,
 	/// Block downloader
 	pub block_downloader: Arc<dyn BlockDownloader<Block>>,
+	/// Directory to persist the warp and state sync progress to, so that they resume after a
+	/// restart.
+	pub progress_dir: Option<PathBuf>,
 }
 
 /// Proxy to specific syncing strategies used in Polkadot.
@@ -357,13 +362,16 @@ where
 		if let SyncMode::Warp = config.mode {
 			let warp_sync_config = warp_sync_config
 				.expect("Warp sync configuration must be supplied in warp sync mode.");
-			let warp_sync = WarpSync::new(
+			let mut warp_sync = WarpSync::new(
 				client.clone(),
 				warp_sync_config,
 				warp_sync_protocol_name,
 				config.block_downloader.clone(),
 				config.min_peers_to_start_warp_sync,
 			);
+			if let Some(dir) = &config.progress_dir {
+				warp_sync = warp_sync.with_progress_dir(dir.clone());
+			}
 			Ok(Self {
 				config,
 				client,
@@ -404,12 +412,18 @@ where
 						target: LOG_TARGET,
 						"Warp sync is complete, continuing with state sync."
 					);
-					let state_sync = StateStrategy::new(
+					let mut state_sync = StateSync::new(
 						self.client.clone(),
 						res.target_header,
 						res.target_body,
 						res.target_justifications,
 						false,
+					);
+					if let Some(dir) = &self.config.progress_dir {
+						state_sync = state_sync.with_journal(dir);
+					}
+					let state_sync = StateStrategy::new_with_provider(
+						Box::new(state_sync),
 						self.peer_best_blocks
 							.iter()
 							.map(|(peer_id, (_, best_number))| (*peer_id, *best_number)),
@@ -425,6 +439,7 @@ where
 						target: LOG_TARGET,
 						"Warp sync failed. Continuing with full sync."
 					);
+					self.clear_progress();
 					let chain_sync = match ChainSync::new(
 						chain_sync_mode(self.config.mode),
 						self.client.clone(),
@@ -455,6 +470,7 @@ where
 			} else {
 				error!(target: LOG_TARGET, "State sync failed. Falling back to full sync.");
 			}
+			self.clear_progress();
 			let chain_sync = match ChainSync::new(
 				chain_sync_mode(self.config.mode),
 				self.client.clone(),
@@ -481,4 +497,13 @@ where
 			unreachable!("Only warp & state strategies can finish; qed")
 		}
 	}
+
+	/// Remove the persisted warp and state sync progress, which can't be resumed anymore.
+	fn clear_progress(&self) {
+		if let Some(dir) = &self.config.progress_dir {
+			if let Err(e) = journal::clear(dir) {
+				warn!(target: LOG_TARGET, "Failed to remove the warp and state sync progress: {e}");
+			}
+		}
+	}
 }
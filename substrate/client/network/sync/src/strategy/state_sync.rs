@@ -20,10 +20,12 @@
 
 use crate::{
 	schema::v1::{KeyValueStateEntry, StateEntry, StateRequest, StateResponse},
+	strategy::journal::StateJournal,
 	LOG_TARGET,
 };
 use codec::{Decode, Encode};
-use log::debug;
+use log::{debug, info, warn};
+use prost::Message;
 use sc_client_api::{CompactProof, KeyValueStates, ProofProvider};
 use sc_consensus::ImportedState;
 use smallvec::SmallVec;
@@ -32,7 +34,7 @@ use sp_runtime::{
 	traits::{Block as BlockT, Header, NumberFor},
 	Justifications,
 };
-use std::{collections::HashMap, fmt, sync::Arc};
+use std::{collections::HashMap, fmt, path::Path, sync::Arc};
 
 /// Generic state sync provider. Used for mocking in tests.
 pub trait StateSyncProvider<B: BlockT>: Send + Sync {
@@ -143,6 +145,7 @@ pub struct StateSync<B: BlockT, Client> {
 	metadata: StateSyncMetadata<B>,
 	state: HashMap<Vec<u8>, (Vec<(Vec<u8>, Vec<u8>)>, Vec<Vec<u8>>)>,
 	client: Arc<Client>,
+	journal: Option<StateJournal>,
 }
 
 impl<B, Client> StateSync<B, Client>
@@ -170,9 +173,74 @@ where
 				skip_proof,
 			},
 			state: HashMap::default(),
+			journal: None,
 		}
 	}
 
+	/// Keep a journal of the imported responses in `dir`, and resume from the responses already
+	/// stored there for the same target block.
+	///
+	/// The stored responses are verified again against the state root of the target block. The
+	/// journal is truncated at the first response that doesn't verify.
+	pub fn with_journal(mut self, dir: &Path) -> Self {
+		let target_hash = self.metadata.target_hash();
+		let mut rejected = false;
+		let mut completed = false;
+		let replay = |response: &[u8]| {
+			let Ok(response) = StateResponse::decode(response) else {
+				rejected = true;
+				return false
+			};
+			match self.import_response(response) {
+				ImportResult::Continue => true,
+				ImportResult::BadResponse => {
+					rejected = true;
+					false
+				},
+				ImportResult::Import(..) => {
+					completed = true;
+					false
+				},
+			}
+		};
+		let (mut journal, mut resumed) = match StateJournal::open(dir, target_hash.as_ref(), replay)
+		{
+			Ok(journal) => journal,
+			Err(e) => {
+				warn!(target: LOG_TARGET, "Failed to open the state sync journal: {e}");
+				return self
+			},
+		};
+
+		if completed {
+			// The response completing the state is never stored.
+			self.metadata.last_key.clear();
+			self.metadata.complete = false;
+			self.metadata.imported_bytes = 0;
+			self.state.clear();
+			resumed = 0;
+			if let Err(e) = journal.clear() {
+				warn!(target: LOG_TARGET, "Failed to clear the state sync journal: {e}");
+				return self
+			}
+		}
+		if rejected {
+			debug!(
+				target: LOG_TARGET,
+				"Dropped the stored state responses from the first one that failed verification",
+			);
+		}
+		if resumed > 0 {
+			info!(
+				target: LOG_TARGET,
+				"Resuming state sync of {target_hash:?} from {resumed} stored responses ({} bytes)",
+				self.metadata.imported_bytes,
+			);
+		}
+		self.journal = Some(journal);
+		self
+	}
+
 	fn process_state_key_values(
 		&mut self,
 		state_root: Vec<u8>,
@@ -211,50 +279,7 @@ where
 		}
 	}
 
-	fn process_state_unverified(&mut self, response: StateResponse) -> bool {
-		let mut complete = true;
-		// if the trie is a child trie and one of its parent trie is empty,
-		// the parent cursor stays valid.
-		// Empty parent trie content only happens when all the response content
-		// is part of a single child trie.
-		if self.metadata.last_key.len() == 2 && response.entries[0].entries.is_empty() {
-			// Do not remove the parent trie position.
-			self.metadata.last_key.pop();
-		} else {
-			self.metadata.last_key.clear();
-		}
-		for state in response.entries {
-			debug!(
-				target: LOG_TARGET,
-				"Importing state from {:?} to {:?}",
-				state.entries.last().map(|e| sp_core::hexdisplay::HexDisplay::from(&e.key)),
-				state.entries.first().map(|e| sp_core::hexdisplay::HexDisplay::from(&e.key)),
-			);
-
-			if !state.complete {
-				if let Some(e) = state.entries.last() {
-					self.metadata.last_key.push(e.key.clone());
-				}
-				complete = false;
-			}
-
-			let KeyValueStateEntry { state_root, entries, complete: _ } = state;
-			self.process_state_key_values(
-				state_root,
-				entries.into_iter().map(|StateEntry { key, value }| (key, value)),
-			);
-		}
-		complete
-	}
-}
-
-impl<B, Client> StateSyncProvider<B> for StateSync<B, Client>
-where
-	B: BlockT,
-	Client: ProofProvider<B> + Send + Sync + 'static,
-{
-	///  Validate and import a state response.
-	fn import(&mut self, response: StateResponse) -> ImportResult<B> {
+	fn import_response(&mut self, response: StateResponse) -> ImportResult<B> {
 		if response.entries.is_empty() && response.proof.is_empty() {
 			debug!(target: LOG_TARGET, "Bad state response");
 			return ImportResult::BadResponse
@@ -316,6 +341,61 @@ where
 		}
 	}
 
+	fn process_state_unverified(&mut self, response: StateResponse) -> bool {
+		let mut complete = true;
+		// if the trie is a child trie and one of its parent trie is empty,
+		// the parent cursor stays valid.
+		// Empty parent trie content only happens when all the response content
+		// is part of a single child trie.
+		if self.metadata.last_key.len() == 2 && response.entries[0].entries.is_empty() {
+			// Do not remove the parent trie position.
+			self.metadata.last_key.pop();
+		} else {
+			self.metadata.last_key.clear();
+		}
+		for state in response.entries {
+			debug!(
+				target: LOG_TARGET,
+				"Importing state from {:?} to {:?}",
+				state.entries.last().map(|e| sp_core::hexdisplay::HexDisplay::from(&e.key)),
+				state.entries.first().map(|e| sp_core::hexdisplay::HexDisplay::from(&e.key)),
+			);
+
+			if !state.complete {
+				if let Some(e) = state.entries.last() {
+					self.metadata.last_key.push(e.key.clone());
+				}
+				complete = false;
+			}
+
+			let KeyValueStateEntry { state_root, entries, complete: _ } = state;
+			self.process_state_key_values(
+				state_root,
+				entries.into_iter().map(|StateEntry { key, value }| (key, value)),
+			);
+		}
+		complete
+	}
+}
+
+impl<B, Client> StateSyncProvider<B> for StateSync<B, Client>
+where
+	B: BlockT,
+	Client: ProofProvider<B> + Send + Sync + 'static,
+{
+	///  Validate and import a state response.
+	fn import(&mut self, response: StateResponse) -> ImportResult<B> {
+		let encoded = self.journal.is_some().then(|| response.encode_to_vec());
+		let result = self.import_response(response);
+		if let (ImportResult::Continue, Some(encoded)) = (&result, encoded) {
+			if let Some(Err(e)) = self.journal.as_mut().map(|journal| journal.append(&encoded)) {
+				warn!(target: LOG_TARGET, "Failed to write the state sync journal: {e}");
+				self.journal = None;
+			}
+		}
+		result
+	}
+
 	/// Produce next state request.
 	fn next_request(&self) -> StateRequest {
 		self.metadata.next_request()
@@ -341,3 +421,68 @@ where
 		self.metadata.progress()
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use sp_blockchain::HeaderBackend;
+	use substrate_test_runtime_client::{
+		runtime::{Block, Hash},
+		DefaultTestClientBuilderExt, TestClientBuilder, TestClientBuilderExt,
+	};
+
+	fn state_response(
+		client: &impl ProofProvider<Block>,
+		hash: Hash,
+		request: StateRequest,
+	) -> StateResponse {
+		// Every response proves a few keys only.
+		let (proof, _) = client.read_proof_collection(hash, &request.start, 1).unwrap();
+		StateResponse { entries: Vec::new(), proof: proof.encode() }
+	}
+
+	#[test]
+	fn state_sync_is_resumed_from_verified_responses() {
+		let dir = tempfile::tempdir().unwrap();
+		let client = Arc::new(TestClientBuilder::new().build());
+		let hash = client.info().genesis_hash;
+		let header = client.header(hash).unwrap().unwrap();
+		let new_state_sync = || {
+			StateSync::new(client.clone(), header.clone(), None, None, false)
+				.with_journal(dir.path())
+		};
+
+		let mut state_sync = new_state_sync();
+		for _ in 0..2 {
+			let response = state_response(&*client, hash, state_sync.next_request());
+			assert!(matches!(state_sync.import(response), ImportResult::Continue));
+		}
+		let request = state_sync.next_request();
+		let progress = state_sync.progress();
+		drop(state_sync);
+
+		// A response that doesn't verify is dropped from the journal.
+		let (mut journal, _) = StateJournal::open(dir.path(), hash.as_ref(), |_| true).unwrap();
+		let mut bad_response = state_response(&*client, hash, request.clone());
+		bad_response.proof.truncate(bad_response.proof.len() / 2);
+		journal.append(&bad_response.encode_to_vec()).unwrap();
+		drop(journal);
+
+		let mut state_sync = new_state_sync();
+		assert_eq!(state_sync.next_request(), request);
+		assert_eq!(state_sync.progress(), progress);
+		assert_eq!(StateJournal::open(dir.path(), hash.as_ref(), |_| true).unwrap().1, 2);
+
+		loop {
+			let response = state_response(&*client, hash, state_sync.next_request());
+			match state_sync.import(response) {
+				ImportResult::Continue => {},
+				ImportResult::Import(imported_hash, ..) => {
+					assert_eq!(imported_hash, hash);
+					break
+				},
+				ImportResult::BadResponse => panic!("Valid response rejected"),
+			}
+		}
+	}
+}
@@ -24,15 +24,17 @@ use crate::{
 	block_relay_protocol::{BlockDownloader, BlockResponseError},
 	service::network::NetworkServiceHandle,
 	strategy::{
-		chain_sync::validate_blocks, disconnected_peers::DisconnectedPeers, StrategyKey,
-		SyncingAction,
+		chain_sync::validate_blocks,
+		disconnected_peers::DisconnectedPeers,
+		journal::{self, WarpProgress},
+		StrategyKey, SyncingAction,
 	},
 	types::{BadPeer, SyncState, SyncStatus},
 	LOG_TARGET,
 };
 use codec::{Decode, Encode};
 use futures::{channel::oneshot, FutureExt};
-use log::{debug, error, trace, warn};
+use log::{debug, error, info, trace, warn};
 use sc_network::{IfDisconnected, ProtocolName};
 use sc_network_common::sync::message::{
 	BlockAnnounce, BlockAttributes, BlockData, BlockRequest, Direction, FromBlock,
@@ -43,7 +45,7 @@ use sp_runtime::{
 	traits::{Block as BlockT, Header, NumberFor, Zero},
 	Justifications, SaturatedConversion,
 };
-use std::{any::Any, collections::HashMap, fmt, sync::Arc};
+use std::{any::Any, collections::HashMap, fmt, path::PathBuf, sync::Arc};
 
 /// Number of peers that need to be connected before warp sync is started.
 const MIN_PEERS_TO_START_WARP_SYNC: usize = 3;
@@ -215,6 +217,8 @@ pub struct WarpSync<B: BlockT, Client> {
 	result: Option<WarpSyncResult<B>>,
 	/// Number of peers that need to be connected before warp sync is started.
 	min_peers_to_start_warp_sync: usize,
+	/// Directory to persist the warp proof download progress to.
+	progress_dir: Option<PathBuf>,
 }
 
 impl<B, Client> WarpSync<B, Client>
@@ -254,6 +258,7 @@ where
 				actions: vec![SyncingAction::Finished],
 				result: None,
 				min_peers_to_start_warp_sync,
+				progress_dir: None,
 			}
 		}
 
@@ -275,9 +280,17 @@ where
 			actions: Vec::new(),
 			result: None,
 			min_peers_to_start_warp_sync,
+			progress_dir: None,
 		}
 	}
 
+	/// Persist the warp proof download progress to `dir`, and resume from the progress already
+	/// stored there.
+	pub fn with_progress_dir(mut self, dir: PathBuf) -> Self {
+		self.progress_dir = Some(dir);
+		self
+	}
+
 	/// Notify that a new peer has connected.
 	pub fn add_peer(&mut self, peer_id: PeerId, _best_hash: B::Hash, best_number: NumberFor<B>) {
 		self.peers.insert(peer_id, Peer { best_number, state: PeerState::Available });
@@ -327,10 +340,27 @@ where
 			return
 		}
 
+		let progress = self.progress_dir.as_deref().and_then(journal::load_warp_progress);
+		let WarpProgress { set_id, authorities, last_hash } = match progress {
+			Some(progress) => {
+				info!(
+					target: LOG_TARGET,
+					"Resuming warp sync from block {:?}, set_id={:?}",
+					progress.last_hash,
+					progress.set_id,
+				);
+				progress
+			},
+			None => WarpProgress {
+				set_id: 0,
+				authorities: warp_sync_provider.current_authorities(),
+				last_hash: self.client.info().genesis_hash,
+			},
+		};
 		self.phase = Phase::WarpProof {
-			set_id: 0,
-			authorities: warp_sync_provider.current_authorities(),
-			last_hash: self.client.info().genesis_hash,
+			set_id,
+			authorities,
+			last_hash,
 			warp_sync_provider: Arc::clone(warp_sync_provider),
 		};
 		trace!(target: LOG_TARGET, "Started warp sync with {} peers.", self.peers.len());
@@ -415,6 +445,16 @@ where
 				*authorities = new_authorities;
 				*last_hash = new_last_hash;
 				self.total_proof_bytes += response.0.len() as u64;
+				if let Some(dir) = &self.progress_dir {
+					let progress = WarpProgress {
+						set_id: *set_id,
+						authorities: authorities.clone(),
+						last_hash: *last_hash,
+					};
+					if let Err(e) = journal::store_warp_progress(dir, &progress) {
+						warn!(target: LOG_TARGET, "Failed to store the warp sync progress: {e}");
+					}
+				}
 			},
 			Ok(VerificationResult::Complete(new_set_id, _, header)) => {
 				log::debug!(
@@ -1271,6 +1311,53 @@ mod test {
 		assert!(matches!(warp_sync.phase, Phase::WarpProof { .. }));
 	}
 
+	#[test]
+	fn warp_proof_progress_is_resumed() {
+		let dir = tempfile::tempdir().unwrap();
+		let last_hash = Hash::random();
+		let mut provider = MockWarpSyncProvider::<Block>::new();
+		provider
+			.expect_current_authorities()
+			.once()
+			.return_const(AuthorityList::default());
+		provider.expect_verify().return_once(move |_proof, set_id, authorities| {
+			Ok(VerificationResult::Partial(set_id + 1, authorities, last_hash))
+		});
+		let mut warp_sync = WarpSync::new(
+			Arc::new(mock_client_without_state()),
+			WarpSyncConfig::WithProvider(Arc::new(provider)),
+			Some(ProtocolName::Static("")),
+			Arc::new(MockBlockDownloader::new()),
+			None,
+		)
+		.with_progress_dir(dir.path().to_path_buf());
+		for best_number in 1..11 {
+			warp_sync.add_peer(PeerId::random(), Hash::random(), best_number);
+		}
+		let (peer_id, _, _) = warp_sync.warp_proof_request().unwrap();
+		warp_sync.on_warp_proof_response(&peer_id, EncodedProof(Vec::new()));
+
+		// The authorities are not requested from the provider when resuming.
+		let mut warp_sync = WarpSync::new(
+			Arc::new(mock_client_without_state()),
+			WarpSyncConfig::WithProvider(Arc::new(MockWarpSyncProvider::<Block>::new())),
+			Some(ProtocolName::Static("")),
+			Arc::new(MockBlockDownloader::new()),
+			None,
+		)
+		.with_progress_dir(dir.path().to_path_buf());
+		for best_number in 1..11 {
+			warp_sync.add_peer(PeerId::random(), Hash::random(), best_number);
+		}
+		match &warp_sync.phase {
+			Phase::WarpProof { set_id, last_hash: resumed_hash, .. } => {
+				assert_eq!(*set_id, 1);
+				assert_eq!(*resumed_hash, last_hash);
+			},
+			_ => panic!("Invalid phase."),
+		}
+	}
+
 	#[test]
 	fn complete_warp_proof_advances_phase() {
 		let client = Arc::new(TestClientBuilder::new().set_no_genesis().build());
@@ -918,6 +918,7 @@ pub trait TestNetFactory: Default + Sized + Send {
 			state_request_protocol_name: state_request_protocol_config.name.clone(),
 			block_downloader: block_relay_params.downloader,
 			min_peers_to_start_warp_sync: None,
+			progress_dir: None,
 		};
 		// Initialize syncing strategy.
 		let syncing_strategy = Box::new(
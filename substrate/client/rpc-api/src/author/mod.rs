@@ -23,7 +23,7 @@ pub mod hash;
 
 use error::Error;
 use jsonrpsee::proc_macros::rpc;
-use sc_transaction_pool_api::TransactionStatus;
+use sc_transaction_pool_api::{PoolInspection, TransactionStatus};
 use sp_core::Bytes;
 
 /// Substrate authoring RPC API
@@ -59,6 +59,11 @@ pub trait AuthorApi<Hash, BlockHash> {
 	#[method(name = "author_pendingExtrinsics")]
 	fn pending_extrinsics(&self) -> Result<Vec<Bytes>, Error>;
 
+	/// Returns the ready and future transactions of every pool view together with their tags,
+	/// and the transactions recently dropped from the pool.
+	#[method(name = "author_inspectPool", with_extensions)]
+	fn inspect_pool(&self) -> Result<PoolInspection<Hash, BlockHash>, Error>;
+
 	/// Remove given extrinsic from the pool and temporarily ban it to prevent reimporting.
 	#[method(name = "author_removeExtrinsic", with_extensions)]
 	fn remove_extrinsic(
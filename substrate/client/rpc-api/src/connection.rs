@@ -0,0 +1,56 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Connection-related types.
+//!
+//! Contains the types describing the client of an RPC call, set in the call extensions by the
+//! substrate rpc server.
+
+use futures::future::{BoxFuture, FutureExt, Shared};
+use std::{future::Future, net::IpAddr};
+
+/// The IP address of the client that sent the RPC call.
+///
+/// This is the address given by the proxy headers when the server trusts them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ClientIp(pub IpAddr);
+
+/// Resolves when the WebSocket connection the RPC call was received on is closed.
+///
+/// Only set for calls received over WebSocket, each HTTP request is received on its own
+/// connection.
+#[derive(Clone)]
+pub struct ConnectionClosed(Shared<BoxFuture<'static, ()>>);
+
+impl ConnectionClosed {
+	/// Create a new [`ConnectionClosed`] from a future resolving when the connection is closed.
+	pub fn new(closed: impl Future<Output = ()> + Send + 'static) -> Self {
+		Self(closed.boxed().shared())
+	}
+
+	/// Wait until the connection is closed.
+	pub async fn closed(self) {
+		self.0.await
+	}
+}
+
+impl std::fmt::Debug for ConnectionClosed {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("ConnectionClosed").finish_non_exhaustive()
+	}
+}
@@ -22,9 +22,11 @@
 
 #![warn(missing_docs)]
 
+mod connection;
 mod error;
 mod policy;
 
+pub use connection::{ClientIp, ConnectionClosed};
 pub use policy::{check_if_safe, DenyUnsafe, UnsafeRpcError};
 
 pub mod author;
@@ -92,4 +92,16 @@ pub trait StatementApi {
 	/// Remove a statement from the store.
 	#[method(name = "statement_remove")]
 	fn remove(&self, statement_hash: [u8; 32]) -> RpcResult<()>;
+
+	/// Subscribe to the new statements which include all topics and, if `dest` is given, whose
+	/// decryption key is identified as `dest`.
+	///
+	/// The SCALE-encoded statements are sent as they are accepted by the store. The subscription
+	/// is closed if the client doesn't keep up with the new statements.
+	#[subscription(
+		name = "statement_subscribeStatement" => "statement_statement",
+		unsubscribe = "statement_unsubscribeStatement",
+		item = Bytes,
+	)]
+	fn subscribe_statement(&self, match_all_topics: Vec<[u8; 32]>, dest: Option<[u8; 32]>);
 }
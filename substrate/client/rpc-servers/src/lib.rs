@@ -32,9 +32,11 @@ use jsonrpsee::{
 	},
 	Methods, RpcModule,
 };
+use sc_rpc_api::{ClientIp, ConnectionClosed};
 use tower::Service;
 use utils::{
-	build_rpc_api, deny_unsafe, format_listen_addrs, get_proxy_ip, ListenAddrError, RpcSettings,
+	build_rpc_api, deny_unsafe, filter_rpc_api, format_listen_addrs, get_api_key, get_proxy_ip,
+	ApiKeyRateLimit, ListenAddrError, RpcSettings,
 };
 
 pub use ip_network::IpNetwork;
@@ -42,8 +44,10 @@ pub use jsonrpsee::{
 	core::id_providers::{RandomIntegerIdProvider, RandomStringIdProvider},
 	server::{middleware::rpc::RpcServiceBuilder, BatchRequestConfig},
 };
-pub use middleware::{Metrics, MiddlewareLayer, NodeHealthProxyLayer, RpcMetrics};
-pub use utils::{RpcEndpoint, RpcMethods};
+pub use middleware::{
+	MethodCosts, Metrics, MiddlewareLayer, NodeHealthProxyLayer, RateLimit, RpcMetrics,
+};
+pub use utils::{RateLimitTier, RpcEndpoint, RpcMethodFilter, RpcMethods};
 
 const MEGABYTE: u32 = 1024 * 1024;
 
@@ -118,9 +122,16 @@ where
 {
 	let Config { endpoints, metrics, tokio_handle, rpc_api, id_provider } = config;
 
+	// Erase the context type to be able to expose a different set of methods on each endpoint.
+	let rpc_api = {
+		let mut module = RpcModule::new(());
+		module.merge(rpc_api)?;
+		module
+	};
+
 	let (stop_handle, server_handle) = stop_channel();
 	let cfg = PerConnection {
-		methods: build_rpc_api(rpc_api).into(),
+		methods: build_rpc_api(rpc_api.clone()).into(),
 		metrics,
 		tokio_handle: tokio_handle.clone(),
 		stop_handle,
@@ -142,7 +153,7 @@ where
 		};
 		let local_addr = listener.local_addr();
 		local_addrs.push(local_addr);
-		let cfg = cfg.clone();
+		let mut cfg = cfg.clone();
 
 		let RpcSettings {
 			batch_config,
@@ -152,13 +163,25 @@ where
 			max_buffer_capacity_per_connection,
 			max_subscriptions_per_connection,
 			rpc_methods,
+			method_filter,
 			rate_limit_trust_proxy_headers,
 			rate_limit_whitelisted_ips,
+			rate_limit_method_costs,
+			rate_limit_api_keys,
 			host_filter,
 			cors,
 			rate_limit,
 		} = listener.rpc_settings();
 
+		if !method_filter.is_empty() {
+			cfg.methods = build_rpc_api(filter_rpc_api(&rpc_api, &method_filter)).into();
+			log::debug!(
+				target: "rpc",
+				"JSON-RPC server on {local_addr} exposes {} methods",
+				cfg.methods.method_names().count(),
+			);
+		}
+
 		let http_middleware = tower::ServiceBuilder::new()
 			.option_layer(host_filter)
 			// Proxy `GET /health, /health/readiness` requests to the internal
@@ -210,6 +233,8 @@ where
 				let cfg2 = cfg.clone();
 				let service_builder2 = service_builder.clone();
 				let rate_limit_whitelisted_ips2 = rate_limit_whitelisted_ips.clone();
+				let rate_limit_method_costs2 = rate_limit_method_costs.clone();
+				let rate_limit_api_keys2 = rate_limit_api_keys.clone();
 
 				let svc =
 					tower::service_fn(move |mut req: http::Request<hyper::body::Incoming>| {
@@ -221,36 +246,49 @@ where
 
 						let proxy_ip =
 							if rate_limit_trust_proxy_headers { get_proxy_ip(&req) } else { None };
+						req.extensions_mut().insert(ClientIp(proxy_ip.unwrap_or(ip)));
+
+						let api_key = get_api_key(&req).and_then(|key| {
+							let rate_limit = rate_limit_api_keys2.get(key).cloned();
+							if rate_limit.is_none() {
+								log::debug!(target: "rpc", "ip={ip}, proxy_ip={:?} unknown API key, rate-limit of the connection enabled", proxy_ip);
+							}
+							rate_limit
+						});
 
-						let rate_limit_cfg = if rate_limit_whitelisted_ips2
+						let (rate_limit_cfg, tier) = if rate_limit_whitelisted_ips2
 							.iter()
 							.any(|ips| ips.contains(proxy_ip.unwrap_or(ip)))
 						{
 							log::debug!(target: "rpc", "ip={ip}, proxy_ip={:?} is trusted, disabling rate-limit", proxy_ip);
-							None
+							(None, None)
+						} else if let Some(ApiKeyRateLimit { tier, rate_limit }) = api_key {
+							(rate_limit, Some(tier))
 						} else {
 							if !rate_limit_whitelisted_ips2.is_empty() {
 								log::debug!(target: "rpc", "ip={ip}, proxy_ip={:?} is not trusted, rate-limit enabled", proxy_ip);
 							}
-							rate_limit
+							(rate_limit.map(RateLimit::per_minute), None)
 						};
 
 						let is_websocket = ws::is_upgrade_request(&req);
 						let transport_label = if is_websocket { "ws" } else { "http" };
 
-						let middleware_layer = match (metrics, rate_limit_cfg) {
-							(None, None) => None,
-							(Some(metrics), None) => Some(
-								MiddlewareLayer::new()
-									.with_metrics(Metrics::new(metrics, transport_label)),
-							),
-							(None, Some(rate_limit)) =>
-								Some(MiddlewareLayer::new().with_rate_limit_per_minute(rate_limit)),
-							(Some(metrics), Some(rate_limit)) => Some(
-								MiddlewareLayer::new()
-									.with_metrics(Metrics::new(metrics, transport_label))
-									.with_rate_limit_per_minute(rate_limit),
-							),
+						let middleware_layer = if metrics.is_none() && rate_limit_cfg.is_none() {
+							None
+						} else {
+							let mut layer = MiddlewareLayer::new()
+								.with_method_costs(rate_limit_method_costs2.clone());
+							if let Some(metrics) = metrics {
+								layer = layer.with_metrics(Metrics::new(metrics, transport_label));
+							}
+							if let Some(rate_limit) = rate_limit_cfg {
+								layer = layer.with_rate_limit(rate_limit);
+							}
+							if let Some(tier) = tier {
+								layer = layer.with_tier(tier);
+							}
+							Some(layer)
 						};
 
 						let rpc_middleware = RpcServiceBuilder::new()
@@ -262,13 +300,14 @@ where
 
 						async move {
 							if is_websocket {
-								let on_disconnect = svc.on_session_closed();
+								let on_disconnect = ConnectionClosed::new(svc.on_session_closed());
+								req.extensions_mut().insert(on_disconnect.clone());
 
 								// Spawn a task to handle when the connection is closed.
 								tokio_handle.spawn(async move {
 									let now = std::time::Instant::now();
 									middleware_layer.as_ref().map(|m| m.ws_connect());
-									on_disconnect.await;
+									on_disconnect.closed().await;
 									middleware_layer.as_ref().map(|m| m.ws_disconnect(now));
 								});
 							}
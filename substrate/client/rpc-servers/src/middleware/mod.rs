@@ -20,11 +20,12 @@
 
 use std::{
 	num::NonZeroU32,
+	sync::Arc,
 	time::{Duration, Instant},
 };
 
 use futures::future::{BoxFuture, FutureExt};
-use governor::{clock::Clock, Jitter};
+use governor::Jitter;
 use jsonrpsee::{
 	server::middleware::rpc::RpcServiceT,
 	types::{ErrorObject, Id, Request},
@@ -42,10 +43,15 @@ pub use rate_limit::*;
 const MAX_JITTER: Duration = Duration::from_millis(50);
 const MAX_RETRIES: usize = 10;
 
+/// Rate limit tier of the calls made without an API key.
+pub const DEFAULT_RATE_LIMIT_TIER: &str = "default";
+
 /// JSON-RPC middleware layer.
 #[derive(Debug, Clone, Default)]
 pub struct MiddlewareLayer {
 	rate_limit: Option<RateLimit>,
+	method_costs: MethodCosts,
+	tier: Option<Arc<str>>,
 	metrics: Option<Metrics>,
 }
 
@@ -57,12 +63,27 @@ impl MiddlewareLayer {
 
 	/// Enable new rate limit middleware enforced per minute.
 	pub fn with_rate_limit_per_minute(self, n: NonZeroU32) -> Self {
-		Self { rate_limit: Some(RateLimit::per_minute(n)), metrics: self.metrics }
+		self.with_rate_limit(RateLimit::per_minute(n))
+	}
+
+	/// Enable rate limit middleware with a rate limit which may be shared with other layers.
+	pub fn with_rate_limit(self, rate_limit: RateLimit) -> Self {
+		Self { rate_limit: Some(rate_limit), ..self }
+	}
+
+	/// Set the cost of the methods in rate limit units.
+	pub fn with_method_costs(self, method_costs: MethodCosts) -> Self {
+		Self { method_costs, ..self }
+	}
+
+	/// Set the rate limit tier of the calls, reported in the metrics.
+	pub fn with_tier(self, tier: impl Into<Arc<str>>) -> Self {
+		Self { tier: Some(tier.into()), ..self }
 	}
 
 	/// Enable metrics middleware.
 	pub fn with_metrics(self, metrics: Metrics) -> Self {
-		Self { rate_limit: self.rate_limit, metrics: Some(metrics) }
+		Self { metrics: Some(metrics), ..self }
 	}
 
 	/// Register a new websocket connection.
@@ -80,7 +101,13 @@ impl<S> tower::Layer<S> for MiddlewareLayer {
 	type Service = Middleware<S>;
 
 	fn layer(&self, service: S) -> Self::Service {
-		Middleware { service, rate_limit: self.rate_limit.clone(), metrics: self.metrics.clone() }
+		Middleware {
+			service,
+			rate_limit: self.rate_limit.clone(),
+			method_costs: self.method_costs.clone(),
+			tier: self.tier.clone(),
+			metrics: self.metrics.clone(),
+		}
 	}
 }
 
@@ -94,6 +121,8 @@ impl<S> tower::Layer<S> for MiddlewareLayer {
 pub struct Middleware<S> {
 	service: S,
 	rate_limit: Option<RateLimit>,
+	method_costs: MethodCosts,
+	tier: Option<Arc<str>>,
 	metrics: Option<Metrics>,
 }
 
@@ -110,6 +139,8 @@ where
 
 		let service = self.service.clone();
 		let rate_limit = self.rate_limit.clone();
+		let cost = self.method_costs.cost(req.method_name());
+		let tier = self.tier.clone();
 		let metrics = self.metrics.clone();
 
 		async move {
@@ -121,12 +152,13 @@ where
 
 				loop {
 					if attempts >= MAX_RETRIES {
+						let tier = tier.as_deref().unwrap_or(DEFAULT_RATE_LIMIT_TIER);
+						metrics.as_ref().map(|m| m.on_throttled(&req, tier, true));
 						return reject_too_many_calls(req.id);
 					}
 
-					if let Err(rejected) = limit.inner.check() {
-						tokio::time::sleep(jitter + rejected.wait_time_from(limit.clock.now()))
-							.await;
+					if let Err(wait_time) = limit.check(cost) {
+						tokio::time::sleep(jitter + wait_time).await;
 					} else {
 						break;
 					}
@@ -136,6 +168,11 @@ where
 				}
 			}
 
+			if is_rate_limited {
+				let tier = tier.as_deref().unwrap_or(DEFAULT_RATE_LIMIT_TIER);
+				metrics.as_ref().map(|m| m.on_throttled(&req, tier, false));
+			}
+
 			let rp = service.call(req.clone()).await;
 			metrics.as_ref().map(|m| m.on_response(&req, &rp, is_rate_limited, now));
 
@@ -19,12 +19,12 @@
 //! RPC rate limit.
 
 use governor::{
-	clock::{DefaultClock, QuantaClock},
+	clock::{Clock, DefaultClock, QuantaClock},
 	middleware::NoOpMiddleware,
 	state::{InMemoryState, NotKeyed},
 	Quota,
 };
-use std::{num::NonZeroU32, sync::Arc};
+use std::{collections::HashMap, num::NonZeroU32, sync::Arc, time::Duration};
 
 type RateLimitInner = governor::RateLimiter<NotKeyed, InMemoryState, DefaultClock, NoOpMiddleware>;
 
@@ -33,6 +33,7 @@ type RateLimitInner = governor::RateLimiter<NotKeyed, InMemoryState, DefaultCloc
 pub struct RateLimit {
 	pub(crate) inner: Arc<RateLimitInner>,
 	pub(crate) clock: QuantaClock,
+	max_cost: NonZeroU32,
 }
 
 impl RateLimit {
@@ -42,6 +43,72 @@ impl RateLimit {
 		Self {
 			inner: Arc::new(RateLimitInner::direct_with_clock(Quota::per_minute(n), &clock)),
 			clock,
+			max_cost: n,
 		}
 	}
+
+	/// Consume `cost` units of the rate limit.
+	///
+	/// Returns how long to wait before the units are available if the limit is exceeded. A call
+	/// costing more than the limit per minute consumes the whole limit.
+	pub(crate) fn check(&self, cost: NonZeroU32) -> Result<(), Duration> {
+		match self.inner.check_n(cost.min(self.max_cost)) {
+			Ok(Ok(())) => Ok(()),
+			Ok(Err(not_until)) => Err(not_until.wait_time_from(self.clock.now())),
+			Err(_) => unreachable!("The cost is capped to the burst size of the quota; qed"),
+		}
+	}
+}
+
+/// Cost of the RPC methods in rate limit units.
+///
+/// The methods not listed cost one unit.
+#[derive(Debug, Clone, Default)]
+pub struct MethodCosts(Arc<HashMap<String, NonZeroU32>>);
+
+impl MethodCosts {
+	/// Create a new `MethodCosts`.
+	pub fn new(costs: HashMap<String, NonZeroU32>) -> Self {
+		Self(Arc::new(costs))
+	}
+
+	/// Returns the cost of the method `name`.
+	pub fn cost(&self, name: &str) -> NonZeroU32 {
+		self.0.get(name).copied().unwrap_or(NonZeroU32::MIN)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn method_costs_default_to_one() {
+		let costs = MethodCosts::new(
+			[("state_queryStorage".to_string(), NonZeroU32::new(50).unwrap())].into(),
+		);
+		assert_eq!(costs.cost("state_queryStorage").get(), 50);
+		assert_eq!(costs.cost("system_health").get(), 1);
+	}
+
+	#[test]
+	fn calls_consume_their_cost() {
+		let limit = RateLimit::per_minute(NonZeroU32::new(10).unwrap());
+		let cost = NonZeroU32::new(4).unwrap();
+
+		assert!(limit.check(cost).is_ok());
+		assert!(limit.check(cost).is_ok());
+		assert!(limit.check(cost).is_err());
+		assert!(limit.check(NonZeroU32::MIN).is_ok());
+		assert!(limit.check(NonZeroU32::MIN).is_ok());
+		assert!(limit.check(NonZeroU32::MIN).is_err());
+	}
+
+	#[test]
+	fn calls_costing_more_than_the_limit_consume_it_all() {
+		let limit = RateLimit::per_minute(NonZeroU32::new(10).unwrap());
+
+		assert!(limit.check(NonZeroU32::new(100).unwrap()).is_ok());
+		assert!(limit.check(NonZeroU32::MIN).is_err());
+	}
 }
@@ -18,12 +18,17 @@
 
 //! Substrate RPC server utils.
 
-use crate::BatchRequestConfig;
+use crate::{
+	middleware::{MethodCosts, RateLimit},
+	BatchRequestConfig,
+};
 use std::{
+	collections::HashMap,
 	error::Error as StdError,
 	net::{IpAddr, SocketAddr},
 	num::NonZeroU32,
 	str::FromStr,
+	sync::Arc,
 };
 
 use forwarded_header_value::ForwardedHeaderValue;
@@ -36,6 +41,8 @@ use tower_http::cors::{AllowOrigin, CorsLayer};
 const X_FORWARDED_FOR: HeaderName = HeaderName::from_static("x-forwarded-for");
 const X_REAL_IP: HeaderName = HeaderName::from_static("x-real-ip");
 const FORWARDED: HeaderName = HeaderName::from_static("forwarded");
+const X_API_KEY: HeaderName = HeaderName::from_static("x-api-key");
+const API_KEY_QUERY_PARAM: &str = "api_key";
 
 #[derive(Debug)]
 pub(crate) struct ListenAddrError;
@@ -78,6 +85,52 @@ impl FromStr for RpcMethods {
 	}
 }
 
+/// Filter of the RPC methods exposed by an endpoint.
+///
+/// A pattern is either the name of a method or a prefix followed by `*`, for example
+/// `chainHead_v1_*` for all the methods of the `chainHead_v1` namespace. The unsubscribe methods
+/// are filtered like any other method.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RpcMethodFilter {
+	/// Patterns of the exposed methods, all the methods are exposed if empty.
+	pub allow: Vec<String>,
+	/// Patterns of the methods which are not exposed, even if allowed.
+	pub deny: Vec<String>,
+}
+
+impl RpcMethodFilter {
+	/// Returns whether the filter exposes every method.
+	pub fn is_empty(&self) -> bool {
+		self.allow.is_empty() && self.deny.is_empty()
+	}
+
+	/// Returns whether the method `name` is exposed.
+	pub fn is_allowed(&self, name: &str) -> bool {
+		let matches = |pattern: &String| match pattern.strip_suffix('*') {
+			Some(prefix) => name.starts_with(prefix),
+			None => name == pattern,
+		};
+
+		(self.allow.is_empty() || self.allow.iter().any(matches)) && !self.deny.iter().any(matches)
+	}
+}
+
+/// Rate limit tier of API keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitTier {
+	/// Name of the tier, reported in the metrics.
+	pub name: String,
+	/// Rate limit per minute of each API key of the tier, `None` if unlimited.
+	pub rate_limit: Option<NonZeroU32>,
+}
+
+/// Rate limit of an API key, shared by all the connections using the key.
+#[derive(Debug, Clone)]
+pub(crate) struct ApiKeyRateLimit {
+	pub(crate) tier: Arc<str>,
+	pub(crate) rate_limit: Option<RateLimit>,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct RpcSettings {
 	pub(crate) batch_config: BatchRequestConfig,
@@ -87,9 +140,12 @@ pub(crate) struct RpcSettings {
 	pub(crate) max_subscriptions_per_connection: u32,
 	pub(crate) max_buffer_capacity_per_connection: u32,
 	pub(crate) rpc_methods: RpcMethods,
+	pub(crate) method_filter: RpcMethodFilter,
 	pub(crate) rate_limit: Option<NonZeroU32>,
 	pub(crate) rate_limit_trust_proxy_headers: bool,
 	pub(crate) rate_limit_whitelisted_ips: Vec<IpNetwork>,
+	pub(crate) rate_limit_method_costs: MethodCosts,
+	pub(crate) rate_limit_api_keys: Arc<HashMap<String, ApiKeyRateLimit>>,
 	pub(crate) cors: CorsLayer,
 	pub(crate) host_filter: Option<HostFilterLayer>,
 }
@@ -117,10 +173,20 @@ pub struct RpcEndpoint {
 	pub rate_limit_trust_proxy_headers: bool,
 	/// Whitelisted IPs for rate limiting.
 	pub rate_limit_whitelisted_ips: Vec<IpNetwork>,
+	/// Cost of the methods in rate limit units, the methods not listed cost one unit.
+	pub rate_limit_method_costs: HashMap<String, NonZeroU32>,
+	/// API keys with their rate limit tier.
+	///
+	/// The calls made with an API key are limited by the rate limit of its tier, shared by all
+	/// the connections using the key, instead of the rate limit of the connection. The key is read
+	/// from the `X-Api-Key` header or from the `api_key` query parameter of the request.
+	pub rate_limit_api_keys: HashMap<String, RateLimitTier>,
 	/// CORS.
 	pub cors: Option<Vec<String>>,
 	/// RPC methods to expose.
 	pub rpc_methods: RpcMethods,
+	/// Filter of the RPC methods to expose, applied on top of `rpc_methods`.
+	pub method_filter: RpcMethodFilter,
 	/// Whether it's an optional listening address i.e, it's ignored if it fails to bind.
 	/// For example substrate tries to bind both ipv4 and ipv6 addresses but some platforms
 	/// may not support ipv6.
@@ -145,6 +211,17 @@ impl RpcEndpoint {
 		let local_addr = listener.local_addr()?;
 		let host_filter = host_filtering(self.cors.is_some(), local_addr);
 		let cors = try_into_cors(self.cors)?;
+		let rate_limit_api_keys = self
+			.rate_limit_api_keys
+			.into_iter()
+			.map(|(key, tier)| {
+				let rate_limit = ApiKeyRateLimit {
+					tier: tier.name.into(),
+					rate_limit: tier.rate_limit.map(RateLimit::per_minute),
+				};
+				(key, rate_limit)
+			})
+			.collect();
 
 		Ok(Listener {
 			listener,
@@ -157,9 +234,12 @@ impl RpcEndpoint {
 				max_subscriptions_per_connection: self.max_subscriptions_per_connection,
 				max_buffer_capacity_per_connection: self.max_buffer_capacity_per_connection,
 				rpc_methods: self.rpc_methods,
+				method_filter: self.method_filter,
 				rate_limit: self.rate_limit,
 				rate_limit_trust_proxy_headers: self.rate_limit_trust_proxy_headers,
 				rate_limit_whitelisted_ips: self.rate_limit_whitelisted_ips,
+				rate_limit_method_costs: MethodCosts::new(self.rate_limit_method_costs),
+				rate_limit_api_keys: Arc::new(rate_limit_api_keys),
 				host_filter,
 				cors,
 			},
@@ -207,6 +287,16 @@ pub(crate) fn host_filtering(enabled: bool, addr: SocketAddr) -> Option<HostFilt
 	}
 }
 
+/// Returns the RPC API without the methods denied by `filter`.
+pub(crate) fn filter_rpc_api(rpc_api: &RpcModule<()>, filter: &RpcMethodFilter) -> RpcModule<()> {
+	let mut filtered = rpc_api.clone();
+	let denied = rpc_api.method_names().filter(|name| !filter.is_allowed(name));
+	for name in denied {
+		filtered.remove_method(name);
+	}
+	filtered
+}
+
 pub(crate) fn build_rpc_api<M: Send + Sync + 'static>(mut rpc_api: RpcModule<M>) -> RpcModule<M> {
 	let mut available_methods = rpc_api.method_names().collect::<Vec<_>>();
 	// The "rpc_methods" is defined below and we want it to be part of the reported methods.
@@ -280,6 +370,20 @@ pub(crate) fn get_proxy_ip<B>(req: &http::Request<B>) -> Option<IpAddr> {
 	None
 }
 
+/// Extracts the API key from the HTTP request.
+///
+/// It is read from the `X-Api-Key` header, or else from the `api_key` query parameter.
+pub(crate) fn get_api_key<B>(req: &http::Request<B>) -> Option<&str> {
+	if let Some(key) = req.headers().get(&X_API_KEY).and_then(|v| v.to_str().ok()) {
+		return Some(key);
+	}
+
+	req.uri().query()?.split('&').find_map(|param| match param.split_once('=') {
+		Some((API_KEY_QUERY_PARAM, key)) => Some(key),
+		_ => None,
+	})
+}
+
 /// Get the `deny_unsafe` setting based on the address and the RPC methods exposed by the interface.
 pub fn deny_unsafe(addr: &SocketAddr, methods: &RpcMethods) -> DenyUnsafe {
 	match (addr.ip().is_loopback(), methods) {
@@ -366,4 +470,60 @@ mod tests {
 		let ip = get_proxy_ip(&req);
 		assert_eq!(Some(IpAddr::from_str("127.0.0.1").unwrap()), ip);
 	}
+
+	#[test]
+	fn method_filter_works() {
+		let filter = RpcMethodFilter::default();
+		assert!(filter.is_empty());
+		assert!(filter.is_allowed("author_submitExtrinsic"));
+
+		let filter = RpcMethodFilter {
+			allow: vec!["chainHead_v1_*".into(), "transaction_v1_*".into(), "system_health".into()],
+			deny: vec!["transaction_v1_stop".into()],
+		};
+		assert!(filter.is_allowed("chainHead_v1_follow"));
+		assert!(filter.is_allowed("chainHead_v1_unfollow"));
+		assert!(filter.is_allowed("transaction_v1_broadcast"));
+		assert!(filter.is_allowed("system_health"));
+		assert!(!filter.is_allowed("transaction_v1_stop"));
+		assert!(!filter.is_allowed("system_healthy"));
+		assert!(!filter.is_allowed("author_submitExtrinsic"));
+
+		let filter = RpcMethodFilter { allow: vec![], deny: vec!["author_*".into()] };
+		assert!(filter.is_allowed("chainHead_v1_follow"));
+		assert!(!filter.is_allowed("author_submitExtrinsic"));
+	}
+
+	#[test]
+	fn filtered_rpc_api_only_has_allowed_methods() {
+		let mut rpc_api = RpcModule::new(());
+		for name in ["chainHead_v1_header", "author_rotateKeys", "system_health"] {
+			rpc_api.register_method(name, |_, _, _| serde_json::Value::Null).unwrap();
+		}
+		let filter = RpcMethodFilter {
+			allow: vec!["chainHead_v1_*".into(), "system_*".into()],
+			deny: vec![],
+		};
+
+		let filtered = filter_rpc_api(&rpc_api, &filter);
+		let mut names = filtered.method_names().collect::<Vec<_>>();
+		names.sort();
+		assert_eq!(names, vec!["chainHead_v1_header", "system_health"]);
+		assert_eq!(rpc_api.method_names().count(), 3);
+	}
+
+	#[test]
+	fn api_key_from_header_or_query() {
+		let req = request();
+		assert!(get_api_key(&req).is_none());
+
+		let mut req = HttpRequest::builder()
+			.uri("/?foo=bar&api_key=query-key")
+			.body(HttpBody::empty())
+			.unwrap();
+		assert_eq!(get_api_key(&req), Some("query-key"));
+
+		req.headers_mut().insert(&X_API_KEY, HeaderValue::from_static("header-key"));
+		assert_eq!(get_api_key(&req), Some("header-key"));
+	}
 }
@@ -21,10 +21,11 @@
 use crate::{
 	archive::{
 		error::{Error, Infallible},
-		types::MethodResult,
+		types::{ExtrinsicLocation, MethodResult},
 	},
 	common::events::{
-		ArchiveStorageDiffEvent, ArchiveStorageDiffItem, ArchiveStorageEvent, StorageQuery,
+		ArchiveStorageDiffEvent, ArchiveStorageDiffItem, ArchiveStorageEvent,
+		ArchiveStorageHistoryEvent, StorageQuery,
 	},
 };
 use jsonrpsee::proc_macros::rpc;
@@ -84,6 +85,21 @@ pub trait ArchiveApi<Hash> {
 	#[method(name = "archive_v1_hashByHeight")]
 	fn archive_v1_hash_by_height(&self, height: u64) -> Result<Vec<String>, Error>;
 
+	/// Get the blocks containing the extrinsic with the given hash.
+	///
+	/// Returns an array (possibly empty) of the blocks of the finalized chain and of the
+	/// unfinalized blocks containing the extrinsic, with the index of the extrinsic in their body.
+	/// Fails if the node doesn't maintain an index of the extrinsics.
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and can change in minor or patch releases.
+	#[method(name = "archive_v1_extrinsicLocations")]
+	fn archive_v1_extrinsic_locations(
+		&self,
+		extrinsic_hash: Hash,
+	) -> Result<Vec<ExtrinsicLocation>, Error>;
+
 	/// Call into the Runtime API at a specified block's state.
 	///
 	/// # Unstable
@@ -130,4 +146,25 @@ pub trait ArchiveApi<Hash> {
 		items: Vec<ArchiveStorageDiffItem<String>>,
 		previous_hash: Option<Hash>,
 	);
+
+	/// Returns the storage changes of the finalized blocks in the range `start..=end` of heights.
+	///
+	/// Every block is compared with its parent, the changes are reported in ascending order of
+	/// heights. Long ranges are reported in multiple subscriptions, the final event carries the
+	/// height to resume from.
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and can change in minor or patch releases.
+	#[subscription(
+		name = "archive_v1_storageHistory" => "archive_v1_storageHistoryEvent",
+		unsubscribe = "archive_v1_stopStorageHistory",
+		item = ArchiveStorageHistoryEvent,
+	)]
+	fn archive_v1_storage_history(
+		&self,
+		items: Vec<ArchiveStorageDiffItem<String>>,
+		start: u64,
+		end: u64,
+	);
 }
@@ -20,14 +20,15 @@
 
 use crate::{
 	archive::{
-		archive_storage::ArchiveStorageDiff,
+		archive_storage::{ArchiveStorageDiff, HistoryBlock},
 		error::{Error as ArchiveError, Infallible},
-		types::MethodResult,
+		types::{ExtrinsicLocation, MethodResult},
 		ArchiveApiServer,
 	},
 	common::{
 		events::{
-			ArchiveStorageDiffEvent, ArchiveStorageDiffItem, ArchiveStorageEvent, StorageQuery,
+			ArchiveStorageDiffEvent, ArchiveStorageDiffItem, ArchiveStorageEvent,
+			ArchiveStorageHistoryEvent, StorageQuery,
 		},
 		storage::{QueryResult, StorageSubscriptionClient},
 	},
@@ -63,6 +64,12 @@ pub(crate) const LOG_TARGET: &str = "rpc-spec-v2::archive";
 /// its down buffer capacity per connection as well.
 const STORAGE_QUERY_BUF: usize = 16;
 
+/// The maximum number of blocks reported by a single storage history subscription.
+///
+/// The history of longer ranges is reported by further subscriptions, starting from the block
+/// carried by the final event.
+const MAX_STORAGE_HISTORY_BLOCKS: u64 = 512;
+
 /// An API for archive RPC calls.
 pub struct Archive<BE: Backend<Block>, Block: BlockT, Client> {
 	/// Substrate client.
@@ -195,6 +202,31 @@ where
 		Ok(result)
 	}
 
+	fn archive_v1_extrinsic_locations(
+		&self,
+		extrinsic_hash: Block::Hash,
+	) -> Result<Vec<ExtrinsicLocation>, ArchiveError> {
+		let locations = self
+			.backend
+			.blockchain()
+			.extrinsic_locations(extrinsic_hash)
+			.map_err(|error| ArchiveError::ExtrinsicIndex(error.to_string()))?
+			.ok_or_else(|| ArchiveError::ExtrinsicIndex("the index is disabled".into()))?;
+
+		Ok(locations
+			.into_iter()
+			.filter_map(|(hash, index)| {
+				let Ok(Some(header)) = self.client.header(hash) else { return None };
+
+				Some(ExtrinsicLocation {
+					block_hash: hex_string(&hash.as_ref()),
+					block_number: (*header.number()).saturated_into(),
+					index,
+				})
+			})
+			.collect())
+	}
+
 	fn archive_v1_call(
 		&self,
 		hash: Block::Hash,
@@ -306,6 +338,97 @@ where
 
 		self.executor.spawn("substrate-rpc-subscription", Some("rpc"), fut.boxed());
 	}
+
+	fn archive_v1_storage_history(
+		&self,
+		pending: PendingSubscriptionSink,
+		items: Vec<ArchiveStorageDiffItem<String>>,
+		start: u64,
+		end: u64,
+	) {
+		let storage_client = ArchiveStorageDiff::new(self.client.clone());
+		let client = self.client.clone();
+
+		log::trace!(target: LOG_TARGET, "Storage history subscription started");
+
+		let fut = async move {
+			let Ok(mut sink) = pending.accept().await.map(Subscription::from) else { return };
+
+			let (blocks, next_block) = match storage_history_blocks(&*client, start, end) {
+				Ok(blocks) => blocks,
+				Err(error) => {
+					let _ = sink.send(&ArchiveStorageHistoryEvent::err(error)).await;
+					return
+				},
+			};
+
+			let (tx, mut rx) = tokio::sync::mpsc::channel(STORAGE_QUERY_BUF);
+			let storage_fut = storage_client.handle_history_queries(blocks, items, next_block, tx);
+
+			// We don't care about the return value of this join:
+			// - process_events might encounter an error (if the client disconnected)
+			// - storage_fut might encounter an error while processing a trie queries and
+			// the error is propagated via the sink.
+			let _ = futures::future::join(
+				storage_fut,
+				process_storage_history_events(&mut rx, &mut sink),
+			)
+			.await;
+		};
+
+		self.executor.spawn("substrate-rpc-subscription", Some("rpc"), fut.boxed());
+	}
+}
+
+/// Resolve the finalized blocks in the range `start..=end` of heights of a storage history query.
+///
+/// At most [`MAX_STORAGE_HISTORY_BLOCKS`] blocks are returned, together with the first block of
+/// the range that is not returned.
+fn storage_history_blocks<Block, Client>(
+	client: &Client,
+	start: u64,
+	end: u64,
+) -> Result<(Vec<HistoryBlock<Block::Hash>>, Option<u64>), String>
+where
+	Block: BlockT,
+	Client: HeaderBackend<Block>,
+{
+	if start > end {
+		return Err(ArchiveError::InvalidParam(format!(
+			"Start of the range {start} is above its end {end}"
+		))
+		.to_string())
+	}
+	let finalized_number: u64 = client.info().finalized_number.saturated_into();
+	if end > finalized_number {
+		return Err(ArchiveError::InvalidParam(format!(
+			"End of the range {end} is above the finalized height {finalized_number}"
+		))
+		.to_string())
+	}
+
+	let last = end.min(start.saturating_add(MAX_STORAGE_HISTORY_BLOCKS - 1));
+	let next_block = (last < end).then(|| last + 1);
+
+	let mut blocks = Vec::with_capacity((last - start + 1) as usize);
+	for number in start..=last {
+		let hash = client
+			.hash(number.saturated_into())
+			.map_err(|error| error.to_string())?
+			.ok_or_else(|| format!("Block hash is not present: {number}"))?;
+		let parent_hash = if number == 0 {
+			None
+		} else {
+			let header = client
+				.header(hash)
+				.map_err(|error| error.to_string())?
+				.ok_or_else(|| format!("Block header is not present: {hash}"))?;
+			Some(*header.parent_hash())
+		};
+		blocks.push(HistoryBlock { number, hash, parent_hash });
+	}
+
+	Ok((blocks, next_block))
 }
 
 /// Sends all the events of the storage_diff method to the sink.
@@ -338,6 +461,36 @@ async fn process_storage_diff_events(
 	}
 }
 
+/// Sends all the events of the storage_history method to the sink.
+async fn process_storage_history_events(
+	rx: &mut mpsc::Receiver<ArchiveStorageHistoryEvent>,
+	sink: &mut Subscription,
+) {
+	loop {
+		tokio::select! {
+			_ = sink.closed() => {
+				return
+			},
+
+			maybe_event = rx.recv() => {
+				let Some(event) = maybe_event else {
+					break;
+				};
+
+				if event.is_done() {
+					log::debug!(target: LOG_TARGET, "Finished processing storage history query");
+				} else if event.is_err() {
+					log::debug!(target: LOG_TARGET, "Error encountered while processing storage history query");
+				}
+
+				if sink.send(&event).await.is_err() {
+					return
+				}
+			}
+		}
+	}
+}
+
 /// Sends all the events of the storage method to the sink.
 async fn process_storage_events(rx: &mut mpsc::Receiver<QueryResult>, sink: &mut Subscription) {
 	loop {
@@ -23,7 +23,7 @@ use std::{
 	sync::Arc,
 };
 
-use itertools::Itertools;
+use itertools::{Either, Itertools};
 use sc_client_api::{Backend, ChildInfo, StorageKey, StorageProvider};
 use sp_runtime::traits::Block as BlockT;
 
@@ -33,10 +33,12 @@ use crate::{
 	common::{
 		events::{
 			ArchiveStorageDiffEvent, ArchiveStorageDiffItem, ArchiveStorageDiffOperationType,
-			ArchiveStorageDiffResult, ArchiveStorageDiffType, StorageResult,
+			ArchiveStorageDiffResult, ArchiveStorageDiffType, ArchiveStorageHistoryDone,
+			ArchiveStorageHistoryEvent, ArchiveStorageHistoryResult, StorageResult,
 		},
 		storage::Storage,
 	},
+	hex_string,
 };
 use tokio::sync::mpsc;
 
@@ -82,6 +84,28 @@ enum FetchedStorage {
 	Both { value: StorageResult, hash: StorageResult },
 }
 
+impl FetchedStorage {
+	/// The storage results to report, the value comes first.
+	fn into_results(self) -> Vec<StorageResult> {
+		match self {
+			FetchedStorage::Value(storage_result) | FetchedStorage::Hash(storage_result) =>
+				vec![storage_result],
+			FetchedStorage::Both { value, hash } => vec![value, hash],
+		}
+	}
+}
+
+/// A finalized block of a storage history query.
+#[derive(Debug, PartialEq, Clone)]
+pub struct HistoryBlock<Hash> {
+	/// The number of the block.
+	pub number: u64,
+	/// The hash of the block.
+	pub hash: Hash,
+	/// The hash of the parent block, `None` for the genesis block.
+	pub parent_hash: Option<Hash>,
+}
+
 pub struct ArchiveStorageDiff<Client, Block, BE> {
 	client: Storage<Client, Block, BE>,
 }
@@ -178,13 +202,7 @@ where
 		operation_type: ArchiveStorageDiffOperationType,
 		child_trie_key: Option<String>,
 	) -> bool {
-		let items = match result {
-			FetchedStorage::Value(storage_result) | FetchedStorage::Hash(storage_result) =>
-				vec![storage_result],
-			FetchedStorage::Both { value, hash } => vec![value, hash],
-		};
-
-		for item in items {
+		for item in result.into_results() {
 			let res = ArchiveStorageDiffEvent::StorageDiff(ArchiveStorageDiffResult {
 				key: item.key,
 				result: item.result,
@@ -199,24 +217,90 @@ where
 		true
 	}
 
-	fn handle_trie_queries_inner(
+	/// Pass the change of `key` made by the block `hash` to `send` if the key belongs to the
+	/// query items.
+	///
+	/// Modified keys are only reported if their values differ from the block `previous_hash`.
+	/// Returns `false` if `send` returned `false`.
+	fn diff_key(
 		&self,
 		hash: Block::Hash,
-		previous_hash: Block::Hash,
-		items: Vec<DiffDetails>,
-		tx: &mpsc::Sender<ArchiveStorageDiffEvent>,
-	) -> Result<(), String> {
-		// Parse the child trie key as `ChildInfo` and `String`.
+		previous_hash: Option<Block::Hash>,
+		key: StorageKey,
+		operation_type: ArchiveStorageDiffOperationType,
+		items: &[DiffDetails],
+		send: &mut impl FnMut(FetchedStorage, ArchiveStorageDiffOperationType) -> bool,
+	) -> Result<bool, String> {
+		let maybe_child_trie = items.first().and_then(|item| item.child_trie_key.clone());
+
+		let Some(fetch_type) = Self::belongs_to_query(&key, items) else {
+			// The key does not belong the the query items.
+			return Ok(true)
+		};
+
+		let maybe_result = match (operation_type, previous_hash) {
+			(ArchiveStorageDiffOperationType::Added, _) =>
+				self.fetch_storage(hash, key, maybe_child_trie, fetch_type)?,
+			// Keys are only deleted or kept if there is a previous block.
+			(_, None) => None,
+			(ArchiveStorageDiffOperationType::Deleted, Some(previous_hash)) =>
+				self.fetch_storage(previous_hash, key, maybe_child_trie, fetch_type)?,
+			(ArchiveStorageDiffOperationType::Modified, Some(previous_hash)) => {
+				let Some(storage_result) =
+					self.fetch_storage(hash, key.clone(), maybe_child_trie.clone(), fetch_type)?
+				else {
+					return Ok(true)
+				};
+
+				let Some(previous_storage_result) =
+					self.fetch_storage(previous_hash, key, maybe_child_trie, fetch_type)?
+				else {
+					return Ok(true)
+				};
+
+				// For modified records we need to check the actual storage values.
+				if storage_result == previous_storage_result {
+					return Ok(true)
+				}
+
+				Some(storage_result)
+			},
+		};
+
+		match maybe_result {
+			Some(storage_result) => Ok(send(storage_result, operation_type)),
+			None => Ok(true),
+		}
+	}
+
+	/// Compare the keys of the block `hash` starting with `prefix` with the keys of the block
+	/// `previous_hash` and pass the changes of the keys that belong to the query items to `send`.
+	///
+	/// All the keys of the block `hash` are reported as added when there is no previous block.
+	/// Returns `false` if `send` returned `false`.
+	fn diff_keys(
+		&self,
+		hash: Block::Hash,
+		previous_hash: Option<Block::Hash>,
+		prefix: Option<&StorageKey>,
+		items: &[DiffDetails],
+		send: &mut impl FnMut(FetchedStorage, ArchiveStorageDiffOperationType) -> bool,
+	) -> Result<bool, String> {
 		let maybe_child_trie = items.first().and_then(|item| item.child_trie_key.clone());
-		let maybe_child_trie_str =
-			items.first().and_then(|item| item.child_trie_key_string.clone());
 
 		// Iterator over the current block and previous block
 		// at the same time to compare the keys. This approach effectively
 		// leverages backpressure to avoid memory consumption.
-		let keys_iter = self.client.raw_keys_iter(hash, maybe_child_trie.clone())?;
-		let previous_keys_iter =
-			self.client.raw_keys_iter(previous_hash, maybe_child_trie.clone())?;
+		let keys_iter = self.client.raw_keys_iter(hash, maybe_child_trie.clone(), prefix, None)?;
+		let previous_keys_iter = match previous_hash {
+			Some(previous_hash) => Either::Left(self.client.raw_keys_iter(
+				previous_hash,
+				maybe_child_trie.clone(),
+				prefix,
+				None,
+			)?),
+			None => Either::Right(core::iter::empty()),
+		};
 
 		let mut diff_iter = lexicographic_diff(keys_iter, previous_keys_iter);
 
@@ -227,65 +311,180 @@ where
 				Diff::Equal(key) => (ArchiveStorageDiffOperationType::Modified, key),
 			};
 
-			let Some(fetch_type) = Self::belongs_to_query(&key, &items) else {
-				// The key does not belong the the query items.
-				continue;
-			};
+			if !self.diff_key(hash, previous_hash, key, operation_type, items, send)? {
+				return Ok(false)
+			}
+		}
 
-			let maybe_result = match operation_type {
-				ArchiveStorageDiffOperationType::Added =>
-					self.fetch_storage(hash, key.clone(), maybe_child_trie.clone(), fetch_type)?,
-				ArchiveStorageDiffOperationType::Deleted => self.fetch_storage(
-					previous_hash,
-					key.clone(),
-					maybe_child_trie.clone(),
-					fetch_type,
-				)?,
-				ArchiveStorageDiffOperationType::Modified => {
-					let Some(storage_result) = self.fetch_storage(
-						hash,
-						key.clone(),
-						maybe_child_trie.clone(),
-						fetch_type,
-					)?
-					else {
-						continue
-					};
-
-					let Some(previous_storage_result) = self.fetch_storage(
-						previous_hash,
-						key.clone(),
-						maybe_child_trie.clone(),
-						fetch_type,
-					)?
-					else {
-						continue
-					};
+		Ok(true)
+	}
 
-					// For modified records we need to check the actual storage values.
-					if storage_result == previous_storage_result {
-						continue
-					}
+	/// Pass the changes of the keys starting with `prefix` made by the block `hash` to the block
+	/// `previous_hash` to `send`, in lexicographic order.
+	///
+	/// The trie is only descended where the closest merkle values of the blocks differ, so the
+	/// keys of unchanged subtries are never read from the database. A subtrie that only exists in
+	/// one of the blocks is iterated, since all of its keys changed.
+	///
+	/// Returns `false` if `send` returned `false`.
+	fn diff_subtrie(
+		&self,
+		hash: Block::Hash,
+		previous_hash: Block::Hash,
+		prefix: StorageKey,
+		items: &[DiffDetails],
+		send: &mut impl FnMut(FetchedStorage, ArchiveStorageDiffOperationType) -> bool,
+	) -> Result<bool, String> {
+		let maybe_child_trie = items.first().and_then(|item| item.child_trie_key.clone());
 
-					Some(storage_result)
-				},
-			};
+		let merkle_value =
+			self.client.query_merkle_value(hash, &prefix, maybe_child_trie.as_ref())?;
+		let previous_merkle_value =
+			self.client
+				.query_merkle_value(previous_hash, &prefix, maybe_child_trie.as_ref())?;
+		if merkle_value == previous_merkle_value {
+			return Ok(true)
+		}
+		if merkle_value.is_none() || previous_merkle_value.is_none() {
+			return self.diff_keys(hash, Some(previous_hash), Some(&prefix), items, send)
+		}
 
-			if let Some(storage_result) = maybe_result {
-				if !Self::send_result(
-					&tx,
-					storage_result,
-					operation_type,
-					maybe_child_trie_str.clone(),
-				) {
-					return Ok(())
+		// The prefix itself is sorted before the other keys of the subtrie.
+		if Self::belongs_to_query(&prefix, items).is_some() {
+			let exists =
+				self.client.query_hash(hash, &prefix, maybe_child_trie.as_ref())?.is_some();
+			let previously_exists = self
+				.client
+				.query_hash(previous_hash, &prefix, maybe_child_trie.as_ref())?
+				.is_some();
+			let operation_type = match (exists, previously_exists) {
+				(true, true) => Some(ArchiveStorageDiffOperationType::Modified),
+				(true, false) => Some(ArchiveStorageDiffOperationType::Added),
+				(false, true) => Some(ArchiveStorageDiffOperationType::Deleted),
+				(false, false) => None,
+			};
+			if let Some(operation_type) = operation_type {
+				let key = prefix.clone();
+				if !self.diff_key(hash, Some(previous_hash), key, operation_type, items, send)? {
+					return Ok(false)
 				}
 			}
 		}
 
+		let mut next_byte = Some(0u8);
+		while let Some(byte) = next_byte {
+			let first_child = [hash, previous_hash]
+				.into_iter()
+				.map(|hash| self.first_child(hash, maybe_child_trie.as_ref(), &prefix, byte))
+				.collect::<Result<Vec<_>, _>>()?
+				.into_iter()
+				.flatten()
+				.min();
+			let Some(child) = first_child else { break };
+
+			let mut child_prefix = prefix.0.clone();
+			child_prefix.push(child);
+			if !self.diff_subtrie(hash, previous_hash, StorageKey(child_prefix), items, send)? {
+				return Ok(false)
+			}
+
+			next_byte = child.checked_add(1);
+		}
+
+		Ok(true)
+	}
+
+	/// The byte following `prefix` in the first key of the block `hash` that starts with `prefix`
+	/// followed by a byte greater than or equal to `from`.
+	fn first_child(
+		&self,
+		hash: Block::Hash,
+		child_trie: Option<&ChildInfo>,
+		prefix: &StorageKey,
+		from: u8,
+	) -> Result<Option<u8>, String> {
+		let mut start_key = prefix.0.clone();
+		start_key.push(from);
+		let start_key = StorageKey(start_key);
+
+		// The keys iterator starts after the start key.
+		if self.client.query_hash(hash, &start_key, child_trie)?.is_some() {
+			return Ok(Some(from))
+		}
+
+		let mut keys =
+			self.client
+				.raw_keys_iter(hash, child_trie.cloned(), Some(prefix), Some(&start_key))?;
+		Ok(keys.next().map(|key| key.0[prefix.0.len()]))
+	}
+
+	fn handle_trie_queries_inner(
+		&self,
+		hash: Block::Hash,
+		previous_hash: Block::Hash,
+		items: Vec<DiffDetails>,
+		tx: &mpsc::Sender<ArchiveStorageDiffEvent>,
+	) -> Result<(), String> {
+		let maybe_child_trie_str =
+			items.first().and_then(|item| item.child_trie_key_string.clone());
+		let mut send = |result: FetchedStorage, operation_type: ArchiveStorageDiffOperationType| {
+			Self::send_result(tx, result, operation_type, maybe_child_trie_str.clone())
+		};
+
+		for prefix in minimal_prefixes(&items) {
+			if !self.diff_subtrie(hash, previous_hash, prefix, &items, &mut send)? {
+				break
+			}
+		}
+
 		Ok(())
 	}
 
+	/// Report the changes of the query items made by the block to its parent.
+	///
+	/// Only the subtries whose closest merkle values differ from the parent are visited, see
+	/// [`Self::diff_subtrie`].
+	///
+	/// Returns `false` if the sender has been closed.
+	fn handle_history_block(
+		&self,
+		block: &HistoryBlock<Block::Hash>,
+		items: &[DiffDetails],
+		tx: &mpsc::Sender<ArchiveStorageHistoryEvent>,
+	) -> Result<bool, String> {
+		let maybe_child_trie_str =
+			items.first().and_then(|item| item.child_trie_key_string.clone());
+		let block_hash = hex_string(&block.hash.as_ref());
+		let mut send = |result: FetchedStorage, operation_type: ArchiveStorageDiffOperationType| {
+			result.into_results().into_iter().all(|item| {
+				let event =
+					ArchiveStorageHistoryEvent::StorageHistory(ArchiveStorageHistoryResult {
+						block_hash: block_hash.clone(),
+						block_number: block.number,
+						key: item.key,
+						result: item.result,
+						operation_type,
+						child_trie_key: maybe_child_trie_str.clone(),
+					});
+				tx.blocking_send(event).is_ok()
+			})
+		};
+
+		for prefix in minimal_prefixes(items) {
+			let sent = match block.parent_hash {
+				Some(parent_hash) =>
+					self.diff_subtrie(block.hash, parent_hash, prefix, items, &mut send)?,
+				// All the keys of the first block are added.
+				None => self.diff_keys(block.hash, None, Some(&prefix), items, &mut send)?,
+			};
+			if !sent {
+				return Ok(false)
+			}
+		}
+
+		Ok(true)
+	}
+
 	/// This method will iterate over the keys of the main trie or a child trie and fetch the
 	/// given keys. The fetched keys will be sent to the provided `tx` sender to leverage
 	/// the backpressure mechanism.
@@ -348,6 +547,78 @@ where
 
 		Ok(())
 	}
+
+	/// Report the changes of the given items made by each of the blocks, in the order of the
+	/// blocks. The events are sent to the provided `tx` sender to leverage the backpressure
+	/// mechanism.
+	///
+	/// The final event carries `next_block`, the first block of the range that was not reported.
+	pub async fn handle_history_queries(
+		&self,
+		blocks: Vec<HistoryBlock<Block::Hash>>,
+		items: Vec<ArchiveStorageDiffItem<String>>,
+		next_block: Option<u64>,
+		tx: mpsc::Sender<ArchiveStorageHistoryEvent>,
+	) -> Result<(), tokio::task::JoinError> {
+		let this = ArchiveStorageDiff { client: self.client.clone() };
+
+		tokio::task::spawn_blocking(move || {
+			let mut trie_items = match deduplicate_storage_diff_items(items) {
+				Ok(items) => items,
+				Err(error) => {
+					let _ = tx.blocking_send(ArchiveStorageHistoryEvent::err(error.to_string()));
+					return
+				},
+			};
+			// Default to using the main storage trie if no items are provided.
+			if trie_items.is_empty() {
+				trie_items.push(Vec::new());
+			}
+
+			for block in &blocks {
+				log::trace!(target: LOG_TARGET, "handle_history_queries: block={:?}", block);
+
+				for items in &trie_items {
+					match this.handle_history_block(block, items, &tx) {
+						Ok(true) => {},
+						Ok(false) => return,
+						Err(error) => {
+							let _ = tx.blocking_send(ArchiveStorageHistoryEvent::err(error));
+							return
+						},
+					}
+				}
+			}
+
+			let _ = tx.blocking_send(ArchiveStorageHistoryEvent::StorageHistoryDone(
+				ArchiveStorageHistoryDone { next_block },
+			));
+		})
+		.await?;
+
+		Ok(())
+	}
+}
+
+/// The shortest keys of the query items, no key is a prefix of another.
+///
+/// The changes of the query items are the changes of the keys starting with these prefixes. The
+/// whole trie is queried when there are no items.
+fn minimal_prefixes(items: &[DiffDetails]) -> Vec<StorageKey> {
+	let mut prefixes: Vec<StorageKey> = Vec::new();
+	// A key is sorted after its prefixes and before the keys that don't start with them.
+	for key in items.iter().map(|item| &item.key).sorted().dedup() {
+		if !prefixes
+			.last()
+			.map_or(false, |prefix| key.as_ref().starts_with(prefix.as_ref()))
+		{
+			prefixes.push(key.clone());
+		}
+	}
+	if prefixes.is_empty() {
+		prefixes.push(StorageKey(Vec::new()));
+	}
+	prefixes
 }
 
 /// The result of the `lexicographic_diff` method.
@@ -506,6 +777,31 @@ mod tests {
 		assert_eq!(result[0][0], expected);
 	}
 
+	#[test]
+	fn minimal_prefixes_of_items() {
+		let item = |key: &[u8], return_type| DiffDetails {
+			key: StorageKey(key.to_vec()),
+			return_type,
+			child_trie_key: None,
+			child_trie_key_string: None,
+		};
+
+		assert_eq!(minimal_prefixes(&[]), vec![StorageKey(vec![])]);
+
+		let items = vec![
+			item(&[1, 2], ArchiveStorageDiffType::Value),
+			item(&[2], ArchiveStorageDiffType::Hash),
+			item(&[1], ArchiveStorageDiffType::Hash),
+			item(&[2], ArchiveStorageDiffType::Value),
+			item(&[1, 3, 4], ArchiveStorageDiffType::Value),
+			item(&[3, 1], ArchiveStorageDiffType::Value),
+		];
+		assert_eq!(
+			minimal_prefixes(&items),
+			vec![StorageKey(vec![1]), StorageKey(vec![2]), StorageKey(vec![3, 1])]
+		);
+	}
+
 	#[test]
 	fn dedup_with_different_keys() {
 		let items = vec![
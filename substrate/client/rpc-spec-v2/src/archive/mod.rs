@@ -34,4 +34,4 @@ pub mod error;
 
 pub use api::ArchiveApiServer;
 pub use archive::Archive;
-pub use types::{MethodResult, MethodResultErr, MethodResultOk};
+pub use types::{ExtrinsicLocation, MethodResult, MethodResultErr, MethodResultOk};
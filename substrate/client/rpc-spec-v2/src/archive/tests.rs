@@ -20,8 +20,9 @@ use crate::{
 	archive::MethodResult,
 	common::events::{
 		ArchiveStorageDiffEvent, ArchiveStorageDiffItem, ArchiveStorageDiffOperationType,
-		ArchiveStorageDiffResult, ArchiveStorageDiffType, ArchiveStorageEvent, StorageQuery,
-		StorageQueryType, StorageResult, StorageResultType,
+		ArchiveStorageDiffResult, ArchiveStorageDiffType, ArchiveStorageEvent,
+		ArchiveStorageHistoryDone, ArchiveStorageHistoryEvent, ArchiveStorageHistoryResult,
+		StorageQuery, StorageQueryType, StorageResult, StorageResultType,
 	},
 	hex_string,
 };
@@ -133,6 +134,58 @@ async fn archive_body() {
 	assert_eq!(vec![expected_tx], body);
 }
 
+#[tokio::test]
+async fn archive_extrinsic_locations() {
+	// The extrinsic index is disabled by default.
+	let (_client, api) = setup_api();
+	let err = api
+		.call::<_, Vec<ExtrinsicLocation>>(
+			"archive_v1_extrinsicLocations",
+			[hex_string(&INVALID_HASH)],
+		)
+		.await
+		.unwrap_err();
+	assert_matches!(err, Error::JsonRpc(err) if err.code() == 3004);
+
+	let backend = Arc::new(Backend::new_test_with_extrinsic_index());
+	let client = Arc::new(TestClientBuilder::with_backend(backend.clone()).build());
+	let api = Archive::new(
+		client.clone(),
+		backend,
+		CHAIN_GENESIS,
+		Arc::new(TokioTestExecutor::default()),
+	)
+	.into_rpc();
+
+	let mut builder = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(client.chain_info().genesis_hash)
+		.with_parent_block_number(0)
+		.build()
+		.unwrap();
+	builder
+		.push_transfer(runtime::Transfer {
+			from: Sr25519Keyring::Alice.into(),
+			to: Sr25519Keyring::Ferdie.into(),
+			amount: 42,
+			nonce: 0,
+		})
+		.unwrap();
+	let block = builder.build().unwrap().block;
+	let block_hash = format!("{:?}", block.header.hash());
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	let extrinsic_hash = format!("{:?}", Blake2Hasher::hash(&block.extrinsics[0].encode()));
+	let locations: Vec<ExtrinsicLocation> =
+		api.call("archive_v1_extrinsicLocations", [extrinsic_hash]).await.unwrap();
+	assert_eq!(locations, vec![ExtrinsicLocation { block_hash, block_number: 1, index: 0 }]);
+
+	let locations: Vec<ExtrinsicLocation> = api
+		.call("archive_v1_extrinsicLocations", [hex_string(&INVALID_HASH)])
+		.await
+		.unwrap();
+	assert!(locations.is_empty());
+}
+
 #[tokio::test]
 async fn archive_header() {
 	let (client, api) = setup_api();
@@ -966,6 +1019,74 @@ async fn archive_storage_diff_deleted_changes() {
 	assert_eq!(ArchiveStorageDiffEvent::StorageDiffDone, event);
 }
 
+#[tokio::test]
+async fn archive_storage_diff_nested_changes() {
+	let (client, api) = setup_api();
+
+	let mut builder = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(client.chain_info().genesis_hash)
+		.with_parent_block_number(0)
+		.build()
+		.unwrap();
+	builder.push_storage_change(b":A".to_vec(), Some(b"1".to_vec())).unwrap();
+	builder.push_storage_change(b":AB".to_vec(), Some(b"2".to_vec())).unwrap();
+	builder.push_storage_change(b":ABC".to_vec(), Some(b"3".to_vec())).unwrap();
+	builder.push_storage_change(b":AC".to_vec(), Some(b"4".to_vec())).unwrap();
+	builder.push_storage_change(b":B".to_vec(), Some(b"5".to_vec())).unwrap();
+	let prev_block = builder.build().unwrap().block;
+	let prev_hash = format!("{:?}", prev_block.header.hash());
+	client.import(BlockOrigin::Own, prev_block.clone()).await.unwrap();
+
+	let mut builder = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(prev_block.hash())
+		.with_parent_block_number(1)
+		.build()
+		.unwrap();
+	// The subtrie of ":AB" is unchanged.
+	builder.push_storage_change(b":A".to_vec(), None).unwrap();
+	builder.push_storage_change(b":AC".to_vec(), Some(b"44".to_vec())).unwrap();
+	builder.push_storage_change(b":ACD".to_vec(), Some(b"6".to_vec())).unwrap();
+	builder.push_storage_change(b":B".to_vec(), Some(b"55".to_vec())).unwrap();
+	let block = builder.build().unwrap().block;
+	let block_hash = format!("{:?}", block.header.hash());
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	// Search for items in the main trie with keys prefixed with ":A".
+	let items = vec![ArchiveStorageDiffItem::<String> {
+		key: hex_string(b":A"),
+		return_type: ArchiveStorageDiffType::Value,
+		child_trie_key: None,
+	}];
+	let mut sub = api
+		.subscribe_unbounded(
+			"archive_v1_storageDiff",
+			rpc_params![&block_hash, items.clone(), &prev_hash],
+		)
+		.await
+		.unwrap();
+
+	let expected = [
+		(&b":A"[..], &b"1"[..], ArchiveStorageDiffOperationType::Deleted),
+		(&b":AC"[..], &b"44"[..], ArchiveStorageDiffOperationType::Modified),
+		(&b":ACD"[..], &b"6"[..], ArchiveStorageDiffOperationType::Added),
+	];
+	for (key, value, operation_type) in expected {
+		let event = get_next_event::<ArchiveStorageDiffEvent>(&mut sub).await;
+		assert_eq!(
+			ArchiveStorageDiffEvent::StorageDiff(ArchiveStorageDiffResult {
+				key: hex_string(&key),
+				result: StorageResultType::Value(hex_string(&value)),
+				operation_type,
+				child_trie_key: None,
+			}),
+			event,
+		);
+	}
+
+	let event = get_next_event::<ArchiveStorageDiffEvent>(&mut sub).await;
+	assert_eq!(ArchiveStorageDiffEvent::StorageDiffDone, event);
+}
+
 #[tokio::test]
 async fn archive_storage_diff_invalid_params() {
 	let invalid_hash = hex_string(&INVALID_HASH);
@@ -999,3 +1120,85 @@ async fn archive_storage_diff_invalid_params() {
 		ArchiveStorageDiffEvent::StorageDiffError(ref err) if err.error.contains("Header was not found")
 	);
 }
+
+#[tokio::test]
+async fn archive_storage_history() {
+	let (client, api) = setup_api();
+
+	let mut parent_hash = client.chain_info().genesis_hash;
+	let mut hashes = Vec::new();
+	for (number, (key, value)) in
+		[(b":A", b"B"), (b":B", b"B"), (b":A", b"C")].into_iter().enumerate()
+	{
+		let mut builder = BlockBuilderBuilder::new(&*client)
+			.on_parent_block(parent_hash)
+			.with_parent_block_number(number as u64)
+			.build()
+			.unwrap();
+		builder.push_storage_change(key.to_vec(), Some(value.to_vec())).unwrap();
+		let block = builder.build().unwrap().block;
+		parent_hash = block.hash();
+		hashes.push(format!("{:?}", block.hash()));
+		client.import_as_final(BlockOrigin::Own, block).await.unwrap();
+	}
+
+	let items = vec![ArchiveStorageDiffItem::<String> {
+		key: hex_string(b":A"),
+		return_type: ArchiveStorageDiffType::Value,
+		child_trie_key: None,
+	}];
+	let mut sub = api
+		.subscribe_unbounded("archive_v1_storageHistory", rpc_params![items.clone(), 1, 3])
+		.await
+		.unwrap();
+
+	let event = get_next_event::<ArchiveStorageHistoryEvent>(&mut sub).await;
+	assert_eq!(
+		ArchiveStorageHistoryEvent::StorageHistory(ArchiveStorageHistoryResult {
+			block_hash: hashes[0].clone(),
+			block_number: 1,
+			key: hex_string(b":A"),
+			result: StorageResultType::Value(hex_string(b"B")),
+			operation_type: ArchiveStorageDiffOperationType::Added,
+			child_trie_key: None,
+		}),
+		event,
+	);
+
+	// Block 2 doesn't change the queried keys.
+	let event = get_next_event::<ArchiveStorageHistoryEvent>(&mut sub).await;
+	assert_eq!(
+		ArchiveStorageHistoryEvent::StorageHistory(ArchiveStorageHistoryResult {
+			block_hash: hashes[2].clone(),
+			block_number: 3,
+			key: hex_string(b":A"),
+			result: StorageResultType::Value(hex_string(b"C")),
+			operation_type: ArchiveStorageDiffOperationType::Modified,
+			child_trie_key: None,
+		}),
+		event,
+	);
+
+	let event = get_next_event::<ArchiveStorageHistoryEvent>(&mut sub).await;
+	assert_eq!(
+		ArchiveStorageHistoryEvent::StorageHistoryDone(ArchiveStorageHistoryDone {
+			next_block: None
+		}),
+		event,
+	);
+
+	// The range must only contain finalized blocks.
+	let mut sub = api
+		.subscribe_unbounded("archive_v1_storageHistory", rpc_params![items.clone(), 2, 4])
+		.await
+		.unwrap();
+	let event = get_next_event::<ArchiveStorageHistoryEvent>(&mut sub).await;
+	assert!(event.is_err());
+
+	let mut sub = api
+		.subscribe_unbounded("archive_v1_storageHistory", rpc_params![items, 3, 2])
+		.await
+		.unwrap();
+	let event = get_next_event::<ArchiveStorageHistoryEvent>(&mut sub).await;
+	assert!(event.is_err());
+}
@@ -60,6 +60,18 @@ pub struct MethodResultErr {
 	pub error: String,
 }
 
+/// The location of an extrinsic in a block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtrinsicLocation {
+	/// The hexadecimal-encoded hash of the block.
+	pub block_hash: String,
+	/// The height of the block.
+	pub block_number: u64,
+	/// The index of the extrinsic in the body of the block.
+	pub index: u32,
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -87,4 +99,16 @@ mod tests {
 		let ok_dec: MethodResult = serde_json::from_str(exp).unwrap();
 		assert_eq!(ok_dec, ok);
 	}
+
+	#[test]
+	fn extrinsic_location() {
+		let location = ExtrinsicLocation { block_hash: "0x1".into(), block_number: 1, index: 2 };
+
+		let ser = serde_json::to_string(&location).unwrap();
+		let exp = r#"{"blockHash":"0x1","blockNumber":1,"index":2}"#;
+		assert_eq!(ser, exp);
+
+		let dec: ExtrinsicLocation = serde_json::from_str(exp).unwrap();
+		assert_eq!(dec, location);
+	}
 }
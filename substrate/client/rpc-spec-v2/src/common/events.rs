@@ -262,6 +262,72 @@ impl ArchiveStorageDiffEvent {
 	}
 }
 
+/// The change of an individual key reported by the `archive_storageHistory` method.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveStorageHistoryResult {
+	/// The hex-encoded hash of the block that changed the key.
+	pub block_hash: String,
+	/// The number of the block that changed the key.
+	pub block_number: u64,
+	/// The hex-encoded key of the result.
+	pub key: String,
+	/// The result of the query.
+	#[serde(flatten)]
+	pub result: StorageResultType,
+	/// The operation type.
+	#[serde(rename = "type")]
+	pub operation_type: ArchiveStorageDiffOperationType,
+	/// The child trie key if provided.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[serde(default)]
+	pub child_trie_key: Option<String>,
+}
+
+/// The end of the `archive_storageHistory` method.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveStorageHistoryDone {
+	/// The first block of the range that was not reported, if the range was too large to be
+	/// reported at once. The history of the remaining blocks can be requested starting from it.
+	pub next_block: Option<u64>,
+}
+
+/// The event generated by the `archive_storageHistory` method.
+///
+/// The `archive_storageHistory` can generate the following events:
+///  - `storageHistory` event - generated when a `ArchiveStorageHistoryResult` is produced.
+///  - `storageHistoryError` event - generated when an error is produced.
+///  - `storageHistoryDone` event - generated when the `archive_storageHistory` method completed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(tag = "event")]
+pub enum ArchiveStorageHistoryEvent {
+	/// The `storageHistory` event.
+	StorageHistory(ArchiveStorageHistoryResult),
+	/// The `storageHistoryError` event.
+	StorageHistoryError(ArchiveStorageMethodErr),
+	/// The `storageHistoryDone` event.
+	StorageHistoryDone(ArchiveStorageHistoryDone),
+}
+
+impl ArchiveStorageHistoryEvent {
+	/// Create a new `ArchiveStorageHistoryEvent::StorageHistoryError` event.
+	pub fn err(error: String) -> Self {
+		Self::StorageHistoryError(ArchiveStorageMethodErr { error })
+	}
+
+	/// Checks if the event is a `StorageHistoryDone` event.
+	pub fn is_done(&self) -> bool {
+		matches!(self, Self::StorageHistoryDone(_))
+	}
+
+	/// Checks if the event is a `StorageHistoryError` event.
+	pub fn is_err(&self) -> bool {
+		matches!(self, Self::StorageHistoryError(_))
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -373,6 +439,36 @@ mod tests {
 		assert_eq!(dec, item);
 	}
 
+	#[test]
+	fn archive_history_output() {
+		let item = ArchiveStorageHistoryEvent::StorageHistory(ArchiveStorageHistoryResult {
+			block_hash: "0x2".into(),
+			block_number: 2,
+			key: "0x1".into(),
+			result: StorageResultType::Value("res".into()),
+			operation_type: ArchiveStorageDiffOperationType::Modified,
+			child_trie_key: None,
+		});
+		// Encode
+		let ser = serde_json::to_string(&item).unwrap();
+		let exp = r#"{"event":"storageHistory","blockHash":"0x2","blockNumber":2,"key":"0x1","value":"res","type":"modified"}"#;
+		assert_eq!(ser, exp);
+		// Decode
+		let dec: ArchiveStorageHistoryEvent = serde_json::from_str(exp).unwrap();
+		assert_eq!(dec, item);
+
+		let item = ArchiveStorageHistoryEvent::StorageHistoryDone(ArchiveStorageHistoryDone {
+			next_block: Some(3),
+		});
+		// Encode
+		let ser = serde_json::to_string(&item).unwrap();
+		let exp = r#"{"event":"storageHistoryDone","nextBlock":3}"#;
+		assert_eq!(ser, exp);
+		// Decode
+		let dec: ArchiveStorageHistoryEvent = serde_json::from_str(exp).unwrap();
+		assert_eq!(dec, item);
+	}
+
 	#[test]
 	fn storage_result() {
 		// Item with Value.
@@ -199,16 +199,20 @@ where
 		}
 	}
 
-	/// Raw iterator over the keys.
+	/// Raw iterator over the keys, optionally restricted to the keys starting with `prefix`.
+	///
+	/// The iteration starts after `start_key` when provided.
 	pub fn raw_keys_iter(
 		&self,
 		hash: Block::Hash,
 		child_key: Option<ChildInfo>,
+		prefix: Option<&StorageKey>,
+		start_key: Option<&StorageKey>,
 	) -> Result<impl Iterator<Item = StorageKey>, String> {
 		let keys_iter = if let Some(child_key) = child_key {
-			self.client.child_storage_keys(hash, child_key, None, None)
+			self.client.child_storage_keys(hash, child_key, prefix, start_key)
 		} else {
-			self.client.storage_keys(hash, None, None)
+			self.client.storage_keys(hash, prefix, start_key)
 		};
 
 		keys_iter.map_err(|err| err.to_string())
@@ -30,8 +30,8 @@ use codec::{Decode, Encode};
 use jsonrpsee::{core::async_trait, types::ErrorObject, Extensions, PendingSubscriptionSink};
 use sc_rpc_api::check_if_safe;
 use sc_transaction_pool_api::{
-	error::IntoPoolError, BlockHash, InPoolTransaction, TransactionFor, TransactionPool,
-	TransactionSource, TxHash, TxInvalidityReportMap,
+	error::IntoPoolError, BlockHash, InPoolTransaction, PoolInspection, TransactionFor,
+	TransactionPool, TransactionSource, TxHash, TxInvalidityReportMap,
 };
 use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
@@ -153,6 +153,11 @@ where
 		Ok(self.pool.ready().map(|tx| tx.data().encode().into()).collect())
 	}
 
+	fn inspect_pool(&self, ext: &Extensions) -> Result<PoolInspection<TxHash<P>, BlockHash<P>>> {
+		check_if_safe(ext)?;
+		Ok(self.pool.inspect())
+	}
+
 	fn remove_extrinsic(
 		&self,
 		ext: &Extensions,
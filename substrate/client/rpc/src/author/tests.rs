@@ -182,6 +182,30 @@ async fn author_should_return_pending_extrinsics() {
 	assert_eq!(pending, vec![xt_bytes]);
 }
 
+#[tokio::test]
+async fn author_should_inspect_pool() {
+	let api = TestSetup::into_rpc();
+
+	let xt1 = to_hex(&uxt(Sr25519Keyring::Alice, 0).encode(), true);
+	let xt1_hash: H256 = api.call("author_submitExtrinsic", [xt1]).await.unwrap();
+	let xt2 = to_hex(&uxt(Sr25519Keyring::Alice, 2).encode(), true);
+	let xt2_hash: H256 = api.call("author_submitExtrinsic", [xt2]).await.unwrap();
+
+	let inspection: PoolInspection<H256, H256> =
+		api.call("author_inspectPool", EmptyParams::new()).await.unwrap();
+
+	assert_eq!(inspection.views.len(), 1);
+	let view = &inspection.views[0];
+	assert!(view.at.is_some());
+	assert_eq!(view.ready.iter().map(|tx| tx.hash).collect::<Vec<_>>(), vec![xt1_hash]);
+	assert!(view.ready[0].missing.is_empty());
+	assert_eq!(view.future.iter().map(|tx| tx.hash).collect::<Vec<_>>(), vec![xt2_hash]);
+	// the transaction with nonce 1 is missing
+	assert_eq!(view.future[0].missing, view.future[0].requires);
+	assert!(!view.future[0].missing.is_empty());
+	assert!(inspection.dropped.is_empty());
+}
+
 #[tokio::test]
 async fn author_should_remove_extrinsics() {
 	const METHOD: &'static str = "author_removeExtrinsic";
@@ -18,26 +18,35 @@
 
 //! Substrate statement store API.
 
+use crate::{
+	utils::{spawn_subscription_task, BoundedVecDeque, PendingSubscription},
+	SubscriptionTaskExecutor,
+};
 use codec::{Decode, Encode};
+use futures::StreamExt;
 use jsonrpsee::{
 	core::{async_trait, RpcResult},
-	Extensions,
+	Extensions, PendingSubscriptionSink,
 };
 /// Re-export the API for backward compatibility.
 pub use sc_rpc_api::statement::{error::Error, StatementApiServer};
 use sp_core::Bytes;
-use sp_statement_store::{StatementSource, SubmitResult};
+use sp_statement_store::{StatementFilter, StatementSource, SubmitResult};
 use std::sync::Arc;
 
 /// Statement store API
 pub struct StatementStore {
 	store: Arc<dyn sp_statement_store::StatementStore>,
+	executor: SubscriptionTaskExecutor,
 }
 
 impl StatementStore {
 	/// Create new instance of Offchain API.
-	pub fn new(store: Arc<dyn sp_statement_store::StatementStore>) -> Self {
-		StatementStore { store }
+	pub fn new(
+		store: Arc<dyn sp_statement_store::StatementStore>,
+		executor: SubscriptionTaskExecutor,
+	) -> Self {
+		StatementStore { store, executor }
 	}
 }
 
@@ -141,4 +150,27 @@ impl StatementApiServer for StatementStore {
 	fn remove(&self, hash: [u8; 32]) -> RpcResult<()> {
 		Ok(self.store.remove(&hash).map_err(|e| Error::StatementStore(e.to_string()))?)
 	}
+
+	fn subscribe_statement(
+		&self,
+		pending: PendingSubscriptionSink,
+		match_all_topics: Vec<[u8; 32]>,
+		dest: Option<[u8; 32]>,
+	) {
+		let filter = StatementFilter { match_all_topics, decryption_key: dest };
+		let stream = match self.store.subscribe(filter) {
+			Ok(stream) => stream,
+			Err(e) => {
+				let err = Error::StatementStore(e.to_string());
+				spawn_subscription_task(&self.executor, pending.reject(err));
+				return
+			},
+		};
+
+		let stream = stream.map(|statement| Bytes::from(statement.encode()));
+		spawn_subscription_task(
+			&self.executor,
+			PendingSubscription::from(pending).pipe_from_stream(stream, BoundedVecDeque::default()),
+		);
+	}
 }
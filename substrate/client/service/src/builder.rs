@@ -286,6 +286,18 @@ where
 			}
 		}
 
+		if let Some(migration) = backend.state_pruning_migration() {
+			info!(
+				"✂️ State pruning migration in progress, {} historical blocks left to prune",
+				migration.remaining(),
+			);
+			task_manager.spawn_handle().spawn_blocking(
+				"state-pruning-migration",
+				None,
+				prune_migrated_state(backend.clone()),
+			);
+		}
+
 		client
 	};
 
@@ -299,6 +311,36 @@ fn child_info(key: Vec<u8>) -> Option<ChildInfo> {
 	})
 }
 
+/// Prunes the historical state left by a state pruning migration, one block at a time.
+async fn prune_migrated_state<TBl: BlockT>(backend: Arc<TFullBackend<TBl>>) {
+	let start_time = std::time::Instant::now();
+	loop {
+		match backend.prune_migrated_state() {
+			Ok(Some(migration)) =>
+				if migration.next % 10_000 == 0 {
+					info!(
+						"✂️ State pruning migration pruned #{}, {} historical blocks left",
+						migration.next - 1,
+						migration.remaining(),
+					);
+				},
+			Ok(None) => {
+				info!(
+					"✂️ State pruning migration finished in {:.2} secs",
+					start_time.elapsed().as_secs_f32(),
+				);
+				return
+			},
+			Err(e) => {
+				error!("State pruning migration failed: {e}");
+				return
+			},
+		}
+		// Let the task be stopped between blocks, the migration resumes on the next start.
+		tokio::task::yield_now().await;
+	}
+}
+
 fn warm_up_trie_cache<TBl: BlockT>(
 	backend: Arc<TFullBackend<TBl>>,
 	storage_root: TBl::Hash,
@@ -1467,6 +1509,11 @@ where
 		metrics_registry: metrics_registry.cloned(),
 		state_request_protocol_name,
 		block_downloader,
+		progress_dir: net_config
+			.network_config
+			.net_config_path
+			.as_ref()
+			.map(|path| path.join("sync-progress")),
 	};
 	Ok(Box::new(PolkadotSyncingStrategy::new(
 		syncing_config,
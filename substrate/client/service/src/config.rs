@@ -34,12 +34,14 @@ pub use sc_network::{
 	Multiaddr,
 };
 pub use sc_rpc_server::{
-	IpNetwork, RpcEndpoint, RpcMethods, SubscriptionIdProvider as RpcSubscriptionIdProvider,
+	IpNetwork, RateLimitTier, RpcEndpoint, RpcMethodFilter, RpcMethods,
+	SubscriptionIdProvider as RpcSubscriptionIdProvider,
 };
 pub use sc_telemetry::TelemetryEndpoints;
 pub use sc_transaction_pool::TransactionPoolOptions;
 use sp_core::crypto::SecretString;
 use std::{
+	collections::HashMap,
 	io, iter,
 	net::SocketAddr,
 	num::NonZeroU32,
@@ -78,6 +80,8 @@ pub struct Configuration {
 	///
 	/// NOTE: only finalized blocks are subject for removal!
 	pub blocks_pruning: BlocksPruning,
+	/// Maintain an index of the blocks containing each extrinsic, by extrinsic hash.
+	pub extrinsic_index: bool,
 	/// Chain configuration.
 	pub chain_spec: Box<dyn ChainSpec>,
 	/// Runtime executor configuration.
@@ -235,6 +239,7 @@ impl Configuration {
 			state_pruning: self.state_pruning.clone(),
 			source: self.database.clone(),
 			blocks_pruning: self.blocks_pruning,
+			extrinsic_index: self.extrinsic_index,
 			metrics_registry: self.prometheus_registry().cloned(),
 		}
 	}
@@ -340,6 +345,10 @@ pub struct RpcConfiguration {
 	pub rate_limit_whitelisted_ips: Vec<IpNetwork>,
 	/// RPC rate limit trust proxy headers.
 	pub rate_limit_trust_proxy_headers: bool,
+	/// Cost of the RPC methods in rate limit units.
+	pub rate_limit_method_costs: HashMap<String, NonZeroU32>,
+	/// RPC API keys with their rate limit tier.
+	pub rate_limit_api_keys: HashMap<String, RateLimitTier>,
 }
 
 /// Runtime executor configuration.
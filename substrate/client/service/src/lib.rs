@@ -29,6 +29,7 @@ pub mod error;
 
 mod builder;
 mod metrics;
+mod state_checker;
 mod task_manager;
 
 use crate::config::Multiaddr;
@@ -67,6 +68,7 @@ pub use self::{
 	client::{ClientConfig, LocalCallExecutor},
 	error::Error,
 	metrics::MetricsService,
+	state_checker::{StateChecker, StateCheckerConfig},
 };
 #[allow(deprecated)]
 pub use builder::new_native_or_wasm_executor;
@@ -410,9 +412,12 @@ where
 				max_payload_out_mb: rpc_configuration.max_response_size,
 				max_subscriptions_per_connection: rpc_configuration.max_subs_per_conn,
 				rpc_methods: rpc_configuration.methods.into(),
+				method_filter: Default::default(),
 				rate_limit: rpc_configuration.rate_limit,
 				rate_limit_trust_proxy_headers: rpc_configuration.rate_limit_trust_proxy_headers,
 				rate_limit_whitelisted_ips: rpc_configuration.rate_limit_whitelisted_ips.clone(),
+				rate_limit_method_costs: rpc_configuration.rate_limit_method_costs.clone(),
+				rate_limit_api_keys: rpc_configuration.rate_limit_api_keys.clone(),
 				retry_random_port: true,
 				is_optional: false,
 			},
@@ -426,9 +431,12 @@ where
 				max_payload_out_mb: rpc_configuration.max_response_size,
 				max_subscriptions_per_connection: rpc_configuration.max_subs_per_conn,
 				rpc_methods: rpc_configuration.methods.into(),
+				method_filter: Default::default(),
 				rate_limit: rpc_configuration.rate_limit,
 				rate_limit_trust_proxy_headers: rpc_configuration.rate_limit_trust_proxy_headers,
 				rate_limit_whitelisted_ips: rpc_configuration.rate_limit_whitelisted_ips.clone(),
+				rate_limit_method_costs: rpc_configuration.rate_limit_method_costs.clone(),
+				rate_limit_api_keys: rpc_configuration.rate_limit_api_keys.clone(),
 				retry_random_port: true,
 				is_optional: true,
 			},
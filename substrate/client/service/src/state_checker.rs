@@ -0,0 +1,215 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Background integrity check of the state of the finalized block.
+
+use crate::TFullBackend;
+use log::{debug, error, info, warn};
+use sc_client_api::backend::Backend as _;
+use sc_client_db::state_check::DamagedNode;
+use sc_network::{service::traits::NetworkService, ProtocolName};
+use sc_network_sync::{state_request_handler, SyncingService};
+use sp_blockchain::{HeaderBackend, Result as ClientResult};
+use sp_core::hexdisplay::HexDisplay;
+use sp_runtime::traits::{Block as BlockT, HashingFor, Header as HeaderT};
+use std::{sync::Arc, time::Duration};
+
+const LOG_TARGET: &str = "state-checker";
+
+/// Number of nodes checked between two pauses.
+const NODES_PER_BATCH: usize = 1_000;
+
+/// Pause between two batches, to keep the impact of the check on block import low.
+const BATCH_DELAY: Duration = Duration::from_millis(10);
+
+/// Maximum number of peers asked for a damaged node.
+const MAX_PEERS_PER_NODE: usize = 3;
+
+/// Configuration of the [`StateChecker`].
+#[derive(Debug, Clone)]
+pub struct StateCheckerConfig {
+	/// Time between two checks of the state.
+	pub interval: Duration,
+	/// Repair damaged nodes with state proofs requested from peers.
+	pub repair: bool,
+}
+
+/// Periodically checks the integrity of the state of the finalized block.
+///
+/// Missing and corrupt trie nodes are reported and, if enabled, repaired with the nodes of the
+/// state proofs served by peers through the state request protocol.
+pub struct StateChecker<Block: BlockT> {
+	config: StateCheckerConfig,
+	backend: Arc<TFullBackend<Block>>,
+	network: Arc<dyn NetworkService>,
+	sync_service: Arc<SyncingService<Block>>,
+	protocol_name: ProtocolName,
+}
+
+impl<Block: BlockT> StateChecker<Block> {
+	/// Create a new [`StateChecker`].
+	pub fn new(
+		config: StateCheckerConfig,
+		backend: Arc<TFullBackend<Block>>,
+		network: Arc<dyn NetworkService>,
+		sync_service: Arc<SyncingService<Block>>,
+		fork_id: Option<&str>,
+	) -> Self {
+		let genesis_hash = backend.blockchain().info().genesis_hash;
+		let protocol_name =
+			state_request_handler::generate_protocol_name(genesis_hash, fork_id).into();
+		Self { config, backend, network, sync_service, protocol_name }
+	}
+
+	/// Run the [`StateChecker`], intended to be spawned as a blocking task.
+	pub async fn run(self) {
+		let mut delay = self.config.interval;
+		loop {
+			tokio::time::sleep(delay).await;
+			delay = self.config.interval;
+
+			let hash = self.backend.blockchain().info().finalized_hash;
+			if let Err(e) = self.backend.pin_block(hash) {
+				debug!(target: LOG_TARGET, "Failed to pin block {hash:?}: {e}");
+				continue
+			}
+			let damaged = self.check(hash).await;
+			if let Ok(damaged) = &damaged {
+				if self.config.repair && !damaged.is_empty() {
+					// The children of a missing node can only be checked once it is repaired.
+					if self.repair(hash, damaged.clone()).await {
+						delay = Duration::ZERO;
+					}
+				}
+			}
+			self.backend.unpin_block(hash);
+
+			if let Err(e) = damaged {
+				warn!(target: LOG_TARGET, "Failed to check the state of {hash:?}: {e}");
+			}
+		}
+	}
+
+	/// Check the state of the block `hash` and return the damaged nodes.
+	async fn check(&self, hash: Block::Hash) -> ClientResult<Vec<DamagedNode<Block::Hash>>> {
+		debug!(target: LOG_TARGET, "Checking the state of {hash:?}");
+		let mut check = self.backend.start_state_check(hash)?;
+		while !self.backend.continue_state_check(&mut check, NODES_PER_BATCH)? {
+			tokio::time::sleep(BATCH_DELAY).await;
+		}
+
+		for node in check.damaged_nodes() {
+			let path = node.path.iter().map(|nibble| format!("{nibble:x}")).collect::<String>();
+			let trie = node.child_trie.as_ref().map_or("main trie".into(), |key| {
+				format!("child trie 0x{}", HexDisplay::from(key))
+			});
+			error!(
+				target: LOG_TARGET,
+				"💔 {:?} trie node {:?} at nibble path '{path}' of the {trie}",
+				node.damage,
+				node.hash,
+			);
+		}
+		info!(
+			target: LOG_TARGET,
+			"Checked {} trie nodes of the state of {hash:?}, {} damaged",
+			check.checked_nodes(),
+			check.damaged_nodes().len(),
+		);
+		Ok(check.damaged_nodes().to_vec())
+	}
+
+	/// Repair the damaged nodes with state proofs requested from peers.
+	///
+	/// Returns `true` if any node was repaired.
+	async fn repair(&self, hash: Block::Hash, mut damaged: Vec<DamagedNode<Block::Hash>>) -> bool {
+		let header = match self.backend.blockchain().header(hash) {
+			Ok(Some(header)) => header,
+			_ => return false,
+		};
+		let peers = match self.sync_service.peers_info().await {
+			Ok(peers) => peers
+				.into_iter()
+				.filter(|(_, info)| info.best_number >= *header.number())
+				.map(|(peer, _)| peer)
+				.collect::<Vec<_>>(),
+			Err(_) => return false,
+		};
+
+		let total = damaged.len();
+		let mut index = 0;
+		while index < damaged.len() {
+			let start = damaged[index].state_request_start();
+			let mut repaired = Vec::new();
+			for peer in peers.iter().take(MAX_PEERS_PER_NODE) {
+				let proof = match state_request_handler::request_state_proof(
+					&*self.network,
+					*peer,
+					self.protocol_name.clone(),
+					hash,
+					start.clone(),
+				)
+				.await
+				{
+					Ok(proof) => proof,
+					Err(e) => {
+						debug!(target: LOG_TARGET, "State request to {peer} failed: {e}");
+						continue
+					},
+				};
+				let nodes =
+					match proof.to_storage_proof::<HashingFor<Block>>(Some(header.state_root())) {
+						Ok((proof, _)) => proof.into_iter_nodes(),
+						Err(e) => {
+							debug!(target: LOG_TARGET, "Invalid state proof from {peer}: {e:?}");
+							continue
+						},
+					};
+				match self.backend.repair_state(&damaged[index..], nodes) {
+					Ok(nodes) => repaired = nodes,
+					Err(e) => {
+						error!(target: LOG_TARGET, "Failed to repair the state: {e}");
+						return false
+					},
+				}
+				if !repaired.is_empty() {
+					break
+				}
+			}
+			if repaired.is_empty() {
+				index += 1;
+			}
+			// Proofs usually contain more than the requested node.
+			damaged.retain(|node| !repaired.contains(node));
+		}
+
+		let repaired = total - damaged.len();
+		if repaired > 0 {
+			info!(target: LOG_TARGET, "🩹 Repaired {repaired} of {total} damaged trie nodes");
+		}
+		if !damaged.is_empty() {
+			warn!(
+				target: LOG_TARGET,
+				"{} damaged trie nodes could not be repaired from {} peers",
+				damaged.len(),
+				peers.len(),
+			);
+		}
+		repaired > 0
+	}
+}
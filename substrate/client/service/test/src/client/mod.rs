@@ -1485,6 +1485,7 @@ fn doesnt_import_blocks_that_revert_finality() {
 				trie_cache_maximum_size: Some(1 << 20),
 				state_pruning: Some(PruningMode::ArchiveAll),
 				blocks_pruning: BlocksPruning::KeepAll,
+				extrinsic_index: false,
 				source: DatabaseSource::RocksDb { path: tmp.path().into(), cache_size: 1024 },
 				metrics_registry: None,
 			},
@@ -1762,6 +1763,7 @@ fn returns_status_for_pruned_blocks() {
 				trie_cache_maximum_size: Some(1 << 20),
 				state_pruning: Some(PruningMode::blocks_pruning(1)),
 				blocks_pruning: BlocksPruning::KeepFinalized,
+				extrinsic_index: false,
 				source: DatabaseSource::RocksDb { path: tmp.path().into(), cache_size: 1024 },
 				metrics_registry: None,
 			},
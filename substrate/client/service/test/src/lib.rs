@@ -238,6 +238,7 @@ fn node_config<E: ChainSpecExtension + Clone + 'static + Send + Sync>(
 		warm_up_trie_cache: None,
 		state_pruning: Default::default(),
 		blocks_pruning: BlocksPruning::KeepFinalized,
+		extrinsic_index: false,
 		chain_spec: Box::new((*spec).clone()),
 		executor: ExecutorConfiguration::default(),
 		wasm_runtime_overrides: Default::default(),
@@ -256,6 +257,8 @@ fn node_config<E: ChainSpecExtension + Clone + 'static + Send + Sync>(
 			rate_limit: None,
 			rate_limit_whitelisted_ips: Default::default(),
 			rate_limit_trust_proxy_headers: Default::default(),
+			rate_limit_method_costs: Default::default(),
+			rate_limit_api_keys: Default::default(),
 		},
 		prometheus_config: None,
 		telemetry_endpoints: None,
@@ -40,20 +40,29 @@
 //! # Pruning.
 //! See `RefWindow` for pruning algorithm details. `StateDb` prunes on each canonicalization until
 //! pruning constraints are satisfied.
+//!
+//! # Pruning migration.
+//! An archive database doesn't keep the pruning journal, so it can't be pruned by `RefWindow`.
+//! [`StateDb::start_pruning_migration`] switches such a database to the constrained pruning mode.
+//! The pruning window starts with the last canonicalized block, while the historical blocks are
+//! pruned one by one with [`StateDb::prune_migrated_block`], using the keys
+//! deleted by each block as computed by the caller. The progress is journaled, so the migration is
+//! resumed after a restart.
 
 mod noncanonical;
 mod pruning;
 #[cfg(test)]
 mod test;
 
-use codec::Codec;
+use codec::{Codec, Decode, Encode};
 use log::trace;
-use noncanonical::NonCanonicalOverlay;
+use noncanonical::{NonCanonicalOverlay, LAST_CANONICAL};
 use parking_lot::RwLock;
-use pruning::{HaveBlock, RefWindow};
+use pruning::{empty_journal_entry, HaveBlock, RefWindow, LAST_PRUNED};
 use std::{
 	collections::{hash_map::Entry, HashMap},
 	fmt,
+	ops::RangeInclusive,
 };
 
 const LOG_TARGET: &str = "state-db";
@@ -62,6 +71,7 @@ const PRUNING_MODE: &[u8] = b"mode";
 const PRUNING_MODE_ARCHIVE: &[u8] = b"archive";
 const PRUNING_MODE_ARCHIVE_CANON: &[u8] = b"archive_canonical";
 const PRUNING_MODE_CONSTRAINED: &[u8] = b"constrained";
+const PRUNING_MIGRATION: &[u8] = b"pruning_migration";
 pub(crate) const DEFAULT_MAX_BLOCK_CONSTRAINT: u32 = 256;
 
 /// Database value type.
@@ -145,6 +155,8 @@ pub enum StateDbError {
 	BlockUnavailable,
 	/// Block record is missing from the pruning window
 	BlockMissing,
+	/// Pruning migration is only possible from an archive pruning mode.
+	InvalidPruningMigration,
 }
 
 impl<E> From<StateDbError> for Error<E> {
@@ -196,6 +208,8 @@ impl fmt::Debug for StateDbError {
 				write!(f, "Trying to get a block record from db while it is not commit to db yet")
 			},
 			Self::BlockMissing => write!(f, "Block record is missing from the pruning window"),
+			Self::InvalidPruningMigration =>
+				write!(f, "Pruning migration is only possible from an archive pruning mode"),
 		}
 	}
 }
@@ -288,6 +302,24 @@ fn to_meta_key<S: Codec>(suffix: &[u8], data: &S) -> Vec<u8> {
 	buffer
 }
 
+/// Progress of a pruning migration.
+///
+/// Historical blocks in `next..end` are still to be pruned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub struct PruningMigration {
+	/// Next historical block to prune.
+	pub next: u64,
+	/// Block following the last canonicalized block when the migration started.
+	pub end: u64,
+}
+
+impl PruningMigration {
+	/// Number of historical blocks left to prune.
+	pub fn remaining(&self) -> u64 {
+		self.end.saturating_sub(self.next)
+	}
+}
+
 /// Status information about the last canonicalized block.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum LastCanonicalized {
@@ -305,6 +337,7 @@ pub struct StateDbSync<BlockHash: Hash, Key: Hash, D: MetaDb> {
 	pruning: Option<RefWindow<BlockHash, Key, D>>,
 	pinned: HashMap<BlockHash, u32>,
 	ref_counting: bool,
+	migration: Option<PruningMigration>,
 }
 
 impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
@@ -316,13 +349,25 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 		trace!(target: LOG_TARGET, "StateDb settings: {:?}. Ref-counting: {}", mode, ref_counting);
 
 		let non_canonical: NonCanonicalOverlay<BlockHash, Key> = NonCanonicalOverlay::new(&db)?;
+		let migration =
+			match db.get_meta(&to_meta_key(PRUNING_MIGRATION, &())).map_err(Error::Db)? {
+				Some(buffer) => Some(PruningMigration::decode(&mut buffer.as_slice())?),
+				None => None,
+			};
 		let pruning: Option<RefWindow<BlockHash, Key, D>> = match mode {
 			PruningMode::Constrained(Constraints { max_blocks }) =>
 				Some(RefWindow::new(db, max_blocks.unwrap_or(0), ref_counting)?),
 			PruningMode::ArchiveAll | PruningMode::ArchiveCanonical => None,
 		};
 
-		Ok(StateDbSync { mode, non_canonical, pruning, pinned: Default::default(), ref_counting })
+		Ok(StateDbSync {
+			mode,
+			non_canonical,
+			pruning,
+			pinned: Default::default(),
+			ref_counting,
+			migration,
+		})
 	}
 
 	fn insert_block(
@@ -436,6 +481,58 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 		Ok(())
 	}
 
+	/// Returns the range of canonical blocks whose state is kept by the pruning window.
+	fn retained_canonical_blocks(&self) -> Option<RangeInclusive<u64>> {
+		let pruning = self.pruning.as_ref()?;
+		let last = self.non_canonical.last_canonicalized_block_number()?;
+		let window_size = pruning.window_size();
+		let first = if window_size == 0 { last } else { last + 1 - window_size };
+		Some(first..=last)
+	}
+
+	fn prune_migrated_block(
+		&mut self,
+		number: u64,
+		deleted: Vec<Key>,
+	) -> Result<CommitSet<Key>, Error<D::Error>> {
+		let migration = match self.migration.as_mut() {
+			Some(migration) if migration.next == number => migration,
+			_ => return Err(StateDbError::InvalidBlockNumber.into()),
+		};
+
+		// Keys re-inserted after the migration started may still be in the non-canonical overlay.
+		// With reference counting they may also be pending deletion in the pruning window, and are
+		// deleted when the block that removed them is pruned. Without reference counting, the
+		// caller keeps the keys that are part of the state of the retained canonical blocks.
+		let (non_canonical, pruning) = (&self.non_canonical, &self.pruning);
+		let ref_counting = self.ref_counting;
+		let mut commit = CommitSet::default();
+		commit.data.deleted = deleted
+			.into_iter()
+			.filter(|k| {
+				non_canonical.get(k).is_none() &&
+					!(ref_counting &&
+						pruning.as_ref().map_or(false, |p| p.is_pending_deletion(k)))
+			})
+			.collect();
+
+		trace!(
+			target: LOG_TARGET,
+			"Pruning migrated block #{} ({} deleted)",
+			number,
+			commit.data.deleted.len(),
+		);
+		migration.next += 1;
+		let key = to_meta_key(PRUNING_MIGRATION, &());
+		if migration.next >= migration.end {
+			commit.meta.deleted.push(key);
+			self.migration = None;
+		} else {
+			commit.meta.inserted.push((key, migration.encode()));
+		}
+		Ok(commit)
+	}
+
 	/// Revert all non-canonical blocks with the best block number.
 	/// Returns a database commit or `None` if not possible.
 	/// For archive an empty commit set is returned.
@@ -579,6 +676,83 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDb<BlockHash, Key, D> {
 		self.db.read().mode.clone()
 	}
 
+	/// Returns the pruning mode stored in the database, if it was initialized.
+	pub fn stored_pruning_mode(db: &D) -> Result<Option<PruningMode>, Error<D::Error>> {
+		fetch_stored_pruning_mode(db)
+	}
+
+	/// Switch an archive database to the constrained pruning mode.
+	///
+	/// `finalized` is the last finalized block, which must be the last block in the database for
+	/// [`PruningMode::ArchiveAll`]. The historical blocks up to the last canonicalized block are
+	/// left to [`StateDb::prune_migrated_block`]. Returns the changes to apply to the database,
+	/// after which the database must be reopened with a constrained pruning mode, along with the
+	/// historical blocks to prune.
+	pub fn start_pruning_migration(
+		db: &D,
+		finalized: (BlockHash, u64),
+	) -> Result<(CommitSet<Key>, Option<PruningMigration>), Error<D::Error>> {
+		let last_canonical = match fetch_stored_pruning_mode(db)? {
+			Some(PruningMode::ArchiveAll) => Some(finalized),
+			Some(PruningMode::ArchiveCanonical) =>
+				match db.get_meta(&to_meta_key(LAST_CANONICAL, &())).map_err(Error::Db)? {
+					Some(buffer) => Some(<(BlockHash, u64)>::decode(&mut buffer.as_slice())?),
+					None => None,
+				},
+			_ => return Err(StateDbError::InvalidPruningMigration.into()),
+		};
+
+		let mut commit = CommitSet::default();
+		commit
+			.meta
+			.inserted
+			.push((to_meta_key(PRUNING_MODE, &()), PRUNING_MODE_CONSTRAINED.to_vec()));
+		let mut migration = None;
+		if let Some((hash, number)) = last_canonical.filter(|(_, number)| *number > 0) {
+			// The pruning window starts with the last canonicalized block. The nodes it deleted
+			// are pruned by the migration along with the historical blocks.
+			commit
+				.meta
+				.inserted
+				.push((to_meta_key(LAST_CANONICAL, &()), (hash.clone(), number).encode()));
+			commit
+				.meta
+				.inserted
+				.push((to_meta_key(LAST_PRUNED, &()), (number - 1).encode()));
+			commit.meta.inserted.push(empty_journal_entry::<BlockHash, Key>(&hash, number));
+			let progress = PruningMigration { next: 1, end: number + 1 };
+			commit
+				.meta
+				.inserted
+				.push((to_meta_key(PRUNING_MIGRATION, &()), progress.encode()));
+			migration = Some(progress);
+		}
+		Ok((commit, migration))
+	}
+
+	/// Returns the progress of the pruning migration, if any.
+	pub fn pruning_migration(&self) -> Option<PruningMigration> {
+		self.db.read().migration
+	}
+
+	/// Prune the historical block `number` of the pruning migration by deleting the given keys.
+	///
+	/// Blocks must be pruned in order. Keys are expected to be removed from the state by the block,
+	/// and to not be part of the state of any of [`StateDb::retained_canonical_blocks`]. Keys kept
+	/// by the non-canonical overlay are never deleted.
+	pub fn prune_migrated_block(
+		&self,
+		number: u64,
+		deleted: Vec<Key>,
+	) -> Result<CommitSet<Key>, Error<D::Error>> {
+		self.db.write().prune_migrated_block(number, deleted)
+	}
+
+	/// Returns the range of canonical blocks whose state is kept by the pruning window.
+	pub fn retained_canonical_blocks(&self) -> Option<RangeInclusive<u64>> {
+		self.db.read().retained_canonical_blocks()
+	}
+
 	/// Add a new non-canonical block.
 	pub fn insert_block(
 		&self,
@@ -706,7 +880,7 @@ fn choose_pruning_mode(
 mod tests {
 	use crate::{
 		test::{make_changeset, make_db, TestDb},
-		Constraints, Error, IsPruned, PruningMode, StateDb, StateDbError,
+		Constraints, Error, IsPruned, PruningMigration, PruningMode, StateDb, StateDbError,
 	};
 	use sp_core::H256;
 
@@ -869,6 +1043,181 @@ mod tests {
 		assert!(state_db_open_result.is_err());
 	}
 
+	#[test]
+	fn pruning_migration_works() {
+		let (mut db, state_db) = make_test_db(PruningMode::ArchiveCanonical);
+		std::mem::drop(state_db);
+		db.commit(
+			&StateDb::<H256, H256, TestDb>::start_pruning_migration(
+				&db,
+				(H256::from_low_u64_be(3), 3),
+			)
+			.unwrap()
+			.0,
+		);
+
+		let (_, state_db) = StateDb::<H256, H256, TestDb>::open(
+			db.clone(),
+			Some(PruningMode::blocks_pruning(1)),
+			false,
+			false,
+		)
+		.unwrap();
+		assert_eq!(state_db.pruning_migration(), Some(PruningMigration { next: 1, end: 4 }));
+		assert_eq!(state_db.retained_canonical_blocks(), Some(3..=3));
+		assert_eq!(state_db.is_pruned(&H256::from_low_u64_be(21), 2), IsPruned::Pruned);
+		assert_eq!(state_db.is_pruned(&H256::from_low_u64_be(3), 3), IsPruned::NotPruned);
+		assert!(state_db.prune_migrated_block(2, vec![]).is_err());
+
+		db.commit(&state_db.prune_migrated_block(1, vec![H256::from_low_u64_be(91)]).unwrap());
+		std::mem::drop(state_db);
+
+		// The migration is resumed after reopening the database.
+		let (_, state_db) = StateDb::<H256, H256, TestDb>::open(
+			db.clone(),
+			Some(PruningMode::blocks_pruning(1)),
+			false,
+			false,
+		)
+		.unwrap();
+		assert_eq!(state_db.pruning_migration(), Some(PruningMigration { next: 2, end: 4 }));
+		db.commit(&state_db.prune_migrated_block(2, vec![H256::from_low_u64_be(921)]).unwrap());
+		db.commit(&state_db.prune_migrated_block(3, vec![H256::from_low_u64_be(93)]).unwrap());
+		assert_eq!(state_db.pruning_migration(), None);
+		assert!(db.data_eq(&make_db(&[1, 21, 3, 922, 94])));
+
+		// Blocks canonicalized after the migration are pruned by the window.
+		db.commit(&state_db.canonicalize_block(&H256::from_low_u64_be(4)).unwrap());
+		assert_eq!(state_db.retained_canonical_blocks(), Some(4..=4));
+		assert_eq!(state_db.is_pruned(&H256::from_low_u64_be(3), 3), IsPruned::Pruned);
+		assert!(db.data_eq(&make_db(&[1, 21, 3, 4, 922, 94])));
+	}
+
+	#[test]
+	fn pruning_migration_keeps_reinserted_keys() {
+		let mut db = make_db(&[1, 2, 91, 92]);
+		let (init, state_db) = StateDb::<H256, H256, TestDb>::open(
+			db.clone(),
+			Some(PruningMode::ArchiveAll),
+			true,
+			true,
+		)
+		.unwrap();
+		db.commit(&init);
+		std::mem::drop(state_db);
+		db.commit(
+			&StateDb::<H256, H256, TestDb>::start_pruning_migration(
+				&db,
+				(H256::from_low_u64_be(2), 2),
+			)
+			.unwrap()
+			.0,
+		);
+
+		let (_, state_db) = StateDb::<H256, H256, TestDb>::open(
+			db.clone(),
+			Some(PruningMode::blocks_pruning(4)),
+			true,
+			false,
+		)
+		.unwrap();
+		db.commit(
+			&state_db
+				.insert_block(
+					&H256::from_low_u64_be(3),
+					3,
+					&H256::from_low_u64_be(2),
+					make_changeset(&[], &[92]),
+				)
+				.unwrap(),
+		);
+		db.commit(&state_db.canonicalize_block(&H256::from_low_u64_be(3)).unwrap());
+		db.commit(
+			&state_db
+				.insert_block(
+					&H256::from_low_u64_be(4),
+					4,
+					&H256::from_low_u64_be(3),
+					make_changeset(&[91], &[]),
+				)
+				.unwrap(),
+		);
+
+		// Key 91 is re-inserted by the non-canonical block 4 and key 92 is deleted when the
+		// window prunes block 3.
+		db.commit(
+			&state_db
+				.prune_migrated_block(
+					1,
+					vec![
+						H256::from_low_u64_be(1),
+						H256::from_low_u64_be(91),
+						H256::from_low_u64_be(92),
+					],
+				)
+				.unwrap(),
+		);
+		assert!(db.data_eq(&make_db(&[2, 91, 92])));
+		db.commit(&state_db.prune_migrated_block(2, vec![]).unwrap());
+		assert_eq!(state_db.pruning_migration(), None);
+	}
+
+	#[test]
+	fn pruning_migration_keeps_non_canonical_keys_without_ref_counting() {
+		let (mut db, state_db) = make_test_db(PruningMode::ArchiveCanonical);
+		std::mem::drop(state_db);
+		db.commit(
+			&StateDb::<H256, H256, TestDb>::start_pruning_migration(
+				&db,
+				(H256::from_low_u64_be(3), 3),
+			)
+			.unwrap()
+			.0,
+		);
+
+		let (_, state_db) = StateDb::<H256, H256, TestDb>::open(
+			db.clone(),
+			Some(PruningMode::blocks_pruning(1)),
+			false,
+			false,
+		)
+		.unwrap();
+		db.commit(
+			&state_db
+				.insert_block(
+					&H256::from_low_u64_be(5),
+					4,
+					&H256::from_low_u64_be(3),
+					make_changeset(&[91], &[]),
+				)
+				.unwrap(),
+		);
+
+		// Key 91 is re-inserted by the unfinalized block, so it is not deleted.
+		db.commit(&state_db.prune_migrated_block(1, vec![H256::from_low_u64_be(91)]).unwrap());
+		assert!(db.data_eq(&make_db(&[1, 21, 3, 91, 921, 922, 93, 94])));
+	}
+
+	#[test]
+	fn pruning_migration_requires_archive() {
+		let mut db = make_db(&[]);
+		let (init, _) = StateDb::<H256, H256, TestDb>::open(
+			db.clone(),
+			Some(PruningMode::blocks_pruning(1)),
+			false,
+			true,
+		)
+		.unwrap();
+		db.commit(&init);
+		assert!(matches!(
+			StateDb::<H256, H256, TestDb>::start_pruning_migration(
+				&db,
+				(H256::from_low_u64_be(0), 0)
+			),
+			Err(Error::StateDb(StateDbError::InvalidPruningMigration))
+		));
+	}
+
 	fn check_stored_and_requested_mode_compatibility(
 		mode_when_created: Option<PruningMode>,
 		mode_when_reopened: Option<PruningMode>,
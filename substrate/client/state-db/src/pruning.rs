@@ -219,6 +219,14 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> DeathRowQueue<BlockHash, Key, D> {
 		}
 	}
 
+	/// Check if the key is pending deletion, only tracked by the `Mem` queue
+	fn is_pending_deletion(&self, key: &Key) -> bool {
+		match self {
+			DeathRowQueue::DbBacked { .. } => false,
+			DeathRowQueue::Mem { death_index, .. } => death_index.contains_key(key),
+		}
+	}
+
 	/// Return the number of block in the pruning window
 	fn len(&self, base: u64) -> u64 {
 		match self {
@@ -279,6 +287,19 @@ fn to_journal_key(block: u64) -> Vec<u8> {
 	to_meta_key(PRUNING_JOURNAL, &block)
 }
 
+/// Returns the meta entry of a journal record that doesn't delete anything for the given block.
+pub(crate) fn empty_journal_entry<BlockHash: Hash, Key: Hash>(
+	hash: &BlockHash,
+	number: u64,
+) -> (Vec<u8>, Vec<u8>) {
+	let record = JournalRecord::<BlockHash, Key> {
+		hash: hash.clone(),
+		inserted: Vec::new(),
+		deleted: Vec::new(),
+	};
+	(to_journal_key(number), record.encode())
+}
+
 /// The result return by `RefWindow::have_block`
 #[derive(Debug, PartialEq, Eq)]
 pub enum HaveBlock {
@@ -372,6 +393,11 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> RefWindow<BlockHash, Key, D> {
 		self.window_size() == 0
 	}
 
+	/// Check if the key is going to be deleted when pruning one of the blocks in the window.
+	pub fn is_pending_deletion(&self, key: &Key) -> bool {
+		self.queue.is_pending_deletion(key)
+	}
+
 	// Check if a block is in the pruning window and not be pruned yet
 	pub fn have_block(&self, hash: &BlockHash, number: u64) -> HaveBlock {
 		// if the queue is empty or the block number exceed the pruning window, we definitely
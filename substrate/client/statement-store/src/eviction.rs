@@ -0,0 +1,153 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Eviction of statements from the store.
+//!
+//! Statements of an account are evicted by priority to satisfy the limits of the account. When the
+//! whole store is full, new statements are rejected by default. Other [`EvictionPolicy`] variants
+//! evict statements of any account instead.
+
+/// Order in which statements of any account are evicted when the whole store is full.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EvictionPolicy {
+	/// Never evict statements of other accounts, reject new statements instead.
+	///
+	/// Statements are still evicted from the oldest when the limits of the store are lowered.
+	#[default]
+	Reject,
+	/// Evict the statements with the lowest priority first. A statement is only evicted for a
+	/// statement with a higher priority.
+	///
+	/// The priority is chosen by the signer of the statement, so any account may evict the
+	/// statements of other accounts by submitting statements with a high priority.
+	Priority,
+	/// Evict the oldest statements first. A statement is always evicted for a newer one.
+	///
+	/// The age of a statement is the order in which it was inserted into the store. The
+	/// statements loaded on startup are older than the statements submitted since, in no
+	/// particular order.
+	Age,
+	/// Evict the statements without a channel first, by priority. A statement in a channel is
+	/// only evicted for a statement in a channel with a higher priority.
+	Channel,
+}
+
+impl EvictionPolicy {
+	/// Returns the eviction key of a statement, `seq` being its insertion order.
+	pub(crate) fn key(&self, priority: u32, in_channel: bool, seq: u64) -> EvictionKey {
+		let rank = match self {
+			// No statement is ever evictable for another one.
+			EvictionPolicy::Reject => 0,
+			EvictionPolicy::Priority => priority as u64,
+			EvictionPolicy::Age => seq,
+			EvictionPolicy::Channel => (in_channel as u64) << 32 | priority as u64,
+		};
+		EvictionKey { rank, seq }
+	}
+}
+
+/// Eviction order of a statement. Statements are evicted from the lowest key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct EvictionKey {
+	rank: u64,
+	// Statements of the same rank are evicted from the oldest.
+	seq: u64,
+}
+
+impl EvictionKey {
+	/// Returns `true` if the statement may be evicted for a statement with the key `other`.
+	pub fn evictable_for(&self, other: &EvictionKey) -> bool {
+		self.rank < other.rank
+	}
+}
+
+/// Reason for a statement to be rejected by the store.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum RejectionReason {
+	/// The statement is larger than allowed for its account.
+	Oversize,
+	/// The channel of the statement holds a statement with a higher or equal priority.
+	ChannelPriority,
+	/// Not enough statements of lower priority can be evicted from the account.
+	AccountFull,
+	/// Not enough statements can be evicted from the store.
+	StoreFull,
+}
+
+impl RejectionReason {
+	/// Label of the reason in the metrics.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			RejectionReason::Oversize => "oversize",
+			RejectionReason::ChannelPriority => "channel_priority",
+			RejectionReason::AccountFull => "account_full",
+			RejectionReason::StoreFull => "store_full",
+		}
+	}
+}
+
+/// Reason for a statement to be evicted from the store.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum EvictionReason {
+	/// Replaced by a statement with a higher priority in the same channel.
+	ChannelReplaced,
+	/// Evicted for a statement of the same account.
+	AccountFull,
+	/// Evicted for a statement of any account.
+	StoreFull,
+	/// Evicted because the limits of the store were lowered.
+	LimitsLowered,
+}
+
+impl EvictionReason {
+	/// Label of the reason in the metrics.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			EvictionReason::ChannelReplaced => "channel_replaced",
+			EvictionReason::AccountFull => "account_full",
+			EvictionReason::StoreFull => "store_full",
+			EvictionReason::LimitsLowered => "limits_lowered",
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn policies_order_statements() {
+		let policy = EvictionPolicy::Reject;
+		assert!(!policy.key(1, false, 0).evictable_for(&policy.key(2, true, 1)));
+		assert!(policy.key(2, true, 0) < policy.key(1, false, 1));
+
+		let policy = EvictionPolicy::Priority;
+		assert!(policy.key(1, true, 1).evictable_for(&policy.key(2, false, 0)));
+		assert!(!policy.key(2, false, 0).evictable_for(&policy.key(2, false, 1)));
+		assert!(policy.key(2, false, 0) < policy.key(2, false, 1));
+
+		let policy = EvictionPolicy::Age;
+		assert!(policy.key(2, false, 0).evictable_for(&policy.key(1, false, 1)));
+		assert!(!policy.key(1, false, 1).evictable_for(&policy.key(2, false, 0)));
+
+		let policy = EvictionPolicy::Channel;
+		assert!(policy.key(5, false, 0).evictable_for(&policy.key(1, true, 1)));
+		assert!(!policy.key(1, true, 0).evictable_for(&policy.key(5, false, 1)));
+		assert!(policy.key(1, true, 0).evictable_for(&policy.key(2, true, 1)));
+	}
+}
@@ -29,9 +29,12 @@
 //! * For a given account id, there may be at most `max_count` statements with `max_size` total data
 //!   size. To satisfy this, statements for this account ID are removed from the store starting with
 //!   the lowest priority until a constraint is satisfied.
-//! * There may not be more than `MAX_TOTAL_STATEMENTS` total statements with `MAX_TOTAL_SIZE` size.
-//!   To satisfy this, statements are removed from the store starting with the lowest
-//!   `global_priority` until a constraint is satisfied.
+//! * There may not be more than `max_total_statements` total statements with `max_total_size` size.
+//!   By default, new statements are rejected once a constraint is reached. Other [`EvictionPolicy`]
+//!   variants remove statements of any account from the store until the constraint is satisfied.
+//!   The limits configured on chain, as returned by the `store_limits` runtime call, override the
+//!   limits of the [`Options`]. They are read when the store is created and again on each
+//!   maintenance, and statements are evicted if the limits were lowered.
 //!
 //! When a new statement is inserted that would not satisfy constraints in the first place, no
 //! statements are deleted and `Ignored` result is returned.
@@ -43,33 +46,45 @@
 //! explicitly with the `remove` function) the statement is marked as expired. Expired statements
 //! can't be added to the store for `Options::purge_after_sec` seconds. This is to prevent old
 //! statements from being propagated on the network.
+//!
+//! Subscriptions.
+//!
+//! The statements accepted by the store are sent to the subscribers whose filter they match. Each
+//! subscriber may have at most `Options::subscription_buffer` statements queued. A subscriber that
+//! doesn't keep up is dropped instead of slowing down the store.
 
 #![warn(missing_docs)]
 #![warn(unused_extern_crates)]
 
+mod eviction;
 mod metrics;
+mod subscription;
 
+pub use eviction::EvictionPolicy;
 pub use sp_statement_store::{Error, StatementStore, MAX_TOPICS};
 
+use eviction::{EvictionKey, EvictionReason, RejectionReason};
 use metrics::MetricsLink as PrometheusMetrics;
 use parking_lot::RwLock;
 use prometheus_endpoint::Registry as PrometheusRegistry;
 use sc_keystore::LocalKeystore;
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_core::{crypto::UncheckedFrom, hexdisplay::HexDisplay, traits::SpawnNamed, Decode, Encode};
 use sp_runtime::traits::Block as BlockT;
 use sp_statement_store::{
 	runtime_api::{
-		InvalidStatement, StatementSource, StatementStoreExt, ValidStatement, ValidateStatement,
+		InvalidStatement, StatementSource, StatementStoreExt, StoreLimits, ValidStatement,
+		ValidateStatement,
 	},
 	AccountId, BlockHash, Channel, DecryptionKey, Hash, NetworkPriority, Proof, Result, Statement,
-	SubmitResult, Topic,
+	StatementFilter, StatementStream, SubmitResult, Topic,
 };
 use std::{
-	collections::{BTreeMap, HashMap, HashSet},
+	collections::{BTreeMap, BTreeSet, HashMap, HashSet},
 	sync::Arc,
 };
+use subscription::Subscriptions;
 
 const KEY_VERSION: &[u8] = b"version".as_slice();
 const CURRENT_VERSION: u32 = 1;
@@ -79,6 +94,8 @@ const LOG_TARGET: &str = "statement-store";
 const DEFAULT_PURGE_AFTER_SEC: u64 = 2 * 24 * 60 * 60; //48h
 const DEFAULT_MAX_TOTAL_STATEMENTS: usize = 8192;
 const DEFAULT_MAX_TOTAL_SIZE: usize = 64 * 1024 * 1024;
+const DEFAULT_MAX_SUBSCRIPTIONS: usize = 1024;
+const DEFAULT_SUBSCRIPTION_BUFFER: usize = 256;
 
 const MAINTENANCE_PERIOD: std::time::Duration = std::time::Duration::from_secs(30);
 
@@ -129,14 +146,21 @@ struct StatementsForAccount {
 
 /// Store configuration
 pub struct Options {
-	/// Maximum statement allowed in the store. Once this limit is reached lower-priority
-	/// statements may be evicted.
+	/// Maximum statement allowed in the store. Once this limit is reached statements may be
+	/// evicted according to the `eviction_policy`.
 	max_total_statements: usize,
-	/// Maximum total data size allowed in the store. Once this limit is reached lower-priority
-	/// statements may be evicted.
+	/// Maximum total data size allowed in the store. Once this limit is reached statements may be
+	/// evicted according to the `eviction_policy`.
 	max_total_size: usize,
+	/// Order in which statements are evicted when the store is full.
+	eviction_policy: EvictionPolicy,
 	/// Number of seconds for which removed statements won't be allowed to be added back in.
 	purge_after_sec: u64,
+	/// Maximum number of subscriptions to the new statements.
+	max_subscriptions: usize,
+	/// Maximum number of statements queued for a subscriber. Subscribers that don't keep up are
+	/// dropped.
+	subscription_buffer: usize,
 }
 
 impl Default for Options {
@@ -144,20 +168,36 @@ impl Default for Options {
 		Options {
 			max_total_statements: DEFAULT_MAX_TOTAL_STATEMENTS,
 			max_total_size: DEFAULT_MAX_TOTAL_SIZE,
+			eviction_policy: EvictionPolicy::default(),
 			purge_after_sec: DEFAULT_PURGE_AFTER_SEC,
+			max_subscriptions: DEFAULT_MAX_SUBSCRIPTIONS,
+			subscription_buffer: DEFAULT_SUBSCRIPTION_BUFFER,
 		}
 	}
 }
 
+impl Options {
+	/// Set the order in which statements are evicted when the store is full.
+	pub fn with_eviction_policy(self, eviction_policy: EvictionPolicy) -> Self {
+		Options { eviction_policy, ..self }
+	}
+}
+
 #[derive(Default)]
 struct Index {
 	by_topic: HashMap<Topic, HashSet<Hash>>,
 	by_dec_key: HashMap<Option<DecryptionKey>, HashSet<Hash>>,
 	topics_and_keys: HashMap<Hash, ([Option<Topic>; MAX_TOPICS], Option<DecryptionKey>)>,
-	entries: HashMap<Hash, (AccountId, Priority, usize)>,
+	entries: HashMap<Hash, (AccountId, Priority, usize, EvictionKey)>,
 	expired: HashMap<Hash, u64>, // Value is expiration timestamp.
 	accounts: HashMap<AccountId, StatementsForAccount>,
+	// Statements in eviction order.
+	by_eviction: BTreeSet<(EvictionKey, Hash)>,
+	// Insertion order of the next statement.
+	next_seq: u64,
 	options: Options,
+	// Limits configured on chain, overriding the limits of the options.
+	limits: Option<StoreLimits>,
 	total_size: usize,
 }
 
@@ -187,6 +227,20 @@ where
 		api.validate_statement(block, source, statement)
 			.map_err(|_| InvalidStatement::InternalError)?
 	}
+
+	fn store_limits(&self) -> Result<Option<StoreLimits>> {
+		let api = self.client.runtime_api();
+		let block = self.client.info().finalized_hash;
+		let version = api
+			.api_version::<dyn ValidateStatement<Block>>(block)
+			.map_err(|_| Error::Runtime)?;
+		// The limits can only be configured on chain since version 2.
+		if version.is_some_and(|version| version >= 2) {
+			api.store_limits(block).map_err(|_| Error::Runtime)
+		} else {
+			Ok(None)
+		}
+	}
 }
 
 /// Statement store.
@@ -202,7 +256,9 @@ pub struct Store {
 			+ Send
 			+ Sync,
 	>,
+	limits_fn: Box<dyn Fn() -> Result<Option<StoreLimits>> + Send + Sync>,
 	keystore: Arc<LocalKeystore>,
+	subscriptions: Subscriptions,
 	// Used for testing
 	time_override: Option<u64>,
 	metrics: PrometheusMetrics,
@@ -215,8 +271,8 @@ enum IndexQuery {
 }
 
 enum MaybeInserted {
-	Inserted(HashSet<Hash>),
-	Ignored,
+	Inserted(HashMap<Hash, EvictionReason>),
+	Ignored(RejectionReason),
 }
 
 impl Index {
@@ -238,7 +294,15 @@ impl Index {
 			self.topics_and_keys.insert(hash, (all_topics, key));
 		}
 		let priority = Priority(statement.priority().unwrap_or(0));
-		self.entries.insert(hash, (account, priority, statement.data_len()));
+		let eviction_key = self.options.eviction_policy.key(
+			priority.0,
+			statement.channel().is_some(),
+			self.next_seq,
+		);
+		self.next_seq += 1;
+		self.entries
+			.insert(hash, (account, priority, statement.data_len(), eviction_key));
+		self.by_eviction.insert((eviction_key, hash));
 		self.total_size += statement.data_len();
 		let account_info = self.accounts.entry(account).or_default();
 		account_info.data_size += statement.data_len();
@@ -321,8 +385,9 @@ impl Index {
 	}
 
 	fn make_expired(&mut self, hash: &Hash, current_time: u64) -> bool {
-		if let Some((account, priority, len)) = self.entries.remove(hash) {
+		if let Some((account, priority, len, eviction_key)) = self.entries.remove(hash) {
 			self.total_size -= len;
+			self.by_eviction.remove(&(eviction_key, *hash));
 			if let Some((topics, key)) = self.topics_and_keys.remove(hash) {
 				for t in topics.into_iter().flatten() {
 					if let std::collections::hash_map::Entry::Occupied(mut set) =
@@ -381,10 +446,10 @@ impl Index {
 				HexDisplay::from(&hash),
 				statement_len,
 			);
-			return MaybeInserted::Ignored
+			return MaybeInserted::Ignored(RejectionReason::Oversize)
 		}
 
-		let mut evicted = HashSet::new();
+		let mut evicted = HashMap::new();
 		let mut would_free_size = 0;
 		let priority = Priority(statement.priority().unwrap_or(0));
 		let (max_size, max_count) = (validation.max_size as usize, validation.max_count as usize);
@@ -403,7 +468,7 @@ impl Index {
 							priority,
 							channel_record.priority,
 						);
-						return MaybeInserted::Ignored
+						return MaybeInserted::Ignored(RejectionReason::ChannelPriority)
 					} else {
 						// Would replace channel message. Still need to check for size constraints
 						// below.
@@ -421,7 +486,7 @@ impl Index {
 						};
 						if let Some((_channel, len)) = account_rec.by_priority.get(&key) {
 							would_free_size += *len;
-							evicted.insert(channel_record.hash);
+							evicted.insert(channel_record.hash, EvictionReason::ChannelReplaced);
 						}
 					}
 				}
@@ -434,7 +499,7 @@ impl Index {
 					// Satisfied
 					break
 				}
-				if evicted.contains(&entry.hash) {
+				if evicted.contains_key(&entry.hash) {
 					// Already accounted for above
 					continue
 				}
@@ -446,16 +511,38 @@ impl Index {
 						priority,
 						entry.priority,
 					);
-					return MaybeInserted::Ignored
+					return MaybeInserted::Ignored(RejectionReason::AccountFull)
 				}
-				evicted.insert(entry.hash);
+				evicted.insert(entry.hash, EvictionReason::AccountFull);
 				would_free_size += len;
 			}
 		}
-		// Now check global constraints as well.
-		if !((self.total_size - would_free_size + statement_len <= self.options.max_total_size) &&
-			self.entries.len() + 1 - evicted.len() <= self.options.max_total_statements)
-		{
+		// Now check global constraints as well, evicting statements of any account in the order of
+		// the eviction policy.
+		let (max_total_statements, max_total_size) = self.max_total();
+		let mut total_size = self.total_size - would_free_size + statement_len;
+		let mut total_statements = self.entries.len() + 1 - evicted.len();
+		let eviction_key = self.options.eviction_policy.key(
+			priority.0,
+			statement.channel().is_some(),
+			self.next_seq,
+		);
+		for (entry_key, entry_hash) in &self.by_eviction {
+			if total_size <= max_total_size && total_statements <= max_total_statements {
+				break
+			}
+			if evicted.contains_key(entry_hash) {
+				continue
+			}
+			if !entry_key.evictable_for(&eviction_key) {
+				break
+			}
+			let (_, _, len, _) = self.entries[entry_hash];
+			evicted.insert(*entry_hash, EvictionReason::StoreFull);
+			total_size -= len;
+			total_statements -= 1;
+		}
+		if total_size > max_total_size || total_statements > max_total_statements {
 			log::debug!(
 				target: LOG_TARGET,
 				"Ignored statement {} because the store is full (size={}, count={})",
@@ -463,15 +550,35 @@ impl Index {
 				self.total_size,
 				self.entries.len(),
 			);
-			return MaybeInserted::Ignored
+			return MaybeInserted::Ignored(RejectionReason::StoreFull)
 		}
 
-		for h in &evicted {
+		for h in evicted.keys() {
 			self.make_expired(h, current_time);
 		}
 		self.insert_new(hash, *account, statement);
 		MaybeInserted::Inserted(evicted)
 	}
+
+	/// Returns the maximum number of statements and total data size of the store.
+	fn max_total(&self) -> (usize, usize) {
+		match self.limits {
+			Some(limits) => (limits.max_count as usize, limits.max_size as usize),
+			None => (self.options.max_total_statements, self.options.max_total_size),
+		}
+	}
+
+	/// Evict statements in the order of the eviction policy until the store is within its limits.
+	fn enforce_limits(&mut self, current_time: u64) -> Vec<Hash> {
+		let (max_total_statements, max_total_size) = self.max_total();
+		let mut evicted = Vec::new();
+		while self.total_size > max_total_size || self.entries.len() > max_total_statements {
+			let Some(&(_, hash)) = self.by_eviction.first() else { break };
+			self.make_expired(&hash, current_time);
+			evicted.push(hash);
+		}
+		evicted
+	}
 }
 
 impl Store {
@@ -562,14 +669,28 @@ impl Store {
 		}
 
 		let validator = ClientWrapper { client, _block: Default::default() };
+		let validator = Arc::new(validator);
+		let limits_validator = validator.clone();
 		let validate_fn = Box::new(move |block, source, statement| {
 			validator.validate_statement(block, source, statement)
 		});
+		let limits_fn = Box::new(move || limits_validator.store_limits());
 
+		// The statements loaded from the database are checked against the on-chain limits.
+		let mut index = Index::new(options);
+		index.limits = limits_fn().unwrap_or_else(|e| {
+			log::debug!(target: LOG_TARGET, "Error reading the store limits: {:?}", e);
+			None
+		});
+
+		let subscriptions =
+			Subscriptions::new(index.options.max_subscriptions, index.options.subscription_buffer);
 		let store = Store {
 			db,
-			index: RwLock::new(Index::new(options)),
+			index: RwLock::new(index),
+			subscriptions,
 			validate_fn,
+			limits_fn,
 			keystore,
 			time_override: None,
 			metrics: PrometheusMetrics::new(prometheus),
@@ -671,14 +792,35 @@ impl Store {
 	/// Perform periodic store maintenance
 	pub fn maintain(&self) {
 		log::trace!(target: LOG_TARGET, "Started store maintenance");
-		let deleted = self.index.write().maintain(self.timestamp());
-		let deleted: Vec<_> =
-			deleted.into_iter().map(|hash| (col::EXPIRED, hash.to_vec(), None)).collect();
+		let current_time = self.timestamp();
+		let limits = (self.limits_fn)();
+		let (evicted, deleted) = {
+			let mut index = self.index.write();
+			match limits {
+				Ok(limits) => index.limits = limits,
+				Err(e) =>
+					log::debug!(target: LOG_TARGET, "Error reading the store limits: {:?}", e),
+			}
+			(index.enforce_limits(current_time), index.maintain(current_time))
+		};
 		let count = deleted.len() as u64;
-		if let Err(e) = self.db.commit(deleted) {
+		let evicted_count = evicted.len() as u64;
+		let mut commit: Vec<_> =
+			deleted.into_iter().map(|hash| (col::EXPIRED, hash.to_vec(), None)).collect();
+		for hash in evicted {
+			commit.push((col::STATEMENTS, hash.to_vec(), None));
+			commit.push((col::EXPIRED, hash.to_vec(), Some((hash, current_time).encode())));
+		}
+		if let Err(e) = self.db.commit(commit) {
 			log::warn!(target: LOG_TARGET, "Error writing to the statement database: {:?}", e);
 		} else {
-			self.metrics.report(|metrics| metrics.statements_pruned.inc_by(count));
+			self.metrics.report(|metrics| {
+				metrics.statements_pruned.inc_by(count);
+				metrics
+					.evicted_statements
+					.with_label_values(&[EvictionReason::LimitsLowered.as_str()])
+					.inc_by(evicted_count);
+			});
 		}
 		log::trace!(
 			target: LOG_TARGET,
@@ -917,12 +1059,20 @@ impl StatementStore for Store {
 
 			let evicted =
 				match index.insert(hash, &statement, &account_id, &validation, current_time) {
-					MaybeInserted::Ignored => return SubmitResult::Ignored,
+					MaybeInserted::Ignored(reason) => {
+						self.metrics.report(|metrics| {
+							metrics.rejected_statements.with_label_values(&[reason.as_str()]).inc()
+						});
+						return SubmitResult::Ignored
+					},
 					MaybeInserted::Inserted(evicted) => evicted,
 				};
 
 			commit.push((col::STATEMENTS, hash.to_vec(), Some(statement.encode())));
-			for hash in evicted {
+			for (hash, reason) in evicted {
+				self.metrics.report(|metrics| {
+					metrics.evicted_statements.with_label_values(&[reason.as_str()]).inc()
+				});
 				commit.push((col::STATEMENTS, hash.to_vec(), None));
 				commit.push((col::EXPIRED, hash.to_vec(), Some((hash, current_time).encode())));
 			}
@@ -937,6 +1087,11 @@ impl StatementStore for Store {
 			}
 		} // Release index lock
 		self.metrics.report(|metrics| metrics.submitted_statements.inc());
+		let lagging = self.subscriptions.notify(&statement);
+		self.metrics.report(|metrics| {
+			metrics.subscribers.set(self.subscriptions.len() as u64);
+			metrics.lagging_subscribers.inc_by(lagging as u64);
+		});
 		let network_priority = NetworkPriority::High;
 		log::trace!(target: LOG_TARGET, "Statement submitted: {:?}", HexDisplay::from(&hash));
 		SubmitResult::New(network_priority)
@@ -965,17 +1120,26 @@ impl StatementStore for Store {
 		}
 		Ok(())
 	}
+
+	/// Subscribe to the new statements matching `filter`.
+	fn subscribe(&self, filter: StatementFilter) -> Result<StatementStream> {
+		let stream = self.subscriptions.subscribe(filter)?;
+		self.metrics
+			.report(|metrics| metrics.subscribers.set(self.subscriptions.len() as u64));
+		Ok(stream)
+	}
 }
 
 #[cfg(test)]
 mod tests {
-	use crate::Store;
+	use crate::{EvictionPolicy, Store};
 	use sc_keystore::Keystore;
 	use sp_core::{Decode, Encode, Pair};
 	use sp_statement_store::{
-		runtime_api::{InvalidStatement, ValidStatement, ValidateStatement},
-		AccountId, Channel, DecryptionKey, NetworkPriority, Proof, SignatureVerificationResult,
-		Statement, StatementSource, StatementStore, SubmitResult, Topic,
+		runtime_api::{InvalidStatement, StoreLimits, ValidStatement, ValidateStatement},
+		AccountId, Channel, DecryptionKey, Error, NetworkPriority, Proof,
+		SignatureVerificationResult, Statement, StatementSource, StatementStore, SubmitResult,
+		Topic,
 	};
 
 	type Extrinsic = sp_runtime::OpaqueExtrinsic;
@@ -1291,6 +1455,109 @@ mod tests {
 		assert_eq!(expected_statements, statements);
 	}
 
+	#[test]
+	fn store_full_rejects_by_default() {
+		let (store, _temp) = test_store();
+		store.index.write().options.max_total_statements = 2;
+		let source = StatementSource::Network;
+		let ok = SubmitResult::New(NetworkPriority::High);
+
+		assert_eq!(store.submit(statement(5, 1, None, 100), source), ok);
+		assert_eq!(store.submit(statement(6, 1, None, 100), source), ok);
+		// Statements of other accounts are not evicted, whatever the priority.
+		assert_eq!(store.submit(statement(7, 9, None, 100), source), SubmitResult::Ignored);
+		assert!(store.index.read().expired.is_empty());
+	}
+
+	#[test]
+	fn store_full_evicts_by_priority() {
+		let (store, _temp) = test_store();
+		{
+			let mut index = store.index.write();
+			index.options.max_total_statements = 3;
+			index.options.eviction_policy = EvictionPolicy::Priority;
+		}
+		let source = StatementSource::Network;
+		let ok = SubmitResult::New(NetworkPriority::High);
+
+		assert_eq!(store.submit(statement(5, 2, None, 100), source), ok);
+		assert_eq!(store.submit(statement(6, 1, None, 100), source), ok);
+		assert_eq!(store.submit(statement(7, 3, None, 100), source), ok);
+		// Evicts the lowest priority statement, of another account.
+		assert_eq!(store.submit(statement(8, 2, None, 100), source), ok);
+		assert!(store.index.read().expired.contains_key(&statement(6, 1, None, 100).hash()));
+		// No statement with a lower priority is left.
+		assert_eq!(store.submit(statement(9, 2, None, 100), source), SubmitResult::Ignored);
+		assert_eq!(store.index.read().entries.len(), 3);
+	}
+
+	#[test]
+	fn store_full_evicts_by_age_or_channel() {
+		let (store, _temp) = test_store();
+		{
+			let mut index = store.index.write();
+			index.options.max_total_statements = 2;
+			index.options.eviction_policy = EvictionPolicy::Age;
+		}
+		let source = StatementSource::Network;
+		let ok = SubmitResult::New(NetworkPriority::High);
+
+		assert_eq!(store.submit(statement(5, 3, None, 100), source), ok);
+		assert_eq!(store.submit(statement(6, 2, None, 100), source), ok);
+		// Evicts the oldest statement, whatever its priority.
+		assert_eq!(store.submit(statement(7, 1, None, 100), source), ok);
+		assert!(store.index.read().expired.contains_key(&statement(5, 3, None, 100).hash()));
+
+		let (store, _temp) = test_store();
+		{
+			let mut index = store.index.write();
+			index.options.max_total_statements = 2;
+			index.options.eviction_policy = EvictionPolicy::Channel;
+		}
+
+		assert_eq!(store.submit(statement(5, 1, Some(1), 100), source), ok);
+		assert_eq!(store.submit(statement(6, 5, None, 100), source), ok);
+		// Evicts the statement without a channel first.
+		assert_eq!(store.submit(statement(7, 2, Some(2), 100), source), ok);
+		assert!(store.index.read().expired.contains_key(&statement(6, 5, None, 100).hash()));
+		// Statements in a channel are not evicted for statements without one.
+		assert_eq!(store.submit(statement(8, 9, None, 100), source), SubmitResult::Ignored);
+		assert_eq!(store.submit(statement(9, 2, Some(3), 100), source), ok);
+		assert!(store.index.read().expired.contains_key(&statement(5, 1, Some(1), 100).hash()));
+	}
+
+	#[test]
+	fn on_chain_limits_override_options() {
+		let (mut store, _temp) = test_store();
+		let source = StatementSource::Network;
+		let ok = SubmitResult::New(NetworkPriority::High);
+		for account in 5..9 {
+			assert_eq!(store.submit(statement(account, account as u32, None, 100), source), ok);
+		}
+
+		let limits = StoreLimits { max_count: 2, max_size: 1000 };
+		store.limits_fn = Box::new(move || Ok(Some(limits)));
+		store.maintain();
+		// Statements are evicted to satisfy the lowered limits.
+		let mut expected_statements =
+			vec![statement(7, 7, None, 100).hash(), statement(8, 8, None, 100).hash()];
+		expected_statements.sort();
+		let mut statements: Vec<_> =
+			store.statements().unwrap().into_iter().map(|(hash, _)| hash).collect();
+		statements.sort();
+		assert_eq!(expected_statements, statements);
+		assert_eq!(store.submit(statement(9, 1, None, 100), source), SubmitResult::Ignored);
+
+		// The limits are kept if they can't be read.
+		store.limits_fn = Box::new(|| Err(Error::Runtime));
+		store.maintain();
+		assert_eq!(store.index.read().limits, Some(limits));
+
+		store.limits_fn = Box::new(|| Ok(None));
+		store.maintain();
+		assert_eq!(store.submit(statement(9, 1, None, 100), source), ok);
+	}
+
 	#[test]
 	fn expired_statements_are_purged() {
 		use super::DEFAULT_PURGE_AFTER_SEC;
@@ -1503,4 +1770,32 @@ mod tests {
 		// exactly one element, equal to the expected plaintext
 		assert_eq!(retrieved, vec![plaintext_good]);
 	}
+
+	#[test]
+	fn subscribers_receive_new_matching_statements() {
+		use futures::{FutureExt, StreamExt};
+		use sp_statement_store::StatementFilter;
+
+		let (store, _temp) = test_store();
+		let known = signed_statement_with_topics(1, &[topic(1)], None);
+		store.submit(known.clone(), StatementSource::Network);
+
+		let mut subscription = store
+			.subscribe(StatementFilter { match_all_topics: vec![topic(1)], decryption_key: None })
+			.unwrap();
+		let matching = signed_statement_with_topics(2, &[topic(1), topic(2)], None);
+		let other = signed_statement_with_topics(3, &[topic(2)], None);
+		assert_eq!(store.submit(known, StatementSource::Network), SubmitResult::Known);
+		assert_eq!(
+			store.submit(matching.clone(), StatementSource::Network),
+			SubmitResult::New(NetworkPriority::High)
+		);
+		assert_eq!(
+			store.submit(other, StatementSource::Network),
+			SubmitResult::New(NetworkPriority::High)
+		);
+
+		assert_eq!(subscription.next().now_or_never(), Some(Some(matching)));
+		assert!(subscription.next().now_or_never().is_none());
+	}
 }
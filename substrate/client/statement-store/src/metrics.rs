@@ -20,7 +20,9 @@
 
 use std::sync::Arc;
 
-use prometheus_endpoint::{register, Counter, PrometheusError, Registry, U64};
+use prometheus_endpoint::{
+	register, Counter, CounterVec, Gauge, Opts, PrometheusError, Registry, U64,
+};
 
 #[derive(Clone, Default)]
 pub struct MetricsLink(Arc<Option<Metrics>>);
@@ -48,6 +50,10 @@ pub struct Metrics {
 	pub submitted_statements: Counter<U64>,
 	pub validations_invalid: Counter<U64>,
 	pub statements_pruned: Counter<U64>,
+	pub subscribers: Gauge<U64>,
+	pub lagging_subscribers: Counter<U64>,
+	pub rejected_statements: CounterVec<U64>,
+	pub evicted_statements: CounterVec<U64>,
 }
 
 impl Metrics {
@@ -74,6 +80,40 @@ impl Metrics {
 				)?,
 				registry,
 			)?,
+			subscribers: register(
+				Gauge::new(
+					"substrate_sub_statement_store_subscribers",
+					"Number of subscribers to the new statements",
+				)?,
+				registry,
+			)?,
+			lagging_subscribers: register(
+				Counter::new(
+					"substrate_sub_statement_store_lagging_subscribers",
+					"Total number of subscribers dropped because they didn't keep up",
+				)?,
+				registry,
+			)?,
+			rejected_statements: register(
+				CounterVec::new(
+					Opts::new(
+						"substrate_sub_statement_store_rejected_statements",
+						"Total number of valid statements rejected because of the store limits",
+					),
+					&["reason"],
+				)?,
+				registry,
+			)?,
+			evicted_statements: register(
+				CounterVec::new(
+					Opts::new(
+						"substrate_sub_statement_store_evicted_statements",
+						"Total number of statements evicted from the store",
+					),
+					&["reason"],
+				)?,
+				registry,
+			)?,
 		})
 	}
 }
@@ -0,0 +1,162 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Subscriptions to the statements accepted by the store.
+//!
+//! Every subscriber has a bounded queue of statements. The store never waits for the
+//! subscribers: a subscriber whose queue is full is dropped, which ends its stream.
+
+use crate::LOG_TARGET;
+use futures::{channel::mpsc, StreamExt};
+use parking_lot::Mutex;
+use sp_statement_store::{Error, Result, Statement, StatementFilter, StatementStream, MAX_TOPICS};
+
+struct Subscriber {
+	filter: StatementFilter,
+	sender: mpsc::Sender<Statement>,
+}
+
+/// Subscribers to the new statements.
+pub(crate) struct Subscriptions {
+	subscribers: Mutex<Vec<Subscriber>>,
+	/// Maximum number of subscribers.
+	max_subscriptions: usize,
+	/// Number of statements queued for a subscriber before it is dropped.
+	buffer_size: usize,
+}
+
+impl Subscriptions {
+	pub fn new(max_subscriptions: usize, buffer_size: usize) -> Self {
+		Self { subscribers: Mutex::new(Vec::new()), max_subscriptions, buffer_size }
+	}
+
+	/// Add a subscriber to the statements matching `filter`.
+	pub fn subscribe(&self, filter: StatementFilter) -> Result<StatementStream> {
+		if filter.match_all_topics.len() > MAX_TOPICS {
+			return Err(Error::Subscription(format!("More than {MAX_TOPICS} topics")))
+		}
+
+		let mut subscribers = self.subscribers.lock();
+		subscribers.retain(|subscriber| !subscriber.sender.is_closed());
+		if subscribers.len() >= self.max_subscriptions {
+			return Err(Error::Subscription("Too many subscriptions".into()))
+		}
+		// The capacity of the channel is the buffer plus one slot per sender.
+		let (sender, receiver) = mpsc::channel(self.buffer_size.saturating_sub(1));
+		subscribers.push(Subscriber { filter, sender });
+		Ok(receiver.boxed())
+	}
+
+	/// Send a new statement to the subscribers it matches.
+	///
+	/// Returns the number of subscribers dropped because they didn't keep up.
+	pub fn notify(&self, statement: &Statement) -> usize {
+		let mut lagging = 0;
+		self.subscribers.lock().retain_mut(|subscriber| {
+			if !subscriber.filter.matches(statement) {
+				return !subscriber.sender.is_closed()
+			}
+			match subscriber.sender.try_send(statement.clone()) {
+				Ok(()) => true,
+				Err(e) if e.is_full() => {
+					log::debug!(target: LOG_TARGET, "Dropping lagging statement subscriber");
+					lagging += 1;
+					false
+				},
+				Err(_) => false,
+			}
+		});
+		lagging
+	}
+
+	/// Returns the number of subscribers.
+	pub fn len(&self) -> usize {
+		self.subscribers.lock().len()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::FutureExt;
+
+	fn statement(topic: u8, key: Option<u8>) -> Statement {
+		let mut statement = Statement::new();
+		statement.set_topic(0, [topic; 32]);
+		if let Some(key) = key {
+			statement.set_decryption_key([key; 32]);
+		}
+		statement
+	}
+
+	#[test]
+	fn statements_are_sent_to_matching_subscribers() {
+		let subscriptions = Subscriptions::new(10, 4);
+		let mut by_topic = subscriptions
+			.subscribe(StatementFilter { match_all_topics: vec![[1; 32]], decryption_key: None })
+			.unwrap();
+		let mut by_key = subscriptions
+			.subscribe(StatementFilter { match_all_topics: vec![], decryption_key: Some([9; 32]) })
+			.unwrap();
+
+		assert_eq!(subscriptions.notify(&statement(1, None)), 0);
+		assert_eq!(subscriptions.notify(&statement(2, Some(9))), 0);
+		assert_eq!(subscriptions.notify(&statement(2, None)), 0);
+
+		assert_eq!(by_topic.next().now_or_never(), Some(Some(statement(1, None))));
+		assert!(by_topic.next().now_or_never().is_none());
+		assert_eq!(by_key.next().now_or_never(), Some(Some(statement(2, Some(9)))));
+		assert!(by_key.next().now_or_never().is_none());
+	}
+
+	#[test]
+	fn lagging_subscribers_are_dropped() {
+		let subscriptions = Subscriptions::new(10, 2);
+		let mut lagging = subscriptions.subscribe(StatementFilter::default()).unwrap();
+		let mut filtered = subscriptions
+			.subscribe(StatementFilter { match_all_topics: vec![[2; 32]], decryption_key: None })
+			.unwrap();
+
+		assert_eq!(subscriptions.notify(&statement(1, None)), 0);
+		assert_eq!(subscriptions.notify(&statement(1, None)), 0);
+		assert_eq!(subscriptions.notify(&statement(1, None)), 1);
+		assert_eq!(subscriptions.len(), 1);
+
+		// The queued statements are still delivered before the stream ends.
+		assert!(lagging.next().now_or_never().unwrap().is_some());
+		assert!(lagging.next().now_or_never().unwrap().is_some());
+		assert_eq!(lagging.next().now_or_never(), Some(None));
+
+		subscriptions.notify(&statement(2, None));
+		assert!(filtered.next().now_or_never().unwrap().is_some());
+	}
+
+	#[test]
+	fn subscriptions_are_limited() {
+		let subscriptions = Subscriptions::new(1, 2);
+		let filter = StatementFilter { match_all_topics: vec![[0; 32]; 5], decryption_key: None };
+		assert!(subscriptions.subscribe(filter).is_err());
+
+		let subscription = subscriptions.subscribe(StatementFilter::default()).unwrap();
+		assert!(subscriptions.subscribe(StatementFilter::default()).is_err());
+
+		// Closed subscriptions don't count.
+		drop(subscription);
+		assert!(subscriptions.subscribe(StatementFilter::default()).is_ok());
+	}
+}
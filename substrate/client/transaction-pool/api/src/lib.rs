@@ -25,9 +25,15 @@ use async_trait::async_trait;
 use codec::Codec;
 use futures::Stream;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use sp_core::offchain::TransactionPoolExt;
+use sp_core::{offchain::TransactionPoolExt, Bytes};
 use sp_runtime::traits::{Block as BlockT, Member};
-use std::{collections::HashMap, hash::Hash, marker::PhantomData, pin::Pin, sync::Arc};
+use std::{
+	collections::{HashMap, HashSet},
+	hash::Hash,
+	marker::PhantomData,
+	pin::Pin,
+	sync::Arc,
+};
 
 const LOG_TARGET: &str = "txpool::api";
 
@@ -56,6 +62,87 @@ impl PoolStatus {
 	}
 }
 
+/// Snapshot of the transaction pool content, see [`TransactionPool::inspect`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolInspection<Hash, BlockHash> {
+	/// The content of the pool as seen at particular blocks.
+	///
+	/// The single-state pool maintains a single view, the fork-aware pool one for every fork.
+	pub views: Vec<InspectedView<Hash, BlockHash>>,
+	/// Transactions recently dropped from the pool, most recent first.
+	///
+	/// Only the fork-aware pool keeps track of the dropped transactions, this is always empty for
+	/// the single-state pool.
+	pub dropped: Vec<InspectedDrop<Hash>>,
+}
+
+/// The content of the pool as seen at a particular block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InspectedView<Hash, BlockHash> {
+	/// The block the transactions were validated at, if known.
+	pub at: Option<BlockHash>,
+	/// Transactions in the ready queue, ordered by priority.
+	pub ready: Vec<InspectedTransaction<Hash>>,
+	/// Transactions in the future queue.
+	pub future: Vec<InspectedTransaction<Hash>>,
+}
+
+/// Details of a transaction in the pool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InspectedTransaction<Hash> {
+	/// Hash of the transaction.
+	pub hash: Hash,
+	/// Priority of the transaction.
+	pub priority: TransactionPriority,
+	/// Longevity of the transaction.
+	pub longevity: TransactionLongevity,
+	/// Tags required by the transaction.
+	pub requires: Vec<Bytes>,
+	/// Tags provided by the transaction.
+	pub provides: Vec<Bytes>,
+	/// Required tags which are not satisfied yet, always empty for ready transactions.
+	pub missing: Vec<Bytes>,
+}
+
+impl<Hash: Clone> InspectedTransaction<Hash> {
+	/// Creates the details of the given in-pool transaction.
+	pub fn new<T: InPoolTransaction<Hash = Hash>>(tx: &T, missing: Vec<TransactionTag>) -> Self {
+		Self {
+			hash: tx.hash().clone(),
+			priority: *tx.priority(),
+			longevity: *tx.longevity(),
+			requires: tx.requires().iter().cloned().map(Bytes).collect(),
+			provides: tx.provides().iter().cloned().map(Bytes).collect(),
+			missing: missing.into_iter().map(Bytes).collect(),
+		}
+	}
+}
+
+/// A transaction which was dropped from the pool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InspectedDrop<Hash> {
+	/// Hash of the dropped transaction.
+	pub hash: Hash,
+	/// Reason of the transaction being dropped.
+	pub reason: DropReason<Hash>,
+}
+
+/// Reason of a transaction being dropped from the pool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DropReason<Hash> {
+	/// Transaction was replaced by the transaction with the given hash.
+	Usurped(Hash),
+	/// Transaction was dropped because of the pool limits being enforced.
+	LimitsEnforced,
+	/// Transaction was dropped because of being invalid.
+	Invalid,
+}
+
 /// Possible transaction status events.
 ///
 /// These events are being emitted by `TransactionPool` watchers,
@@ -324,6 +411,37 @@ pub trait TransactionPool: Send + Sync {
 	/// Returns pool status.
 	fn status(&self) -> PoolStatus;
 
+	/// Returns a snapshot of the pool content for inspection purposes.
+	///
+	/// The default implementation reports a single view built from [`Self::ready`] and
+	/// [`Self::futures`], approximating the missing tags by the tags not provided by any other
+	/// transaction in the pool.
+	fn inspect(&self) -> PoolInspection<TxHash<Self>, BlockHash<Self>> {
+		let ready = self.ready().collect::<Vec<_>>();
+		let future = self.futures();
+		let provided = ready
+			.iter()
+			.map(|tx| tx.provides())
+			.chain(future.iter().map(|tx| tx.provides()))
+			.flatten()
+			.collect::<HashSet<_>>();
+		let missing = |tx: &Self::InPoolTransaction| {
+			tx.requires().iter().filter(|tag| !provided.contains(tag)).cloned().collect()
+		};
+
+		PoolInspection {
+			views: vec![InspectedView {
+				at: None,
+				ready: ready.iter().map(|tx| InspectedTransaction::new(&**tx, vec![])).collect(),
+				future: future
+					.iter()
+					.map(|tx| InspectedTransaction::new(tx, missing(tx)))
+					.collect(),
+			}],
+			dropped: vec![],
+		}
+	}
+
 	// *** logging / RPC / networking
 	/// Return an event stream of transactions imported to the pool.
 	fn import_notification_stream(&self) -> ImportNotificationStream<TxHash<Self>>;
@@ -19,9 +19,12 @@
 //! Utility for building substrate transaction pool trait object.
 
 use crate::{
-	common::api::FullChainApi,
+	common::{api::FullChainApi, journal::TransactionJournal},
 	fork_aware_txpool::ForkAwareTxPool as ForkAwareFullPool,
-	graph::{base_pool::Transaction, ChainApi, ExtrinsicFor, ExtrinsicHash, IsValidator, Options},
+	graph::{
+		base_pool::{FairnessLimits, Transaction},
+		ChainApi, ExtrinsicFor, ExtrinsicHash, IsValidator, Options,
+	},
 	single_state_txpool::BasicPool as SingleStateFullPool,
 	TransactionPoolWrapper, LOG_TARGET,
 };
@@ -29,7 +32,7 @@ use prometheus_endpoint::Registry as PrometheusRegistry;
 use sc_transaction_pool_api::{LocalTransactionPool, MaintainedTransactionPool};
 use sp_core::traits::SpawnEssentialNamed;
 use sp_runtime::traits::Block as BlockT;
-use std::{marker::PhantomData, sync::Arc, time::Duration};
+use std::{marker::PhantomData, path::PathBuf, sync::Arc, time::Duration};
 
 /// The type of transaction pool.
 #[derive(Debug, Clone)]
@@ -40,16 +43,61 @@ pub enum TransactionPoolType {
 	ForkAware,
 }
 
+/// Options of the on-disk transaction journal.
+///
+/// When enabled, transactions accepted by the pool are persisted to disk and re-submitted
+/// (and thereby revalidated against the best block) after the node restarts.
+#[derive(Debug, Clone)]
+pub struct JournalOptions {
+	/// Directory holding the journal.
+	///
+	/// If `None`, the node's data directory is used (see
+	/// [`TransactionPoolOptions::set_default_journal_path`]). The journal is disabled if no
+	/// path could be determined.
+	pub path: Option<PathBuf>,
+	/// Journal transactions from all sources.
+	///
+	/// By default only transactions with [`TransactionSource::Local`] and
+	/// [`TransactionSource::External`] are journaled, the latter including the ones submitted via
+	/// RPC. This also journals transactions re-submitted from retracted blocks.
+	///
+	/// [`TransactionSource::Local`]: sc_transaction_pool_api::TransactionSource::Local
+	/// [`TransactionSource::External`]: sc_transaction_pool_api::TransactionSource::External
+	pub all_sources: bool,
+	/// Transactions that were journaled longer ago than this are discarded.
+	pub max_age: Duration,
+	/// The maximum size of the journal in bytes.
+	///
+	/// If exceeded, the oldest transactions are discarded.
+	pub max_bytes: usize,
+}
+
+impl Default for JournalOptions {
+	fn default() -> Self {
+		Self {
+			path: None,
+			all_sources: false,
+			max_age: Duration::from_secs(3 * 60 * 60),
+			max_bytes: 16 * 1024 * 1024,
+		}
+	}
+}
+
 /// Transaction pool options.
 #[derive(Debug, Clone)]
 pub struct TransactionPoolOptions {
 	txpool_type: TransactionPoolType,
 	options: Options,
+	journal: Option<JournalOptions>,
 }
 
 impl Default for TransactionPoolOptions {
 	fn default() -> Self {
-		Self { txpool_type: TransactionPoolType::SingleState, options: Default::default() }
+		Self {
+			txpool_type: TransactionPoolType::SingleState,
+			options: Default::default(),
+			journal: None,
+		}
 	}
 }
 
@@ -81,7 +129,26 @@ impl TransactionPoolOptions {
 			Duration::from_secs(30 * 60)
 		};
 
-		TransactionPoolOptions { options, txpool_type }
+		TransactionPoolOptions { options, txpool_type, journal: None }
+	}
+
+	/// Enables the on-disk transaction journal.
+	pub fn with_journal(mut self, journal: JournalOptions) -> Self {
+		self.journal = Some(journal);
+		self
+	}
+
+	/// Sets the per-sender quotas and the priority lane of the pool.
+	pub fn with_fairness(mut self, fairness: FairnessLimits) -> Self {
+		self.options.fairness = fairness;
+		self
+	}
+
+	/// Sets the directory of the transaction journal if it is enabled but no path was given.
+	pub fn set_default_journal_path(&mut self, path: PathBuf) {
+		if let Some(journal) = self.journal.as_mut() {
+			journal.path.get_or_insert(path);
+		}
 	}
 
 	/// Creates predefined options for benchmarking
@@ -98,8 +165,10 @@ impl TransactionPoolOptions {
 				},
 				reject_future_transactions: false,
 				ban_time: Duration::from_secs(30 * 60),
+				fairness: Default::default(),
 			},
 			txpool_type: TransactionPoolType::SingleState,
+			journal: None,
 		}
 	}
 }
@@ -229,9 +298,23 @@ where
 			txpool_type = ?self.options.txpool_type,
 			ready = ?self.options.options.ready,
 			future = ?self.options.options.future,
+			fairness = ?self.options.options.fairness,
+			journal = ?self.options.journal,
 			"Creating transaction pool"
 		);
-		TransactionPoolWrapper::<Block, Client>(match self.options.txpool_type {
+		let journal = self.options.journal.and_then(|options| {
+			TransactionJournal::open(options)
+				.map_err(|error| {
+					tracing::warn!(
+						target: LOG_TARGET,
+						?error,
+						"Failed to open the transaction journal, continuing without it"
+					)
+				})
+				.ok()
+		});
+		let pool: Box<dyn FullClientTransactionPool<Block, Client>> = match self.options.txpool_type
+		{
 			TransactionPoolType::SingleState => Box::new(SingleStateFullPool::new_full(
 				self.options.options,
 				self.is_validator,
@@ -246,6 +329,7 @@ where
 				self.spawner,
 				self.client,
 			)),
-		})
+		};
+		TransactionPoolWrapper::new(pool, journal)
 	}
 }
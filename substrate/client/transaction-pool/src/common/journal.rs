@@ -0,0 +1,570 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! On-disk journal of pool transactions.
+//!
+//! The journal is an append-only file of SCALE encoded [`JournalEntry`]s. Transactions are
+//! appended when they are accepted by the pool. Since nothing is ever removed from the file in
+//! place, the journal is periodically rewritten ("rotated") to only contain the transactions
+//! which are still pending in the pool.
+//!
+//! At startup the journal is read back, entries exceeding the configured age are discarded and
+//! the remaining transactions are handed out once via [`TransactionJournal::take_pending`] so that
+//! they can be re-submitted to (and revalidated by) the pool.
+//!
+//! The journal file is only written by a dedicated thread, so that submitting transactions and
+//! maintaining the pool never wait for the disk.
+
+use crate::{builder::JournalOptions, LOG_TARGET};
+use codec::{Decode, Encode};
+use parking_lot::Mutex;
+use sc_transaction_pool_api::TransactionSource;
+use sp_runtime::traits::{Block as BlockT, Hash, HashingFor};
+use std::{
+	collections::{HashMap, HashSet},
+	fs::{self, File, OpenOptions},
+	io::{self, Write},
+	path::{Path, PathBuf},
+	sync::{mpsc, Arc},
+	thread,
+	time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// Name of the journal file within the journal directory.
+const JOURNAL_FILE: &str = "transactions.journal";
+
+/// Minimum time between two rotations of the journal.
+const ROTATION_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A single record of the journal.
+#[derive(Encode, Decode)]
+struct JournalEntry {
+	/// Unix timestamp (in seconds) at which the transaction was first journaled.
+	timestamp: u64,
+	/// The source the transaction was originally submitted with.
+	source: TransactionSource,
+	/// The encoded extrinsic.
+	extrinsic: Vec<u8>,
+}
+
+/// Journaled transactions mapped to the time they were first journaled.
+type Journaled<Block> = Arc<Mutex<HashMap<<Block as BlockT>::Hash, u64>>>;
+
+/// Requests handled by the [`Writer`].
+enum Command<Block: BlockT> {
+	/// Append the entry of the given transaction.
+	Append(Block::Hash, JournalEntry),
+	/// Rewrite the journal with the given transactions, see [`TransactionJournal::rotate`].
+	Rotate(Vec<(Block::Hash, TransactionSource, Vec<u8>)>),
+}
+
+/// Owns the journal file, running on the journal thread.
+struct Writer<Block: BlockT> {
+	path: PathBuf,
+	max_age: Duration,
+	max_bytes: usize,
+	/// Append handle of the journal file.
+	file: File,
+	/// Current size of the journal file in bytes.
+	size: usize,
+	journaled: Journaled<Block>,
+}
+
+/// Persists transactions accepted by the pool across node restarts.
+pub(crate) struct TransactionJournal<Block: BlockT> {
+	all_sources: bool,
+	journaled: Journaled<Block>,
+	/// Transactions read at startup which were not yet handed out for re-submission.
+	pending: Mutex<Vec<(TransactionSource, Block::Extrinsic)>>,
+	/// The last time the journal was rotated.
+	last_rotation: Mutex<Instant>,
+	commands: Option<mpsc::Sender<Command<Block>>>,
+	writer: Option<thread::JoinHandle<()>>,
+}
+
+impl<Block: BlockT> TransactionJournal<Block> {
+	/// Opens (or creates) the journal in the directory given by `options`.
+	///
+	/// Expired and undecodable entries are dropped and the journal is rewritten with the
+	/// remaining ones, which can then be retrieved using [`Self::take_pending`].
+	pub fn open(options: JournalOptions) -> io::Result<Self> {
+		let dir = options.path.ok_or_else(|| {
+			io::Error::new(io::ErrorKind::InvalidInput, "no journal directory configured")
+		})?;
+		fs::create_dir_all(&dir)?;
+		let path = dir.join(JOURNAL_FILE);
+
+		let now = unix_now();
+		let max_age = options.max_age.as_secs();
+		let mut entries = match fs::read(&path) {
+			Ok(bytes) => decode_entries(&bytes),
+			Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+			Err(e) => return Err(e),
+		};
+		let total = entries.len();
+		entries.retain(|entry| {
+			accepts(options.all_sources, entry.source) &&
+				now.saturating_sub(entry.timestamp) <= max_age
+		});
+
+		let mut seen = HashSet::new();
+		let mut restored = Vec::new();
+		for entry in entries {
+			let Ok(xt) = Block::Extrinsic::decode(&mut &entry.extrinsic[..]) else { continue };
+			let hash = HashingFor::<Block>::hash(&entry.extrinsic);
+			if seen.insert(hash) {
+				restored.push((hash, xt, entry));
+			}
+		}
+		restored.sort_by_key(|(_, _, entry)| entry.timestamp);
+		let skip = restored.len() -
+			fitting_entries(restored.iter().map(|(_, _, entry)| entry), options.max_bytes);
+		restored.drain(..skip);
+
+		let mut journaled = HashMap::new();
+		let mut pending = Vec::new();
+		let mut kept = Vec::new();
+		for (hash, xt, entry) in restored {
+			journaled.insert(hash, entry.timestamp);
+			pending.push((entry.source, xt));
+			kept.push(entry);
+		}
+
+		tracing::info!(
+			target: LOG_TARGET,
+			path = ?path,
+			total,
+			restored = pending.len(),
+			"Opened transaction journal"
+		);
+
+		let size = write_journal(&path, &kept)?;
+		let file = OpenOptions::new().append(true).open(&path)?;
+
+		let journaled = Arc::new(Mutex::new(journaled));
+		let writer = Writer::<Block> {
+			path,
+			max_age: options.max_age,
+			max_bytes: options.max_bytes,
+			file,
+			size,
+			journaled: journaled.clone(),
+		};
+		let (commands, receiver) = mpsc::channel();
+		let writer = thread::Builder::new()
+			.name("txpool-journal".into())
+			.spawn(move || writer.run(receiver))?;
+
+		Ok(Self {
+			all_sources: options.all_sources,
+			journaled,
+			pending: Mutex::new(pending),
+			last_rotation: Mutex::new(Instant::now()),
+			commands: Some(commands),
+			writer: Some(writer),
+		})
+	}
+
+	/// Returns the transactions restored at startup.
+	///
+	/// Only the first call returns anything.
+	pub fn take_pending(&self) -> Vec<(TransactionSource, Block::Extrinsic)> {
+		core::mem::take(&mut self.pending.lock())
+	}
+
+	/// Returns `true` if transactions from `source` are persisted.
+	pub fn accepts(&self, source: TransactionSource) -> bool {
+		accepts(self.all_sources, source)
+	}
+
+	/// Appends the transaction to the journal.
+	///
+	/// Transactions that are already journaled or do not fit into the journal anymore are
+	/// ignored. Those get a chance to be persisted during the next rotation.
+	pub fn insert(&self, hash: Block::Hash, source: TransactionSource, xt: &Block::Extrinsic) {
+		if !self.accepts(source) {
+			return
+		}
+
+		let timestamp = unix_now();
+		if self.journaled.lock().insert(hash, timestamp).is_some() {
+			return
+		}
+		let entry = JournalEntry { timestamp, source, extrinsic: xt.encode() };
+		self.send(Command::Append(hash, entry));
+	}
+
+	/// Returns `true` if enough time passed since the last rotation.
+	pub fn should_rotate(&self) -> bool {
+		self.last_rotation.lock().elapsed() >= ROTATION_INTERVAL
+	}
+
+	/// Rewrites the journal to only contain the given transactions.
+	///
+	/// `transactions` should be all transactions currently held by the pool. The ones not
+	/// accepted by the journal, expired ones and the oldest ones exceeding the size limit are
+	/// skipped.
+	pub fn rotate(
+		&self,
+		transactions: impl IntoIterator<Item = (Block::Hash, TransactionSource, Vec<u8>)>,
+	) {
+		let transactions = transactions
+			.into_iter()
+			.filter(|(_, source, _)| self.accepts(*source))
+			.collect();
+		*self.last_rotation.lock() = Instant::now();
+		self.send(Command::Rotate(transactions));
+	}
+
+	fn send(&self, command: Command<Block>) {
+		let sent = self.commands.as_ref().map_or(false, |commands| commands.send(command).is_ok());
+		if !sent {
+			tracing::warn!(target: LOG_TARGET, "Transaction journal thread is gone");
+		}
+	}
+}
+
+impl<Block: BlockT> Drop for TransactionJournal<Block> {
+	fn drop(&mut self) {
+		// Closing the channel stops the writer once all queued commands are written.
+		self.commands.take();
+		if let Some(writer) = self.writer.take() {
+			let _ = writer.join();
+		}
+	}
+}
+
+impl<Block: BlockT> Writer<Block> {
+	fn run(mut self, commands: mpsc::Receiver<Command<Block>>) {
+		for command in commands {
+			match command {
+				Command::Append(hash, entry) => self.append(hash, entry),
+				Command::Rotate(transactions) => self.rotate(transactions),
+			}
+		}
+	}
+
+	fn append(&mut self, hash: Block::Hash, entry: JournalEntry) {
+		let encoded = entry.encode();
+		let result = if self.size + encoded.len() > self.max_bytes {
+			tracing::debug!(target: LOG_TARGET, ?hash, "Transaction journal is full");
+			Ok(false)
+		} else {
+			self.file.write_all(&encoded).map(|_| true)
+		};
+		match result {
+			Ok(true) => {
+				self.size += encoded.len();
+				// A rotation may have dropped the transaction while it was queued.
+				self.journaled.lock().insert(hash, entry.timestamp);
+			},
+			Ok(false) => {
+				self.journaled.lock().remove(&hash);
+			},
+			Err(error) => {
+				tracing::warn!(target: LOG_TARGET, ?error, "Failed to append to transaction journal");
+				self.journaled.lock().remove(&hash);
+			},
+		}
+	}
+
+	fn rotate(&mut self, transactions: Vec<(Block::Hash, TransactionSource, Vec<u8>)>) {
+		let now = unix_now();
+		let max_age = self.max_age.as_secs();
+
+		let timestamps = {
+			let journaled = self.journaled.lock();
+			transactions
+				.iter()
+				.map(|(hash, _, _)| journaled.get(hash).copied().unwrap_or(now))
+				.collect::<Vec<_>>()
+		};
+		let mut entries = transactions
+			.into_iter()
+			.zip(timestamps)
+			.map(|((hash, source, extrinsic), timestamp)| {
+				(hash, JournalEntry { timestamp, source, extrinsic })
+			})
+			.filter(|(_, entry)| now.saturating_sub(entry.timestamp) <= max_age)
+			.collect::<Vec<_>>();
+		entries.sort_by_key(|(_, entry)| entry.timestamp);
+
+		let skip =
+			entries.len() - fitting_entries(entries.iter().map(|(_, entry)| entry), self.max_bytes);
+		let (hashes, entries): (Vec<_>, Vec<_>) = entries.into_iter().skip(skip).unzip();
+
+		let result = write_journal(&self.path, &entries).and_then(|size| {
+			let file = OpenOptions::new().append(true).open(&self.path)?;
+			Ok((file, size))
+		});
+		match result {
+			Ok((file, size)) => {
+				self.file = file;
+				self.size = size;
+				*self.journaled.lock() = hashes
+					.into_iter()
+					.zip(&entries)
+					.map(|(hash, entry)| (hash, entry.timestamp))
+					.collect();
+				tracing::debug!(
+					target: LOG_TARGET,
+					transactions = entries.len(),
+					size,
+					"Rotated transaction journal"
+				);
+			},
+			Err(error) => {
+				tracing::warn!(target: LOG_TARGET, ?error, "Failed to rotate transaction journal");
+			},
+		}
+	}
+}
+
+/// Returns `true` if transactions from `source` are journaled.
+///
+/// Transactions submitted via RPC are [`TransactionSource::External`], like the ones received from
+/// the network, so both are journaled by default along with the local ones. Transactions
+/// re-submitted from retracted blocks are only journaled with `all_sources`.
+fn accepts(all_sources: bool, source: TransactionSource) -> bool {
+	all_sources || source != TransactionSource::InBlock
+}
+
+/// Seconds since the unix epoch.
+fn unix_now() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map(|d| d.as_secs())
+		.unwrap_or_default()
+}
+
+/// Decodes all entries of the journal.
+///
+/// Decoding stops at the first corrupted entry, which can happen if the node was stopped while
+/// writing to the journal.
+fn decode_entries(mut bytes: &[u8]) -> Vec<JournalEntry> {
+	let mut entries = Vec::new();
+	while !bytes.is_empty() {
+		match JournalEntry::decode(&mut bytes) {
+			Ok(entry) => entries.push(entry),
+			Err(error) => {
+				tracing::warn!(
+					target: LOG_TARGET,
+					?error,
+					"Transaction journal is corrupted, ignoring remaining entries"
+				);
+				break
+			},
+		}
+	}
+	entries
+}
+
+/// Returns how many of the newest `entries` fit into `max_bytes`.
+///
+/// `entries` are expected to be sorted from oldest to newest.
+fn fitting_entries<'a>(
+	entries: impl DoubleEndedIterator<Item = &'a JournalEntry>,
+	max_bytes: usize,
+) -> usize {
+	let mut size = 0;
+	entries
+		.rev()
+		.take_while(|entry| {
+			size += entry.encoded_size();
+			size <= max_bytes
+		})
+		.count()
+}
+
+/// Atomically replaces the journal at `path` with `entries`.
+///
+/// Returns the size of the written journal.
+fn write_journal(path: &Path, entries: &[JournalEntry]) -> io::Result<usize> {
+	let tmp = path.with_extension("tmp");
+	let mut file = File::create(&tmp)?;
+	let mut size = 0;
+	for entry in entries {
+		let encoded = entry.encode();
+		file.write_all(&encoded)?;
+		size += encoded.len();
+	}
+	file.sync_all()?;
+	fs::rename(&tmp, path)?;
+	Ok(size)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::common::tests::uxt;
+	use substrate_test_runtime::{AccountId, Block, Extrinsic, Transfer, H256};
+	use substrate_test_runtime_client::Sr25519Keyring::Alice;
+
+	fn xt(nonce: u64) -> (H256, Extrinsic) {
+		let xt = uxt(Transfer {
+			from: Alice.into(),
+			to: AccountId::from_h256(H256::from_low_u64_be(2)),
+			amount: 5,
+			nonce,
+		});
+		(HashingFor::<Block>::hash(&xt.encode()), xt)
+	}
+
+	fn options(dir: &tempfile::TempDir) -> JournalOptions {
+		JournalOptions { path: Some(dir.path().to_path_buf()), ..Default::default() }
+	}
+
+	#[test]
+	fn journaled_transactions_are_restored() {
+		let dir = tempfile::tempdir().unwrap();
+		let journal = TransactionJournal::<Block>::open(options(&dir)).unwrap();
+		assert!(journal.take_pending().is_empty());
+
+		let (hash0, xt0) = xt(0);
+		let (hash1, xt1) = xt(1);
+		journal.insert(hash0, TransactionSource::Local, &xt0);
+		journal.insert(hash1, TransactionSource::Local, &xt1);
+		// duplicates are ignored
+		journal.insert(hash1, TransactionSource::Local, &xt1);
+		drop(journal);
+
+		let journal = TransactionJournal::<Block>::open(options(&dir)).unwrap();
+		assert_eq!(
+			journal.take_pending(),
+			vec![(TransactionSource::Local, xt0), (TransactionSource::Local, xt1)]
+		);
+		assert!(journal.take_pending().is_empty());
+	}
+
+	#[test]
+	fn submitted_transactions_are_journaled_by_default() {
+		let dir = tempfile::tempdir().unwrap();
+		let journal = TransactionJournal::<Block>::open(options(&dir)).unwrap();
+
+		let (hash0, xt0) = xt(0);
+		let (hash1, xt1) = xt(1);
+		let (hash2, xt2) = xt(2);
+		journal.insert(hash0, TransactionSource::InBlock, &xt0);
+		journal.insert(hash1, TransactionSource::External, &xt1);
+		journal.insert(hash2, TransactionSource::Local, &xt2);
+		drop(journal);
+
+		let journal = TransactionJournal::<Block>::open(options(&dir)).unwrap();
+		assert_eq!(
+			journal.take_pending(),
+			vec![
+				(TransactionSource::External, xt1.clone()),
+				(TransactionSource::Local, xt2.clone())
+			]
+		);
+		drop(journal);
+
+		let all = JournalOptions { all_sources: true, ..options(&dir) };
+		let journal = TransactionJournal::<Block>::open(all).unwrap();
+		journal.insert(hash0, TransactionSource::InBlock, &xt0);
+		drop(journal);
+
+		let all = JournalOptions { all_sources: true, ..options(&dir) };
+		let journal = TransactionJournal::<Block>::open(all).unwrap();
+		assert_eq!(
+			journal.take_pending(),
+			vec![
+				(TransactionSource::External, xt1),
+				(TransactionSource::Local, xt2),
+				(TransactionSource::InBlock, xt0)
+			]
+		);
+	}
+
+	#[test]
+	fn expired_transactions_are_discarded() {
+		let dir = tempfile::tempdir().unwrap();
+		let (hash0, xt0) = xt(0);
+		let (_, xt1) = xt(1);
+		let entries = [
+			JournalEntry {
+				timestamp: unix_now() - 120,
+				source: TransactionSource::Local,
+				extrinsic: xt0.encode(),
+			},
+			JournalEntry {
+				timestamp: unix_now(),
+				source: TransactionSource::Local,
+				extrinsic: xt1.encode(),
+			},
+		];
+		write_journal(&dir.path().join(JOURNAL_FILE), &entries).unwrap();
+
+		let expiring = JournalOptions { max_age: Duration::from_secs(60), ..options(&dir) };
+		let journal = TransactionJournal::<Block>::open(expiring).unwrap();
+		assert_eq!(journal.take_pending(), vec![(TransactionSource::Local, xt1)]);
+
+		// the expired transaction was removed and can be journaled again
+		journal.insert(hash0, TransactionSource::Local, &xt0);
+		assert!(journal.journaled.lock().contains_key(&hash0));
+	}
+
+	#[test]
+	fn size_limit_is_respected() {
+		let dir = tempfile::tempdir().unwrap();
+		let (hash0, xt0) = xt(0);
+		let (hash1, xt1) = xt(1);
+		let entry_size = JournalEntry {
+			timestamp: unix_now(),
+			source: TransactionSource::Local,
+			extrinsic: xt0.encode(),
+		}
+		.encoded_size();
+
+		let limited = JournalOptions { max_bytes: entry_size, ..options(&dir) };
+		let journal = TransactionJournal::<Block>::open(limited.clone()).unwrap();
+		journal.insert(hash0, TransactionSource::Local, &xt0);
+		journal.insert(hash1, TransactionSource::Local, &xt1);
+		drop(journal);
+
+		let journal = TransactionJournal::<Block>::open(limited).unwrap();
+		assert_eq!(journal.take_pending(), vec![(TransactionSource::Local, xt0)]);
+	}
+
+	#[test]
+	fn rotation_keeps_pending_transactions_only() {
+		let dir = tempfile::tempdir().unwrap();
+		let journal = TransactionJournal::<Block>::open(options(&dir)).unwrap();
+
+		let (hash0, xt0) = xt(0);
+		let (hash1, xt1) = xt(1);
+		let (hash2, xt2) = xt(2);
+		journal.insert(hash0, TransactionSource::Local, &xt0);
+		journal.insert(hash1, TransactionSource::Local, &xt1);
+
+		// `xt0` was re-submitted from a retracted block, `xt2` was never journaled
+		journal.rotate([
+			(hash1, TransactionSource::Local, xt1.encode()),
+			(hash2, TransactionSource::Local, xt2.encode()),
+			(hash0, TransactionSource::InBlock, xt0.encode()),
+		]);
+		drop(journal);
+
+		let journal = TransactionJournal::<Block>::open(options(&dir)).unwrap();
+		let mut pending = journal.take_pending();
+		pending.sort_by_key(|(_, xt)| xt.encode());
+		let mut expected = vec![(TransactionSource::Local, xt1), (TransactionSource::Local, xt2)];
+		expected.sort_by_key(|(_, xt)| xt.encode());
+		assert_eq!(pending, expected);
+	}
+}
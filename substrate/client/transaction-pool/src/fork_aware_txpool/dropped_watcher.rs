@@ -28,7 +28,7 @@ use crate::{
 	LOG_TARGET,
 };
 use futures::stream::StreamExt;
-use sc_transaction_pool_api::TransactionStatus;
+use sc_transaction_pool_api::{DropReason, InspectedDrop, TransactionStatus};
 use sc_utils::mpsc;
 use sp_runtime::traits::Block as BlockT;
 use std::{
@@ -69,6 +69,20 @@ impl<Hash> DroppedTransaction<Hash> {
 	}
 }
 
+impl<Hash: Clone> DroppedTransaction<Hash> {
+	/// Returns the details of the dropped transaction reported by [`TransactionPool::inspect`].
+	///
+	/// [`TransactionPool::inspect`]: sc_transaction_pool_api::TransactionPool::inspect
+	pub fn inspect(&self) -> InspectedDrop<Hash> {
+		let reason = match &self.reason {
+			DroppedReason::Usurped(by) => DropReason::Usurped(by.clone()),
+			DroppedReason::LimitsEnforced => DropReason::LimitsEnforced,
+			DroppedReason::Invalid => DropReason::Invalid,
+		};
+		InspectedDrop { hash: self.tx_hash.clone(), reason }
+	}
+}
+
 /// Provides reason of why transactions was dropped.
 #[derive(Debug, PartialEq)]
 pub enum DroppedReason<Hash> {
@@ -52,9 +52,10 @@ use futures::{
 use parking_lot::Mutex;
 use prometheus_endpoint::Registry as PrometheusRegistry;
 use sc_transaction_pool_api::{
-	error::Error as TxPoolApiError, ChainEvent, ImportNotificationStream,
-	MaintainedTransactionPool, PoolStatus, TransactionFor, TransactionPool, TransactionPriority,
-	TransactionSource, TransactionStatusStreamFor, TxHash, TxInvalidityReportMap,
+	error::Error as TxPoolApiError, BlockHash, ChainEvent, ImportNotificationStream, InspectedDrop,
+	MaintainedTransactionPool, PoolInspection, PoolStatus, TransactionFor, TransactionPool,
+	TransactionPriority, TransactionSource, TransactionStatusStreamFor, TxHash,
+	TxInvalidityReportMap,
 };
 use sp_blockchain::{HashAndNumber, TreeRoute};
 use sp_core::traits::SpawnEssentialNamed;
@@ -65,7 +66,7 @@ use sp_runtime::{
 	Saturating,
 };
 use std::{
-	collections::{BTreeMap, HashMap, HashSet},
+	collections::{BTreeMap, HashMap, HashSet, VecDeque},
 	pin::Pin,
 	sync::Arc,
 	time::Instant,
@@ -78,6 +79,12 @@ use tracing::{debug, info, trace, warn};
 /// as stale and are subject to cleanup.
 const FINALITY_TIMEOUT_THRESHOLD: usize = 128;
 
+/// The maximum number of recently dropped transactions kept for inspection.
+const RECENTLY_DROPPED_LIMIT: usize = 1024;
+
+/// Recently dropped transactions, most recent first.
+type RecentlyDropped<Hash> = Arc<Mutex<VecDeque<InspectedDrop<Hash>>>>;
+
 /// Fork aware transaction pool task, that needs to be polled.
 pub type ForkAwareTxPoolTask = Pin<Box<dyn Future<Output = ()> + Send>>;
 
@@ -183,6 +190,9 @@ where
 	/// Intended to be used in the finality stall cleanups and also as a cache for all in-block
 	/// transactions.
 	included_transactions: Mutex<BTreeMap<HashAndNumber<Block>, Vec<ExtrinsicHash<ChainApi>>>>,
+
+	/// Transactions recently dropped by all the views, exposed for inspection.
+	recently_dropped: RecentlyDropped<ExtrinsicHash<ChainApi>>,
 }
 
 impl<ChainApi, Block> ForkAwareTxPool<ChainApi, Block>
@@ -241,11 +251,13 @@ where
 		let view_store =
 			Arc::new(ViewStore::new(pool_api.clone(), listener, dropped_stream_controller));
 
+		let recently_dropped = RecentlyDropped::default();
 		let dropped_monitor_task = Self::dropped_monitor_task(
 			dropped_stream,
 			mempool.clone(),
 			view_store.clone(),
 			import_notification_sink.clone(),
+			recently_dropped.clone(),
 		);
 
 		let combined_tasks = async move {
@@ -278,6 +290,7 @@ where
 				finality_timeout_threshold: finality_timeout_threshold
 					.unwrap_or(FINALITY_TIMEOUT_THRESHOLD),
 				included_transactions: Default::default(),
+				recently_dropped,
 			},
 			combined_tasks,
 		)
@@ -298,6 +311,7 @@ where
 			Block::Hash,
 			ExtrinsicHash<ChainApi>,
 		>,
+		recently_dropped: RecentlyDropped<ExtrinsicHash<ChainApi>>,
 	) {
 		loop {
 			let Some(dropped) = dropped_stream.next().await else {
@@ -330,6 +344,11 @@ where
 
 			mempool.remove_transactions(&[tx_hash]);
 			import_notification_sink.clean_notified_items(&[tx_hash]);
+			{
+				let mut recently_dropped = recently_dropped.lock();
+				recently_dropped.push_front(dropped.inspect());
+				recently_dropped.truncate(RECENTLY_DROPPED_LIMIT);
+			}
 			view_store.listener.transaction_dropped(dropped);
 		}
 	}
@@ -375,11 +394,13 @@ where
 		let view_store =
 			Arc::new(ViewStore::new(pool_api.clone(), listener, dropped_stream_controller));
 
+		let recently_dropped = RecentlyDropped::default();
 		let dropped_monitor_task = Self::dropped_monitor_task(
 			dropped_stream,
 			mempool.clone(),
 			view_store.clone(),
 			import_notification_sink.clone(),
+			recently_dropped.clone(),
 		);
 
 		let combined_tasks = async move {
@@ -411,6 +432,7 @@ where
 			is_validator,
 			finality_timeout_threshold: FINALITY_TIMEOUT_THRESHOLD,
 			included_transactions: Default::default(),
+			recently_dropped,
 		}
 	}
 
@@ -928,6 +950,14 @@ where
 			.unwrap_or(PoolStatus { ready: 0, ready_bytes: 0, future: 0, future_bytes: 0 })
 	}
 
+	/// Returns the content of all active views and the recently dropped transactions.
+	fn inspect(&self) -> PoolInspection<TxHash<Self>, BlockHash<Self>> {
+		PoolInspection {
+			views: self.view_store.inspect(),
+			dropped: self.recently_dropped.lock().iter().cloned().collect(),
+		}
+	}
+
 	/// Return an event stream of notifications when transactions are imported to the pool.
 	///
 	/// Consumers of this stream should use the `ready` method to actually get the
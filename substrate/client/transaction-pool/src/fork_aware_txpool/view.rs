@@ -35,7 +35,9 @@ use crate::{
 };
 use indexmap::IndexMap;
 use parking_lot::Mutex;
-use sc_transaction_pool_api::{error::Error as TxPoolError, PoolStatus, TransactionStatus};
+use sc_transaction_pool_api::{
+	error::Error as TxPoolError, InspectedView, PoolStatus, TransactionStatus,
+};
 use sc_utils::mpsc::{tracing_unbounded, TracingUnboundedReceiver, TracingUnboundedSender};
 use sp_blockchain::HashAndNumber;
 use sp_runtime::{
@@ -394,6 +396,12 @@ where
 		self.pool.validated_pool().status()
 	}
 
+	/// Ready and future transactions of the pool associated with the view.
+	pub(super) fn inspect(&self) -> InspectedView<ExtrinsicHash<ChainApi>, BlockHash<ChainApi>> {
+		let (ready, future) = self.pool.validated_pool().pool.read().inspect();
+		InspectedView { at: Some(self.at.hash), ready, future }
+	}
+
 	/// Revalidates some part of transaction from the internal pool.
 	///
 	/// Intended to be called from the revalidation worker. The revalidation process can be
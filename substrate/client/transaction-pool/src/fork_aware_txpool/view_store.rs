@@ -35,7 +35,8 @@ use crate::{
 use itertools::Itertools;
 use parking_lot::RwLock;
 use sc_transaction_pool_api::{
-	error::Error as PoolError, PoolStatus, TransactionTag as Tag, TxInvalidityReportMap,
+	error::Error as PoolError, InspectedView, PoolStatus, TransactionTag as Tag,
+	TxInvalidityReportMap,
 };
 use sp_blockchain::{HashAndNumber, TreeRoute};
 use sp_runtime::{
@@ -333,6 +334,11 @@ where
 		self.active_views.read().iter().map(|(h, v)| (*h, v.status())).collect()
 	}
 
+	/// The content of all active views.
+	pub(super) fn inspect(&self) -> Vec<InspectedView<ExtrinsicHash<ChainApi>, Block::Hash>> {
+		self.active_views.read().values().map(|view| view.inspect()).collect()
+	}
+
 	/// Returns true if there are no active views.
 	pub(super) fn is_empty(&self) -> bool {
 		self.active_views.read().is_empty() && self.inactive_views.read().is_empty()
@@ -20,10 +20,16 @@
 //!
 //! For a more full-featured pool, have a look at the `pool` module.
 
-use std::{cmp::Ordering, collections::HashSet, fmt, hash, sync::Arc, time::Instant};
+use std::{
+	cmp::Reverse,
+	collections::{BTreeMap, HashSet},
+	fmt, hash,
+	sync::Arc,
+	time::Instant,
+};
 
 use crate::LOG_TARGET;
-use sc_transaction_pool_api::{error, InPoolTransaction, PoolStatus};
+use sc_transaction_pool_api::{error, InPoolTransaction, InspectedTransaction, PoolStatus};
 use serde::Serialize;
 use sp_core::hexdisplay::HexDisplay;
 use sp_runtime::{
@@ -248,6 +254,7 @@ const RECENTLY_PRUNED_TAGS: usize = 2;
 #[derive(Clone, Debug)]
 pub struct BasePool<Hash: hash::Hash + Eq, Ex> {
 	reject_future_transactions: bool,
+	fairness: FairnessLimits,
 	future: FutureTransactions<Hash, Ex>,
 	ready: ReadyTransactions<Hash, Ex>,
 	/// Store recently pruned tags (for last two invocations).
@@ -260,17 +267,18 @@ pub struct BasePool<Hash: hash::Hash + Eq, Ex> {
 
 impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> Default for BasePool<Hash, Ex> {
 	fn default() -> Self {
-		Self::new(false)
+		Self::new(false, Default::default())
 	}
 }
 
 impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash, Ex> {
-	/// Create new pool given reject_future_transactions flag.
-	pub fn new(reject_future_transactions: bool) -> Self {
+	/// Create new pool given reject_future_transactions flag and fairness limits.
+	pub fn new(reject_future_transactions: bool, fairness: FairnessLimits) -> Self {
 		Self {
 			reject_future_transactions,
-			future: Default::default(),
-			ready: Default::default(),
+			future: FutureTransactions::new(fairness.clone()),
+			ready: ReadyTransactions::new(fairness.clone()),
+			fairness,
 			recently_pruned: Default::default(),
 			recently_pruned_index: 0,
 		}
@@ -315,6 +323,15 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 			return Err(error::Error::AlreadyImported(Box::new(tx.hash)))
 		}
 
+		if self.is_sender_quota_reached(&tx) {
+			trace!(
+				target: LOG_TARGET,
+				tx_hash = ?tx.hash,
+				"Sender quota reached, dropping transaction"
+			);
+			return Err(error::Error::ImmediatelyDropped)
+		}
+
 		let tx = WaitingTransaction::new(tx, self.ready.provided_tags(), &self.recently_pruned);
 		trace!(
 			target: LOG_TARGET,
@@ -438,6 +455,20 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		self.future.all()
 	}
 
+	/// Returns the details of the ready and future transactions in the pool.
+	pub fn inspect(&self) -> (Vec<InspectedTransaction<Hash>>, Vec<InspectedTransaction<Hash>>) {
+		let ready = self.ready().map(|tx| InspectedTransaction::new(&*tx, vec![])).collect();
+		let future = self
+			.future
+			.waiting()
+			.map(|waiting| {
+				let missing = waiting.missing_tags.iter().cloned().collect();
+				InspectedTransaction::new(&*waiting.transaction, missing)
+			})
+			.collect();
+		(ready, future)
+	}
+
 	/// Returns pool transactions given list of hashes.
 	///
 	/// Includes both ready and future pool. For every hash in the `hashes`
@@ -454,12 +485,48 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		self.ready.by_hash(hash)
 	}
 
+	/// Returns true if the sender of the transaction can't have any more transactions in the pool.
+	///
+	/// Transactions replacing one in the ready queue are always admitted, as they don't increase
+	/// the number of transactions of the sender.
+	fn is_sender_quota_reached(&self, tx: &Transaction<Hash, Ex>) -> bool {
+		let replaces = tx.provides.iter().any(|tag| self.ready.provided_tags().contains_key(tag));
+
+		!replaces && self.is_sender_at_quota(tx)
+	}
+
+	/// Returns true if the sender of the transaction has as many transactions in the pool as its
+	/// quota allows, or more.
+	fn is_sender_at_quota(&self, tx: &Transaction<Hash, Ex>) -> bool {
+		let Some(limit) = self.fairness.per_sender else { return false };
+
+		self.ready.fairness_counts().sender(tx) + self.future.fairness_counts().sender(tx) >= limit
+	}
+
+	/// Returns true if the queues exceed any of the provided limits.
+	pub fn is_limit_exceeded(&self, ready: &Limit, future: &Limit) -> bool {
+		ready.is_exceeded(self.ready.len(), self.ready.bytes()) ||
+			future.is_exceeded(self.future.len(), self.future.bytes()) ||
+			self.is_reserved_lane_exceeded(ready)
+	}
+
+	/// Returns true if transactions outside of the priority lane occupy the slots reserved for it.
+	fn is_reserved_lane_exceeded(&self, ready: &Limit) -> bool {
+		self.fairness.lane_reserved > 0 &&
+			self.ready.fairness_counts().regular() >
+				ready.count.saturating_sub(self.fairness.lane_reserved)
+	}
+
 	/// Makes sure that the transactions in the queues stay within provided limits.
 	///
 	/// Removes and returns worst transactions from the queues and all transactions that depend on
 	/// them. Technically the worst transaction should be evaluated by computing the entire pending
 	/// set. We use a simplified approach to remove transactions with the lowest priority first or
 	/// those that occupy the pool for the longest time in case priority is the same.
+	///
+	/// Transactions outside of the priority lane of the [`FairnessLimits`] are removed before the
+	/// ones in it. Transactions of senders which reached their quota are removed before the ones of
+	/// other senders.
 	pub fn enforce_limits(
 		&mut self,
 		ready: &Limit,
@@ -467,33 +534,35 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 	) -> Vec<Arc<Transaction<Hash, Ex>>> {
 		let mut removed = vec![];
 
-		while ready.is_exceeded(self.ready.len(), self.ready.bytes()) {
+		while ready.is_exceeded(self.ready.len(), self.ready.bytes()) ||
+			self.is_reserved_lane_exceeded(ready)
+		{
 			// find the worst transaction
-			let worst =
-				self.ready.fold::<Option<TransactionRef<Hash, Ex>>, _>(None, |worst, current| {
+			//
+			// Here we don't use `TransactionRef`'s ordering implementation because while it
+			// prefers priority like need here, it also prefers older transactions for inclusion
+			// purposes and limit enforcement needs to prefer newer transactions instead and drop
+			// the older ones.
+			let fairness = &self.fairness;
+			let worst = self.ready.fold::<Option<(_, TransactionRef<Hash, Ex>)>, _>(
+				None,
+				|worst, current| {
 					let transaction = &current.transaction;
-					worst
-						.map(|worst| {
-							// Here we don't use `TransactionRef`'s ordering implementation because
-							// while it prefers priority like need here, it also prefers older
-							// transactions for inclusion purposes and limit enforcement needs to
-							// prefer newer transactions instead and drop the older ones.
-							match worst.transaction.priority.cmp(&transaction.transaction.priority)
-							{
-								Ordering::Less => worst,
-								Ordering::Equal =>
-									if worst.insertion_id > transaction.insertion_id {
-										transaction.clone()
-									} else {
-										worst
-									},
-								Ordering::Greater => transaction.clone(),
-							}
-						})
-						.or_else(|| Some(transaction.clone()))
-				});
+					let rank = (
+						fairness.lane_reserved > 0 && !fairness.is_lane(&transaction.transaction),
+						self.is_sender_at_quota(&transaction.transaction),
+						Reverse(transaction.transaction.priority),
+						transaction.insertion_id,
+					);
+					match worst {
+						Some((worst_rank, worst)) if worst_rank >= rank =>
+							Some((worst_rank, worst)),
+						_ => Some((rank, transaction.clone())),
+					}
+				},
+			);
 
-			if let Some(worst) = worst {
+			if let Some((_, worst)) = worst {
 				removed.append(&mut self.remove_subtree(&[worst.transaction.hash.clone()]))
 			} else {
 				break
@@ -505,21 +574,30 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 			let worst = self.future.fold(|worst, current| match worst {
 				None => Some(current.clone()),
 				Some(worst) => Some(
-					match (worst.transaction.source.timestamp, current.transaction.source.timestamp)
-					{
-						(Some(worst_timestamp), Some(current_timestamp)) => {
-							if worst_timestamp > current_timestamp {
-								current.clone()
-							} else {
-								worst
-							}
-						},
-						_ =>
-							if worst.imported_at > current.imported_at {
-								current.clone()
-							} else {
-								worst
+					match (
+						self.is_sender_at_quota(&worst.transaction),
+						self.is_sender_at_quota(&current.transaction),
+					) {
+						(true, false) => worst,
+						(false, true) => current.clone(),
+						_ => match (
+							worst.transaction.source.timestamp,
+							current.transaction.source.timestamp,
+						) {
+							(Some(worst_timestamp), Some(current_timestamp)) => {
+								if worst_timestamp > current_timestamp {
+									current.clone()
+								} else {
+									worst
+								}
 							},
+							_ =>
+								if worst.imported_at > current.imported_at {
+									current.clone()
+								} else {
+									worst
+								},
+						},
 					},
 				),
 			});
@@ -630,6 +708,139 @@ impl Limit {
 	}
 }
 
+/// Limits ensuring that the pool is shared fairly under load.
+#[derive(Debug, Clone)]
+pub struct FairnessLimits {
+	/// Maximal number of transactions of a single sender in the queues.
+	///
+	/// Further transactions of a sender reaching the quota are rejected, unless they replace a
+	/// transaction already in the pool. `None` disables the quota.
+	pub per_sender: Option<usize>,
+	/// Number of ready queue slots reserved for the priority lane.
+	///
+	/// The priority lane consists of transactions with [`TransactionSource::Local`] and
+	/// transactions with a priority of at least [`Self::lane_priority`]. Other transactions can
+	/// not occupy the reserved slots and are removed first once the ready queue is full. `0`
+	/// disables the lane.
+	pub lane_reserved: usize,
+	/// Minimal priority of a transaction to enter the priority lane.
+	///
+	/// Meant to admit operational transactions, whose priority is boosted by the runtime.
+	pub lane_priority: Priority,
+}
+
+impl Default for FairnessLimits {
+	fn default() -> Self {
+		Self { per_sender: None, lane_reserved: 0, lane_priority: Priority::MAX }
+	}
+}
+
+impl FairnessLimits {
+	/// Returns true if the transaction belongs to the priority lane.
+	pub fn is_lane<Hash, Ex>(&self, tx: &Transaction<Hash, Ex>) -> bool {
+		tx.source.source == TransactionSource::Local || tx.priority >= self.lane_priority
+	}
+}
+
+/// Returns the bytes identifying the sender of a transaction.
+///
+/// The pool has no notion of senders, so they are derived from the tags. Transactions using the
+/// `CheckNonce` extension of FRAME provide `(sender, nonce)` and require `(sender, nonce - 1)`,
+/// hence the common prefix of such tags identifies the sender. Transactions which don't require
+/// any tag are identified by the first tag they provide, which starts with the key of the other
+/// transactions of the sender. [`FairnessCounts::sender`] accounts them together.
+fn sender_key<Hash, Ex>(tx: &Transaction<Hash, Ex>) -> Option<&[u8]> {
+	tx.requires
+		.iter()
+		.flat_map(|required| {
+			tx.provides.iter().filter(|provided| provided.len() == required.len()).map(
+				move |provided| {
+					let len = required.iter().zip(provided).take_while(|(a, b)| a == b).count();
+					&provided[..len]
+				},
+			)
+		})
+		.max_by_key(|prefix| prefix.len())
+		.filter(|prefix| !prefix.is_empty())
+		.or_else(|| tx.provides.first().map(|tag| &tag[..]))
+}
+
+/// Counters of the transactions in a queue, used to apply the [`FairnessLimits`].
+///
+/// The counters are updated whenever a transaction enters or leaves the queue, so checking the
+/// limits doesn't require iterating over the queue.
+#[derive(Debug, Clone, Default)]
+pub struct FairnessCounts {
+	limits: FairnessLimits,
+	/// Number of transactions outside of the priority lane.
+	regular: usize,
+	/// Number of transactions per key returned by [`sender_key`].
+	senders: BTreeMap<Vec<u8>, usize>,
+}
+
+impl FairnessCounts {
+	/// Create new counters for the given limits.
+	pub fn new(limits: FairnessLimits) -> Self {
+		Self { limits, regular: 0, senders: Default::default() }
+	}
+
+	/// Accounts a transaction entering the queue.
+	pub fn insert<Hash, Ex>(&mut self, tx: &Transaction<Hash, Ex>) {
+		if self.limits.lane_reserved > 0 && !self.limits.is_lane(tx) {
+			self.regular += 1;
+		}
+		if let (Some(_), Some(key)) = (self.limits.per_sender, sender_key(tx)) {
+			*self.senders.entry(key.to_vec()).or_default() += 1;
+		}
+	}
+
+	/// Accounts a transaction leaving the queue.
+	pub fn remove<Hash, Ex>(&mut self, tx: &Transaction<Hash, Ex>) {
+		if self.limits.lane_reserved > 0 && !self.limits.is_lane(tx) {
+			self.regular = self.regular.saturating_sub(1);
+		}
+		if let (Some(_), Some(key)) = (self.limits.per_sender, sender_key(tx)) {
+			if let Some(count) = self.senders.get_mut(key) {
+				*count = count.saturating_sub(1);
+				if *count == 0 {
+					self.senders.remove(key);
+				}
+			}
+		}
+	}
+
+	/// Resets the counters after the queue got cleared.
+	pub fn clear(&mut self) {
+		self.regular = 0;
+		self.senders.clear();
+	}
+
+	/// Returns the number of transactions outside of the priority lane.
+	pub fn regular(&self) -> usize {
+		self.regular
+	}
+
+	/// Returns the number of transactions of the sender of the given transaction.
+	///
+	/// The first transaction of a sender is keyed by its full tag, so the sender is identified by
+	/// the longest counted key which is a prefix of the key of the transaction, and all keys
+	/// starting with it are counted.
+	pub fn sender<Hash, Ex>(&self, tx: &Transaction<Hash, Ex>) -> usize {
+		let Some(key) = sender_key(tx) else { return 0 };
+		let sender = (1..key.len())
+			.rev()
+			.map(|len| &key[..len])
+			.find(|prefix| self.senders.contains_key(*prefix))
+			.unwrap_or(key);
+
+		self.senders
+			.range(sender.to_vec()..)
+			.take_while(|(key, _)| key.starts_with(sender))
+			.map(|(_, count)| count)
+			.sum()
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -1274,4 +1485,119 @@ source: TimedTransactionSource { source: TransactionSource::External, timestamp:
 		assert_eq!(pool.reject_future_transactions, true);
 		assert_eq!(pool.future.len(), 1);
 	}
+
+	fn sender_tx(sender: u8, nonce: u8, priority: Priority) -> Transaction<Hash, Vec<u8>> {
+		Transaction {
+			data: vec![sender, nonce],
+			hash: ((sender as u64) << 8) | nonce as u64,
+			priority,
+			requires: if nonce > 0 { vec![vec![sender, nonce - 1]] } else { vec![] },
+			provides: vec![vec![sender, nonce]],
+			..default_tx().clone()
+		}
+	}
+
+	#[test]
+	fn should_derive_sender_from_tags() {
+		assert_eq!(sender_key(&sender_tx(1, 1, 5)), Some(&[1u8][..]));
+		assert_eq!(sender_key(&sender_tx(1, 0, 5)), Some(&[1u8, 0][..]));
+		assert_eq!(sender_key(&default_tx()), None);
+
+		// the first transaction of a sender is accounted with the others
+		let mut counts =
+			FairnessCounts::new(FairnessLimits { per_sender: Some(1), ..Default::default() });
+		counts.insert(&sender_tx(1, 1, 5));
+		counts.insert(&sender_tx(1, 0, 5));
+		counts.insert(&sender_tx(2, 0, 5));
+		assert_eq!(counts.sender(&sender_tx(1, 0, 5)), 2);
+		assert_eq!(counts.sender(&sender_tx(1, 2, 5)), 2);
+		assert_eq!(counts.sender(&sender_tx(2, 1, 5)), 1);
+		counts.remove(&sender_tx(1, 1, 5));
+		assert_eq!(counts.sender(&sender_tx(1, 0, 5)), 1);
+	}
+
+	#[test]
+	fn should_reject_transactions_over_sender_quota() {
+		// given
+		let fairness = FairnessLimits { per_sender: Some(2), ..Default::default() };
+		let mut pool = BasePool::new(false, fairness);
+		for nonce in 0..2 {
+			pool.import(sender_tx(1, nonce, 10)).unwrap();
+		}
+		pool.import(sender_tx(2, 0, 5)).unwrap();
+
+		// when
+		let err = pool.import(sender_tx(1, 2, 10));
+
+		// then
+		if let Err(error::Error::ImmediatelyDropped) = err {
+		} else {
+			assert!(false, "Invalid error kind: {:?}", err);
+		}
+		assert_eq!(pool.ready.fairness_counts().sender(&sender_tx(1, 0, 10)), 2);
+		// replacements don't increase the number of transactions of the sender
+		pool.import(Transaction { hash: 0x1ff, ..sender_tx(1, 1, 20) }).unwrap();
+		assert!(!pool.ready.contains(&0x101));
+		pool.import(sender_tx(1, 2, 10)).unwrap_err();
+
+		// removed transactions free the quota
+		pool.remove_subtree(&[0x1ff]);
+		assert_eq!(pool.ready.fairness_counts().sender(&sender_tx(1, 1, 10)), 1);
+		pool.import(sender_tx(1, 2, 10)).unwrap();
+		assert_eq!(pool.future.fairness_counts().sender(&sender_tx(1, 2, 10)), 1);
+	}
+
+	#[test]
+	fn should_remove_transactions_of_senders_at_quota_first() {
+		// given
+		let fairness = FairnessLimits { per_sender: Some(2), ..Default::default() };
+		let mut pool = BasePool::new(false, fairness);
+		pool.import(sender_tx(1, 0, 20)).unwrap();
+		pool.import(sender_tx(1, 1, 20)).unwrap();
+		pool.import(sender_tx(2, 0, 10)).unwrap();
+		pool.import(sender_tx(3, 0, 5)).unwrap();
+		let ready = Limit { count: 3, total_bytes: usize::MAX };
+		let future = Limit { count: 0, total_bytes: 0 };
+
+		// when
+		let removed = pool.enforce_limits(&ready, &future);
+
+		// then
+		// the newest transaction of the sender at its quota goes first, despite its priority
+		assert_eq!(removed.iter().map(|tx| tx.hash).collect::<Vec<_>>(), vec![0x101]);
+		assert!(pool.ready.contains(&0x300));
+	}
+
+	#[test]
+	fn should_keep_reserved_lane_free() {
+		// given
+		let fairness =
+			FairnessLimits { lane_reserved: 2, lane_priority: 100, ..Default::default() };
+		let mut pool = BasePool::new(false, fairness.clone());
+		pool.import(Transaction {
+			source: TimedTransactionSource::new_local(false),
+			..sender_tx(1, 0, 1)
+		})
+		.unwrap();
+		pool.import(sender_tx(3, 0, 10)).unwrap();
+		pool.import(sender_tx(4, 0, 20)).unwrap();
+		pool.import(sender_tx(5, 0, 30)).unwrap();
+		let ready = Limit { count: 4, total_bytes: usize::MAX };
+		let future = Limit { count: 0, total_bytes: 0 };
+
+		// when
+		assert!(!ready.is_exceeded(pool.ready.len(), pool.ready.bytes()));
+		assert_eq!(pool.ready.fairness_counts().regular(), 3);
+		assert!(pool.is_limit_exceeded(&ready, &future));
+		let removed = pool.enforce_limits(&ready, &future);
+
+		// then
+		// the local transaction is kept, regular ones are limited to 2 slots
+		assert_eq!(removed.iter().map(|tx| tx.hash).collect::<Vec<_>>(), vec![0x300]);
+		assert!(pool.ready.contains(&0x100));
+		assert_eq!(pool.ready.fairness_counts().regular(), 2);
+		assert!(!pool.is_limit_exceeded(&ready, &future));
+		assert!(fairness.is_lane(&sender_tx(2, 0, 100)));
+		assert!(!fairness.is_lane(&sender_tx(2, 0, 99)));
+	}
 }
@@ -26,7 +26,7 @@ use sp_core::hexdisplay::HexDisplay;
 use sp_runtime::transaction_validity::TransactionTag as Tag;
 use std::time::Instant;
 
-use super::base_pool::Transaction;
+use super::base_pool::{FairnessCounts, FairnessLimits, Transaction};
 use crate::{common::tracing_log_xt::log_xt_trace, LOG_TARGET};
 
 /// Transaction with partially satisfied dependencies.
@@ -114,11 +114,17 @@ pub struct FutureTransactions<Hash: hash::Hash + Eq, Ex> {
 	wanted_tags: HashMap<Tag, HashSet<Hash>>,
 	/// Transactions waiting for a particular other transaction
 	waiting: HashMap<Hash, WaitingTransaction<Hash, Ex>>,
+	/// Counters used to apply the fairness limits.
+	fairness_counts: FairnessCounts,
 }
 
 impl<Hash: hash::Hash + Eq, Ex> Default for FutureTransactions<Hash, Ex> {
 	fn default() -> Self {
-		Self { wanted_tags: Default::default(), waiting: Default::default() }
+		Self {
+			wanted_tags: Default::default(),
+			waiting: Default::default(),
+			fairness_counts: Default::default(),
+		}
 	}
 }
 
@@ -132,6 +138,11 @@ qed
 impl<Hash: hash::Hash + Eq + Clone + std::fmt::Debug, Ex: std::fmt::Debug>
 	FutureTransactions<Hash, Ex>
 {
+	/// Create new queue tracking the counters of the given fairness limits.
+	pub fn new(fairness: FairnessLimits) -> Self {
+		Self { fairness_counts: FairnessCounts::new(fairness), ..Default::default() }
+	}
+
 	/// Import transaction to Future queue.
 	///
 	/// Only transactions that don't have all their tags satisfied should occupy
@@ -152,6 +163,7 @@ impl<Hash: hash::Hash + Eq + Clone + std::fmt::Debug, Ex: std::fmt::Debug>
 		}
 
 		// Add the transaction to a by-hash waiting map
+		self.fairness_counts.insert(&tx.transaction);
 		self.waiting.insert(tx.transaction.hash.clone(), tx);
 	}
 
@@ -209,6 +221,7 @@ impl<Hash: hash::Hash + Eq + Clone + std::fmt::Debug, Ex: std::fmt::Debug>
 
 					if is_ready {
 						let tx = self.waiting.remove(&hash).expect(WAITING_PROOF);
+						self.fairness_counts.remove(&tx.transaction);
 						became_ready.push(tx);
 					}
 				}
@@ -225,6 +238,7 @@ impl<Hash: hash::Hash + Eq + Clone + std::fmt::Debug, Ex: std::fmt::Debug>
 		let mut removed = vec![];
 		for hash in hashes {
 			if let Some(waiting_tx) = self.waiting.remove(hash) {
+				self.fairness_counts.remove(&waiting_tx.transaction);
 				// remove from wanted_tags as well
 				for tag in waiting_tx.missing_tags {
 					let remove = if let Some(wanted) = self.wanted_tags.get_mut(&tag) {
@@ -247,12 +261,17 @@ impl<Hash: hash::Hash + Eq + Clone + std::fmt::Debug, Ex: std::fmt::Debug>
 
 	/// Fold a list of future transactions to compute a single value.
 	pub fn fold<R, F: FnMut(Option<R>, &WaitingTransaction<Hash, Ex>) -> Option<R>>(
-		&mut self,
+		&self,
 		f: F,
 	) -> Option<R> {
 		self.waiting.values().fold(None, f)
 	}
 
+	/// Returns iterator over all future transactions along with their missing tags.
+	pub fn waiting(&self) -> impl Iterator<Item = &WaitingTransaction<Hash, Ex>> {
+		self.waiting.values()
+	}
+
 	/// Returns iterator over all future transactions
 	pub fn all(&self) -> impl Iterator<Item = &Transaction<Hash, Ex>> {
 		self.waiting.values().map(|waiting| &*waiting.transaction)
@@ -261,6 +280,7 @@ impl<Hash: hash::Hash + Eq + Clone + std::fmt::Debug, Ex: std::fmt::Debug>
 	/// Removes and returns all future transactions.
 	pub fn clear(&mut self) -> Vec<Arc<Transaction<Hash, Ex>>> {
 		self.wanted_tags.clear();
+		self.fairness_counts.clear();
 		self.waiting.drain().map(|(_, tx)| tx.transaction).collect()
 	}
 
@@ -273,4 +293,9 @@ impl<Hash: hash::Hash + Eq + Clone + std::fmt::Debug, Ex: std::fmt::Debug>
 	pub fn bytes(&self) -> usize {
 		self.waiting.values().fold(0, |acc, tx| acc + tx.transaction.bytes)
 	}
+
+	/// Returns the counters of the fairness limits.
+	pub fn fairness_counts(&self) -> &FairnessCounts {
+		&self.fairness_counts
+	}
 }
@@ -146,6 +146,8 @@ pub struct Options {
 	pub reject_future_transactions: bool,
 	/// How long the extrinsic is banned for.
 	pub ban_time: Duration,
+	/// Per-sender quotas and the priority lane.
+	pub fairness: base::FairnessLimits,
 }
 
 impl Default for Options {
@@ -155,6 +157,7 @@ impl Default for Options {
 			future: base::Limit { count: 512, total_bytes: 1 * 1024 * 1024 },
 			reject_future_transactions: false,
 			ban_time: Duration::from_secs(60 * 30),
+			fairness: Default::default(),
 		}
 	}
 }
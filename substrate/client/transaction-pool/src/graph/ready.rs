@@ -30,7 +30,7 @@ use sp_runtime::{traits::Member, transaction_validity::TransactionTag as Tag};
 use tracing::trace;
 
 use super::{
-	base_pool::Transaction,
+	base_pool::{FairnessCounts, FairnessLimits, Transaction},
 	future::WaitingTransaction,
 	tracked_map::{self, TrackedMap},
 };
@@ -118,6 +118,8 @@ pub struct ReadyTransactions<Hash: hash::Hash + Eq, Ex> {
 	/// Best transactions that are ready to be included to the block without any other previous
 	/// transaction.
 	best: BTreeSet<TransactionRef<Hash, Ex>>,
+	/// Counters used to apply the fairness limits.
+	fairness_counts: FairnessCounts,
 }
 
 impl<Hash, Ex> tracked_map::Size for ReadyTx<Hash, Ex> {
@@ -133,11 +135,17 @@ impl<Hash: hash::Hash + Eq, Ex> Default for ReadyTransactions<Hash, Ex> {
 			provided_tags: Default::default(),
 			ready: Default::default(),
 			best: Default::default(),
+			fairness_counts: Default::default(),
 		}
 	}
 }
 
 impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
+	/// Create new queue tracking the counters of the given fairness limits.
+	pub fn new(fairness: FairnessLimits) -> Self {
+		Self { fairness_counts: FairnessCounts::new(fairness), ..Default::default() }
+	}
+
 	/// Borrows a map of tags that are provided by transactions in this queue.
 	pub fn provided_tags(&self) -> &HashMap<Tag, Hash> {
 		&self.provided_tags
@@ -227,6 +235,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
 		}
 
 		// insert to Ready
+		self.fairness_counts.insert(&transaction.transaction);
 		ready.insert(hash, ReadyTx { transaction, unlocks, requires_offset });
 
 		Ok(replaced)
@@ -281,6 +290,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
 		let mut ready = self.ready.write();
 		while let Some(tx_hash) = to_remove.pop() {
 			if let Some(mut tx) = ready.remove(&tx_hash) {
+				self.fairness_counts.remove(&tx.transaction.transaction);
 				let invalidated = tx.transaction.transaction.provides.iter().filter(|tag| {
 					provides_tag_filter
 						.as_ref()
@@ -337,6 +347,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
 				.and_then(|hash| self.ready.write().remove(&hash));
 
 			if let Some(tx) = res {
+				self.fairness_counts.remove(&tx.transaction.transaction);
 				let unlocks = tx.unlocks;
 
 				// Make sure we remove it from best txs
@@ -479,6 +490,11 @@ impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
 	pub fn bytes(&self) -> usize {
 		self.ready.bytes()
 	}
+
+	/// Returns the counters of the fairness limits.
+	pub fn fairness_counts(&self) -> &FairnessCounts {
+		&self.fairness_counts
+	}
 }
 
 /// Iterator of ready transactions ordered by priority.
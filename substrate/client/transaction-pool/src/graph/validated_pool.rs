@@ -239,7 +239,8 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 		rotator: PoolRotator<ExtrinsicHash<B>>,
 		event_handler: Option<L>,
 	) -> Self {
-		let base_pool = base::BasePool::new(options.reject_future_transactions);
+		let base_pool =
+			base::BasePool::new(options.reject_future_transactions, options.fairness.clone());
 		Self {
 			is_validator,
 			options,
@@ -372,13 +373,10 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 	}
 
 	fn enforce_limits(&self) -> HashSet<ExtrinsicHash<B>> {
-		let status = self.pool.read().status();
 		let ready_limit = &self.options.ready;
 		let future_limit = &self.options.future;
 
-		if ready_limit.is_exceeded(status.ready, status.ready_bytes) ||
-			future_limit.is_exceeded(status.future, status.future_bytes)
-		{
+		if self.pool.read().is_limit_exceeded(ready_limit, future_limit) {
 			debug!(
 				target: LOG_TARGET,
 				ready_count = ready_limit.count,
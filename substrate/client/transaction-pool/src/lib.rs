@@ -33,11 +33,13 @@ use common::{api, enactment_state};
 use std::sync::Arc;
 
 pub use api::FullChainApi;
-pub use builder::{Builder, TransactionPoolHandle, TransactionPoolOptions, TransactionPoolType};
+pub use builder::{
+	Builder, JournalOptions, TransactionPoolHandle, TransactionPoolOptions, TransactionPoolType,
+};
 pub use common::notification_future;
 pub use fork_aware_txpool::{ForkAwareTxPool, ForkAwareTxPoolTask};
 pub use graph::{
-	base_pool::{Limit as PoolLimit, TimedTransactionSource},
+	base_pool::{FairnessLimits as PoolFairnessLimits, Limit as PoolLimit, TimedTransactionSource},
 	ChainApi, Options, Pool,
 };
 use single_state_txpool::prune_known_txs_for_block;
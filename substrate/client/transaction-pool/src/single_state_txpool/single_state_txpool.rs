@@ -40,9 +40,9 @@ use futures::{channel::oneshot, future, prelude::*, Future, FutureExt};
 use parking_lot::Mutex;
 use prometheus_endpoint::Registry as PrometheusRegistry;
 use sc_transaction_pool_api::{
-	error::Error as TxPoolError, ChainEvent, ImportNotificationStream, MaintainedTransactionPool,
-	PoolStatus, TransactionFor, TransactionPool, TransactionSource, TransactionStatusStreamFor,
-	TxHash, TxInvalidityReportMap,
+	error::Error as TxPoolError, BlockHash, ChainEvent, ImportNotificationStream, InspectedView,
+	MaintainedTransactionPool, PoolInspection, PoolStatus, TransactionFor, TransactionPool,
+	TransactionSource, TransactionStatusStreamFor, TxHash, TxInvalidityReportMap,
 };
 use sp_blockchain::{HashAndNumber, TreeRoute};
 use sp_core::traits::SpawnEssentialNamed;
@@ -350,6 +350,13 @@ where
 		self.pool.validated_pool().status()
 	}
 
+	fn inspect(&self) -> PoolInspection<TxHash<Self>, BlockHash<Self>> {
+		let (ready, future) = self.pool.validated_pool().pool.read().inspect();
+		let at = Some(self.enactment_state.lock().recent_best_block());
+		// The dropped transactions are not kept track of by the single-state pool.
+		PoolInspection { views: vec![InspectedView { at, ready, future }], dropped: vec![] }
+	}
+
 	fn import_notification_stream(&self) -> ImportNotificationStream<TxHash<Self>> {
 		self.pool.validated_pool().import_notification_stream()
 	}
@@ -21,14 +21,16 @@
 
 use crate::{
 	builder::FullClientTransactionPool,
+	common::journal::TransactionJournal,
 	graph::{base_pool::Transaction, ExtrinsicFor, ExtrinsicHash},
-	ChainApi, FullChainApi, ReadyIteratorFor,
+	ChainApi, FullChainApi, ReadyIteratorFor, LOG_TARGET,
 };
 use async_trait::async_trait;
+use codec::Encode;
 use sc_transaction_pool_api::{
-	ChainEvent, ImportNotificationStream, LocalTransactionFor, LocalTransactionPool,
-	MaintainedTransactionPool, PoolStatus, ReadyTransactions, TransactionFor, TransactionPool,
-	TransactionSource, TransactionStatusStreamFor, TxHash, TxInvalidityReportMap,
+	BlockHash, ChainEvent, ImportNotificationStream, LocalTransactionFor, LocalTransactionPool,
+	MaintainedTransactionPool, PoolInspection, PoolStatus, ReadyTransactions, TransactionFor,
+	TransactionPool, TransactionSource, TransactionStatusStreamFor, TxHash, TxInvalidityReportMap,
 };
 use sp_runtime::traits::Block as BlockT;
 use std::{collections::HashMap, pin::Pin, sync::Arc};
@@ -36,8 +38,10 @@ use std::{collections::HashMap, pin::Pin, sync::Arc};
 /// The wrapper for actual object providing implementation of TransactionPool.
 ///
 /// This wraps actual implementation of the TransactionPool, e.g. fork-aware or single-state.
+/// If enabled, it also maintains the on-disk journal of submitted transactions.
 pub struct TransactionPoolWrapper<Block, Client>(
 	pub Box<dyn FullClientTransactionPool<Block, Client>>,
+	Option<TransactionJournal<Block>>,
 )
 where
 	Block: BlockT,
@@ -49,6 +53,76 @@ where
 		+ 'static,
 	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>;
 
+impl<Block, Client> TransactionPoolWrapper<Block, Client>
+where
+	Block: BlockT,
+	Client: sp_api::ProvideRuntimeApi<Block>
+		+ sc_client_api::BlockBackend<Block>
+		+ sc_client_api::blockchain::HeaderBackend<Block>
+		+ sp_runtime::traits::BlockIdTo<Block>
+		+ sp_blockchain::HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ 'static,
+	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
+{
+	/// Wraps the given pool, optionally persisting its transactions to the given journal.
+	pub(crate) fn new(
+		pool: Box<dyn FullClientTransactionPool<Block, Client>>,
+		journal: Option<TransactionJournal<Block>>,
+	) -> Self {
+		Self(pool, journal)
+	}
+
+	/// Returns the journal if transactions from `source` should be persisted.
+	fn journal_for(&self, source: TransactionSource) -> Option<&TransactionJournal<Block>> {
+		self.1.as_ref().filter(|journal| journal.accepts(source))
+	}
+
+	/// Re-submits the transactions restored from the journal at startup.
+	async fn replay_journal(&self, journal: &TransactionJournal<Block>, at: Block::Hash) {
+		let restored = journal.take_pending();
+		if restored.is_empty() {
+			return
+		}
+
+		let total = restored.len();
+		let mut by_source = HashMap::<_, Vec<_>>::new();
+		for (source, xt) in restored {
+			by_source.entry(source).or_default().push(xt);
+		}
+
+		let mut valid = 0;
+		for (source, xts) in by_source {
+			match self.0.submit_at(at, source, xts).await {
+				Ok(results) => valid += results.iter().filter(|result| result.is_ok()).count(),
+				Err(error) => tracing::warn!(
+					target: LOG_TARGET,
+					?error,
+					"Failed to re-submit journaled transactions"
+				),
+			}
+		}
+
+		tracing::info!(
+			target: LOG_TARGET,
+			?at,
+			total,
+			valid,
+			"Re-submitted transactions from journal"
+		);
+	}
+
+	/// Rewrites the journal with the transactions currently held by the pool.
+	fn rotate_journal(&self, journal: &TransactionJournal<Block>) {
+		let ready = self.0.ready().map(|tx| (tx.hash, tx.source.source, tx.data.encode()));
+		let future = self
+			.0
+			.futures()
+			.into_iter()
+			.map(|tx| (tx.hash, tx.source.source, tx.data.encode()));
+		journal.rotate(ready.chain(future));
+	}
+}
+
 #[async_trait]
 impl<Block, Client> TransactionPool for TransactionPoolWrapper<Block, Client>
 where
@@ -75,7 +149,17 @@ where
 		source: TransactionSource,
 		xts: Vec<TransactionFor<Self>>,
 	) -> Result<Vec<Result<TxHash<Self>, Self::Error>>, Self::Error> {
-		self.0.submit_at(at, source, xts).await
+		let Some(journal) = self.journal_for(source) else {
+			return self.0.submit_at(at, source, xts).await
+		};
+
+		let results = self.0.submit_at(at, source, xts.clone()).await?;
+		for (xt, result) in xts.iter().zip(&results) {
+			if let Ok(hash) = result {
+				journal.insert(*hash, source, xt);
+			}
+		}
+		Ok(results)
 	}
 
 	async fn submit_one(
@@ -84,7 +168,13 @@ where
 		source: TransactionSource,
 		xt: TransactionFor<Self>,
 	) -> Result<TxHash<Self>, Self::Error> {
-		self.0.submit_one(at, source, xt).await
+		let Some(journal) = self.journal_for(source) else {
+			return self.0.submit_one(at, source, xt).await
+		};
+
+		let hash = self.0.submit_one(at, source, xt.clone()).await?;
+		journal.insert(hash, source, &xt);
+		Ok(hash)
 	}
 
 	async fn submit_and_watch(
@@ -93,7 +183,14 @@ where
 		source: TransactionSource,
 		xt: TransactionFor<Self>,
 	) -> Result<Pin<Box<TransactionStatusStreamFor<Self>>>, Self::Error> {
-		self.0.submit_and_watch(at, source, xt).await
+		let Some(journal) = self.journal_for(source) else {
+			return self.0.submit_and_watch(at, source, xt).await
+		};
+
+		let hash = self.0.hash_of(&xt);
+		let watcher = self.0.submit_and_watch(at, source, xt.clone()).await?;
+		journal.insert(hash, source, &xt);
+		Ok(watcher)
 	}
 
 	async fn ready_at(
@@ -123,6 +220,10 @@ where
 		self.0.status()
 	}
 
+	fn inspect(&self) -> PoolInspection<TxHash<Self>, BlockHash<Self>> {
+		self.0.inspect()
+	}
+
 	fn import_notification_stream(&self) -> ImportNotificationStream<TxHash<Self>> {
 		self.0.import_notification_stream()
 	}
@@ -161,7 +262,15 @@ where
 	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
 {
 	async fn maintain(&self, event: ChainEvent<Self::Block>) {
+		let (at, is_finalized) = (event.hash(), event.is_finalized());
 		self.0.maintain(event).await;
+
+		if let Some(journal) = &self.1 {
+			self.replay_journal(journal, at).await;
+			if is_finalized && journal.should_rotate() {
+				self.rotate_journal(journal);
+			}
+		}
 	}
 }
 
@@ -185,6 +294,12 @@ where
 		at: <Self::Block as BlockT>::Hash,
 		xt: LocalTransactionFor<Self>,
 	) -> Result<Self::Hash, Self::Error> {
-		self.0.submit_local(at, xt)
+		let Some(journal) = self.journal_for(TransactionSource::Local) else {
+			return self.0.submit_local(at, xt)
+		};
+
+		let hash = self.0.submit_local(at, xt.clone())?;
+		journal.insert(hash, TransactionSource::Local, &xt);
+		Ok(hash)
 	}
 }
@@ -25,8 +25,8 @@ use fatp_common::{
 use futures::{executor::block_on, task::Poll, FutureExt, StreamExt};
 use sc_transaction_pool::ChainApi;
 use sc_transaction_pool_api::{
-	error::Error as TxPoolError, ChainEvent, MaintainedTransactionPool, TransactionPool,
-	TransactionStatus,
+	error::Error as TxPoolError, ChainEvent, InspectedTransaction, MaintainedTransactionPool,
+	TransactionPool, TransactionStatus,
 };
 use sp_runtime::transaction_validity::InvalidTransaction;
 use std::{sync::Arc, time::Duration};
@@ -336,6 +336,50 @@ fn fatp_two_views_future_and_ready_submit_one() {
 	assert_pool_status!(header01b.hash(), &pool, 1, 0);
 }
 
+#[test]
+fn fatp_two_views_inspect_works() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let genesis = api.genesis_hash();
+	let header01a = api.push_block(1, vec![], true);
+	let header01b = api.push_block(1, vec![], true);
+
+	let event = new_best_block_event(&pool, None, header01a.hash());
+	block_on(pool.maintain(event));
+
+	let event = new_best_block_event(&pool, None, header01b.hash());
+	block_on(pool.maintain(event));
+
+	api.set_nonce(header01b.hash(), Alice.into(), 202);
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 202);
+
+	let submissions = vec![
+		pool.submit_one(genesis, SOURCE, xt0.clone()),
+		pool.submit_one(genesis, SOURCE, xt1.clone()),
+	];
+
+	block_on(futures::future::join_all(submissions));
+
+	let inspection = pool.inspect();
+	assert_eq!(inspection.views.len(), 2);
+	let view = |at| inspection.views.iter().find(|view| view.at == Some(at)).unwrap();
+	let hashes =
+		|txs: &Vec<InspectedTransaction<_>>| txs.iter().map(|tx| tx.hash).collect::<Vec<_>>();
+
+	let view01a = view(header01a.hash());
+	assert_eq!(hashes(&view01a.ready), vec![pool.hash_of(&xt0)]);
+	assert_eq!(hashes(&view01a.future), vec![pool.hash_of(&xt1)]);
+	assert!(!view01a.future[0].missing.is_empty());
+
+	let view01b = view(header01b.hash());
+	assert_eq!(hashes(&view01b.ready), vec![pool.hash_of(&xt1)]);
+	assert!(view01b.future.is_empty());
+}
+
 #[test]
 fn fatp_two_views_future_and_ready_submit_many() {
 	sp_tracing::try_init_simple();
@@ -22,10 +22,13 @@
 
 #![warn(missing_docs)]
 
+use futures::channel::mpsc;
 use jsonrpsee::RpcModule;
 use polkadot_sdk::{
+	sc_consensus_manual_seal::{consensus::timestamp::TimeTravel, EngineCommand},
 	sc_transaction_pool_api::TransactionPool,
 	sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata},
+	sp_runtime::traits::Block as BlockT,
 	*,
 };
 use revive_dev_runtime::{AccountId, Nonce, OpaqueBlock};
@@ -37,6 +40,10 @@ pub struct FullDeps<C, P> {
 	pub client: Arc<C>,
 	/// Transaction pool instance.
 	pub pool: Arc<P>,
+	/// Channel to send commands to the manual seal authorship task, if blocks are sealed manually.
+	pub command_sink: Option<mpsc::Sender<EngineCommand<<OpaqueBlock as BlockT>::Hash>>>,
+	/// Handle to move the time of the blocks sealed manually.
+	pub time_travel: TimeTravel,
 }
 
 #[docify::export]
@@ -56,11 +63,18 @@ where
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<OpaqueBlock, AccountId, Nonce>,
 	P: TransactionPool + 'static,
 {
-	use polkadot_sdk::substrate_frame_rpc_system::{System, SystemApiServer};
+	use polkadot_sdk::{
+		sc_consensus_manual_seal::rpc::{ManualSeal, ManualSealApiServer},
+		substrate_frame_rpc_system::{System, SystemApiServer},
+	};
 	let mut module = RpcModule::new(());
-	let FullDeps { client, pool } = deps;
+	let FullDeps { client, pool, command_sink, time_travel } = deps;
 
 	module.merge(System::new(client.clone(), pool.clone()).into_rpc())?;
 
+	if let Some(command_sink) = command_sink {
+		module.merge(ManualSeal::new(command_sink).with_time_travel(time_travel).into_rpc())?;
+	}
+
 	Ok(module)
 }
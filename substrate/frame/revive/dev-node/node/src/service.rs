@@ -19,6 +19,7 @@ use crate::cli::Consensus;
 use futures::FutureExt;
 use polkadot_sdk::{
 	sc_client_api::backend::Backend,
+	sc_consensus_manual_seal::consensus::timestamp::TimeTravel,
 	sc_executor::WasmExecutor,
 	sc_service::{error::Error as ServiceError, Configuration, TaskManager},
 	sc_telemetry::{Telemetry, TelemetryWorker},
@@ -163,12 +164,24 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 		);
 	}
 
+	// Blocks sealed manually can also be created, reverted and moved in time over RPC.
+	let (command_sink, commands_stream) = futures::channel::mpsc::channel(1024);
+	let time_travel = TimeTravel::default();
+
 	let rpc_extensions_builder = {
 		let client = client.clone();
 		let pool = transaction_pool.clone();
+		let command_sink =
+			matches!(consensus, Consensus::ManualSeal(_)).then(|| command_sink.clone());
+		let time_travel = time_travel.clone();
 
 		Box::new(move |_| {
-			let deps = crate::rpc::FullDeps { client: client.clone(), pool: pool.clone() };
+			let deps = crate::rpc::FullDeps {
+				client: client.clone(),
+				pool: pool.clone(),
+				command_sink: command_sink.clone(),
+				time_travel: time_travel.clone(),
+			};
 			crate::rpc::create_full(deps).map_err(Into::into)
 		})
 	};
@@ -180,7 +193,7 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 		task_manager: &mut task_manager,
 		transaction_pool: transaction_pool.clone(),
 		rpc_builder: rpc_extensions_builder,
-		backend,
+		backend: backend.clone(),
 		system_rpc_tx,
 		tx_handler_controller,
 		sync_service,
@@ -219,7 +232,7 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 			);
 		},
 		Consensus::ManualSeal(block_time) => {
-			let (mut sink, commands_stream) = futures::channel::mpsc::channel(1024);
+			let mut sink = command_sink;
 			task_manager.spawn_handle().spawn("block_authoring", None, async move {
 				loop {
 					futures_timer::Delay::new(std::time::Duration::from_millis(block_time)).await;
@@ -237,12 +250,14 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 				block_import: client.clone(),
 				env: proposer,
 				client,
+				backend: Some(backend),
 				pool: transaction_pool,
 				select_chain,
 				commands_stream: Box::pin(commands_stream),
 				consensus_data_provider: None,
-				create_inherent_data_providers: move |_, ()| async move {
-					Ok(sp_timestamp::InherentDataProvider::from_system_time())
+				create_inherent_data_providers: move |_, ()| {
+					let timestamp = time_travel.timestamp_provider();
+					async move { Ok::<_, Box<dyn std::error::Error + Send + Sync>>(timestamp?) }
 				},
 			};
 			let authorship_future = sc_consensus_manual_seal::run_manual_seal(params);
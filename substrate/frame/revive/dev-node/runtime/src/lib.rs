@@ -120,10 +120,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: alloc::borrow::Cow::Borrowed("revive-dev-runtime"),
 	impl_name: alloc::borrow::Cow::Borrowed("revive-dev-runtime"),
 	authoring_version: 1,
-	spec_version: 0,
+	spec_version: 1,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	system_version: 1,
 };
 
@@ -151,6 +151,8 @@ type TxExtension = (
 	frame_system::CheckEra<Runtime>,
 	// Checks that the nonce is valid.
 	frame_system::CheckNonce<Runtime>,
+	// Applies the EIP-7702 authorizations of Ethereum transactions.
+	pallet_revive::evm::ApplyAuthorizations<Runtime>,
 	// Checks that the weight is valid.
 	frame_system::CheckWeight<Runtime>,
 	// Ensures that the sender has enough funds to pay for the transaction
@@ -170,7 +172,11 @@ impl EthExtra for EthExtraImpl {
 	type Config = Runtime;
 	type Extension = TxExtension;
 
-	fn get_eth_extension(nonce: u32, tip: Balance) -> Self::Extension {
+	fn get_eth_extension(
+		nonce: u32,
+		tip: Balance,
+		authorization_list: pallet_revive::evm::AuthorizationList,
+	) -> Self::Extension {
 		(
 			frame_system::CheckNonZeroSender::<Runtime>::new(),
 			frame_system::CheckSpecVersion::<Runtime>::new(),
@@ -178,6 +184,7 @@ impl EthExtra for EthExtraImpl {
 			frame_system::CheckGenesis::<Runtime>::new(),
 			frame_system::CheckMortality::from(sp_runtime::generic::Era::Immortal),
 			frame_system::CheckNonce::<Runtime>::from(nonce),
+			pallet_revive::evm::ApplyAuthorizations::<Runtime>::from(authorization_list),
 			frame_system::CheckWeight::<Runtime>::new(),
 			pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
 			frame_system::WeightReclaim::<Runtime>::new(),
@@ -0,0 +1,7 @@
+-- Aggregated logs bloom of all the logs indexed in a section of
+-- `LOG_BLOOM_SECTION_SIZE` consecutive blocks.
+-- Used to skip sections that can't match an `eth_getLogs` filter.
+CREATE TABLE IF NOT EXISTS log_blooms (
+	section INTEGER NOT NULL PRIMARY KEY,
+	bloom BLOB NOT NULL
+);
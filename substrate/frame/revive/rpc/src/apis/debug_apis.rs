@@ -32,6 +32,18 @@ pub trait DebugRpc {
 		tracer_config: TracerConfig,
 	) -> RpcResult<Vec<TransactionTrace>>;
 
+	/// Returns the tracing of the execution of a specific block using its hash.
+	///
+	/// ## References
+	///
+	/// - <https://geth.ethereum.org/docs/interacting-with-geth/rpc/ns-debug#debugtraceblockbyhash>
+	#[method(name = "debug_traceBlockByHash")]
+	async fn trace_block_by_hash(
+		&self,
+		block_hash: H256,
+		tracer_config: TracerConfig,
+	) -> RpcResult<Vec<TransactionTrace>>;
+
 	/// Returns a transaction's traces by replaying it.
 	///
 	/// ## References
@@ -56,6 +68,38 @@ pub trait DebugRpc {
 		block: BlockNumberOrTagOrHash,
 		tracer_config: TracerConfig,
 	) -> RpcResult<Trace>;
+
+	/// Returns the RLP encoded block.
+	///
+	/// ## References
+	///
+	/// - <https://geth.ethereum.org/docs/interacting-with-geth/rpc/ns-debug#debuggetrawblock>
+	#[method(name = "debug_getRawBlock")]
+	async fn get_raw_block(&self, block: BlockNumberOrTagOrHash) -> RpcResult<Bytes>;
+
+	/// Returns the EIP-2718 encoded receipts of a block.
+	///
+	/// ## References
+	///
+	/// - <https://geth.ethereum.org/docs/interacting-with-geth/rpc/ns-debug#debuggetrawreceipts>
+	#[method(name = "debug_getRawReceipts")]
+	async fn get_raw_receipts(&self, block: BlockNumberOrTagOrHash) -> RpcResult<Vec<Bytes>>;
+
+	/// Returns the EIP-2718 encoded transaction.
+	///
+	/// ## References
+	///
+	/// - <https://geth.ethereum.org/docs/interacting-with-geth/rpc/ns-debug#debuggetrawtransaction>
+	#[method(name = "debug_getRawTransaction")]
+	async fn get_raw_transaction(&self, transaction_hash: H256) -> RpcResult<Option<Bytes>>;
+
+	/// Returns the Parity-style call traces matching the given filter.
+	///
+	/// ## References
+	///
+	/// - <https://openethereum.github.io/JSONRPC-trace-module#trace_filter>
+	#[method(name = "trace_filter")]
+	async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<LocalizedTrace>>;
 }
 
 pub struct DebugRpcServerImpl {
@@ -97,6 +141,15 @@ impl DebugRpcServer for DebugRpcServerImpl {
 		with_timeout(timeout, self.client.trace_block_by_number(block, config)).await
 	}
 
+	async fn trace_block_by_hash(
+		&self,
+		block_hash: H256,
+		tracer_config: TracerConfig,
+	) -> RpcResult<Vec<TransactionTrace>> {
+		let TracerConfig { config, timeout } = tracer_config;
+		with_timeout(timeout, self.client.trace_block_by_hash(block_hash, config)).await
+	}
+
 	async fn trace_transaction(
 		&self,
 		transaction_hash: H256,
@@ -115,4 +168,20 @@ impl DebugRpcServer for DebugRpcServerImpl {
 		let TracerConfig { config, timeout } = tracer_config;
 		with_timeout(timeout, self.client.trace_call(transaction, block, config)).await
 	}
+
+	async fn get_raw_block(&self, block: BlockNumberOrTagOrHash) -> RpcResult<Bytes> {
+		Ok(self.client.raw_block(block).await?)
+	}
+
+	async fn get_raw_receipts(&self, block: BlockNumberOrTagOrHash) -> RpcResult<Vec<Bytes>> {
+		Ok(self.client.raw_receipts(block).await?)
+	}
+
+	async fn get_raw_transaction(&self, transaction_hash: H256) -> RpcResult<Option<Bytes>> {
+		Ok(self.client.raw_transaction(&transaction_hash).await)
+	}
+
+	async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<LocalizedTrace>> {
+		Ok(self.client.trace_filter(filter).await?)
+	}
 }
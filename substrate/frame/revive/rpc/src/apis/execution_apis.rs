@@ -32,24 +32,40 @@ pub trait EthRpc {
 	async fn block_number(&self) -> RpcResult<U256>;
 
 	/// Executes a new message call immediately without creating a transaction on the block chain.
+	///
+	/// The call is executed against the state and block patched with the optional overrides.
 	#[method(name = "eth_call")]
 	async fn call(
 		&self,
 		transaction: GenericTransaction,
 		block: Option<BlockNumberOrTagOrHash>,
+		state_overrides: Option<StateOverride>,
+		block_overrides: Option<BlockOverrides>,
 	) -> RpcResult<Bytes>;
 
 	/// Returns the chain ID of the current network.
 	#[method(name = "eth_chainId")]
 	async fn chain_id(&self) -> RpcResult<U256>;
 
+	/// Generates an access list for a transaction.
+	#[method(name = "eth_createAccessList")]
+	async fn create_access_list(
+		&self,
+		transaction: GenericTransaction,
+		block: Option<BlockNumberOrTagOrHash>,
+	) -> RpcResult<AccessListResult>;
+
 	/// Generates and returns an estimate of how much gas is necessary to allow the transaction to
 	/// complete.
+	///
+	/// The transaction is executed against the state and block patched with the optional overrides.
 	#[method(name = "eth_estimateGas")]
 	async fn estimate_gas(
 		&self,
 		transaction: GenericTransaction,
 		block: Option<BlockNumberOrTag>,
+		state_overrides: Option<StateOverride>,
+		block_overrides: Option<BlockOverrides>,
 	) -> RpcResult<U256>;
 
 	/// Returns the current price per gas in wei.
@@ -95,6 +111,15 @@ pub trait EthRpc {
 	#[method(name = "eth_getCode")]
 	async fn get_code(&self, address: Address, block: BlockNumberOrTagOrHash) -> RpcResult<Bytes>;
 
+	/// Polling method for a filter, which returns an array of logs which occurred since last
+	/// poll.
+	#[method(name = "eth_getFilterChanges", with_extensions)]
+	async fn get_filter_changes(&self, filter_id: U256) -> RpcResult<FilterResults>;
+
+	/// Returns an array of all logs matching filter with given id.
+	#[method(name = "eth_getFilterLogs", with_extensions)]
+	async fn get_filter_logs(&self, filter_id: U256) -> RpcResult<FilterResults>;
+
 	/// Returns an array of all logs matching filter with given id.
 	#[method(name = "eth_getLogs")]
 	async fn get_logs(&self, filter: Option<Filter>) -> RpcResult<FilterResults>;
@@ -150,6 +175,19 @@ pub trait EthRpc {
 	#[method(name = "eth_maxPriorityFeePerGas")]
 	async fn max_priority_fee_per_gas(&self) -> RpcResult<U256>;
 
+	/// Creates a filter in the node, to notify when a new block arrives.
+	#[method(name = "eth_newBlockFilter", with_extensions)]
+	async fn new_block_filter(&self) -> RpcResult<U256>;
+
+	/// Install a log filter in the server, allowing for later polling. Registers client interest in
+	/// logs matching the filter, and returns an identifier.
+	#[method(name = "eth_newFilter", with_extensions)]
+	async fn new_filter(&self, filter: Filter) -> RpcResult<U256>;
+
+	/// Creates a filter in the node, to notify when new pending transactions arrive.
+	#[method(name = "eth_newPendingTransactionFilter", with_extensions)]
+	async fn new_pending_transaction_filter(&self) -> RpcResult<U256>;
+
 	/// Submits a raw transaction. For EIP-4844 transactions, the raw form must be the network form.
 	/// This means it includes the blobs, KZG commitments, and KZG proofs.
 	#[method(name = "eth_sendRawTransaction")]
@@ -159,10 +197,23 @@ pub trait EthRpc {
 	#[method(name = "eth_sendTransaction")]
 	async fn send_transaction(&self, transaction: GenericTransaction) -> RpcResult<H256>;
 
+	/// Executes a sequence of calls across one or more simulated blocks, on top of the given
+	/// block. Every call sees the state changes made by the previous ones.
+	#[method(name = "eth_simulateV1")]
+	async fn simulate_v1(
+		&self,
+		payload: SimulatePayload,
+		block: Option<BlockNumberOrTagOrHash>,
+	) -> RpcResult<Vec<SimulatedBlock>>;
+
 	/// Returns an object with data about the sync status or false.
 	#[method(name = "eth_syncing")]
 	async fn syncing(&self) -> RpcResult<SyncingStatus>;
 
+	/// Uninstalls a filter with given id.
+	#[method(name = "eth_uninstallFilter", with_extensions)]
+	async fn uninstall_filter(&self, filter_id: U256) -> RpcResult<bool>;
+
 	/// Returns true when the client is actively listening for network connections, otherwise false
 	#[method(name = "net_listening")]
 	async fn net_listening(&self) -> RpcResult<bool>;
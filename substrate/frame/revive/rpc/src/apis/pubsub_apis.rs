@@ -0,0 +1,146 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Ethereum publish/subscribe JSON-RPC methods.
+
+use crate::{client::NewBlockNotification, *};
+use futures::{Stream, StreamExt};
+use jsonrpsee::{proc_macros::rpc, PendingSubscriptionSink};
+use sc_rpc::utils::{BoundedVecDeque, PendingSubscription};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// The kind of events an `eth_subscribe` subscription listens to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum SubscriptionKind {
+	/// Headers of new best blocks.
+	NewHeads,
+	/// Logs included in new best blocks, matching an optional filter.
+	Logs,
+	/// Hashes of the transactions submitted through this server.
+	NewPendingTransactions,
+}
+
+/// An item pushed to `eth_subscribe` subscribers.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum SubscriptionItem {
+	/// A new block header.
+	Header(Box<Block>),
+	/// A log matching the subscription filter.
+	Log(Log),
+	/// The hash of a pending transaction.
+	TransactionHash(H256),
+}
+
+/// Ethereum publish/subscribe JSON-RPC apis.
+#[rpc(server, client)]
+pub trait EthPubSubRpc {
+	/// Subscribe to new block headers, logs or pending transactions.
+	///
+	/// ## References
+	///
+	/// - <https://geth.ethereum.org/docs/interacting-with-geth/rpc/pubsub>
+	#[subscription(
+		name = "eth_subscribe" => "eth_subscription",
+		unsubscribe = "eth_unsubscribe",
+		item = SubscriptionItem
+	)]
+	fn subscribe(&self, kind: SubscriptionKind, filter: Option<Filter>);
+}
+
+pub struct EthPubSubRpcServerImpl {
+	client: client::Client,
+}
+
+impl EthPubSubRpcServerImpl {
+	pub fn new(client: client::Client) -> Self {
+		Self { client }
+	}
+}
+
+/// Convert a broadcast receiver into a stream, skipping the items missed by lagging receivers.
+fn broadcast_stream<T: Clone + Send + 'static>(
+	rx: broadcast::Receiver<T>,
+) -> impl Stream<Item = T> + Send + 'static {
+	futures::stream::unfold(rx, |mut rx| async move {
+		loop {
+			match rx.recv().await {
+				Ok(item) => return Some((item, rx)),
+				Err(broadcast::error::RecvError::Lagged(skipped)) => {
+					log::debug!(target: LOG_TARGET, "Subscriber lagged behind, skipped {skipped} items");
+				},
+				Err(broadcast::error::RecvError::Closed) => return None,
+			}
+		}
+	})
+}
+
+/// Extract the logs matching the filter from a new block notification.
+fn matching_logs(notification: &NewBlockNotification, filter: &Filter) -> Vec<SubscriptionItem> {
+	notification
+		.receipts
+		.iter()
+		.flat_map(|receipt| receipt.logs.iter())
+		.filter(|log| filter.matches_log(log))
+		.map(|log| SubscriptionItem::Log(log.clone()))
+		.collect()
+}
+
+impl EthPubSubRpcServer for EthPubSubRpcServerImpl {
+	fn subscribe(
+		&self,
+		pending: PendingSubscriptionSink,
+		kind: SubscriptionKind,
+		filter: Option<Filter>,
+	) {
+		let stream = match kind {
+			SubscriptionKind::NewHeads => broadcast_stream(self.client.new_block_notifications())
+				.map(|notification: Arc<NewBlockNotification>| {
+					SubscriptionItem::Header(Box::new(notification.block.clone()))
+				})
+				.boxed(),
+			SubscriptionKind::Logs => {
+				let filter = filter.unwrap_or_default();
+				if filter.topics.as_ref().is_some_and(|topics| topics.len() > 4) {
+					let err = ErrorObjectOwned::owned::<String>(
+						ErrorCode::InvalidParams.code(),
+						"exceed max topics".to_string(),
+						None,
+					);
+					tokio::spawn(pending.reject(err));
+					return;
+				}
+
+				broadcast_stream(self.client.new_block_notifications())
+					.flat_map(move |notification| {
+						futures::stream::iter(matching_logs(&notification, &filter))
+					})
+					.boxed()
+			},
+			SubscriptionKind::NewPendingTransactions =>
+				broadcast_stream(self.client.pending_transaction_notifications())
+					.map(SubscriptionItem::TransactionHash)
+					.boxed(),
+		};
+
+		tokio::spawn(
+			PendingSubscription::from(pending).pipe_from_stream(stream, BoundedVecDeque::default()),
+		);
+	}
+}
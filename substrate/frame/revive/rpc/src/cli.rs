@@ -17,9 +17,9 @@
 //! The Ethereum JSON-RPC server.
 use crate::{
 	client::{connect, Client, SubscriptionType, SubstrateBlockNumber},
-	DebugRpcServer, DebugRpcServerImpl, EthRpcServer, EthRpcServerImpl, ReceiptExtractor,
-	ReceiptProvider, SubxtBlockInfoProvider, SystemHealthRpcServer, SystemHealthRpcServerImpl,
-	LOG_TARGET,
+	DebugRpcServer, DebugRpcServerImpl, EthPubSubRpcServer, EthPubSubRpcServerImpl, EthRpcServer,
+	EthRpcServerImpl, FilterManager, ReceiptExtractor, ReceiptProvider, SqliteReceiptIndex,
+	SubxtBlockInfoProvider, SystemHealthRpcServer, SystemHealthRpcServerImpl, LOG_TARGET,
 };
 use clap::Parser;
 use futures::{pin_mut, FutureExt};
@@ -30,6 +30,7 @@ use sc_service::{
 	start_rpc_servers, TaskManager,
 };
 use sqlx::sqlite::SqlitePoolOptions;
+use std::{sync::Arc, time::Duration};
 
 // Default port if --prometheus-port is not specified
 const DEFAULT_PROMETHEUS_PORT: u16 = 9616;
@@ -62,9 +63,32 @@ pub struct CliCommand {
 	pub database_url: String,
 
 	/// If provided, index the last n blocks
-	#[clap(long)]
+	#[clap(long, conflicts_with = "index_from_block")]
 	pub index_last_n_blocks: Option<SubstrateBlockNumber>,
 
+	/// If provided, index the blocks starting from the given block number.
+	#[clap(long)]
+	pub index_from_block: Option<SubstrateBlockNumber>,
+
+	/// The last block to index when `--index-from-block` is provided.
+	/// Defaults to the latest block.
+	#[clap(long, requires = "index_from_block")]
+	pub index_to_block: Option<SubstrateBlockNumber>,
+
+	/// The maximum number of filters (`eth_newFilter`, `eth_newBlockFilter`, ...) installed
+	/// across all clients.
+	#[clap(long, default_value_t = crate::DEFAULT_MAX_FILTERS)]
+	pub max_filters: usize,
+
+	/// The maximum number of filters a single client can install. WebSocket clients are
+	/// identified by their connection, HTTP clients by their IP address.
+	#[clap(long, default_value_t = crate::DEFAULT_MAX_FILTERS_PER_CLIENT)]
+	pub max_filters_per_client: usize,
+
+	/// The number of seconds after which a filter that has not been polled is removed.
+	#[clap(long, default_value_t = crate::DEFAULT_FILTER_TIMEOUT.as_secs())]
+	pub filter_timeout: u64,
+
 	#[allow(missing_docs)]
 	#[clap(flatten)]
 	pub shared_params: SharedParams,
@@ -76,6 +100,30 @@ pub struct CliCommand {
 	#[allow(missing_docs)]
 	#[clap(flatten)]
 	pub prometheus_params: PrometheusParams,
+
+	#[allow(missing_docs)]
+	#[clap(subcommand)]
+	pub subcommand: Option<Subcommand>,
+}
+
+/// Sub-commands of the JSON-RPC server.
+#[derive(Debug, clap::Subcommand)]
+pub enum Subcommand {
+	/// Re-derive the receipts of a range of blocks from the chain, and report the mismatches
+	/// with the receipts and logs stored in the database.
+	CheckIndex(CheckIndexCmd),
+}
+
+/// The `check-index` command.
+#[derive(Debug, clap::Args)]
+pub struct CheckIndexCmd {
+	/// The first block to check.
+	#[clap(long, default_value = "0")]
+	pub from_block: SubstrateBlockNumber,
+
+	/// The last block to check. Defaults to the latest block.
+	#[clap(long)]
+	pub to_block: Option<SubstrateBlockNumber>,
 }
 
 /// Initialize the logger
@@ -105,6 +153,7 @@ fn build_client(
 	earliest_receipt_block: Option<SubstrateBlockNumber>,
 	node_rpc_url: &str,
 	database_url: &str,
+	filter_manager: FilterManager,
 	abort_signal: Signals,
 ) -> anyhow::Result<Client> {
 	let fut = async {
@@ -129,16 +178,17 @@ fn build_client(
 			api.clone(),
 			earliest_receipt_block).await?;
 
+		let receipt_index = SqliteReceiptIndex::new(pool).await?;
 		let receipt_provider = ReceiptProvider::new(
-				pool,
+				Arc::new(receipt_index),
 				block_provider.clone(),
 				receipt_extractor.clone(),
 				keep_latest_n_blocks,
-			)
-			.await?;
+			);
 
 		let client =
-			Client::new(api, rpc_client, rpc, block_provider, receipt_provider).await?;
+			Client::new(api, rpc_client, rpc, block_provider, receipt_provider, filter_manager)
+				.await?;
 
 		Ok(client)
 	}
@@ -162,12 +212,45 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 		database_url,
 		earliest_receipt_block,
 		index_last_n_blocks,
+		index_from_block,
+		index_to_block,
+		max_filters,
+		max_filters_per_client,
+		filter_timeout,
 		shared_params,
+		subcommand,
 		..
 	} = cmd;
 
 	#[cfg(not(test))]
 	init_logger(&shared_params)?;
+
+	if let Some(Subcommand::CheckIndex(CheckIndexCmd { from_block, to_block })) = subcommand {
+		if database_url == IN_MEMORY_DB {
+			anyhow::bail!("check-index requires a persistent database, use --database-url");
+		}
+
+		let tokio_runtime = sc_cli::build_runtime()?;
+		let client = build_client(
+			tokio_runtime.handle(),
+			cache_size,
+			earliest_receipt_block,
+			&node_rpc_url,
+			&database_url,
+			FilterManager::new(
+				max_filters,
+				max_filters_per_client,
+				Duration::from_secs(filter_timeout),
+			),
+			tokio_runtime.block_on(async { Signals::capture() })?,
+		)?;
+
+		let mismatches = tokio_runtime.block_on(client.check_index(from_block, to_block))?;
+		if mismatches > 0 {
+			anyhow::bail!("Found {mismatches} mismatch(es) between the index and the chain");
+		}
+		return Ok(());
+	}
 	let is_dev = shared_params.dev;
 	let rpc_addrs: Option<Vec<sc_service::config::RpcEndpoint>> = rpc_params
 		.rpc_addr(is_dev, false, 8545)?
@@ -186,6 +269,8 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 		message_buffer_capacity: rpc_params.rpc_message_buffer_capacity_per_connection,
 		batch_config: rpc_params.rpc_batch_config()?,
 		rate_limit: rpc_params.rpc_rate_limit,
+		rate_limit_method_costs: rpc_params.rpc_rate_limit_method_costs(),
+		rate_limit_api_keys: rpc_params.rpc_rate_limit_api_keys()?,
 		rate_limit_whitelisted_ips: rpc_params.rpc_rate_limit_whitelisted_ips,
 		rate_limit_trust_proxy_headers: rpc_params.rpc_rate_limit_trust_proxy_headers,
 	};
@@ -204,6 +289,11 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 		earliest_receipt_block,
 		&node_rpc_url,
 		&database_url,
+		FilterManager::new(
+			max_filters,
+			max_filters_per_client,
+			Duration::from_secs(filter_timeout),
+		),
 		tokio_runtime.block_on(async { Signals::capture() })?,
 	)?;
 
@@ -233,6 +323,9 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 			let res = if let Some(index_last_n_blocks) = index_last_n_blocks {
 				let fut3 = client.subscribe_and_cache_blocks(index_last_n_blocks);
 				tokio::try_join!(fut1, fut2, fut3).map(|_| ())
+			} else if let Some(index_from_block) = index_from_block {
+				let fut3 = client.index_block_range(index_from_block, index_to_block);
+				tokio::try_join!(fut1, fut2, fut3).map(|_| ())
 			} else {
 				tokio::try_join!(fut1, fut2).map(|_| ())
 			};
@@ -254,11 +347,13 @@ fn rpc_module(is_dev: bool, client: Client) -> Result<RpcModule<()>, sc_service:
 		.with_accounts(if is_dev { vec![crate::Account::default()] } else { vec![] })
 		.into_rpc();
 
+	let pubsub_api = EthPubSubRpcServerImpl::new(client.clone()).into_rpc();
 	let health_api = SystemHealthRpcServerImpl::new(client.clone()).into_rpc();
 	let debug_api = DebugRpcServerImpl::new(client).into_rpc();
 
 	let mut module = RpcModule::new(());
 	module.merge(eth_api).map_err(|e| sc_service::Error::Application(e.into()))?;
+	module.merge(pubsub_api).map_err(|e| sc_service::Error::Application(e.into()))?;
 	module.merge(health_api).map_err(|e| sc_service::Error::Application(e.into()))?;
 	module.merge(debug_api).map_err(|e| sc_service::Error::Application(e.into()))?;
 	Ok(module)
@@ -25,8 +25,8 @@ use storage_api::StorageApi;
 
 use crate::{
 	subxt_client::{self, revive::calls::types::EthTransact, SrcChainConfig},
-	BlockInfoProvider, BlockTag, FeeHistoryProvider, ReceiptProvider, SubxtBlockInfoProvider,
-	TracerType, TransactionInfo, LOG_TARGET,
+	BlockInfoProvider, BlockTag, FeeHistoryProvider, FilterManager, ReceiptProvider,
+	SubxtBlockInfoProvider, TracerType, TransactionInfo, LOG_TARGET,
 };
 use jsonrpsee::{
 	core::traits::ToRpcParams,
@@ -35,9 +35,10 @@ use jsonrpsee::{
 };
 use pallet_revive::{
 	evm::{
-		decode_revert_reason, Block, BlockNumberOrTag, BlockNumberOrTagOrHash, FeeHistoryResult,
-		Filter, GenericTransaction, Log, ReceiptInfo, SyncingProgress, SyncingStatus, Trace,
-		TransactionSigned, TransactionTrace, H256, U256,
+		decode_revert_reason, AccessListResult, Block, BlockNumberOrTag, BlockNumberOrTagOrHash,
+		Bytes, CallTracerConfig, FeeHistoryResult, Filter, GenericTransaction, LocalizedTrace, Log,
+		ReceiptInfo, SyncingProgress, SyncingStatus, Trace, TraceFilter, TransactionSigned,
+		TransactionTrace, H256, U256,
 	},
 	EthTransactError,
 };
@@ -56,6 +57,7 @@ use subxt::{
 	Config, OnlineClient,
 };
 use thiserror::Error;
+use tokio::sync::broadcast;
 
 /// The substrate block type.
 pub type SubstrateBlock = subxt::blocks::Block<SrcChainConfig, OnlineClient<SrcChainConfig>>;
@@ -72,6 +74,21 @@ pub type SubstrateBlockHash = <SrcChainConfig as Config>::Hash;
 /// The runtime balance type.
 pub type Balance = u128;
 
+/// The capacity of the channels used to notify subscribers of new blocks and transactions.
+const NOTIFICATION_CHANNEL_CAPACITY: usize = 256;
+
+/// The maximum number of blocks that can be traced by a single `trace_filter` request.
+const MAX_TRACE_FILTER_BLOCK_RANGE: SubstrateBlockNumber = 1000;
+
+/// A new best block, along with its receipts.
+#[derive(Debug, Clone)]
+pub struct NewBlockNotification {
+	/// The EVM block, with transaction hashes only.
+	pub block: Block,
+	/// The receipts of the transactions included in the block.
+	pub receipts: Vec<ReceiptInfo>,
+}
+
 /// The subscription type used to listen to new blocks.
 #[derive(Debug, Clone, Copy)]
 pub enum SubscriptionType {
@@ -124,6 +141,21 @@ pub enum ClientError {
 	/// Failed to filter logs.
 	#[error("Failed to filter logs")]
 	LogFilterFailed(#[from] anyhow::Error),
+	/// The filter was not found.
+	#[error("filter not found")]
+	FilterNotFound,
+	/// The maximum number of installed filters was reached.
+	#[error("too many filters installed")]
+	FilterLimitReached,
+	/// The requested block range is too large.
+	#[error("block range exceeds the maximum of {0} blocks")]
+	BlockRangeTooLarge(SubstrateBlockNumber),
+	/// The runtime API version of the runtime doesn't support the request.
+	#[error("{0} is not supported by the runtime")]
+	UnsupportedByRuntime(&'static str),
+	/// The struct logger limit is unbounded or too large.
+	#[error("the struct logger limit must be between 1 and {0}")]
+	InvalidStructLoggerLimit(u64),
 }
 
 const REVERT_CODE: i32 = 3;
@@ -161,8 +193,13 @@ pub struct Client {
 	receipt_provider: ReceiptProvider,
 	block_provider: SubxtBlockInfoProvider,
 	fee_history_provider: FeeHistoryProvider,
+	filter_manager: FilterManager,
 	chain_id: u64,
 	max_block_weight: Weight,
+	/// Notifies subscribers of new best blocks.
+	block_notifier: broadcast::Sender<Arc<NewBlockNotification>>,
+	/// Notifies subscribers of transactions submitted through this client.
+	pending_transaction_notifier: broadcast::Sender<H256>,
 }
 
 /// Fetch the chain ID from the substrate chain.
@@ -217,6 +254,7 @@ impl Client {
 		rpc: LegacyRpcMethods<SrcChainConfig>,
 		block_provider: SubxtBlockInfoProvider,
 		receipt_provider: ReceiptProvider,
+		filter_manager: FilterManager,
 	) -> Result<Self, ClientError> {
 		let (chain_id, max_block_weight) =
 			tokio::try_join!(chain_id(&api), max_block_weight(&api))?;
@@ -228,8 +266,11 @@ impl Client {
 			receipt_provider,
 			block_provider,
 			fee_history_provider: FeeHistoryProvider::default(),
+			filter_manager,
 			chain_id,
 			max_block_weight,
+			block_notifier: broadcast::channel(NOTIFICATION_CHANNEL_CAPACITY).0,
+			pending_transaction_notifier: broadcast::channel(NOTIFICATION_CHANNEL_CAPACITY).0,
 		})
 	}
 
@@ -333,6 +374,15 @@ impl Client {
 			self.block_provider.update_latest(block, subscription_type).await;
 
 			self.fee_history_provider.update_fee_history(&evm_block, &receipts).await;
+
+			if matches!(subscription_type, SubscriptionType::BestBlocks) {
+				self.filter_manager.on_new_block(&evm_block, &receipts).await;
+
+				// Sending only fails when there are no subscribers.
+				let _ = self
+					.block_notifier
+					.send(Arc::new(NewBlockNotification { block: evm_block, receipts }));
+			}
 			Ok(())
 		})
 		.await
@@ -344,9 +394,29 @@ impl Client {
 		index_last_n_blocks: SubstrateBlockNumber,
 	) -> Result<(), ClientError> {
 		let last = self.latest_block().await.number().saturating_sub(1);
-		let range = last.saturating_sub(index_last_n_blocks)..last;
-		log::info!(target: LOG_TARGET, "🗄️ Indexing past blocks in range {range:?}");
-		self.subscribe_past_blocks(range, |block| async move {
+		self.index_block_range(last.saturating_sub(index_last_n_blocks), Some(last))
+			.await
+	}
+
+	/// Index the receipts of the blocks from `from` to `to` (inclusive).
+	///
+	/// When `to` is `None`, index up to the parent of the latest block.
+	pub async fn index_block_range(
+		&self,
+		from: SubstrateBlockNumber,
+		to: Option<SubstrateBlockNumber>,
+	) -> Result<(), ClientError> {
+		let to = match to {
+			Some(to) => to,
+			None => self.latest_block().await.number().saturating_sub(1),
+		};
+		if from > to {
+			log::warn!(target: LOG_TARGET, "🗄️ Nothing to index in range #{from}..=#{to}");
+			return Ok(());
+		}
+
+		log::info!(target: LOG_TARGET, "🗄️ Indexing past blocks in range #{from}..=#{to}");
+		self.subscribe_past_blocks(from..to, |block| async move {
 			self.receipt_provider.insert_block_receipts(&block).await?;
 			Ok(())
 		})
@@ -356,6 +426,43 @@ impl Client {
 		Ok(())
 	}
 
+	/// Re-derive the receipts of the blocks from `from` to `to` (inclusive), and compare them
+	/// with the index.
+	///
+	/// Returns the number of mismatches found, each mismatch is logged as it's found.
+	pub async fn check_index(
+		&self,
+		from: SubstrateBlockNumber,
+		to: Option<SubstrateBlockNumber>,
+	) -> Result<usize, ClientError> {
+		let to = match to {
+			Some(to) => to,
+			None => self.latest_block().await.number(),
+		};
+		if from > to {
+			log::warn!(target: LOG_TARGET, "🔎 Nothing to check in range #{from}..=#{to}");
+			return Ok(0);
+		}
+
+		log::info!(target: LOG_TARGET, "🔎 Checking indexed receipts in range #{from}..=#{to}");
+		let mismatches = std::sync::atomic::AtomicUsize::new(0);
+		let mismatches_ref = &mismatches;
+		self.subscribe_past_blocks(from..to, |block| async move {
+			let block_number = block.number();
+			let block_hash = block.hash();
+			for mismatch in self.receipt_provider.check_block_receipts(&block).await? {
+				log::warn!(target: LOG_TARGET, "🔎 Block #{block_number} ({block_hash:?}): {mismatch}");
+				mismatches_ref.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+			}
+			Ok(())
+		})
+		.await?;
+
+		let mismatches = mismatches.into_inner();
+		log::info!(target: LOG_TARGET, "🔎 Finished checking indexed receipts, {mismatches} mismatch(es) found");
+		Ok(mismatches)
+	}
+
 	/// Get the block hash for the given block number or tag.
 	pub async fn block_hash_for_tag(
 		&self,
@@ -410,6 +517,29 @@ impl Client {
 		Ok(hash)
 	}
 
+	/// Subscribe to new best blocks, along with their receipts.
+	pub fn new_block_notifications(&self) -> broadcast::Receiver<Arc<NewBlockNotification>> {
+		self.block_notifier.subscribe()
+	}
+
+	/// Subscribe to the hashes of the transactions submitted through this client.
+	pub fn pending_transaction_notifications(&self) -> broadcast::Receiver<H256> {
+		self.pending_transaction_notifier.subscribe()
+	}
+
+	/// Notify subscribers and installed filters that a transaction was submitted.
+	pub async fn notify_pending_transaction(&self, hash: H256) {
+		self.filter_manager.on_pending_transaction(hash).await;
+
+		// Sending only fails when there are no subscribers.
+		let _ = self.pending_transaction_notifier.send(hash);
+	}
+
+	/// Get the filter manager.
+	pub fn filter_manager(&self) -> &FilterManager {
+		&self.filter_manager
+	}
+
 	/// Get an EVM transaction receipt by hash.
 	pub async fn receipt(&self, tx_hash: &H256) -> Option<ReceiptInfo> {
 		self.receipt_provider.receipt_by_hash(tx_hash).await
@@ -554,10 +684,34 @@ impl Client {
 		}
 
 		let block_hash = self.block_hash_for_tag(at.into()).await?;
+		self.trace_block_by_hash(block_hash, config).await
+	}
+
+	/// Get the transaction traces for the block with the given hash.
+	pub async fn trace_block_by_hash(
+		&self,
+		block_hash: H256,
+		config: TracerType,
+	) -> Result<Vec<TransactionTrace>, ClientError> {
+		let traces = self.trace_block(block_hash, config).await?;
+		Ok(traces
+			.into_iter()
+			.map(|(_, tx_hash, trace)| TransactionTrace { tx_hash, trace })
+			.collect())
+	}
+
+	/// Trace the Ethereum transactions of the given block.
+	///
+	/// Returns the index, the hash and the trace of each transaction.
+	async fn trace_block(
+		&self,
+		block_hash: H256,
+		config: TracerType,
+	) -> Result<Vec<(usize, H256, Trace)>, ClientError> {
 		let block = self.tracing_block(block_hash).await?;
 		let parent_hash = block.header().parent_hash;
 		let runtime_api = RuntimeApi::new(self.api.runtime_api().at(parent_hash));
-		let traces = runtime_api.trace_block(block, config.clone()).await?;
+		let traces = runtime_api.trace_block(block, config).await?;
 
 		let mut hashes = self
 			.receipt_provider
@@ -566,12 +720,77 @@ impl Client {
 			.ok_or(ClientError::EthExtrinsicNotFound)?;
 
 		let traces = traces.into_iter().filter_map(|(index, trace)| {
-			Some(TransactionTrace { tx_hash: hashes.remove(&(index as usize))?, trace })
+			let index = index as usize;
+			Some((index, hashes.remove(&index)?, trace))
 		});
 
 		Ok(traces.collect())
 	}
 
+	/// Get the Parity-style call traces matching the given filter.
+	pub async fn trace_filter(
+		&self,
+		filter: TraceFilter,
+	) -> Result<Vec<LocalizedTrace>, ClientError> {
+		let latest = self.latest_block().await.number();
+		let as_block_number = |block: &Option<BlockNumberOrTag>| match block {
+			None | Some(BlockNumberOrTag::BlockTag(_)) => Ok(latest),
+			Some(BlockNumberOrTag::U256(n)) =>
+				SubstrateBlockNumber::try_from(*n).map_err(|_| ClientError::ConversionFailed),
+		};
+
+		let from = as_block_number(&filter.from_block)?;
+		let to = as_block_number(&filter.to_block)?.min(latest);
+		if to.saturating_sub(from) >= MAX_TRACE_FILTER_BLOCK_RANGE {
+			return Err(ClientError::BlockRangeTooLarge(MAX_TRACE_FILTER_BLOCK_RANGE));
+		}
+
+		let config = TracerType::CallTracer(Some(CallTracerConfig {
+			with_logs: false,
+			only_top_call: false,
+		}));
+		let mut skip = filter.after.unwrap_or(0) as usize;
+		let count = filter.count.map_or(usize::MAX, |count| count as usize);
+
+		let mut result = Vec::new();
+		for block_number in from..=to {
+			let Some(block) = self.block_by_number(block_number).await? else {
+				continue;
+			};
+
+			for (index, transaction_hash, trace) in
+				self.trace_block(block.hash(), config.clone()).await?
+			{
+				let Trace::Call(trace) = trace else {
+					continue;
+				};
+
+				for trace in trace.into_flat_traces() {
+					if !filter.matches(&trace) {
+						continue;
+					}
+					if skip > 0 {
+						skip -= 1;
+						continue;
+					}
+
+					result.push(LocalizedTrace {
+						trace,
+						block_hash: block.hash(),
+						block_number: block_number.into(),
+						transaction_hash,
+						transaction_position: index as u64,
+					});
+					if result.len() >= count {
+						return Ok(result);
+					}
+				}
+			}
+		}
+
+		Ok(result)
+	}
+
 	/// Get the transaction traces for the given transaction.
 	pub async fn trace_transaction(
 		&self,
@@ -603,6 +822,73 @@ impl Client {
 		runtime_api.trace_call(transaction, config.clone()).await
 	}
 
+	/// Create an EIP-2930 access list for the given transaction.
+	///
+	/// The transaction is traced to collect the accessed accounts and storage keys, and dry-run
+	/// again with the resulting access list. Listed accounts and storage keys are not cheaper to
+	/// access, so the gas used only differs by the cost of the larger transaction.
+	pub async fn create_access_list(
+		&self,
+		mut transaction: GenericTransaction,
+		block: BlockNumberOrTagOrHash,
+	) -> Result<AccessListResult, ClientError> {
+		let block_hash = self.block_hash_for_tag(block).await?;
+		let runtime_api = self.runtime_api(block_hash);
+
+		let access_list = match runtime_api
+			.trace_call(transaction.clone(), TracerType::AccessListTracer)
+			.await?
+		{
+			Trace::AccessList(access_list) => access_list,
+			trace => {
+				log::debug!(target: LOG_TARGET, "Unexpected access list trace: {trace:?}");
+				return Err(ClientError::ConversionFailed);
+			},
+		};
+
+		transaction.access_list = Some(access_list.clone());
+		let result = match runtime_api.dry_run(transaction).await {
+			Ok(dry_run) => AccessListResult { access_list, error: None, gas_used: dry_run.eth_gas },
+			Err(err) => {
+				let err = ErrorObjectOwned::from(err);
+				AccessListResult {
+					access_list,
+					error: Some(err.message().to_string()),
+					gas_used: U256::zero(),
+				}
+			},
+		};
+
+		Ok(result)
+	}
+
+	/// Get the RLP encoded Ethereum transaction with the given hash.
+	pub async fn raw_transaction(&self, transaction_hash: &H256) -> Option<Bytes> {
+		let signed_tx = self.receipt_provider.signed_tx_by_hash(transaction_hash).await?;
+		Some(signed_tx.signed_payload().into())
+	}
+
+	/// Get the EIP-2718 encoded receipts of the given block.
+	pub async fn raw_receipts(
+		&self,
+		block: BlockNumberOrTagOrHash,
+	) -> Result<Vec<Bytes>, ClientError> {
+		let block_hash = self.block_hash_for_tag(block).await?;
+		let block = self.block_by_hash(&block_hash).await?.ok_or(ClientError::BlockNotFound)?;
+		let receipts = self.receipt_provider.receipts_from_block(&block).await?;
+		Ok(receipts
+			.into_iter()
+			.map(|(_, receipt)| receipt.receipt_payload().into())
+			.collect())
+	}
+
+	/// Get the RLP encoded EVM block for the given block.
+	pub async fn raw_block(&self, block: BlockNumberOrTagOrHash) -> Result<Bytes, ClientError> {
+		let block_hash = self.block_hash_for_tag(block).await?;
+		let block = self.block_by_hash(&block_hash).await?.ok_or(ClientError::BlockNotFound)?;
+		Ok(self.evm_block(block, true).await.block_payload().into())
+	}
+
 	/// Get the EVM block for the given Substrate block.
 	pub async fn evm_block(
 		&self,
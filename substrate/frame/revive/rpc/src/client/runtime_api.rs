@@ -20,12 +20,101 @@ use crate::{
 	subxt_client::{self, SrcChainConfig},
 	ClientError, LOG_TARGET,
 };
+use codec::{Compact, Decode, Encode};
 use pallet_revive::{
-	evm::{GenericTransaction, Trace, H160, U256},
-	EthTransactInfo,
+	evm::{
+		DryRunOverrides, GenericTransaction, SimulatePayload, SimulatedBlock, Trace, H160,
+		MAX_STRUCT_LOGGER_LIMIT, U256,
+	},
+	EthTransactError, EthTransactInfo,
 };
 use subxt::OnlineClient;
 
+/// The first version of `ReviveApi` whose `eth_transact` takes the [`DryRunOverrides`].
+const OVERRIDES_API_VERSION: u32 = 2;
+
+/// The first version of `ReviveApi` providing `eth_simulate`.
+const SIMULATE_API_VERSION: u32 = 3;
+
+/// The first version of `ReviveApi` whose [`GenericTransaction`] ends with the authorization list.
+const AUTHORIZATION_LIST_API_VERSION: u32 = 4;
+
+/// The first version of `ReviveApi` whose tracing APIs accept the given tracer.
+fn tracer_api_version(tracer_type: &crate::TracerType) -> u32 {
+	match tracer_type {
+		crate::TracerType::AccessListTracer | crate::TracerType::StructLogger(_) => 5,
+		_ => 1,
+	}
+}
+
+/// The beginning of `sp_version::RuntimeVersion`, up to the versions of the runtime APIs.
+#[derive(Decode)]
+struct RuntimeApiVersions {
+	_spec_name: String,
+	_impl_name: String,
+	_authoring_version: u32,
+	_spec_version: u32,
+	_impl_version: u32,
+	apis: Vec<([u8; 8], u32)>,
+}
+
+/// Encodes the transaction as expected by a `ReviveApi` of the given version.
+///
+/// Runtimes predating the authorization list don't know about the trailing field, it is stripped
+/// from the encoding.
+fn encode_tx(tx: &GenericTransaction, api_version: u32) -> Result<Vec<u8>, ClientError> {
+	let mut encoded = tx.encode();
+	if api_version < AUTHORIZATION_LIST_API_VERSION {
+		if !tx.authorization_list.is_empty() {
+			return Err(ClientError::UnsupportedByRuntime("authorizationList"));
+		}
+		encoded.truncate(encoded.len() - tx.authorization_list.encoded_size());
+	}
+	Ok(encoded)
+}
+
+/// Encodes the parameters of `eth_transact` as expected by a `ReviveApi` of the given version.
+///
+/// Runtimes predating the overrides only take the transaction, which is fine as long as nothing
+/// is overridden.
+fn encode_dry_run_params(
+	tx: &GenericTransaction,
+	overrides: &DryRunOverrides,
+	api_version: u32,
+) -> Result<Vec<u8>, ClientError> {
+	let mut encoded = encode_tx(tx, api_version)?;
+	if api_version >= OVERRIDES_API_VERSION {
+		overrides.encode_to(&mut encoded);
+	} else if !overrides.is_empty() {
+		return Err(ClientError::UnsupportedByRuntime("State and block overrides"));
+	}
+	Ok(encoded)
+}
+
+/// Encodes the payload as expected by a `ReviveApi` of the given version, see [`encode_tx`].
+fn encode_simulate_payload(
+	payload: &SimulatePayload,
+	api_version: u32,
+) -> Result<Vec<u8>, ClientError> {
+	if api_version >= AUTHORIZATION_LIST_API_VERSION {
+		return Ok(payload.encode());
+	}
+
+	// Same layout as the derived encoding, with the calls encoded by `encode_tx`.
+	let mut encoded = Compact(payload.block_state_calls.len() as u32).encode();
+	for block in &payload.block_state_calls {
+		block.block_overrides.encode_to(&mut encoded);
+		block.state_overrides.encode_to(&mut encoded);
+		Compact(block.calls.len() as u32).encode_to(&mut encoded);
+		for call in &block.calls {
+			encoded.extend(encode_tx(call, api_version)?);
+		}
+	}
+	payload.trace_transfers.encode_to(&mut encoded);
+	payload.validation.encode_to(&mut encoded);
+	Ok(encoded)
+}
+
 /// A Wrapper around subxt Runtime API
 #[derive(Clone)]
 pub struct RuntimeApi(subxt::runtime_api::RuntimeApi<SrcChainConfig, OnlineClient<SrcChainConfig>>);
@@ -63,15 +152,72 @@ impl RuntimeApi {
 		&self,
 		tx: GenericTransaction,
 	) -> Result<EthTransactInfo<Balance>, ClientError> {
-		let payload = subxt_client::apis().revive_api().eth_transact(tx.into());
-		let result = self.0.call(payload).await?;
-		match result {
-			Err(err) => {
-				log::debug!(target: LOG_TARGET, "Dry run failed {err:?}");
-				Err(ClientError::TransactError(err.0))
-			},
-			Ok(result) => Ok(result.0),
+		self.dry_run_with_overrides(tx, DryRunOverrides::default()).await
+	}
+
+	/// Dry run a transaction against a state and block patched with the given overrides.
+	pub async fn dry_run_with_overrides(
+		&self,
+		tx: GenericTransaction,
+		overrides: DryRunOverrides,
+	) -> Result<EthTransactInfo<Balance>, ClientError> {
+		// The bundled metadata predates the overrides parameter, hence the raw call.
+		let api_version = self.revive_api_version().await?;
+		let params = encode_dry_run_params(&tx, &overrides, api_version)?;
+		let result: Result<EthTransactInfo<Balance>, EthTransactError> =
+			self.0.call_raw("ReviveApi_eth_transact", Some(&params)).await?;
+		result.map_err(|err| {
+			log::debug!(target: LOG_TARGET, "Dry run failed {err:?}");
+			ClientError::TransactError(err)
+		})
+	}
+
+	/// Get the version of `ReviveApi` implemented by the runtime, `0` if it doesn't implement it.
+	pub async fn revive_api_version(&self) -> Result<u32, ClientError> {
+		let id = sp_crypto_hashing::blake2_64(b"ReviveApi");
+		let version: RuntimeApiVersions = self.0.call_raw("Core_version", None).await?;
+		Ok(version
+			.apis
+			.into_iter()
+			.find_map(|(api_id, version)| (api_id == id).then_some(version))
+			.unwrap_or_default())
+	}
+
+	/// Returns an error if the tracing APIs of the runtime don't support the tracer, or if the
+	/// struct logger is requested without a bounded limit.
+	async fn ensure_tracer_supported(
+		&self,
+		tracer_type: &crate::TracerType,
+	) -> Result<(), ClientError> {
+		if let crate::TracerType::StructLogger(Some(config)) = tracer_type {
+			if config.limit == 0 || config.limit > MAX_STRUCT_LOGGER_LIMIT {
+				return Err(ClientError::InvalidStructLoggerLimit(MAX_STRUCT_LOGGER_LIMIT));
+			}
+		}
+		if self.revive_api_version().await? < tracer_api_version(tracer_type) {
+			return Err(ClientError::UnsupportedByRuntime("The requested tracer"));
 		}
+		Ok(())
+	}
+
+	/// Simulate a sequence of calls across one or more blocks.
+	pub async fn simulate(
+		&self,
+		payload: SimulatePayload,
+	) -> Result<Vec<SimulatedBlock>, ClientError> {
+		let api_version = self.revive_api_version().await?;
+		if api_version < SIMULATE_API_VERSION {
+			return Err(ClientError::UnsupportedByRuntime("eth_simulateV1"));
+		}
+
+		// The bundled metadata predates this runtime API, hence the raw call.
+		let params = encode_simulate_payload(&payload, api_version)?;
+		let result: Result<Vec<SimulatedBlock>, EthTransactError> =
+			self.0.call_raw("ReviveApi_eth_simulate", Some(&params)).await?;
+		result.map_err(|err| {
+			log::debug!(target: LOG_TARGET, "Simulation failed {err:?}");
+			ClientError::TransactError(err)
+		})
 	}
 
 	/// Get the nonce of the given address.
@@ -113,6 +259,7 @@ impl RuntimeApi {
 		transaction_index: u32,
 		tracer_type: crate::TracerType,
 	) -> Result<Trace, ClientError> {
+		self.ensure_tracer_supported(&tracer_type).await?;
 		let payload = subxt_client::apis()
 			.revive_api()
 			.trace_tx(block.into(), transaction_index, tracer_type.into())
@@ -131,6 +278,7 @@ impl RuntimeApi {
 		>,
 		tracer_type: crate::TracerType,
 	) -> Result<Vec<(u32, Trace)>, ClientError> {
+		self.ensure_tracer_supported(&tracer_type).await?;
 		let payload = subxt_client::apis()
 			.revive_api()
 			.trace_block(block.into(), tracer_type.into())
@@ -146,6 +294,7 @@ impl RuntimeApi {
 		transaction: GenericTransaction,
 		tracer_type: crate::TracerType,
 	) -> Result<Trace, ClientError> {
+		self.ensure_tracer_supported(&tracer_type).await?;
 		let payload = subxt_client::apis()
 			.revive_api()
 			.trace_call(transaction.into(), tracer_type.into())
@@ -155,3 +304,65 @@ impl RuntimeApi {
 		Ok(trace.0)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pallet_revive::evm::{AuthorizationListEntry, BlockOverrides, SimulateBlock};
+
+	#[test]
+	fn authorization_list_is_stripped_for_older_runtimes() {
+		let tx = GenericTransaction { value: Some(1.into()), ..Default::default() };
+		let encoded = tx.encode();
+		assert_eq!(encode_tx(&tx, AUTHORIZATION_LIST_API_VERSION).unwrap(), encoded);
+		assert_eq!(encode_tx(&tx, 2).unwrap(), encoded[..encoded.len() - 1]);
+
+		let payload = SimulatePayload {
+			block_state_calls: vec![SimulateBlock {
+				calls: vec![tx.clone(); 2],
+				..Default::default()
+			}],
+			..Default::default()
+		};
+		let encoded = payload.encode();
+		assert_eq!(
+			encode_simulate_payload(&payload, AUTHORIZATION_LIST_API_VERSION).unwrap(),
+			encoded
+		);
+		assert_eq!(
+			encode_simulate_payload(&payload, SIMULATE_API_VERSION).unwrap().len(),
+			encoded.len() - 2
+		);
+
+		let tx = GenericTransaction {
+			authorization_list: vec![AuthorizationListEntry::default()],
+			..Default::default()
+		};
+		assert!(matches!(encode_tx(&tx, 2), Err(ClientError::UnsupportedByRuntime(_))));
+	}
+
+	#[test]
+	fn overrides_are_only_sent_to_runtimes_supporting_them() {
+		let tx = GenericTransaction { value: Some(1.into()), ..Default::default() };
+		let overrides = DryRunOverrides::default();
+		let encoded = encode_tx(&tx, 1).unwrap();
+		assert_eq!(encode_dry_run_params(&tx, &overrides, 1).unwrap(), encoded);
+		assert_eq!(
+			encode_dry_run_params(&tx, &overrides, OVERRIDES_API_VERSION).unwrap(),
+			[encoded, overrides.encode()].concat()
+		);
+		assert_eq!(
+			encode_dry_run_params(&tx, &overrides, AUTHORIZATION_LIST_API_VERSION).unwrap(),
+			(&tx, &overrides).encode()
+		);
+
+		let overrides = DryRunOverrides {
+			block: BlockOverrides { number: Some(1.into()), ..Default::default() },
+			..Default::default()
+		};
+		assert!(matches!(
+			encode_dry_run_params(&tx, &overrides, 1),
+			Err(ClientError::UnsupportedByRuntime(_))
+		));
+	}
+}
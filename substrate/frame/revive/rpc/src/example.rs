@@ -127,6 +127,8 @@ impl<Client: EthRpcClient + Send + Sync> TransactionBuilder<Client> {
 					..Default::default()
 				},
 				None,
+				None,
+				None,
 			)
 			.await
 			.with_context(|| "eth_call failed")?;
@@ -156,6 +158,8 @@ impl<Client: EthRpcClient + Send + Sync> TransactionBuilder<Client> {
 					..Default::default()
 				},
 				None,
+				None,
+				None,
 			)
 			.await
 			.with_context(|| "Failed to fetch gas estimate")?;
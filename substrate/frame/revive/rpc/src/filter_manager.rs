@@ -0,0 +1,406 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Server-side filters, polled with `eth_getFilterChanges`.
+use crate::ClientError;
+use jsonrpsee::ConnectionId;
+use pallet_revive::evm::{Block, BlockNumberOrTag, Filter, FilterResults, Log, ReceiptInfo};
+use sc_rpc_api::ConnectionClosed;
+use sp_core::{H256, U256};
+use std::{
+	collections::{HashMap, HashSet, VecDeque},
+	net::IpAddr,
+	sync::Arc,
+	time::{Duration, Instant},
+};
+use tokio::sync::Mutex;
+
+/// The maximum number of changes buffered for a filter between two polls.
+/// The oldest changes are dropped when the limit is reached.
+const MAX_PENDING_CHANGES: usize = 10_000;
+
+/// The default maximum number of filters installed across all clients.
+pub const DEFAULT_MAX_FILTERS: usize = 10_000;
+
+/// The default maximum number of filters a single client can install.
+pub const DEFAULT_MAX_FILTERS_PER_CLIENT: usize = 64;
+
+/// The default duration after which a filter that has not been polled is removed.
+pub const DEFAULT_FILTER_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// The kind of an installed filter, along with the changes accumulated since the last poll.
+#[derive(Debug)]
+enum FilterKind {
+	/// Hashes of new best blocks.
+	Block(VecDeque<H256>),
+	/// Hashes of the transactions submitted through this server.
+	PendingTransaction(VecDeque<H256>),
+	/// Logs of new best blocks matching the filter.
+	Logs { filter: Filter, logs: VecDeque<Log> },
+}
+
+/// The client that installed a filter, only this client can poll or uninstall it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FilterOwner {
+	/// A connection, such as a WebSocket connection.
+	Connection(ConnectionId),
+	/// An HTTP client. Each HTTP request is received on its own connection, so HTTP clients are
+	/// identified by their IP address.
+	Ip(IpAddr),
+}
+
+/// A filter installed by a client.
+#[derive(Debug)]
+struct InstalledFilter {
+	/// The kind of filter and its pending changes.
+	kind: FilterKind,
+	/// The client that installed the filter.
+	owner: FilterOwner,
+	/// The last time the filter was polled.
+	last_poll: Instant,
+}
+
+/// Push an item to the pending changes, dropping the oldest one if the buffer is full.
+fn push_bounded<T>(changes: &mut VecDeque<T>, item: T) {
+	if changes.len() >= MAX_PENDING_CHANGES {
+		changes.pop_front();
+	}
+	changes.push_back(item);
+}
+
+/// Returns `true` if the log is within the block criteria of the filter.
+fn in_block_range(filter: &Filter, log: &Log) -> bool {
+	let after_from = match filter.from_block {
+		Some(BlockNumberOrTag::U256(from)) => log.block_number >= from,
+		_ => true,
+	};
+	let before_to = match filter.to_block {
+		Some(BlockNumberOrTag::U256(to)) => log.block_number <= to,
+		_ => true,
+	};
+	let in_block = filter.block_hash.map_or(true, |hash| hash == log.block_hash);
+	after_from && before_to && in_block
+}
+
+/// Manages the filters installed with `eth_newFilter`, `eth_newBlockFilter` and
+/// `eth_newPendingTransactionFilter`.
+#[derive(Clone)]
+pub struct FilterManager {
+	/// The installed filters, by id.
+	filters: Arc<Mutex<HashMap<U256, InstalledFilter>>>,
+	/// The connections whose filters are removed once they are closed.
+	watched_connections: Arc<Mutex<HashSet<ConnectionId>>>,
+	/// The maximum number of filters installed across all clients.
+	max_filters: usize,
+	/// The maximum number of filters a single client can install.
+	max_filters_per_client: usize,
+	/// The duration after which a filter that has not been polled is removed.
+	timeout: Duration,
+}
+
+impl Default for FilterManager {
+	fn default() -> Self {
+		Self::new(DEFAULT_MAX_FILTERS, DEFAULT_MAX_FILTERS_PER_CLIENT, DEFAULT_FILTER_TIMEOUT)
+	}
+}
+
+impl FilterManager {
+	/// Create a new [`FilterManager`].
+	pub fn new(max_filters: usize, max_filters_per_client: usize, timeout: Duration) -> Self {
+		Self {
+			filters: Default::default(),
+			watched_connections: Default::default(),
+			max_filters,
+			max_filters_per_client,
+			timeout,
+		}
+	}
+
+	/// Install a filter notifying of new best blocks.
+	pub async fn install_block_filter(&self, owner: FilterOwner) -> Result<U256, ClientError> {
+		self.install(owner, FilterKind::Block(VecDeque::new())).await
+	}
+
+	/// Install a filter notifying of the transactions submitted through this server.
+	pub async fn install_pending_transaction_filter(
+		&self,
+		owner: FilterOwner,
+	) -> Result<U256, ClientError> {
+		self.install(owner, FilterKind::PendingTransaction(VecDeque::new())).await
+	}
+
+	/// Install a filter notifying of new logs matching the given filter.
+	pub async fn install_log_filter(
+		&self,
+		owner: FilterOwner,
+		filter: Filter,
+	) -> Result<U256, ClientError> {
+		if filter.topics.as_ref().is_some_and(|topics| topics.len() > 4) {
+			return Err(ClientError::LogFilterFailed(anyhow::anyhow!("exceed max topics")));
+		}
+
+		self.install(owner, FilterKind::Logs { filter, logs: VecDeque::new() }).await
+	}
+
+	async fn install(&self, owner: FilterOwner, kind: FilterKind) -> Result<U256, ClientError> {
+		let mut filters = self.filters.lock().await;
+		self.remove_expired(&mut filters);
+
+		let installed_by_owner = filters.values().filter(|filter| filter.owner == owner).count();
+		if filters.len() >= self.max_filters || installed_by_owner >= self.max_filters_per_client {
+			return Err(ClientError::FilterLimitReached);
+		}
+
+		let id = loop {
+			let id = U256::from(rand::random::<u128>());
+			if !filters.contains_key(&id) {
+				break id;
+			}
+		};
+
+		filters.insert(id, InstalledFilter { kind, owner, last_poll: Instant::now() });
+		log::debug!(target: crate::LOG_TARGET, "Installed filter {id:#x} for {owner:?}");
+		Ok(id)
+	}
+
+	/// Remove the filters installed by the connection once it is closed.
+	pub async fn remove_on_close(&self, connection_id: ConnectionId, closed: ConnectionClosed) {
+		if !self.watched_connections.lock().await.insert(connection_id) {
+			return;
+		}
+
+		let this = self.clone();
+		tokio::spawn(async move {
+			closed.closed().await;
+
+			let owner = FilterOwner::Connection(connection_id);
+			this.filters.lock().await.retain(|_, filter| filter.owner != owner);
+			this.watched_connections.lock().await.remove(&connection_id);
+			log::debug!(target: crate::LOG_TARGET, "Removed the filters of closed {owner:?}");
+		});
+	}
+
+	/// Uninstall the filter with the given id, returns `true` if the filter was installed by the
+	/// given client.
+	pub async fn uninstall(&self, owner: FilterOwner, id: U256) -> bool {
+		let mut filters = self.filters.lock().await;
+		if filters.get(&id).map_or(true, |filter| filter.owner != owner) {
+			return false;
+		}
+		filters.remove(&id).is_some()
+	}
+
+	/// Get the filter with the given id installed by the given client, marking it as polled.
+	fn poll<'a>(
+		filters: &'a mut HashMap<U256, InstalledFilter>,
+		owner: FilterOwner,
+		id: U256,
+	) -> Result<&'a mut InstalledFilter, ClientError> {
+		let filter = filters
+			.get_mut(&id)
+			.filter(|filter| filter.owner == owner)
+			.ok_or(ClientError::FilterNotFound)?;
+		filter.last_poll = Instant::now();
+		Ok(filter)
+	}
+
+	/// Return the changes of the filter since it was last polled.
+	pub async fn changes(
+		&self,
+		owner: FilterOwner,
+		id: U256,
+	) -> Result<FilterResults, ClientError> {
+		let mut filters = self.filters.lock().await;
+		let filter = Self::poll(&mut filters, owner, id)?;
+
+		let changes = match &mut filter.kind {
+			FilterKind::Block(hashes) | FilterKind::PendingTransaction(hashes) =>
+				FilterResults::Hashes(core::mem::take(hashes).into()),
+			FilterKind::Logs { logs, .. } => FilterResults::Logs(core::mem::take(logs).into()),
+		};
+
+		Ok(changes)
+	}
+
+	/// Return the log filter installed with the given id.
+	pub async fn log_filter(&self, owner: FilterOwner, id: U256) -> Result<Filter, ClientError> {
+		let mut filters = self.filters.lock().await;
+		let filter = Self::poll(&mut filters, owner, id)?;
+
+		match &filter.kind {
+			FilterKind::Logs { filter, .. } => Ok(filter.clone()),
+			_ => Err(ClientError::FilterNotFound),
+		}
+	}
+
+	/// Update the installed filters with a new best block and its receipts.
+	pub async fn on_new_block(&self, block: &Block, receipts: &[ReceiptInfo]) {
+		let mut filters = self.filters.lock().await;
+		self.remove_expired(&mut filters);
+
+		for installed in filters.values_mut() {
+			match &mut installed.kind {
+				FilterKind::Block(hashes) => push_bounded(hashes, block.hash),
+				FilterKind::PendingTransaction(_) => {},
+				FilterKind::Logs { filter, logs } => {
+					let matching = receipts
+						.iter()
+						.flat_map(|receipt| receipt.logs.iter())
+						.filter(|log| in_block_range(filter, log) && filter.matches_log(log));
+					for log in matching {
+						push_bounded(logs, log.clone());
+					}
+				},
+			}
+		}
+	}
+
+	/// Update the installed filters with a newly submitted transaction.
+	pub async fn on_pending_transaction(&self, hash: H256) {
+		let mut filters = self.filters.lock().await;
+		for installed in filters.values_mut() {
+			if let FilterKind::PendingTransaction(hashes) = &mut installed.kind {
+				push_bounded(hashes, hash);
+			}
+		}
+	}
+
+	/// Remove the filters that have not been polled within the timeout.
+	fn remove_expired(&self, filters: &mut HashMap<U256, InstalledFilter>) {
+		filters.retain(|id, filter| {
+			let keep = filter.last_poll.elapsed() < self.timeout;
+			if !keep {
+				log::debug!(target: crate::LOG_TARGET, "Filter {id:#x} expired");
+			}
+			keep
+		});
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pallet_revive::evm::FilterTopic;
+	use pretty_assertions::assert_eq;
+	use sp_core::H160;
+
+	const OWNER: FilterOwner = FilterOwner::Connection(ConnectionId(0));
+
+	#[tokio::test]
+	async fn block_filter_works() {
+		let manager = FilterManager::default();
+		let id = manager.install_block_filter(OWNER).await.unwrap();
+
+		let block = Block { hash: H256::from([1u8; 32]), ..Default::default() };
+		manager.on_new_block(&block, &[]).await;
+
+		let changes = manager.changes(OWNER, id).await.unwrap();
+		assert_eq!(changes, FilterResults::Hashes(vec![block.hash]));
+
+		let changes = manager.changes(OWNER, id).await.unwrap();
+		assert_eq!(changes, FilterResults::Hashes(vec![]));
+
+		assert!(manager.uninstall(OWNER, id).await);
+		assert!(matches!(manager.changes(OWNER, id).await, Err(ClientError::FilterNotFound)));
+	}
+
+	#[tokio::test]
+	async fn log_filter_works() {
+		let manager = FilterManager::default();
+		let topic = H256::from([1u8; 32]);
+		let filter = Filter {
+			address: Some(H160::from([1u8; 20]).into()),
+			topics: Some(vec![FilterTopic::Single(topic)]),
+			..Default::default()
+		};
+		let id = manager.install_log_filter(OWNER, filter.clone()).await.unwrap();
+
+		let matching =
+			Log { address: H160::from([1u8; 20]), topics: vec![topic], ..Default::default() };
+		let other =
+			Log { address: H160::from([2u8; 20]), topics: vec![topic], ..Default::default() };
+		let receipts =
+			vec![ReceiptInfo { logs: vec![matching.clone(), other], ..Default::default() }];
+		manager.on_new_block(&Block::default(), &receipts).await;
+
+		let changes = manager.changes(OWNER, id).await.unwrap();
+		assert_eq!(changes, FilterResults::Logs(vec![matching]));
+		assert_eq!(manager.log_filter(OWNER, id).await.unwrap(), filter);
+	}
+
+	#[tokio::test]
+	async fn filters_are_only_accessible_to_their_owner() {
+		let manager = FilterManager::default();
+		let other = FilterOwner::Ip([127, 0, 0, 1].into());
+		let id = manager.install_block_filter(OWNER).await.unwrap();
+
+		assert!(matches!(manager.changes(other, id).await, Err(ClientError::FilterNotFound)));
+		assert!(!manager.uninstall(other, id).await);
+		assert!(manager.changes(OWNER, id).await.is_ok());
+	}
+
+	#[tokio::test]
+	async fn limits_work() {
+		let manager = FilterManager::new(3, 2, DEFAULT_FILTER_TIMEOUT);
+		let other = FilterOwner::Ip([127, 0, 0, 1].into());
+		manager.install_block_filter(OWNER).await.unwrap();
+		manager.install_pending_transaction_filter(OWNER).await.unwrap();
+
+		assert!(matches!(
+			manager.install_block_filter(OWNER).await,
+			Err(ClientError::FilterLimitReached)
+		));
+		assert!(manager.install_block_filter(other).await.is_ok());
+		assert!(matches!(
+			manager.install_block_filter(other).await,
+			Err(ClientError::FilterLimitReached)
+		));
+	}
+
+	#[tokio::test]
+	async fn expired_filters_are_removed() {
+		let manager = FilterManager::new(DEFAULT_MAX_FILTERS, 1, Duration::ZERO);
+		let id = manager.install_block_filter(OWNER).await.unwrap();
+
+		manager.on_new_block(&Block::default(), &[]).await;
+		assert!(matches!(manager.changes(OWNER, id).await, Err(ClientError::FilterNotFound)));
+	}
+
+	#[tokio::test]
+	async fn filters_are_removed_when_the_connection_is_closed() {
+		let manager = FilterManager::default();
+		let other = FilterOwner::Connection(ConnectionId(1));
+		let id = manager.install_block_filter(OWNER).await.unwrap();
+		let other_id = manager.install_block_filter(other).await.unwrap();
+
+		let (close, closed) = tokio::sync::oneshot::channel::<()>();
+		manager
+			.remove_on_close(
+				ConnectionId(0),
+				ConnectionClosed::new(async move {
+					closed.await.ok();
+				}),
+			)
+			.await;
+		close.send(()).unwrap();
+
+		while manager.watched_connections.lock().await.contains(&ConnectionId(0)) {
+			tokio::task::yield_now().await;
+		}
+		assert!(matches!(manager.changes(OWNER, id).await, Err(ClientError::FilterNotFound)));
+		assert!(manager.changes(other, other_id).await.is_ok());
+	}
+}
@@ -21,8 +21,10 @@ use client::ClientError;
 use jsonrpsee::{
 	core::{async_trait, RpcResult},
 	types::{ErrorCode, ErrorObjectOwned},
+	ConnectionId, Extensions,
 };
 use pallet_revive::evm::*;
+use sc_rpc_api::{ClientIp, ConnectionClosed};
 use sp_arithmetic::Permill;
 use sp_core::{keccak_256, H160, H256, U256};
 use thiserror::Error;
@@ -44,6 +46,9 @@ pub use receipt_provider::*;
 mod fee_history_provider;
 pub use fee_history_provider::*;
 
+mod filter_manager;
+pub use filter_manager::*;
+
 mod receipt_extractor;
 pub use receipt_extractor::*;
 
@@ -72,6 +77,22 @@ impl EthRpcServerImpl {
 		self.accounts = accounts;
 		self
 	}
+
+	/// Get the owner of the filter installed by the request.
+	///
+	/// The filters installed over a WebSocket connection are removed when it is closed.
+	async fn filter_installer(&self, ext: &Extensions) -> Result<FilterOwner, EthRpcError> {
+		let owner = filter_owner(ext)?;
+		if let (FilterOwner::Connection(connection_id), Some(closed)) =
+			(owner, ext.get::<ConnectionClosed>())
+		{
+			self.client
+				.filter_manager()
+				.remove_on_close(connection_id, closed.clone())
+				.await;
+		}
+		Ok(owner)
+	}
 }
 
 /// The error type for the EVM RPC server.
@@ -98,6 +119,12 @@ pub enum EthRpcError {
 	/// Received an invalid transaction
 	#[error("Invalid transaction {0:?}")]
 	TransactionTypeNotSupported(Byte),
+	/// The `eth_simulateV1` payload exceeds the limits of [`SimulatePayload`].
+	#[error("Invalid simulation: {0}")]
+	SimulationLimitExceeded(String),
+	/// The request was not received on a connection.
+	#[error("Connection id not found")]
+	ConnectionIdNotFound,
 }
 
 // TODO use https://eips.ethereum.org/EIPS/eip-1474#error-codes
@@ -105,11 +132,29 @@ impl From<EthRpcError> for ErrorObjectOwned {
 	fn from(value: EthRpcError) -> Self {
 		match value {
 			EthRpcError::ClientError(err) => Self::from(err),
+			EthRpcError::ConnectionIdNotFound =>
+				Self::owned::<String>(ErrorCode::InternalError.code(), value.to_string(), None),
 			_ => Self::owned::<String>(ErrorCode::InvalidRequest.code(), value.to_string(), None),
 		}
 	}
 }
 
+/// Get the id of the connection the request was received on.
+fn connection_id(ext: &Extensions) -> Result<ConnectionId, EthRpcError> {
+	ext.get::<ConnectionId>().copied().ok_or(EthRpcError::ConnectionIdNotFound)
+}
+
+/// Get the client that sent the request, which owns the filters it installs.
+///
+/// Each HTTP request is received on its own connection, so HTTP clients are identified by their
+/// IP address instead.
+fn filter_owner(ext: &Extensions) -> Result<FilterOwner, EthRpcError> {
+	match (ext.get::<ConnectionClosed>(), ext.get::<ClientIp>()) {
+		(None, Some(ClientIp(ip))) => Ok(FilterOwner::Ip(*ip)),
+		_ => connection_id(ext).map(FilterOwner::Connection),
+	}
+}
+
 #[async_trait]
 impl EthRpcServer for EthRpcServerImpl {
 	async fn net_version(&self) -> RpcResult<String> {
@@ -143,10 +188,16 @@ impl EthRpcServer for EthRpcServerImpl {
 		&self,
 		transaction: GenericTransaction,
 		block: Option<BlockNumberOrTag>,
+		state_overrides: Option<StateOverride>,
+		block_overrides: Option<BlockOverrides>,
 	) -> RpcResult<U256> {
 		let hash = self.client.block_hash_for_tag(block.unwrap_or_default().into()).await?;
 		let runtime_api = self.client.runtime_api(hash);
-		let dry_run = runtime_api.dry_run(transaction).await?;
+		let overrides = DryRunOverrides {
+			state: state_overrides.unwrap_or_default(),
+			block: block_overrides.unwrap_or_default(),
+		};
+		let dry_run = runtime_api.dry_run_with_overrides(transaction, overrides).await?;
 		Ok(dry_run.eth_gas)
 	}
 
@@ -154,13 +205,31 @@ impl EthRpcServer for EthRpcServerImpl {
 		&self,
 		transaction: GenericTransaction,
 		block: Option<BlockNumberOrTagOrHash>,
+		state_overrides: Option<StateOverride>,
+		block_overrides: Option<BlockOverrides>,
 	) -> RpcResult<Bytes> {
 		let hash = self.client.block_hash_for_tag(block.unwrap_or_default()).await?;
 		let runtime_api = self.client.runtime_api(hash);
-		let dry_run = runtime_api.dry_run(transaction).await?;
+		let overrides = DryRunOverrides {
+			state: state_overrides.unwrap_or_default(),
+			block: block_overrides.unwrap_or_default(),
+		};
+		let dry_run = runtime_api.dry_run_with_overrides(transaction, overrides).await?;
 		Ok(dry_run.data.into())
 	}
 
+	async fn simulate_v1(
+		&self,
+		payload: SimulatePayload,
+		block: Option<BlockNumberOrTagOrHash>,
+	) -> RpcResult<Vec<SimulatedBlock>> {
+		payload.check_limits().map_err(EthRpcError::SimulationLimitExceeded)?;
+		let hash = self.client.block_hash_for_tag(block.unwrap_or_default()).await?;
+		let runtime_api = self.client.runtime_api(hash);
+		let blocks = runtime_api.simulate(payload).await?;
+		Ok(blocks)
+	}
+
 	async fn send_raw_transaction(&self, transaction: Bytes) -> RpcResult<H256> {
 		let hash = H256(keccak_256(&transaction.0));
 		let call = subxt_client::tx().revive().eth_transact(transaction.0);
@@ -170,6 +239,7 @@ impl EthRpcServer for EthRpcServerImpl {
 		})?;
 
 		log::debug!(target: LOG_TARGET, "send_raw_transaction hash: {hash:?}");
+		self.client.notify_pending_transaction(hash).await;
 		Ok(hash)
 	}
 
@@ -188,7 +258,7 @@ impl EthRpcServer for EthRpcServerImpl {
 			.ok_or(EthRpcError::AccountNotFound(from))?;
 
 		if transaction.gas.is_none() {
-			transaction.gas = Some(self.estimate_gas(transaction.clone(), None).await?);
+			transaction.gas = Some(self.estimate_gas(transaction.clone(), None, None, None).await?);
 		}
 
 		if transaction.gas_price.is_none() {
@@ -232,6 +302,15 @@ impl EthRpcServer for EthRpcServerImpl {
 		Ok(self.client.chain_id().into())
 	}
 
+	async fn create_access_list(
+		&self,
+		transaction: GenericTransaction,
+		block: Option<BlockNumberOrTagOrHash>,
+	) -> RpcResult<AccessListResult> {
+		let result = self.client.create_access_list(transaction, block.unwrap_or_default()).await?;
+		Ok(result)
+	}
+
 	async fn gas_price(&self) -> RpcResult<U256> {
 		let hash = self.client.block_hash_for_tag(BlockTag::Latest.into()).await?;
 		let runtime_api = self.client.runtime_api(hash);
@@ -297,6 +376,41 @@ impl EthRpcServer for EthRpcServerImpl {
 		Ok(FilterResults::Logs(logs))
 	}
 
+	async fn get_filter_changes(
+		&self,
+		ext: &Extensions,
+		filter_id: U256,
+	) -> RpcResult<FilterResults> {
+		Ok(self.client.filter_manager().changes(filter_owner(ext)?, filter_id).await?)
+	}
+
+	async fn get_filter_logs(&self, ext: &Extensions, filter_id: U256) -> RpcResult<FilterResults> {
+		let filter = self.client.filter_manager().log_filter(filter_owner(ext)?, filter_id).await?;
+		self.get_logs(Some(filter)).await
+	}
+
+	async fn new_block_filter(&self, ext: &Extensions) -> RpcResult<U256> {
+		let owner = self.filter_installer(ext).await?;
+		let id = self.client.filter_manager().install_block_filter(owner).await?;
+		Ok(id)
+	}
+
+	async fn new_filter(&self, ext: &Extensions, filter: Filter) -> RpcResult<U256> {
+		let owner = self.filter_installer(ext).await?;
+		let id = self.client.filter_manager().install_log_filter(owner, filter).await?;
+		Ok(id)
+	}
+
+	async fn new_pending_transaction_filter(&self, ext: &Extensions) -> RpcResult<U256> {
+		let owner = self.filter_installer(ext).await?;
+		let id = self.client.filter_manager().install_pending_transaction_filter(owner).await?;
+		Ok(id)
+	}
+
+	async fn uninstall_filter(&self, ext: &Extensions, filter_id: U256) -> RpcResult<bool> {
+		Ok(self.client.filter_manager().uninstall(filter_owner(ext)?, filter_id).await)
+	}
+
 	async fn get_storage_at(
 		&self,
 		address: H160,
@@ -19,7 +19,9 @@ use crate::{
 	Address, AddressOrAddresses, BlockInfoProvider, BlockNumberOrTag, BlockTag, Bytes, ClientError,
 	FilterTopic, ReceiptExtractor, SubxtBlockInfoProvider, LOG_TARGET,
 };
-use pallet_revive::evm::{Filter, Log, ReceiptInfo, TransactionSigned};
+use futures::TryStreamExt;
+use jsonrpsee::core::async_trait;
+use pallet_revive::evm::{Bytes256, Filter, Log, ReceiptInfo, TransactionSigned};
 use sp_core::{H256, U256};
 use sqlx::{query, QueryBuilder, Row, Sqlite, SqlitePool};
 use std::{
@@ -28,11 +30,90 @@ use std::{
 };
 use tokio::sync::Mutex;
 
-/// ReceiptProvider stores transaction receipts and logs in a SQLite database.
+/// The number of consecutive blocks whose logs are aggregated into a single bloom.
+///
+/// `eth_getLogs` queries over a block range skip the sections whose bloom can't match the filter.
+pub const LOG_BLOOM_SECTION_SIZE: SubstrateBlockNumber = 4096;
+
+/// The maximum number of logs returned by [`ReceiptProvider::logs`].
+const MAX_LOGS: usize = 10_000;
+
+/// The number of logs read at once from the index when checking the logs of a block.
+const LOG_PAGE_SIZE: usize = 1_000;
+
+/// The storage backend of the receipt index.
+///
+/// It keeps the location of the indexed transactions and their logs, the receipts are re-derived
+/// from the blocks.
+#[async_trait]
+pub trait ReceiptIndex: Send + Sync {
+	/// Returns `true` if receipts of the block are indexed.
+	async fn contains_block(&self, block_hash: &H256) -> Result<bool, ClientError>;
+
+	/// Index the receipts of the block.
+	async fn insert(
+		&self,
+		block_hash: H256,
+		block_number: SubstrateBlockNumber,
+		receipts: &[(TransactionSigned, ReceiptInfo)],
+	) -> Result<(), ClientError>;
+
+	/// Remove the receipts of the given blocks.
+	async fn remove(&self, block_hashes: &[H256]) -> Result<(), ClientError>;
+
+	/// Get the block hash and the index in the block of the given transaction.
+	async fn transaction_location(
+		&self,
+		transaction_hash: &H256,
+	) -> Result<Option<(H256, usize)>, ClientError>;
+
+	/// Get the number of transactions indexed for the given block.
+	async fn block_transaction_count(&self, block_hash: &H256) -> Result<usize, ClientError>;
+
+	/// Get the transaction hashes indexed for the given block, by transaction index.
+	async fn block_transaction_hashes(
+		&self,
+		block_hash: &H256,
+	) -> Result<HashMap<usize, H256>, ClientError>;
+
+	/// Get at most `limit` logs matching the query.
+	///
+	/// The logs are ordered by block number, block hash, transaction index and log index. When
+	/// `after` is set, only the logs that come after it in this order are returned, so a query
+	/// can be read in pages by passing the last log of the previous page.
+	async fn logs(
+		&self,
+		query: &LogQuery,
+		after: Option<&Log>,
+		limit: usize,
+	) -> Result<Vec<Log>, ClientError>;
+}
+
+/// The logs requested from a [`ReceiptIndex`], a [`Filter`] with its block tags resolved.
+#[derive(Debug, Clone)]
+pub struct LogQuery {
+	/// The blocks whose logs are requested.
+	pub blocks: LogBlocks,
+	/// Only request the logs emitted by this address, or by one of these addresses.
+	pub address: Option<AddressOrAddresses>,
+	/// Only request the logs with these topics, by position.
+	pub topics: Vec<FilterTopic>,
+}
+
+/// The blocks of a [`LogQuery`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogBlocks {
+	/// The blocks in the range `from..=to`.
+	Range { from: u64, to: u64 },
+	/// The block with the given hash.
+	Hash(H256),
+}
+
+/// ReceiptProvider stores transaction receipts and logs in a [`ReceiptIndex`].
 #[derive(Clone)]
 pub struct ReceiptProvider<B: BlockInfoProvider = SubxtBlockInfoProvider> {
-	/// The database pool.
-	pool: SqlitePool,
+	/// The index storing the receipts and logs.
+	index: Arc<dyn ReceiptIndex>,
 	/// The block provider used to fetch blocks, and reconstruct receipts.
 	block_provider: B,
 	/// A means to extract receipts from extrinsics.
@@ -43,6 +124,36 @@ pub struct ReceiptProvider<B: BlockInfoProvider = SubxtBlockInfoProvider> {
 	block_number_to_hash: Arc<Mutex<BTreeMap<SubstrateBlockNumber, H256>>>,
 }
 
+/// A mismatch between the indexed receipts of a block and the receipts re-derived from the chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IndexMismatch {
+	/// The transaction is in the block but not in the index.
+	MissingTransaction { transaction_index: usize, transaction_hash: H256 },
+	/// The transaction is in the index but not in the block.
+	UnexpectedTransaction { transaction_index: usize, transaction_hash: H256 },
+	/// The indexed logs of the transaction differ from the logs re-derived from the block.
+	LogsMismatch { transaction_hash: H256, expected: usize, indexed: usize },
+}
+
+impl std::fmt::Display for IndexMismatch {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::MissingTransaction { transaction_index, transaction_hash } => write!(
+				f,
+				"transaction {transaction_hash:?} at index {transaction_index} is not indexed"
+			),
+			Self::UnexpectedTransaction { transaction_index, transaction_hash } => write!(
+				f,
+				"indexed transaction {transaction_hash:?} at index {transaction_index} is not in the block"
+			),
+			Self::LogsMismatch { transaction_hash, expected, indexed } => write!(
+				f,
+				"logs of transaction {transaction_hash:?} do not match ({expected} expected, {indexed} indexed)"
+			),
+		}
+	}
+}
+
 /// Provides information about a block,
 /// This is an abstratction on top of [`SubstrateBlock`] that can't be mocked in tests.
 /// Can be removed once <https://github.com/paritytech/subxt/issues/1883> is fixed.
@@ -63,65 +174,111 @@ impl BlockInfo for SubstrateBlock {
 }
 
 impl<B: BlockInfoProvider> ReceiptProvider<B> {
-	/// Create a new `ReceiptProvider` with the given database URL and block provider.
-	pub async fn new(
-		pool: SqlitePool,
+	/// Create a new `ReceiptProvider` with the given index and block provider.
+	pub fn new(
+		index: Arc<dyn ReceiptIndex>,
 		block_provider: B,
 		receipt_extractor: ReceiptExtractor,
 		keep_latest_n_blocks: Option<usize>,
-	) -> Result<Self, sqlx::Error> {
-		sqlx::migrate!().run(&pool).await?;
-		Ok(Self {
-			pool,
+	) -> Self {
+		Self {
+			index,
 			block_provider,
 			receipt_extractor,
 			keep_latest_n_blocks,
 			block_number_to_hash: Default::default(),
-		})
-	}
-
-	async fn fetch_row(&self, transaction_hash: &H256) -> Option<(H256, usize)> {
-		let transaction_hash = transaction_hash.as_ref();
-		let result = query!(
-			r#"
-			SELECT block_hash, transaction_index
-			FROM transaction_hashes
-			WHERE transaction_hash = $1
-			"#,
-			transaction_hash
-		)
-		.fetch_optional(&self.pool)
-		.await
-		.ok()??;
-
-		let block_hash = H256::from_slice(&result.block_hash[..]);
-		let transaction_index = result.transaction_index.try_into().ok()?;
-		Some((block_hash, transaction_index))
+		}
 	}
 
-	/// Deletes older records from the database.
+	/// Deletes older records from the index.
 	pub async fn remove(&self, block_hashes: &[H256]) -> Result<(), ClientError> {
 		if block_hashes.is_empty() {
 			return Ok(());
 		}
 		log::debug!(target: LOG_TARGET, "Removing block hashes: {block_hashes:?}");
+		self.index.remove(block_hashes).await
+	}
 
-		let placeholders = vec!["?"; block_hashes.len()].join(", ");
-		let sql = format!("DELETE FROM transaction_hashes WHERE block_hash in ({placeholders})");
-		let mut delete_tx_query = sqlx::query(&sql);
+	/// Re-derive the receipts of the given block and compare them with the index.
+	pub async fn check_block_receipts(
+		&self,
+		block: &SubstrateBlock,
+	) -> Result<Vec<IndexMismatch>, ClientError> {
+		let receipts = self.receipts_from_block(block).await?;
+		self.check(block, &receipts).await
+	}
 
-		let sql = format!("DELETE FROM logs WHERE block_hash in ({placeholders})");
-		let mut delete_logs_query = sqlx::query(&sql);
+	/// Compare the given receipts with the receipts and logs indexed for the block.
+	///
+	/// Note: Can be merged into `check_block_receipts` once <https://github.com/paritytech/subxt/issues/1883> is fixed.
+	async fn check(
+		&self,
+		block: &impl BlockInfo,
+		receipts: &[(TransactionSigned, ReceiptInfo)],
+	) -> Result<Vec<IndexMismatch>, ClientError> {
+		let block_hash = block.hash();
+		let mut indexed = self.index.block_transaction_hashes(&block_hash).await?;
+		let indexed_logs = self.block_logs(block_hash).await?;
 
-		for block_hash in block_hashes {
-			delete_tx_query = delete_tx_query.bind(block_hash.as_ref());
-			delete_logs_query = delete_logs_query.bind(block_hash.as_ref());
+		let mut mismatches = Vec::new();
+		for (_, receipt) in receipts {
+			let transaction_index = receipt.transaction_index.as_usize();
+			let transaction_hash = receipt.transaction_hash;
+			if indexed.remove(&transaction_index) != Some(transaction_hash) {
+				mismatches.push(IndexMismatch::MissingTransaction {
+					transaction_index,
+					transaction_hash,
+				});
+				continue;
+			}
+
+			let key = |log: &Log| {
+				(
+					log.log_index,
+					log.address,
+					log.topics.clone(),
+					log.data.clone().unwrap_or_default(),
+				)
+			};
+			let mut expected = receipt.logs.iter().map(key).collect::<Vec<_>>();
+			expected.sort_by_key(|(log_index, ..)| *log_index);
+			let mut found = indexed_logs
+				.iter()
+				.filter(|log| log.transaction_hash == transaction_hash)
+				.map(key)
+				.collect::<Vec<_>>();
+			found.sort_by_key(|(log_index, ..)| *log_index);
+
+			if expected != found {
+				mismatches.push(IndexMismatch::LogsMismatch {
+					transaction_hash,
+					expected: expected.len(),
+					indexed: found.len(),
+				});
+			}
 		}
 
-		let delete_transaction_hashes = delete_tx_query.execute(&self.pool);
-		let delete_logs = delete_logs_query.execute(&self.pool);
-		tokio::try_join!(delete_transaction_hashes, delete_logs)?;
-		Ok(())
+		let mut unexpected = indexed.into_iter().collect::<Vec<_>>();
+		unexpected.sort();
+		mismatches.extend(unexpected.into_iter().map(|(transaction_index, transaction_hash)| {
+			IndexMismatch::UnexpectedTransaction { transaction_index, transaction_hash }
+		}));
+
+		Ok(mismatches)
+	}
+
+	/// Get all the logs indexed for the given block, read in pages of [`LOG_PAGE_SIZE`] logs.
+	async fn block_logs(&self, block_hash: H256) -> Result<Vec<Log>, ClientError> {
+		let query = LogQuery { blocks: LogBlocks::Hash(block_hash), address: None, topics: vec![] };
+		let mut logs = Vec::new();
+		loop {
+			let page = self.index.logs(&query, logs.last(), LOG_PAGE_SIZE).await?;
+			let is_last_page = page.len() < LOG_PAGE_SIZE;
+			logs.extend(page);
+			if is_last_page {
+				return Ok(logs);
+			}
+		}
 	}
 
 	/// Check if the block is before the earliest block.
@@ -165,17 +322,7 @@ impl<B: BlockInfoProvider> ReceiptProvider<B> {
 		}
 
 		let block_hash = block.hash();
-		let block_hash_ref = block_hash.as_ref();
-		let block_number = block.number() as i64;
-
-		let result = sqlx::query!(
-			r#"SELECT EXISTS(SELECT 1 FROM transaction_hashes WHERE block_hash = $1) AS "exists!: bool""#,
-			block_hash_ref
-		)
-		.fetch_one(&self.pool)
-		.await?;
-
-		if result.exists {
+		if self.index.contains_block(&block_hash).await? {
 			return Ok(());
 		}
 
@@ -203,6 +350,270 @@ impl<B: BlockInfoProvider> ReceiptProvider<B> {
 			self.remove(&to_remove).await?;
 		}
 
+		self.index.insert(block_hash, block.number(), receipts).await
+	}
+
+	/// Get logs that match the given filter.
+	pub async fn logs(&self, filter: Option<Filter>) -> anyhow::Result<Vec<Log>> {
+		let filter = filter.unwrap_or_default();
+		let latest_block = U256::from(self.block_provider.latest_block_number().await);
+
+		let as_block_number = |block_param| match block_param {
+			None => Ok(None),
+			Some(BlockNumberOrTag::U256(v)) => Ok(Some(v)),
+			Some(BlockNumberOrTag::BlockTag(BlockTag::Latest)) => Ok(Some(latest_block)),
+			Some(BlockNumberOrTag::BlockTag(tag)) => anyhow::bail!("Unsupported tag: {tag:?}"),
+		};
+
+		let from_block = as_block_number(filter.from_block)?;
+		let to_block = as_block_number(filter.to_block)?;
+
+		let blocks = match (from_block, to_block, filter.block_hash) {
+			(Some(_), _, Some(_)) | (_, Some(_), Some(_)) => {
+				anyhow::bail!("block number and block hash cannot be used together");
+			},
+
+			(Some(block), _, _) | (_, Some(block), _) if block > latest_block => {
+				anyhow::bail!("block number exceeds latest block");
+			},
+			(Some(from_block), Some(to_block), None) if from_block > to_block => {
+				anyhow::bail!("invalid block range params");
+			},
+			(None, None, Some(hash)) => LogBlocks::Hash(hash),
+			(None, None, None) =>
+				LogBlocks::Range { from: latest_block.as_u64(), to: latest_block.as_u64() },
+			(from_block, to_block, None) => LogBlocks::Range {
+				from: from_block.map_or(0, |v| v.as_u64()),
+				to: to_block.unwrap_or(latest_block).as_u64(),
+			},
+		};
+
+		let topics = filter.topics.unwrap_or_default();
+		if topics.len() > 4 {
+			return Err(anyhow::anyhow!("exceed max topics"));
+		}
+
+		let query = LogQuery { blocks, address: filter.address, topics };
+		Ok(self.index.logs(&query, None, MAX_LOGS).await?)
+	}
+
+	/// Get the number of receipts per block.
+	pub async fn receipts_count_per_block(&self, block_hash: &H256) -> Option<usize> {
+		self.index.block_transaction_count(block_hash).await.ok()
+	}
+
+	/// Return all transaction hashes for the given block hash.
+	pub async fn block_transaction_hashes(
+		&self,
+		block_hash: &H256,
+	) -> Option<HashMap<usize, H256>> {
+		self.index.block_transaction_hashes(block_hash).await.ok()
+	}
+
+	/// Get the receipt for the given block hash and transaction index.
+	pub async fn receipt_by_block_hash_and_index(
+		&self,
+		block_hash: &H256,
+		transaction_index: usize,
+	) -> Option<ReceiptInfo> {
+		let block = self.block_provider.block_by_hash(block_hash).await.ok()??;
+		let (_, receipt) = self
+			.receipt_extractor
+			.extract_from_transaction(&block, transaction_index)
+			.await
+			.ok()?;
+		Some(receipt)
+	}
+
+	/// Get the receipt for the given transaction hash.
+	pub async fn receipt_by_hash(&self, transaction_hash: &H256) -> Option<ReceiptInfo> {
+		let (block_hash, transaction_index) =
+			self.index.transaction_location(transaction_hash).await.ok()??;
+
+		let block = self.block_provider.block_by_hash(&block_hash).await.ok()??;
+		let (_, receipt) = self
+			.receipt_extractor
+			.extract_from_transaction(&block, transaction_index)
+			.await
+			.ok()?;
+		Some(receipt)
+	}
+
+	/// Get the signed transaction for the given transaction hash.
+	pub async fn signed_tx_by_hash(&self, transaction_hash: &H256) -> Option<TransactionSigned> {
+		let (block_hash, transaction_index) =
+			self.index.transaction_location(transaction_hash).await.ok()??;
+
+		let block = self.block_provider.block_by_hash(&block_hash).await.ok()??;
+		let (signed_tx, _) = self
+			.receipt_extractor
+			.extract_from_transaction(&block, transaction_index)
+			.await
+			.ok()?;
+		Some(signed_tx)
+	}
+}
+
+/// A [`ReceiptIndex`] stored in a SQLite database.
+#[derive(Clone)]
+pub struct SqliteReceiptIndex {
+	/// The database pool.
+	pool: SqlitePool,
+	/// Serializes the read-modify-write updates of the `log_blooms` table.
+	log_bloom_lock: Arc<Mutex<()>>,
+}
+
+impl SqliteReceiptIndex {
+	/// Create a new `SqliteReceiptIndex`, migrating the database if needed.
+	pub async fn new(pool: SqlitePool) -> Result<Self, sqlx::Error> {
+		sqlx::migrate!().run(&pool).await?;
+		let index = Self { pool, log_bloom_lock: Default::default() };
+		index.rebuild_log_blooms_if_missing().await?;
+		Ok(index)
+	}
+
+	/// Build the `log_blooms` table from the indexed logs.
+	///
+	/// This is only needed for databases that were populated before the table was introduced.
+	async fn rebuild_log_blooms_if_missing(&self) -> Result<(), sqlx::Error> {
+		let has_blooms: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM log_blooms)")
+			.fetch_one(&self.pool)
+			.await?;
+		if has_blooms {
+			return Ok(());
+		}
+
+		let mut blooms = BTreeMap::<i64, Bytes256>::new();
+		let mut rows = sqlx::query(
+			"SELECT block_number, address, topic_0, topic_1, topic_2, topic_3 FROM logs",
+		)
+		.fetch(&self.pool);
+
+		while let Some(row) = rows.try_next().await? {
+			let block_number: i64 = row.try_get("block_number")?;
+			let address: Vec<u8> = row.try_get("address")?;
+			let mut topics = Vec::new();
+			for i in 0..4 {
+				if let Some(topic) = row.try_get::<Option<Vec<u8>>, _>(i + 2)? {
+					topics.push(H256::from_slice(&topic));
+				}
+			}
+
+			let log = Log { address: Address::from_slice(&address), topics, ..Default::default() };
+			blooms
+				.entry(block_number / LOG_BLOOM_SECTION_SIZE as i64)
+				.or_default()
+				.accrue_log(&log);
+		}
+		drop(rows);
+
+		if blooms.is_empty() {
+			return Ok(());
+		}
+
+		log::info!(target: LOG_TARGET, "🗄️ Rebuilt {} log bloom sections", blooms.len());
+		for (section, bloom) in blooms {
+			sqlx::query("INSERT OR REPLACE INTO log_blooms (section, bloom) VALUES ($1, $2)")
+				.bind(section)
+				.bind(&bloom.0[..])
+				.execute(&self.pool)
+				.await?;
+		}
+		Ok(())
+	}
+
+	/// Merge the logs of the given receipts into the bloom of the block's section.
+	async fn accrue_log_bloom(
+		&self,
+		block_number: SubstrateBlockNumber,
+		receipts: &[(TransactionSigned, ReceiptInfo)],
+	) -> Result<(), sqlx::Error> {
+		let mut logs = receipts.iter().flat_map(|(_, receipt)| receipt.logs.iter()).peekable();
+		if logs.peek().is_none() {
+			return Ok(());
+		}
+
+		let mut bloom = Bytes256::default();
+		logs.for_each(|log| bloom.accrue_log(log));
+
+		let section = (block_number / LOG_BLOOM_SECTION_SIZE) as i64;
+		let _guard = self.log_bloom_lock.lock().await;
+		let existing: Option<Vec<u8>> =
+			sqlx::query_scalar("SELECT bloom FROM log_blooms WHERE section = $1")
+				.bind(section)
+				.fetch_optional(&self.pool)
+				.await?;
+
+		if let Some(existing) = existing {
+			bloom.accrue_bloom(&bloom_from_bytes(&existing));
+		}
+
+		sqlx::query("INSERT OR REPLACE INTO log_blooms (section, bloom) VALUES ($1, $2)")
+			.bind(section)
+			.bind(&bloom.0[..])
+			.execute(&self.pool)
+			.await?;
+		Ok(())
+	}
+
+	/// Returns the block ranges, within `from..=to`, of the sections whose bloom may contain logs
+	/// matching the query. Adjacent sections are merged into a single range.
+	async fn log_bloom_ranges(
+		&self,
+		from: u64,
+		to: u64,
+		query: &LogQuery,
+	) -> Result<Vec<(u64, u64)>, sqlx::Error> {
+		let section_size = LOG_BLOOM_SECTION_SIZE as u64;
+		let rows = sqlx::query("SELECT section, bloom FROM log_blooms WHERE section BETWEEN $1 AND $2 ORDER BY section")
+			.bind((from / section_size) as i64)
+			.bind((to / section_size) as i64)
+			.fetch_all(&self.pool)
+			.await?;
+
+		let mut ranges: Vec<(u64, u64)> = Vec::new();
+		for row in rows {
+			let section: i64 = row.try_get("section")?;
+			let bloom: Vec<u8> = row.try_get("bloom")?;
+			if !bloom_matches_query(&bloom_from_bytes(&bloom), query) {
+				continue;
+			}
+
+			let start = (section as u64 * section_size).max(from);
+			let end = (section as u64 * section_size + section_size - 1).min(to);
+			match ranges.last_mut() {
+				Some((_, last_end)) if *last_end + 1 == start => *last_end = end,
+				_ => ranges.push((start, end)),
+			}
+		}
+
+		Ok(ranges)
+	}
+}
+
+#[async_trait]
+impl ReceiptIndex for SqliteReceiptIndex {
+	async fn contains_block(&self, block_hash: &H256) -> Result<bool, ClientError> {
+		let block_hash_ref = block_hash.as_ref();
+		let result = sqlx::query!(
+			r#"SELECT EXISTS(SELECT 1 FROM transaction_hashes WHERE block_hash = $1) AS "exists!: bool""#,
+			block_hash_ref
+		)
+		.fetch_one(&self.pool)
+		.await?;
+
+		Ok(result.exists)
+	}
+
+	async fn insert(
+		&self,
+		block_hash: H256,
+		block_number: SubstrateBlockNumber,
+		receipts: &[(TransactionSigned, ReceiptInfo)],
+	) -> Result<(), ClientError> {
+		let block_hash_ref = block_hash.as_ref();
+		let block_number_value = block_number as i64;
+
 		for (_, receipt) in receipts {
 			let transaction_hash: &[u8] = receipt.transaction_hash.as_ref();
 			let transaction_index = receipt.transaction_index.as_u32() as i32;
@@ -246,7 +657,7 @@ impl<B: BlockInfoProvider> ReceiptProvider<B> {
 					transaction_index,
 					log_index,
 					address,
-					block_number,
+					block_number_value,
 					transaction_hash,
 					topic_0,
 					topic_1,
@@ -258,61 +669,142 @@ impl<B: BlockInfoProvider> ReceiptProvider<B> {
 				.await?;
 			}
 		}
+
+		self.accrue_log_bloom(block_number, receipts).await?;
+		Ok(())
+	}
+
+	async fn remove(&self, block_hashes: &[H256]) -> Result<(), ClientError> {
+		let placeholders = vec!["?"; block_hashes.len()].join(", ");
+		let sql = format!("DELETE FROM transaction_hashes WHERE block_hash in ({placeholders})");
+		let mut delete_tx_query = sqlx::query(&sql);
+
+		let sql = format!("DELETE FROM logs WHERE block_hash in ({placeholders})");
+		let mut delete_logs_query = sqlx::query(&sql);
+
+		for block_hash in block_hashes {
+			delete_tx_query = delete_tx_query.bind(block_hash.as_ref());
+			delete_logs_query = delete_logs_query.bind(block_hash.as_ref());
+		}
+
+		let delete_transaction_hashes = delete_tx_query.execute(&self.pool);
+		let delete_logs = delete_logs_query.execute(&self.pool);
+		tokio::try_join!(delete_transaction_hashes, delete_logs)?;
 		Ok(())
 	}
 
-	/// Get logs that match the given filter.
-	pub async fn logs(&self, filter: Option<Filter>) -> anyhow::Result<Vec<Log>> {
-		let mut qb = QueryBuilder::<Sqlite>::new("SELECT logs.* FROM logs WHERE 1=1");
-		let filter = filter.unwrap_or_default();
+	async fn transaction_location(
+		&self,
+		transaction_hash: &H256,
+	) -> Result<Option<(H256, usize)>, ClientError> {
+		let transaction_hash = transaction_hash.as_ref();
+		let Some(result) = query!(
+			r#"
+			SELECT block_hash, transaction_index
+			FROM transaction_hashes
+			WHERE transaction_hash = $1
+			"#,
+			transaction_hash
+		)
+		.fetch_optional(&self.pool)
+		.await?
+		else {
+			return Ok(None);
+		};
+
+		let block_hash = H256::from_slice(&result.block_hash[..]);
+		let transaction_index =
+			result.transaction_index.try_into().map_err(|_| ClientError::ConversionFailed)?;
+		Ok(Some((block_hash, transaction_index)))
+	}
+
+	async fn block_transaction_count(&self, block_hash: &H256) -> Result<usize, ClientError> {
+		let block_hash = block_hash.as_ref();
+		let row = query!(
+			r#"
+            SELECT COUNT(*) as count
+            FROM transaction_hashes
+            WHERE block_hash = $1
+            "#,
+			block_hash
+		)
+		.fetch_one(&self.pool)
+		.await?;
 
-		let latest_block = U256::from(self.block_provider.latest_block_number().await);
+		Ok(row.count as usize)
+	}
 
-		let as_block_number = |block_param| match block_param {
-			None => Ok(None),
-			Some(BlockNumberOrTag::U256(v)) => Ok(Some(v)),
-			Some(BlockNumberOrTag::BlockTag(BlockTag::Latest)) => Ok(Some(latest_block)),
-			Some(BlockNumberOrTag::BlockTag(tag)) => anyhow::bail!("Unsupported tag: {tag:?}"),
-		};
+	async fn block_transaction_hashes(
+		&self,
+		block_hash: &H256,
+	) -> Result<HashMap<usize, H256>, ClientError> {
+		let block_hash = block_hash.as_ref();
+		let rows = query!(
+			r#"
+		      SELECT transaction_index, transaction_hash
+		      FROM transaction_hashes
+		      WHERE block_hash = $1
+		      "#,
+			block_hash
+		)
+		.map(|row| {
+			let transaction_index = row.transaction_index as usize;
+			let transaction_hash = H256::from_slice(&row.transaction_hash);
+			(transaction_index, transaction_hash)
+		})
+		.fetch_all(&self.pool)
+		.await?;
 
-		let from_block = as_block_number(filter.from_block)?;
-		let to_block = as_block_number(filter.to_block)?;
+		Ok(rows.into_iter().collect())
+	}
 
-		match (from_block, to_block, filter.block_hash) {
-			(Some(_), _, Some(_)) | (_, Some(_), Some(_)) => {
-				anyhow::bail!("block number and block hash cannot be used together");
-			},
+	async fn logs(
+		&self,
+		query: &LogQuery,
+		after: Option<&Log>,
+		limit: usize,
+	) -> Result<Vec<Log>, ClientError> {
+		let mut qb = QueryBuilder::<Sqlite>::new("SELECT logs.* FROM logs WHERE 1=1");
 
-			(Some(block), _, _) | (_, Some(block), _) if block > latest_block => {
-				anyhow::bail!("block number exceeds latest block");
-			},
-			(Some(from_block), Some(to_block), None) if from_block > to_block => {
-				anyhow::bail!("invalid block range params");
+		match query.blocks {
+			LogBlocks::Hash(hash) => {
+				qb.push(" AND block_hash = ").push_bind(hash.0.to_vec());
 			},
-			(Some(from_block), Some(to_block), None) if from_block == to_block => {
-				qb.push(" AND block_number = ").push_bind(from_block.as_u64() as i64);
+			LogBlocks::Range { from, to } if from == to => {
+				qb.push(" AND block_number = ").push_bind(from as i64);
 			},
-			(Some(from_block), Some(to_block), None) => {
+			LogBlocks::Range { from, to } => {
 				qb.push(" AND block_number BETWEEN ")
-					.push_bind(from_block.as_u64() as i64)
+					.push_bind(from as i64)
 					.push(" AND ")
-					.push_bind(to_block.as_u64() as i64);
-			},
-			(Some(from_block), None, None) => {
-				qb.push(" AND block_number >= ").push_bind(from_block.as_u64() as i64);
-			},
-			(None, Some(to_block), None) => {
-				qb.push(" AND block_number <= ").push_bind(to_block.as_u64() as i64);
-			},
-			(None, None, Some(hash)) => {
-				qb.push(" AND block_hash = ").push_bind(hash.0.to_vec());
+					.push_bind(to as i64);
 			},
-			(None, None, None) => {
-				qb.push(" AND block_number = ").push_bind(latest_block.as_u64() as i64);
+		}
+
+		// Narrow down block ranges to the sections whose bloom may contain matching logs.
+		let has_log_filter = query.address.is_some() || !query.topics.is_empty();
+		match query.blocks {
+			LogBlocks::Range { from, to } if has_log_filter => {
+				let ranges = self.log_bloom_ranges(from, to, query).await?;
+				if ranges.is_empty() {
+					return Ok(vec![]);
+				}
+
+				qb.push(" AND (");
+				let mut separated = qb.separated(" OR ");
+				for (start, end) in ranges {
+					separated
+						.push("block_number BETWEEN ")
+						.push_bind_unseparated(start as i64)
+						.push_unseparated(" AND ")
+						.push_bind_unseparated(end as i64);
+				}
+				separated.push_unseparated(")");
 			},
+			_ => {},
 		}
 
-		if let Some(addresses) = filter.address {
+		if let Some(addresses) = &query.address {
 			match addresses {
 				AddressOrAddresses::Address(addr) => {
 					qb.push(" AND address = ").push_bind(addr.0.to_vec());
@@ -328,29 +820,34 @@ impl<B: BlockInfoProvider> ReceiptProvider<B> {
 			}
 		}
 
-		if let Some(topics) = filter.topics {
-			if topics.len() > 4 {
-				return Err(anyhow::anyhow!("exceed max topics"));
+		for (i, topic) in query.topics.iter().enumerate() {
+			match topic {
+				FilterTopic::Single(hash) => {
+					qb.push(format_args!(" AND topic_{i} = ")).push_bind(hash.0.to_vec());
+				},
+				FilterTopic::Multiple(hashes) => {
+					qb.push(format_args!(" AND topic_{i} IN ("));
+					let mut separated = qb.separated(", ");
+					for hash in hashes {
+						separated.push_bind(hash.0.to_vec());
+					}
+					separated.push_unseparated(")");
+				},
 			}
+		}
 
-			for (i, topic) in topics.into_iter().enumerate() {
-				match topic {
-					FilterTopic::Single(hash) => {
-						qb.push(format_args!(" AND topic_{i} = ")).push_bind(hash.0.to_vec());
-					},
-					FilterTopic::Multiple(hashes) => {
-						qb.push(format_args!(" AND topic_{i} IN ("));
-						let mut separated = qb.separated(", ");
-						for hash in hashes {
-							separated.push_bind(hash.0.to_vec());
-						}
-						separated.push_unseparated(")");
-					},
-				}
-			}
+		if let Some(log) = after {
+			qb.push(" AND (block_number, block_hash, transaction_index, log_index) > (");
+			let mut separated = qb.separated(", ");
+			separated.push_bind(log.block_number.as_u64() as i64);
+			separated.push_bind(log.block_hash.0.to_vec());
+			separated.push_bind(log.transaction_index.as_u64() as i64);
+			separated.push_bind(log.log_index.as_u64() as i64);
+			separated.push_unseparated(")");
 		}
 
-		qb.push(" LIMIT 10000");
+		qb.push(" ORDER BY block_number, block_hash, transaction_index, log_index LIMIT ")
+			.push_bind(limit as i64);
 
 		let logs = qb
 			.build()
@@ -389,106 +886,32 @@ impl<B: BlockInfoProvider> ReceiptProvider<B> {
 
 		Ok(logs)
 	}
+}
 
-	/// Get the number of receipts per block.
-	pub async fn receipts_count_per_block(&self, block_hash: &H256) -> Option<usize> {
-		let block_hash = block_hash.as_ref();
-		let row = query!(
-			r#"
-            SELECT COUNT(*) as count
-            FROM transaction_hashes
-            WHERE block_hash = $1
-            "#,
-			block_hash
-		)
-		.fetch_one(&self.pool)
-		.await
-		.ok()?;
-
-		let count = row.count as usize;
-		Some(count)
-	}
+/// Convert a bloom read from the database into a [`Bytes256`].
+fn bloom_from_bytes(bytes: &[u8]) -> Bytes256 {
+	let mut bloom = Bytes256::default();
+	let len = bytes.len().min(bloom.0.len());
+	bloom.0[..len].copy_from_slice(&bytes[..len]);
+	bloom
+}
 
-	/// Return all transaction hashes for the given block hash.
-	pub async fn block_transaction_hashes(
-		&self,
-		block_hash: &H256,
-	) -> Option<HashMap<usize, H256>> {
-		let block_hash = block_hash.as_ref();
-		let rows = query!(
-			r#"
-		      SELECT transaction_index, transaction_hash
-		      FROM transaction_hashes
-		      WHERE block_hash = $1
-		      "#,
-			block_hash
-		)
-		.map(|row| {
-			let transaction_index = row.transaction_index as usize;
-			let transaction_hash = H256::from_slice(&row.transaction_hash);
-			(transaction_index, transaction_hash)
+/// Returns `false` if the bloom can't contain any log matching the address and topics of the
+/// query.
+fn bloom_matches_query(bloom: &Bytes256, query: &LogQuery) -> bool {
+	let address_matches = match &query.address {
+		None => true,
+		Some(AddressOrAddresses::Address(addr)) => bloom.may_contain(addr.as_ref()),
+		Some(AddressOrAddresses::Addresses(addrs)) =>
+			addrs.iter().any(|addr| bloom.may_contain(addr.as_ref())),
+	};
+
+	address_matches &&
+		query.topics.iter().all(|topic| match topic {
+			FilterTopic::Single(hash) => bloom.may_contain(hash.as_ref()),
+			FilterTopic::Multiple(hashes) =>
+				hashes.iter().any(|hash| bloom.may_contain(hash.as_ref())),
 		})
-		.fetch_all(&self.pool)
-		.await
-		.ok()?;
-
-		Some(rows.into_iter().collect())
-	}
-
-	/// Get the receipt for the given block hash and transaction index.
-	pub async fn receipt_by_block_hash_and_index(
-		&self,
-		block_hash: &H256,
-		transaction_index: usize,
-	) -> Option<ReceiptInfo> {
-		let block = self.block_provider.block_by_hash(block_hash).await.ok()??;
-		let (_, receipt) = self
-			.receipt_extractor
-			.extract_from_transaction(&block, transaction_index)
-			.await
-			.ok()?;
-		Some(receipt)
-	}
-
-	/// Get the receipt for the given transaction hash.
-	pub async fn receipt_by_hash(&self, transaction_hash: &H256) -> Option<ReceiptInfo> {
-		let (block_hash, transaction_index) = self.fetch_row(transaction_hash).await?;
-
-		let block = self.block_provider.block_by_hash(&block_hash).await.ok()??;
-		let (_, receipt) = self
-			.receipt_extractor
-			.extract_from_transaction(&block, transaction_index)
-			.await
-			.ok()?;
-		Some(receipt)
-	}
-
-	/// Get the signed transaction for the given transaction hash.
-	pub async fn signed_tx_by_hash(&self, transaction_hash: &H256) -> Option<TransactionSigned> {
-		let transaction_hash = transaction_hash.as_ref();
-		let result = query!(
-			r#"
-			SELECT block_hash, transaction_index
-			FROM transaction_hashes
-			WHERE transaction_hash = $1
-			"#,
-			transaction_hash
-		)
-		.fetch_optional(&self.pool)
-		.await
-		.ok()??;
-
-		let block_hash = H256::from_slice(&result.block_hash[..]);
-		let transaction_index = result.transaction_index.try_into().ok()?;
-
-		let block = self.block_provider.block_by_hash(&block_hash).await.ok()??;
-		let (signed_tx, _) = self
-			.receipt_extractor
-			.extract_from_transaction(&block, transaction_index)
-			.await
-			.ok()?;
-		Some(signed_tx)
-	}
 }
 
 #[cfg(test)]
@@ -517,19 +940,22 @@ mod tests {
 		count as _
 	}
 
+	fn setup_sqlite_index(pool: SqlitePool) -> SqliteReceiptIndex {
+		SqliteReceiptIndex { pool, log_bloom_lock: Default::default() }
+	}
+
 	async fn setup_sqlite_provider(pool: SqlitePool) -> ReceiptProvider<MockBlockInfoProvider> {
-		ReceiptProvider {
-			pool,
-			block_provider: MockBlockInfoProvider {},
-			receipt_extractor: ReceiptExtractor::new_mock(),
-			keep_latest_n_blocks: Some(10),
-			block_number_to_hash: Default::default(),
-		}
+		ReceiptProvider::new(
+			Arc::new(setup_sqlite_index(pool)),
+			MockBlockInfoProvider {},
+			ReceiptExtractor::new_mock(),
+			Some(10),
+		)
 	}
 
 	#[sqlx::test]
 	async fn test_insert_remove(pool: SqlitePool) -> anyhow::Result<()> {
-		let provider = setup_sqlite_provider(pool).await;
+		let provider = setup_sqlite_provider(pool.clone()).await;
 		let block = MockBlockInfo { hash: H256::default(), number: 0 };
 		let receipts = vec![(
 			TransactionSigned::default(),
@@ -540,18 +966,18 @@ mod tests {
 		)];
 
 		provider.insert(&block, &receipts).await?;
-		let row = provider.fetch_row(&receipts[0].1.transaction_hash).await;
+		let row = provider.index.transaction_location(&receipts[0].1.transaction_hash).await?;
 		assert_eq!(row, Some((block.hash, 0)));
 
 		provider.remove(&[block.hash()]).await?;
-		assert_eq!(count(&provider.pool, "transaction_hashes", Some(block.hash())).await, 0);
-		assert_eq!(count(&provider.pool, "logs", Some(block.hash())).await, 0);
+		assert_eq!(count(&pool, "transaction_hashes", Some(block.hash())).await, 0);
+		assert_eq!(count(&pool, "logs", Some(block.hash())).await, 0);
 		Ok(())
 	}
 
 	#[sqlx::test]
 	async fn test_prune(pool: SqlitePool) -> anyhow::Result<()> {
-		let provider = setup_sqlite_provider(pool).await;
+		let provider = setup_sqlite_provider(pool.clone()).await;
 		let n = provider.keep_latest_n_blocks.unwrap();
 
 		for i in 0..2 * n {
@@ -571,8 +997,8 @@ mod tests {
 			)];
 			provider.insert(&block, &receipts).await?;
 		}
-		assert_eq!(count(&provider.pool, "transaction_hashes", None).await, n);
-		assert_eq!(count(&provider.pool, "logs", None).await, n);
+		assert_eq!(count(&pool, "transaction_hashes", None).await, n);
+		assert_eq!(count(&pool, "logs", None).await, n);
 		assert_eq!(provider.block_number_to_hash.lock().await.len(), n);
 
 		return Ok(());
@@ -580,7 +1006,7 @@ mod tests {
 
 	#[sqlx::test]
 	async fn test_fork(pool: SqlitePool) -> anyhow::Result<()> {
-		let provider = setup_sqlite_provider(pool).await;
+		let provider = setup_sqlite_provider(pool.clone()).await;
 
 		for i in [1u8, 2u8] {
 			let block = MockBlockInfo { hash: H256::from([i; 32]), number: 1 };
@@ -599,8 +1025,8 @@ mod tests {
 			)];
 			provider.insert(&block, &receipts).await?;
 		}
-		assert_eq!(count(&provider.pool, "transaction_hashes", None).await, 1);
-		assert_eq!(count(&provider.pool, "logs", None).await, 1);
+		assert_eq!(count(&pool, "transaction_hashes", None).await, 1);
+		assert_eq!(count(&pool, "logs", None).await, 1);
 		assert_eq!(
 			provider.block_number_to_hash.lock().await.clone(),
 			[(1, H256::from([2u8; 32]))].into(),
@@ -781,4 +1207,192 @@ mod tests {
 		assert_eq!(logs, vec![log1.clone(), log2.clone()]);
 		Ok(())
 	}
+
+	#[sqlx::test]
+	async fn test_log_bloom_ranges(pool: SqlitePool) -> anyhow::Result<()> {
+		let index = setup_sqlite_index(pool.clone());
+		let section_size = LOG_BLOOM_SECTION_SIZE;
+
+		for (i, number) in [1, section_size + 1, 2 * section_size + 1].into_iter().enumerate() {
+			let block = MockBlockInfo { hash: H256::from([i as u8 + 1; 32]), number };
+			let transaction_hash = H256::from([i as u8 + 1; 32]);
+			let log = Log {
+				address: H160::from([if i == 1 { 2u8 } else { 1u8 }; 20]),
+				topics: vec![H256::from([i as u8 + 1; 32])],
+				block_hash: block.hash,
+				transaction_hash,
+				..Default::default()
+			};
+			let receipts = vec![(
+				TransactionSigned::default(),
+				ReceiptInfo { transaction_hash, logs: vec![log], ..Default::default() },
+			)];
+			index.insert(block.hash, block.number, &receipts).await?;
+		}
+		assert_eq!(count(&pool, "log_blooms", None).await, 3);
+
+		let to = 3 * section_size as u64;
+		let section = |i: u64| (i * section_size as u64, (i + 1) * section_size as u64 - 1);
+
+		let query = |address: Option<AddressOrAddresses>, topics: Vec<FilterTopic>| LogQuery {
+			blocks: LogBlocks::Range { from: 0, to },
+			address,
+			topics,
+		};
+
+		// Address only present in the first and last sections.
+		let address_query = query(Some(H160::from([1u8; 20]).into()), vec![]);
+		assert_eq!(
+			index.log_bloom_ranges(0, to, &address_query).await?,
+			vec![section(0), (2 * section_size as u64, to)]
+		);
+
+		// Adjacent sections are merged, and the range is clamped to the requested bounds.
+		let addresses_query =
+			query(Some(vec![H160::from([1u8; 20]), H160::from([2u8; 20])].into()), vec![]);
+		assert_eq!(index.log_bloom_ranges(1, to, &addresses_query).await?, vec![(1, to)]);
+
+		// Topic only present in the second section.
+		let topic_query = query(None, vec![FilterTopic::Single(H256::from([2u8; 32]))]);
+		assert_eq!(index.log_bloom_ranges(0, to, &topic_query).await?, vec![section(1)]);
+
+		// No section matches.
+		let no_match_query = query(Some(H160::from([3u8; 20]).into()), vec![]);
+		assert_eq!(index.log_bloom_ranges(0, to, &no_match_query).await?, vec![]);
+
+		// The blooms can be rebuilt from the logs table.
+		sqlx::query("DELETE FROM log_blooms").execute(&pool).await?;
+		index.rebuild_log_blooms_if_missing().await?;
+		assert_eq!(
+			index.log_bloom_ranges(0, to, &address_query).await?,
+			vec![section(0), (2 * section_size as u64, to)]
+		);
+		Ok(())
+	}
+
+	#[sqlx::test]
+	async fn test_check(pool: SqlitePool) -> anyhow::Result<()> {
+		let provider = setup_sqlite_provider(pool).await;
+		let block = MockBlockInfo { hash: H256::from([1u8; 32]), number: 1 };
+		let receipt = |i: u8| {
+			let transaction_hash = H256::from([i; 32]);
+			(
+				TransactionSigned::default(),
+				ReceiptInfo {
+					transaction_hash,
+					transaction_index: i.into(),
+					logs: vec![Log {
+						block_hash: block.hash,
+						transaction_hash,
+						transaction_index: i.into(),
+						address: H160::from([i; 20]),
+						..Default::default()
+					}],
+					..Default::default()
+				},
+			)
+		};
+
+		provider.insert(&block, &[receipt(0), receipt(1)]).await?;
+		assert_eq!(provider.check(&block, &[receipt(0), receipt(1)]).await?, vec![]);
+
+		let mut modified = receipt(1);
+		modified.1.logs[0].address = H160::from([42u8; 20]);
+		assert_eq!(
+			provider.check(&block, &[receipt(0), modified, receipt(2)]).await?,
+			vec![
+				IndexMismatch::LogsMismatch {
+					transaction_hash: H256::from([1u8; 32]),
+					expected: 1,
+					indexed: 1
+				},
+				IndexMismatch::MissingTransaction {
+					transaction_index: 2,
+					transaction_hash: H256::from([2u8; 32])
+				},
+			]
+		);
+
+		assert_eq!(
+			provider.check(&block, &[receipt(0)]).await?,
+			vec![IndexMismatch::UnexpectedTransaction {
+				transaction_index: 1,
+				transaction_hash: H256::from([1u8; 32])
+			}]
+		);
+		Ok(())
+	}
+
+	#[sqlx::test]
+	async fn test_logs_pagination(pool: SqlitePool) -> anyhow::Result<()> {
+		let index = setup_sqlite_index(pool);
+		for number in [1u8, 2u8] {
+			let block_hash = H256::from([number; 32]);
+			let receipts = (0..2u8)
+				.map(|i| {
+					let transaction_hash = H256::from([number * 10 + i; 32]);
+					let logs = (0..2u8)
+						.map(|log_index| Log {
+							block_hash,
+							block_number: number.into(),
+							transaction_hash,
+							transaction_index: i.into(),
+							log_index: log_index.into(),
+							..Default::default()
+						})
+						.collect();
+					(
+						TransactionSigned::default(),
+						ReceiptInfo {
+							transaction_hash,
+							transaction_index: i.into(),
+							logs,
+							..Default::default()
+						},
+					)
+				})
+				.collect::<Vec<_>>();
+			index.insert(block_hash, number.into(), &receipts).await?;
+		}
+
+		let query =
+			LogQuery { blocks: LogBlocks::Range { from: 1, to: 2 }, address: None, topics: vec![] };
+		let all = index.logs(&query, None, 100).await?;
+		assert_eq!(all.len(), 8);
+
+		let mut pages = Vec::new();
+		loop {
+			let page = index.logs(&query, pages.last(), 3).await?;
+			let is_last_page = page.len() < 3;
+			pages.extend(page);
+			if is_last_page {
+				break;
+			}
+		}
+		assert_eq!(pages, all);
+		Ok(())
+	}
+
+	#[sqlx::test]
+	async fn test_check_reads_all_logs(pool: SqlitePool) -> anyhow::Result<()> {
+		let provider = setup_sqlite_provider(pool).await;
+		let block = MockBlockInfo { hash: H256::from([1u8; 32]), number: 1 };
+		let transaction_hash = H256::from([1u8; 32]);
+		let logs = (0..=LOG_PAGE_SIZE)
+			.map(|log_index| Log {
+				block_hash: block.hash,
+				transaction_hash,
+				log_index: log_index.into(),
+				..Default::default()
+			})
+			.collect();
+		let receipts = vec![(
+			TransactionSigned::default(),
+			ReceiptInfo { transaction_hash, logs, ..Default::default() },
+		)];
+
+		provider.insert(&block, &receipts).await?;
+		assert_eq!(provider.check(&block, &receipts).await?, vec![]);
+		Ok(())
+	}
 }
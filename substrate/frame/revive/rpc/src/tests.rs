@@ -23,13 +23,14 @@ use crate::{
 	example::TransactionBuilder,
 	subxt_client,
 	subxt_client::{src_chain::runtime_types::pallet_revive::primitives::Code, SrcChainConfig},
-	EthRpcClient,
+	DebugRpcClient, EthPubSubRpcClient, EthRpcClient, SubscriptionItem, SubscriptionKind,
 };
 use clap::Parser;
+use futures::StreamExt;
 use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
 use pallet_revive::{
 	create1,
-	evm::{Account, BlockTag, U256},
+	evm::{Account, BlockTag, GenericTransaction, TraceFilter, TransactionSigned, U256},
 };
 use static_init::dynamic;
 use std::{sync::Arc, thread};
@@ -256,6 +257,32 @@ async fn runtime_api_dry_run_addr_works() -> anyhow::Result<()> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn call_and_estimate_gas_without_overrides() -> anyhow::Result<()> {
+	let _lock = SHARED_RESOURCES.write();
+	let client = Arc::new(SharedResources::client().await);
+	let account = Account::default();
+	let ethan = Account::from(subxt_signer::eth::dev::ethan());
+	let value = U256::from(1_000_000_000_000u128);
+
+	let data = TransactionBuilder::new(&client)
+		.value(value)
+		.to(ethan.address())
+		.eth_call()
+		.await?;
+	assert!(data.is_empty());
+
+	let tx = GenericTransaction {
+		from: Some(account.address()),
+		to: Some(ethan.address()),
+		value: Some(value),
+		..Default::default()
+	};
+	let gas = client.estimate_gas(tx, None, None, None).await?;
+	assert!(!gas.is_zero(), "Gas estimate should not be zero.");
+	Ok(())
+}
+
 #[tokio::test]
 async fn invalid_transaction() -> anyhow::Result<()> {
 	let _lock = SHARED_RESOURCES.write();
@@ -275,3 +302,71 @@ async fn invalid_transaction() -> anyhow::Result<()> {
 
 	Ok(())
 }
+
+#[tokio::test]
+async fn subscribe_new_heads_and_pending_transactions() -> anyhow::Result<()> {
+	let _lock = SHARED_RESOURCES.write();
+	let client = Arc::new(SharedResources::client().await);
+	let ethan = Account::from(subxt_signer::eth::dev::ethan());
+
+	let mut heads = client.subscribe(SubscriptionKind::NewHeads, None).await?;
+	let mut pending = client.subscribe(SubscriptionKind::NewPendingTransactions, None).await?;
+
+	let tx = TransactionBuilder::new(&client)
+		.value(U256::from(1_000_000_000_000u128))
+		.to(ethan.address())
+		.send()
+		.await?;
+
+	let item = pending.next().await.expect("Subscription should not end")?;
+	assert_eq!(item, SubscriptionItem::TransactionHash(tx.hash()));
+
+	let receipt = tx.wait_for_receipt().await?;
+	loop {
+		let item = heads.next().await.expect("Subscription should not end")?;
+		let SubscriptionItem::Header(block) = item else { panic!("Expected a block header") };
+		if block.number >= receipt.block_number {
+			break;
+		}
+	}
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn get_raw_data_and_trace_filter() -> anyhow::Result<()> {
+	let _lock = SHARED_RESOURCES.write();
+	let client = Arc::new(SharedResources::client().await);
+	let ethan = Account::from(subxt_signer::eth::dev::ethan());
+
+	let tx = TransactionBuilder::new(&client)
+		.value(U256::from(1_000_000_000_000u128))
+		.to(ethan.address())
+		.send()
+		.await?;
+	let receipt = tx.wait_for_receipt().await?;
+
+	let raw_tx = client.get_raw_transaction(tx.hash()).await?.expect("Transaction should exist");
+	assert_eq!(sp_core::keccak_256(&raw_tx.0), tx.hash().0);
+	assert!(TransactionSigned::decode(&raw_tx.0).is_ok());
+
+	let raw_receipts = client.get_raw_receipts(receipt.block_hash.into()).await?;
+	assert!(raw_receipts.len() > receipt.transaction_index.as_usize());
+
+	let raw_block = client.get_raw_block(receipt.block_hash.into()).await?;
+	assert!(!raw_block.0.is_empty());
+
+	let traces = client
+		.trace_filter(TraceFilter {
+			from_block: Some(receipt.block_number.into()),
+			to_block: Some(receipt.block_number.into()),
+			to_address: Some(vec![ethan.address()]),
+			..Default::default()
+		})
+		.await?;
+	assert_eq!(traces.len(), 1);
+	assert_eq!(traces[0].transaction_hash, tx.hash());
+	assert_eq!(traces[0].trace.trace_address, Vec::<usize>::new());
+
+	Ok(())
+}
@@ -2023,6 +2023,191 @@ mod benchmarks {
 		assert_ok!(result);
 	}
 
+	#[benchmark(pov_mode = Measured)]
+	fn p256_verify() {
+		use hex_literal::hex;
+		let input = hex!("af2bdbe1aa9b6ec1e2ade1d694f41fc71a831d0268e9891562113d8a62add1bf838b8383a430a862e9ec10d483e82a75037b595139679e008a6f740f3bcdc290f02437ca185983123ed4a7d68dc560a16dfac8d245c893bbefd1976500e30bc060fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb67903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299").to_vec();
+		let expected = hex!("0000000000000000000000000000000000000000000000000000000000000001");
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = run_builtin_precompile(
+				&mut ext,
+				H160::from_low_u64_be(0x100).as_fixed_bytes(),
+				input,
+			);
+		}
+
+		assert_eq!(result.unwrap().data, expected);
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn bls12_381_g1_add() {
+		use hex_literal::hex;
+		let input = hex!("000000000000000000000000000000000491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20fd6e10c1b77654d067c0618f6e5a7f79a0000000000000000000000000000000017cd7061575d3e8034fcea62adaa1a3bc38dca4b50e4c5c01d04dd78037c9cee914e17944ea99e7ad84278e5d49f36c4000000000000000000000000000000001301803f8b5ac4a1133581fc676dfedc60d891dd5fa99028805e5ea5b08d3491af75d0707adab3b70c6a6a580217bf81000000000000000000000000000000000e4efa61b558d043cd3fed5c44ac75415de0d032586fecb22acbb67d508cde9f9536a7609d69c1d6e60450843e4ec59a").to_vec();
+		let expected = hex!("00000000000000000000000000000000010d7b8a1f6b4b3e7048d06478b88c0f2257f0517b12fdfe59e33ec6240c39f9fc7d4f04e8a37c33e64258ed2fa45850000000000000000000000000000000000e63cd4ec093d8d1a7211e90fda164304847e028f10ce49263a93df2c7ea0e763e5d0777151e181e63827e4c679b3e64");
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = run_builtin_precompile(
+				&mut ext,
+				H160::from_low_u64_be(0x0b).as_fixed_bytes(),
+				input,
+			);
+		}
+
+		assert_eq!(result.unwrap().data, expected);
+	}
+
+	// `n`: number of point-scalar pairs
+	#[benchmark(pov_mode = Measured)]
+	fn bls12_381_g1_msm(n: Linear<1, { 20 }>) {
+		use hex_literal::hex;
+		use rand::{RngCore, SeedableRng};
+		let point = hex!("0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb0000000000000000000000000000000008b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1");
+		let mut rng = rand_pcg::Pcg64::seed_from_u64(1);
+		let mut input = Vec::new();
+		for _ in 0..n {
+			let mut scalar = [0u8; 32];
+			rng.fill_bytes(&mut scalar);
+			input.extend_from_slice(&point);
+			input.extend_from_slice(&scalar);
+		}
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = run_builtin_precompile(
+				&mut ext,
+				H160::from_low_u64_be(0x0c).as_fixed_bytes(),
+				input,
+			);
+		}
+		assert_ok!(result);
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn bls12_381_g2_add() {
+		use hex_literal::hex;
+		let input = hex!("0000000000000000000000000000000014856c22d8cdb2967c720e963eedc999e738373b14172f06fc915769d3cc5ab7ae0a1b9c38f48b5585fb09d4bd2733bb000000000000000000000000000000000c400b70f6f8cd35648f5c126cce5417f3be4d8eefbd42ceb4286a14df7e03135313fe5845e3a575faab3e8b949d248800000000000000000000000000000000149a0aacc34beba2beb2f2a19a440166e76e373194714f108e4ab1c3fd331e80f4e73e6b9ea65fe3ec96d7136de81544000000000000000000000000000000000e4622fef26bdb9b1e8ef6591a7cc99f5b73164500c1ee224b6a761e676b8799b09a3fd4fa7e242645cc1a34708285e4000000000000000000000000000000000bc2357c6782bbb6a078d9e171fc7a81f7bd8ca73eb485e76317359908bb09bd372fd362a637512a9d48019b383e54890000000000000000000000000000000004b8f49c3bac0247a09487049492b0ed99cf90c56263141daa35f011330d3ced3f3ad78d252c51a3bb42fc7d8f182594000000000000000000000000000000000982d17b17404ac198a0ff5f2dffa56a328d95ec4732d9cca9da420ec7cf716dc63d56d0f5179a8b1ec71fe0328fe88200000000000000000000000000000000147c92cb19e43943bb20c5360a6c4347411eb8ffb3d6f19cc428a8dc0cb3fd1eb3ad02b1c21e21c78f65a7691ee63de9").to_vec();
+		let expected = hex!("00000000000000000000000000000000176f2d00c80b0633cf6d78aa70d6a07ad131212d938e6d3be622a33c5167c2d789ffa79950fdc0da185a2feee14121ec0000000000000000000000000000000018f4d3fc08b0d7af62f8fc30b6b3d3c6472cdfaced0b526bd563408816fcf51c793af5716df6acd50c57c5af07b6613c00000000000000000000000000000000082dc2bb87836bbfbf0c994edc80db7d43731525184b418e467e8e2fb8bbd8ad30b21a8514f92460118cefcea57a76ff000000000000000000000000000000000dbc66842294ed088563e2666bb7fb14be6b3dde63a23f4bb77c609641817d903b611a8bee1f72521fe97a44bfe54c62");
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = run_builtin_precompile(
+				&mut ext,
+				H160::from_low_u64_be(0x0d).as_fixed_bytes(),
+				input,
+			);
+		}
+
+		assert_eq!(result.unwrap().data, expected);
+	}
+
+	// `n`: number of point-scalar pairs
+	#[benchmark(pov_mode = Measured)]
+	fn bls12_381_g2_msm(n: Linear<1, { 20 }>) {
+		use hex_literal::hex;
+		use rand::{RngCore, SeedableRng};
+		let point = hex!("00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb80000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be");
+		let mut rng = rand_pcg::Pcg64::seed_from_u64(1);
+		let mut input = Vec::new();
+		for _ in 0..n {
+			let mut scalar = [0u8; 32];
+			rng.fill_bytes(&mut scalar);
+			input.extend_from_slice(&point);
+			input.extend_from_slice(&scalar);
+		}
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = run_builtin_precompile(
+				&mut ext,
+				H160::from_low_u64_be(0x0e).as_fixed_bytes(),
+				input,
+			);
+		}
+		assert_ok!(result);
+	}
+
+	// `n`: pairings to perform
+	#[benchmark(pov_mode = Measured)]
+	fn bls12_381_pairing_check(n: Linear<1, { 20 }>) {
+		use hex_literal::hex;
+		let g1 = hex!("0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb0000000000000000000000000000000008b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1");
+		let g2 = hex!("00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb80000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be");
+		let input = (0..n).flat_map(|_| g1.iter().chain(g2.iter()).copied()).collect::<Vec<_>>();
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = run_builtin_precompile(
+				&mut ext,
+				H160::from_low_u64_be(0x0f).as_fixed_bytes(),
+				input,
+			);
+		}
+		assert_ok!(result);
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn bls12_381_map_fp_to_g1() {
+		use hex_literal::hex;
+		let input = hex!("000000000000000000000000000000000d921c33f2bad966478a03ca35d05719bdf92d347557ea166e5bba579eea9b83e9afa5c088573c2281410369fbd32951").to_vec();
+		let expected = hex!("0000000000000000000000000000000019306391252335df0cc818cceedf113db5fea963fd013afccff5b6fadcb22a1a35a586179ffe391d6010796afaf019500000000000000000000000000000000005a6eb7ad22e39c22e13cd3e8a929d1dbe00e931fc8b776886b3c4e02b5208f8c66b65c78d3d33a9a51288e79518edff");
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = run_builtin_precompile(
+				&mut ext,
+				H160::from_low_u64_be(0x10).as_fixed_bytes(),
+				input,
+			);
+		}
+
+		assert_eq!(result.unwrap().data, expected);
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn bls12_381_map_fp2_to_g2() {
+		use hex_literal::hex;
+		let input = hex!("000000000000000000000000000000000ab114989f4b84d981ec0d0029477bdb046cbfb0ce7c2aada72e8c8218fa80d84d2cff2930428e15753c98a3862caddc00000000000000000000000000000000122ed45116c8dfd8dba19161d27720dabf73bbbe5c8e43e4d880f3d38e78c06b70f837d8dd80c6abd61c8820317f439f").to_vec();
+		let expected = hex!("000000000000000000000000000000000156d038e789107ed67c8214ca469daa7c2f3cefff9de1b3787c195c725916cb8ba1fd2918761fe401e3b02fdfc5d8f900000000000000000000000000000000103f93eb9aaf33233594cc94eec8fd1d003d6822ce910d1825df12b1f9c6fdcdf5085317172279c1749ac13013890c0a00000000000000000000000000000000010c995dd87ed69b0459403f9965118094b18086985e64d2b91bd50a585a16688b06d6c5802286efeb2f3ec341088d20000000000000000000000000000000000f02c56775a29b38ebfb4408537ebf3071cf50093f6addf4102f4fefef85fccaf78fcbdaaf93829924d651fc94bb5824");
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = run_builtin_precompile(
+				&mut ext,
+				H160::from_low_u64_be(0x11).as_fixed_bytes(),
+				input,
+			);
+		}
+
+		assert_eq!(result.unwrap().data, expected);
+	}
+
 	// Only calling the function itself for the list of
 	// generated different ECDSA keys.
 	// This is a slow call: We reduce the number of runs.
@@ -23,5 +23,7 @@ mod tracing;
 pub use tracing::*;
 mod gas_encoder;
 pub use gas_encoder::*;
+mod tx_extension;
+pub use tx_extension::*;
 pub mod runtime;
 pub use alloy_core::sol_types::decode_revert_reason;
@@ -15,7 +15,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::evm::Bytes;
+use crate::evm::{AccessList, BlockNumberOrTag, Bytes};
 use alloc::{collections::BTreeMap, string::String, vec::Vec};
 use codec::{Decode, Encode};
 use derive_more::From;
@@ -36,6 +36,12 @@ pub enum TracerType {
 
 	/// A tracer that traces the prestate.
 	PrestateTracer(Option<PrestateTracerConfig>),
+
+	/// A tracer that records the accessed accounts and storage keys as an EIP-2930 access list.
+	AccessListTracer,
+
+	/// A tracer that logs every executed instruction, like geth's default struct logger.
+	StructLogger(Option<StructLoggerConfig>),
 }
 
 impl From<CallTracerConfig> for TracerType {
@@ -100,6 +106,47 @@ impl Default for PrestateTracerConfig {
 	}
 }
 
+/// The default maximum number of steps logged by the struct logger.
+pub const DEFAULT_STRUCT_LOGGER_LIMIT: u64 = 10_000;
+
+/// The maximum number of steps the struct logger can log.
+pub const MAX_STRUCT_LOGGER_LIMIT: u64 = 100_000;
+
+/// The configuration for the struct logger.
+///
+/// Capturing the registers and the memory of every step is expensive, both are omitted unless
+/// requested.
+#[derive(Clone, Debug, Decode, Serialize, Deserialize, Encode, PartialEq, TypeInfo)]
+#[serde(default, rename_all = "camelCase")]
+pub struct StructLoggerConfig {
+	/// Whether to include the stack, the registers, in the logged steps.
+	pub enable_stack: bool,
+
+	/// Whether to include the memory in the logged steps.
+	pub enable_memory: bool,
+
+	/// Whether to omit the storage from the logged steps.
+	pub disable_storage: bool,
+
+	/// Whether to include the return data in the logged steps.
+	pub enable_return_data: bool,
+
+	/// The maximum number of steps to log, between `1` and [`MAX_STRUCT_LOGGER_LIMIT`].
+	pub limit: u64,
+}
+
+impl Default for StructLoggerConfig {
+	fn default() -> Self {
+		Self {
+			enable_stack: false,
+			enable_memory: false,
+			disable_storage: false,
+			enable_return_data: false,
+			limit: DEFAULT_STRUCT_LOGGER_LIMIT,
+		}
+	}
+}
+
 /// Serialization should support the following JSON format:
 ///
 /// ```json
@@ -137,6 +184,17 @@ fn test_tracer_config_serialization() {
 				timeout: Some(core::time::Duration::from_millis(10)),
 			},
 		),
+		(
+			r#"{"tracer": "structLogger", "tracerConfig": { "enableStack": true, "enableReturnData": true }}"#,
+			TracerConfig {
+				config: TracerType::StructLogger(Some(StructLoggerConfig {
+					enable_stack: true,
+					enable_return_data: true,
+					..Default::default()
+				})),
+				timeout: None,
+			},
+		),
 	];
 
 	for (json_data, expected) in tracers {
@@ -173,6 +231,10 @@ pub enum Trace {
 	Call(CallTrace),
 	/// A prestate trace.
 	Prestate(PrestateTrace),
+	/// An access list trace.
+	AccessList(AccessList),
+	/// A struct logger trace.
+	StructLogger(StructLoggerTrace),
 }
 
 /// A prestate Trace
@@ -309,6 +371,59 @@ pub struct CallLog {
 	pub position: u32,
 }
 
+/// A struct logger trace, listing every instruction executed by a transaction.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLoggerTrace<Gas = U256> {
+	/// Amount of gas used by the transaction.
+	pub gas: Gas,
+	/// Whether the transaction failed.
+	pub failed: bool,
+	/// The data returned by the transaction.
+	pub return_value: Bytes,
+	/// The logged steps.
+	pub struct_logs: Vec<StructLog>,
+}
+
+/// A single instruction logged by the struct logger.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+	/// The program counter of the instruction.
+	pub pc: u64,
+	/// The name of the instruction.
+	pub op: String,
+	/// The gas left before executing the instruction.
+	pub gas: u64,
+	/// The gas consumed by the instruction.
+	pub gas_cost: u64,
+	/// The call depth, starting at `1` for the top level call.
+	pub depth: u32,
+	/// The stack before executing the instruction.
+	///
+	/// For PolkaVM contracts these are the values of the registers.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub stack: Option<Vec<U256>>,
+	/// The memory before executing the instruction, in 32 bytes words.
+	///
+	/// For PolkaVM contracts this is the used part of the guest stack.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub memory: Option<Vec<Bytes>>,
+	/// The storage slots of the current contract accessed so far.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub storage: Option<BTreeMap<H256, H256>>,
+	/// The data returned by the last call made by the current contract.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub return_data: Option<Bytes>,
+	/// The error raised by the instruction, if any.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+}
+
 /// A transaction trace
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct TransactionTrace {
@@ -319,3 +434,297 @@ pub struct TransactionTrace {
 	#[serde(rename = "result")]
 	pub trace: Trace,
 }
+
+/// The parameters of `trace_filter`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TraceFilter {
+	/// The first block to trace, defaults to the latest block.
+	pub from_block: Option<BlockNumberOrTag>,
+	/// The last block to trace, defaults to the latest block.
+	pub to_block: Option<BlockNumberOrTag>,
+	/// Only return the traces of calls sent by one of these addresses.
+	pub from_address: Option<Vec<H160>>,
+	/// Only return the traces of calls received by (or contracts created at) one of these
+	/// addresses.
+	pub to_address: Option<Vec<H160>>,
+	/// The number of matching traces to skip.
+	pub after: Option<u64>,
+	/// The maximum number of traces to return.
+	pub count: Option<u64>,
+}
+
+impl TraceFilter {
+	/// Returns `true` if the trace matches the address filters.
+	pub fn matches(&self, trace: &FlatTrace) -> bool {
+		let (from, to) = match (&trace.action, &trace.result) {
+			(FlatTraceAction::Call(action), _) => (action.from, Some(action.to)),
+			(FlatTraceAction::Create(action), Some(FlatTraceResult::Create(result))) =>
+				(action.from, Some(result.address)),
+			(FlatTraceAction::Create(action), _) => (action.from, None),
+		};
+
+		let from_matches = self.from_address.as_ref().map_or(true, |addrs| addrs.contains(&from));
+		let to_matches = self
+			.to_address
+			.as_ref()
+			.map_or(true, |addrs| to.map_or(false, |to| addrs.contains(&to)));
+		from_matches && to_matches
+	}
+}
+
+/// A Parity-style flat trace of a single call frame.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FlatTrace {
+	/// The call or create action.
+	pub action: FlatTraceAction,
+	/// The result of the action, `None` if it failed.
+	pub result: Option<FlatTraceResult>,
+	/// The error message if the action failed.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+	/// The number of direct sub-calls.
+	pub subtraces: usize,
+	/// The path of the call frame in the call tree.
+	pub trace_address: Vec<usize>,
+	/// The type of the action, either `call` or `create`.
+	#[serde(rename = "type")]
+	pub trace_type: String,
+}
+
+/// The action of a [`FlatTrace`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum FlatTraceAction {
+	/// A call to an existing account.
+	Call(FlatCallAction),
+	/// The creation of a contract.
+	Create(FlatCreateAction),
+}
+
+/// A call action.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FlatCallAction {
+	/// Address of the sender.
+	pub from: H160,
+	/// Address of the receiver.
+	pub to: H160,
+	/// Amount of value transferred.
+	pub value: U256,
+	/// Amount of gas provided for the call.
+	pub gas: U256,
+	/// Call input data.
+	pub input: Bytes,
+	/// The type of call, e.g. `call`, `staticcall` or `delegatecall`.
+	pub call_type: String,
+}
+
+/// A create action.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FlatCreateAction {
+	/// Address of the sender.
+	pub from: H160,
+	/// Amount of value transferred.
+	pub value: U256,
+	/// Amount of gas provided for the creation.
+	pub gas: U256,
+	/// The init code.
+	pub init: Bytes,
+	/// The creation method, either `create` or `create2`.
+	pub creation_method: String,
+}
+
+/// The result of a [`FlatTrace`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum FlatTraceResult {
+	/// The result of a call.
+	Call(FlatCallResult),
+	/// The result of a contract creation.
+	Create(FlatCreateResult),
+}
+
+/// The result of a call action.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FlatCallResult {
+	/// Amount of gas used.
+	pub gas_used: U256,
+	/// Return data.
+	pub output: Bytes,
+}
+
+/// The result of a create action.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FlatCreateResult {
+	/// Amount of gas used.
+	pub gas_used: U256,
+	/// The data returned by the constructor.
+	pub code: Bytes,
+	/// Address of the created contract.
+	pub address: H160,
+}
+
+/// A [`FlatTrace`] along with the position of its transaction in the chain.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalizedTrace {
+	/// The trace.
+	#[serde(flatten)]
+	pub trace: FlatTrace,
+	/// The hash of the block containing the transaction.
+	pub block_hash: H256,
+	/// The number of the block containing the transaction.
+	pub block_number: u64,
+	/// The hash of the transaction.
+	pub transaction_hash: H256,
+	/// The index of the transaction in the block.
+	pub transaction_position: u64,
+}
+
+impl CallTrace {
+	/// Flatten the call tree into Parity-style traces, in depth-first order.
+	pub fn into_flat_traces(self) -> Vec<FlatTrace> {
+		let mut traces = Vec::new();
+		self.flatten_into(Vec::new(), &mut traces);
+		traces
+	}
+
+	fn flatten_into(self, trace_address: Vec<usize>, traces: &mut Vec<FlatTrace>) {
+		let CallTrace {
+			from,
+			gas,
+			gas_used,
+			to,
+			input,
+			output,
+			error,
+			calls,
+			value,
+			call_type,
+			..
+		} = self;
+		let value = value.unwrap_or_default();
+
+		let (action, result, trace_type) = match call_type {
+			CallType::Create | CallType::Create2 => (
+				FlatTraceAction::Create(FlatCreateAction {
+					from,
+					value,
+					gas,
+					init: input,
+					creation_method: if call_type == CallType::Create2 {
+						"create2"
+					} else {
+						"create"
+					}
+					.into(),
+				}),
+				FlatTraceResult::Create(FlatCreateResult { gas_used, code: output, address: to }),
+				"create",
+			),
+			CallType::Call | CallType::StaticCall | CallType::DelegateCall => (
+				FlatTraceAction::Call(FlatCallAction {
+					from,
+					to,
+					value,
+					gas,
+					input,
+					call_type: match call_type {
+						CallType::StaticCall => "staticcall",
+						CallType::DelegateCall => "delegatecall",
+						_ => "call",
+					}
+					.into(),
+				}),
+				FlatTraceResult::Call(FlatCallResult { gas_used, output }),
+				"call",
+			),
+		};
+
+		traces.push(FlatTrace {
+			action,
+			result: if error.is_none() { Some(result) } else { None },
+			error,
+			subtraces: calls.len(),
+			trace_address: trace_address.clone(),
+			trace_type: trace_type.into(),
+		});
+
+		for (i, call) in calls.into_iter().enumerate() {
+			let mut address = trace_address.clone();
+			address.push(i);
+			call.flatten_into(address, traces);
+		}
+	}
+}
+
+#[test]
+fn flat_traces_works() {
+	let trace = CallTrace {
+		from: H160::from([1u8; 20]),
+		to: H160::from([2u8; 20]),
+		gas: 100.into(),
+		gas_used: 50.into(),
+		calls: vec![
+			CallTrace {
+				from: H160::from([2u8; 20]),
+				to: H160::from([3u8; 20]),
+				call_type: CallType::StaticCall,
+				..Default::default()
+			},
+			CallTrace {
+				from: H160::from([2u8; 20]),
+				to: H160::from([4u8; 20]),
+				call_type: CallType::Create2,
+				error: Some("execution reverted".into()),
+				..Default::default()
+			},
+		],
+		..Default::default()
+	};
+
+	let traces = trace.into_flat_traces();
+	assert_eq!(
+		traces
+			.iter()
+			.map(|t| (t.trace_address.clone(), t.subtraces))
+			.collect::<Vec<_>>(),
+		vec![(vec![], 2), (vec![0], 0), (vec![1], 0)]
+	);
+
+	assert_eq!(
+		serde_json::to_value(&traces[1]).unwrap(),
+		serde_json::json!({
+			"action": {
+				"from": "0x0202020202020202020202020202020202020202",
+				"to": "0x0303030303030303030303030303030303030303",
+				"value": "0x0",
+				"gas": "0x0",
+				"input": "0x",
+				"callType": "staticcall"
+			},
+			"result": { "gasUsed": "0x0", "output": "0x" },
+			"subtraces": 0,
+			"traceAddress": [0],
+			"type": "call"
+		})
+	);
+
+	assert_eq!(traces[2].trace_type, "create");
+	assert_eq!(traces[2].result, None);
+	assert_eq!(traces[2].error, Some("execution reverted".into()));
+
+	let filter =
+		TraceFilter { to_address: Some(vec![H160::from([3u8; 20])]), ..Default::default() };
+	assert_eq!(traces.iter().filter(|t| filter.matches(t)).count(), 1);
+
+	// Failed creations have no address to match against.
+	let filter =
+		TraceFilter { to_address: Some(vec![H160::from([4u8; 20])]), ..Default::default() };
+	assert_eq!(traces.iter().filter(|t| filter.matches(t)).count(), 0);
+}
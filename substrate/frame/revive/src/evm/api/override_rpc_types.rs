@@ -0,0 +1,103 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::evm::Bytes;
+use alloc::collections::BTreeMap;
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+use sp_core::{H160, H256, U256};
+
+/// The state overrides applied before executing a call, keyed by account address.
+pub type StateOverride = BTreeMap<H160, AccountOverride>;
+
+/// The fields of an account overridden before executing a call.
+#[derive(
+	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountOverride {
+	/// Fake balance to set for the account.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub balance: Option<U256>,
+	/// Fake nonce to set for the account.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub nonce: Option<U256>,
+	/// Fake code to deploy at the account.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub code: Option<Bytes>,
+	/// Fake storage replacing the whole storage of the account.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub state: Option<BTreeMap<H256, H256>>,
+	/// Fake storage slots patched into the storage of the account.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub state_diff: Option<BTreeMap<H256, H256>>,
+}
+
+/// The block fields overridden before executing a call.
+#[derive(
+	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockOverrides {
+	/// Fake block number.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub number: Option<U256>,
+	/// Fake block timestamp, in seconds.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub time: Option<U256>,
+	/// Fake base fee, returned by the `BASEFEE` opcode.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub base_fee_per_gas: Option<U256>,
+}
+
+/// The overrides applied to the state and block a dry run is executed against.
+#[derive(Debug, Default, Clone, Encode, Decode, TypeInfo, Eq, PartialEq)]
+pub struct DryRunOverrides {
+	/// The state overrides.
+	pub state: StateOverride,
+	/// The block overrides.
+	pub block: BlockOverrides,
+}
+
+impl DryRunOverrides {
+	/// Returns `true` if nothing is overridden.
+	pub fn is_empty(&self) -> bool {
+		self.state.is_empty() && self.block == Default::default()
+	}
+}
+
+#[test]
+fn state_override_deserialization_works() {
+	let json = r#"{
+		"0x0000000000000000000000000000000000000010": {
+			"balance": "0xde0b6b3a7640000",
+			"nonce": "0x2",
+			"stateDiff": {
+				"0x0000000000000000000000000000000000000000000000000000000000000001": "0x00000000000000000000000000000000000000000000000000000000000000ff"
+			}
+		}
+	}"#;
+
+	let overrides: StateOverride = serde_json::from_str(json).unwrap();
+	let account = AccountOverride {
+		balance: Some(U256::from(1_000_000_000_000_000_000u64)),
+		nonce: Some(U256::from(2)),
+		state_diff: Some(BTreeMap::from([(H256::from_low_u64_be(1), H256::from_low_u64_be(0xff))])),
+		..Default::default()
+	};
+	assert_eq!(overrides, BTreeMap::from([(H160::from_low_u64_be(0x10), account)]));
+}
@@ -27,6 +27,10 @@ impl TransactionUnsigned {
 		use TransactionUnsigned::*;
 		let mut s = rlp::RlpStream::new();
 		match self {
+			Transaction7702Unsigned(ref tx) => {
+				s.append(&tx.r#type.value());
+				s.append(tx);
+			},
 			Transaction2930Unsigned(ref tx) => {
 				s.append(&tx.r#type.value());
 				s.append(tx);
@@ -54,6 +58,7 @@ impl TransactionSigned {
 		use TransactionSigned::*;
 		use TransactionUnsigned::*;
 		match self {
+			Transaction7702Signed(tx) => Transaction7702Unsigned(tx.transaction_7702_unsigned),
 			Transaction2930Signed(tx) => Transaction2930Unsigned(tx.transaction_2930_unsigned),
 			Transaction1559Signed(tx) => Transaction1559Unsigned(tx.transaction_1559_unsigned),
 			Transaction4844Signed(tx) => Transaction4844Unsigned(tx.transaction_4844_unsigned),
@@ -67,6 +72,10 @@ impl TransactionSigned {
 		use TransactionSigned::*;
 		let mut s = rlp::RlpStream::new();
 		match self {
+			Transaction7702Signed(ref tx) => {
+				s.append(&tx.transaction_7702_unsigned.r#type.value());
+				s.append(tx);
+			},
 			Transaction2930Signed(ref tx) => {
 				s.append(&tx.transaction_2930_unsigned.r#type.value());
 				s.append(tx);
@@ -96,11 +105,102 @@ impl TransactionSigned {
 			TYPE_EIP2930 => rlp::decode::<Transaction2930Signed>(&data[1..]).map(Into::into),
 			TYPE_EIP1559 => rlp::decode::<Transaction1559Signed>(&data[1..]).map(Into::into),
 			TYPE_EIP4844 => rlp::decode::<Transaction4844Signed>(&data[1..]).map(Into::into),
+			TYPE_EIP7702 => rlp::decode::<Transaction7702Signed>(&data[1..]).map(Into::into),
 			_ => rlp::decode::<TransactionLegacySigned>(data).map(Into::into),
 		}
 	}
 }
 
+impl ReceiptInfo {
+	/// Encode the receipt into bytes, as defined by [EIP-2718](https://eips.ethereum.org/EIPS/eip-2718).
+	pub fn receipt_payload(&self) -> Vec<u8> {
+		let mut s = rlp::RlpStream::new();
+		s.begin_list(4);
+		s.append(&self.status.unwrap_or_default());
+		s.append(&self.cumulative_gas_used);
+		s.append(&self.logs_bloom.0.to_vec());
+		s.begin_list(self.logs.len());
+		for log in &self.logs {
+			s.begin_list(3);
+			s.append(&log.address);
+			s.append_list(&log.topics);
+			s.append(&log.data.clone().unwrap_or_default().0);
+		}
+
+		match self.r#type {
+			Some(Byte(r#type)) if r#type != TYPE_LEGACY => [&[r#type][..], &s.out()].concat(),
+			_ => s.out().to_vec(),
+		}
+	}
+}
+
+impl Block {
+	/// Encode the block header and transactions into bytes.
+	///
+	/// The block is expected to be hydrated, i.e. to contain the full transactions, transaction
+	/// hashes can't be encoded and are skipped.
+	pub fn block_payload(&self) -> Vec<u8> {
+		let transactions = match &self.transactions {
+			HashesOrTransactionInfos::TransactionInfos(infos) => infos.as_slice(),
+			HashesOrTransactionInfos::Hashes(_) => &[],
+		};
+
+		let mut s = rlp::RlpStream::new_list(3);
+
+		let optional_fields = [
+			self.base_fee_per_gas.is_some(),
+			self.withdrawals_root.is_some(),
+			self.blob_gas_used.is_some(),
+			self.excess_blob_gas.is_some(),
+			self.parent_beacon_block_root.is_some(),
+		];
+		// Trailing optional fields are only encoded up to the last one that is set.
+		let optional_len = optional_fields.iter().rposition(|set| *set).map_or(0, |i| i + 1);
+		s.begin_list(15 + optional_len);
+		s.append(&self.parent_hash);
+		s.append(&self.sha_3_uncles);
+		s.append(&self.miner);
+		s.append(&self.state_root);
+		s.append(&self.transactions_root);
+		s.append(&self.receipts_root);
+		s.append(&self.logs_bloom.0.to_vec());
+		s.append(&self.difficulty.unwrap_or_default());
+		s.append(&self.number);
+		s.append(&self.gas_limit);
+		s.append(&self.gas_used);
+		s.append(&self.timestamp);
+		s.append(&self.extra_data.0);
+		s.append(&self.mix_hash);
+		s.append(&self.nonce.0.to_vec());
+		for i in 0..optional_len {
+			match i {
+				0 => s.append(&self.base_fee_per_gas.unwrap_or_default()),
+				1 => s.append(&self.withdrawals_root.unwrap_or_default()),
+				2 => s.append(&self.blob_gas_used.unwrap_or_default()),
+				3 => s.append(&self.excess_blob_gas.unwrap_or_default()),
+				_ => s.append(&self.parent_beacon_block_root.unwrap_or_default()),
+			};
+		}
+
+		s.begin_list(transactions.len());
+		for info in transactions {
+			let payload = info.transaction_signed.signed_payload();
+			match info.transaction_signed {
+				// Legacy transactions are embedded as RLP lists, typed ones as byte strings.
+				TransactionSigned::TransactionLegacySigned(_) => s.append_raw(&payload, 1),
+				_ => s.append(&payload),
+			};
+		}
+
+		s.begin_list(self.uncles.len());
+		for uncle in &self.uncles {
+			s.append(uncle);
+		}
+
+		s.out().to_vec()
+	}
+}
+
 impl TransactionUnsigned {
 	/// Get a signed transaction payload with a dummy 65 bytes signature.
 	pub fn dummy_signed_payload(self) -> Vec<u8> {
@@ -273,6 +373,97 @@ impl Decodable for Transaction1559Signed {
 	}
 }
 
+/// See <https://eips.ethereum.org/EIPS/eip-7702>
+impl Encodable for AuthorizationListEntry {
+	fn rlp_append(&self, s: &mut rlp::RlpStream) {
+		s.begin_list(6);
+		s.append(&self.chain_id);
+		s.append(&self.address);
+		s.append(&self.nonce);
+		s.append(&self.y_parity);
+		s.append(&self.r);
+		s.append(&self.s);
+	}
+}
+
+impl Decodable for AuthorizationListEntry {
+	fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+		Ok(AuthorizationListEntry {
+			chain_id: rlp.val_at(0)?,
+			address: rlp.val_at(1)?,
+			nonce: rlp.val_at(2)?,
+			y_parity: rlp.val_at(3)?,
+			r: rlp.val_at(4)?,
+			s: rlp.val_at(5)?,
+		})
+	}
+}
+
+/// See <https://eips.ethereum.org/EIPS/eip-7702>
+impl Encodable for Transaction7702Unsigned {
+	fn rlp_append(&self, s: &mut rlp::RlpStream) {
+		s.begin_list(10);
+		s.append(&self.chain_id);
+		s.append(&self.nonce);
+		s.append(&self.max_priority_fee_per_gas);
+		s.append(&self.max_fee_per_gas);
+		s.append(&self.gas);
+		s.append(&self.to);
+		s.append(&self.value);
+		s.append(&self.input.0);
+		s.append_list(&self.access_list);
+		s.append_list(&self.authorization_list);
+	}
+}
+
+/// See <https://eips.ethereum.org/EIPS/eip-7702>
+impl Encodable for Transaction7702Signed {
+	fn rlp_append(&self, s: &mut rlp::RlpStream) {
+		let tx = &self.transaction_7702_unsigned;
+		s.begin_list(13);
+		s.append(&tx.chain_id);
+		s.append(&tx.nonce);
+		s.append(&tx.max_priority_fee_per_gas);
+		s.append(&tx.max_fee_per_gas);
+		s.append(&tx.gas);
+		s.append(&tx.to);
+		s.append(&tx.value);
+		s.append(&tx.input.0);
+		s.append_list(&tx.access_list);
+		s.append_list(&tx.authorization_list);
+
+		s.append(&self.y_parity);
+		s.append(&self.r);
+		s.append(&self.s);
+	}
+}
+
+impl Decodable for Transaction7702Signed {
+	fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+		Ok(Transaction7702Signed {
+			transaction_7702_unsigned: {
+				Transaction7702Unsigned {
+					chain_id: rlp.val_at(0)?,
+					nonce: rlp.val_at(1)?,
+					max_priority_fee_per_gas: rlp.val_at(2)?,
+					max_fee_per_gas: rlp.val_at(3)?,
+					gas: rlp.val_at(4)?,
+					to: rlp.val_at(5)?,
+					value: rlp.val_at(6)?,
+					input: Bytes(rlp.val_at(7)?),
+					access_list: rlp.list_at(8)?,
+					authorization_list: rlp.list_at(9)?,
+					..Default::default()
+				}
+			},
+			y_parity: rlp.val_at(10)?,
+			r: rlp.val_at(11)?,
+			s: rlp.val_at(12)?,
+			..Default::default()
+		})
+	}
+}
+
 //See https://eips.ethereum.org/EIPS/eip-2930
 impl Encodable for Transaction2930Unsigned {
 	fn rlp_append(&self, s: &mut rlp::RlpStream) {
@@ -557,6 +748,43 @@ mod test {
 					"yParity": "0x0"
 				}
 				"#
+			),
+			// type 4: EIP7702
+			(
+				"04f8fa018080018301e24194095e7baea6a6c7c4c2dfeb977efac326af552d878080f838f7940000000000000000000000000000000000000001e1a00000000000000000000000000000000000000000000000000000000000000000f85cf85a019400000000000000000000000000000000000000018001a0fe38ca4e44a30002ac54af7cf922a6ac2ba11b7d22f548e8ecb3f51f41cb31b0a06de6a5cbae13c0c856e33acf021b51819636cfc009d39eafb9f606d546e305a880a0fe38ca4e44a30002ac54af7cf922a6ac2ba11b7d22f548e8ecb3f51f41cb31b0a06de6a5cbae13c0c856e33acf021b51819636cfc009d39eafb9f606d546e305a8",
+				r#"
+				{
+					"accessList": [
+						{
+							"address": "0x0000000000000000000000000000000000000001",
+							"storageKeys": ["0x0000000000000000000000000000000000000000000000000000000000000000"]
+						}
+					],
+					"authorizationList": [
+						{
+							"chainId": "0x1",
+							"address": "0x0000000000000000000000000000000000000001",
+							"nonce": "0x0",
+							"yParity": "0x1",
+							"r": "0xfe38ca4e44a30002ac54af7cf922a6ac2ba11b7d22f548e8ecb3f51f41cb31b0",
+							"s": "0x6de6a5cbae13c0c856e33acf021b51819636cfc009d39eafb9f606d546e305a8"
+						}
+					],
+					"chainId": "0x1",
+					"gas": "0x1e241",
+					"gasPrice": "0x0",
+					"input": "0x",
+					"maxFeePerGas": "0x1",
+					"maxPriorityFeePerGas": "0x0",
+					"nonce": "0x0",
+					"to": "0x095e7baea6a6c7c4c2dfeb977efac326af552d87",
+					"type": "0x4",
+					"value": "0x0",
+					"r": "0xfe38ca4e44a30002ac54af7cf922a6ac2ba11b7d22f548e8ecb3f51f41cb31b0",
+					"s": "0x6de6a5cbae13c0c856e33acf021b51819636cfc009d39eafb9f606d546e305a8",
+					"yParity": "0x0"
+				}
+				"#
 			)
 		];
 
@@ -587,4 +815,71 @@ mod test {
 		let payload = Account::default().sign_transaction(tx).signed_payload();
 		assert_eq!(dummy_signed_payload.len(), payload.len());
 	}
+
+	#[test]
+	fn receipt_payload_works() {
+		let receipt = ReceiptInfo {
+			status: Some(U256::one()),
+			cumulative_gas_used: U256::from(21000),
+			logs: vec![Log {
+				address: H160::from([0x11; 20]),
+				topics: vec![H256::from([0x22; 32])],
+				data: Some(Bytes(vec![1, 2])),
+				..Default::default()
+			}],
+			r#type: Some(Byte(TYPE_EIP1559)),
+			..Default::default()
+		};
+
+		let payload = receipt.receipt_payload();
+		assert_eq!(payload[0], TYPE_EIP1559);
+		let rlp = rlp::Rlp::new(&payload[1..]);
+		assert_eq!(rlp.item_count().unwrap(), 4);
+		assert_eq!(rlp.val_at::<U256>(0).unwrap(), U256::one());
+		assert_eq!(rlp.val_at::<U256>(1).unwrap(), U256::from(21000));
+		assert_eq!(rlp.at(2).unwrap().data().unwrap(), &[0u8; 256][..]);
+		let log = rlp.at(3).unwrap().at(0).unwrap();
+		assert_eq!(log.val_at::<H160>(0).unwrap(), H160::from([0x11; 20]));
+		assert_eq!(log.list_at::<H256>(1).unwrap(), vec![H256::from([0x22; 32])]);
+		assert_eq!(log.val_at::<Vec<u8>>(2).unwrap(), vec![1, 2]);
+
+		// Legacy receipts are not prefixed with their type.
+		let receipt = ReceiptInfo { r#type: Some(Byte(TYPE_LEGACY)), ..receipt };
+		assert_eq!(receipt.receipt_payload(), payload[1..].to_vec());
+	}
+
+	#[test]
+	fn block_payload_works() {
+		let tx = Account::default().sign_transaction(
+			TransactionLegacyUnsigned {
+				chain_id: Some(596.into()),
+				gas: U256::from(21000),
+				nonce: U256::from(1),
+				..Default::default()
+			}
+			.into(),
+		);
+		let block = Block {
+			number: U256::from(42),
+			base_fee_per_gas: Some(U256::from(1000)),
+			transactions: HashesOrTransactionInfos::TransactionInfos(vec![TransactionInfo {
+				transaction_signed: tx.clone(),
+				..Default::default()
+			}]),
+			..Default::default()
+		};
+
+		let payload = block.block_payload();
+		let rlp = rlp::Rlp::new(&payload);
+		assert_eq!(rlp.item_count().unwrap(), 3);
+
+		let header = rlp.at(0).unwrap();
+		assert_eq!(header.item_count().unwrap(), 16);
+		assert_eq!(header.val_at::<U256>(8).unwrap(), U256::from(42));
+		assert_eq!(header.val_at::<U256>(15).unwrap(), U256::from(1000));
+
+		let transactions = rlp.at(1).unwrap();
+		assert_eq!(transactions.at(0).unwrap().as_raw(), &tx.signed_payload()[..]);
+		assert_eq!(rlp.at(2).unwrap().item_count().unwrap(), 0);
+	}
 }
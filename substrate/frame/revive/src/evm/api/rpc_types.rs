@@ -16,9 +16,26 @@
 // limitations under the License.
 //! Utility impl for the RPC types.
 use super::*;
-use alloc::vec::Vec;
+use alloc::{string::String, vec::Vec};
+use serde::{Deserialize, Serialize};
 use sp_core::{H160, U256};
 
+/// The result of `eth_createAccessList`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListResult {
+	/// The accounts and storage keys accessed by the transaction.
+	pub access_list: AccessList,
+	/// The error message, if the transaction failed.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+	/// The gas used by the transaction with the access list.
+	///
+	/// Accessing an account or a storage key costs the same whether it is listed or not, the
+	/// access list only adds the cost of its size to the transaction.
+	pub gas_used: U256,
+}
+
 impl From<BlockNumberOrTag> for BlockNumberOrTagOrHash {
 	fn from(b: BlockNumberOrTag) -> Self {
 		match b {
@@ -32,6 +49,7 @@ impl From<TransactionSigned> for TransactionUnsigned {
 	fn from(tx: TransactionSigned) -> Self {
 		use TransactionSigned::*;
 		match tx {
+			Transaction7702Signed(tx) => tx.transaction_7702_unsigned.into(),
 			Transaction4844Signed(tx) => tx.transaction_4844_unsigned.into(),
 			Transaction1559Signed(tx) => tx.transaction_1559_unsigned.into(),
 			Transaction2930Signed(tx) => tx.transaction_2930_unsigned.into(),
@@ -40,6 +58,70 @@ impl From<TransactionSigned> for TransactionUnsigned {
 	}
 }
 
+impl Filter {
+	/// Returns `true` if the log matches the address and topics criteria of the filter.
+	///
+	/// This follows the `eth_getLogs` semantics: topics are matched positionally, and a log with
+	/// fewer topics than the filter never matches. Block range criteria are not checked.
+	pub fn matches_log(&self, log: &Log) -> bool {
+		let address_matches = match &self.address {
+			None => true,
+			Some(AddressOrAddresses::Address(address)) => *address == log.address,
+			Some(AddressOrAddresses::Addresses(addresses)) => addresses.contains(&log.address),
+		};
+
+		if !address_matches {
+			return false;
+		}
+
+		let Some(topics) = &self.topics else { return true };
+		topics.iter().enumerate().all(|(i, topic)| {
+			let Some(log_topic) = log.topics.get(i) else { return false };
+			match topic {
+				FilterTopic::Single(hash) => hash == log_topic,
+				FilterTopic::Multiple(hashes) => hashes.contains(log_topic),
+			}
+		})
+	}
+}
+
+#[test]
+fn filter_matches_log_works() {
+	let log = Log {
+		address: H160::from([1u8; 20]),
+		topics: vec![H256::from([1u8; 32]), H256::from([2u8; 32])],
+		..Default::default()
+	};
+
+	assert!(Filter::default().matches_log(&log));
+	assert!(Filter { address: Some(log.address.into()), ..Default::default() }.matches_log(&log));
+	assert!(!Filter { address: Some(H160::from([2u8; 20]).into()), ..Default::default() }
+		.matches_log(&log));
+	assert!(Filter {
+		address: Some(vec![H160::from([2u8; 20]), log.address].into()),
+		topics: Some(vec![
+			FilterTopic::Multiple(vec![H256::from([3u8; 32]), log.topics[0]]),
+			FilterTopic::Single(log.topics[1]),
+		]),
+		..Default::default()
+	}
+	.matches_log(&log));
+	assert!(!Filter {
+		topics: Some(vec![FilterTopic::Single(log.topics[1])]),
+		..Default::default()
+	}
+	.matches_log(&log));
+	assert!(!Filter {
+		topics: Some(vec![
+			FilterTopic::Single(log.topics[0]),
+			FilterTopic::Single(log.topics[1]),
+			FilterTopic::Single(log.topics[1]),
+		]),
+		..Default::default()
+	}
+	.matches_log(&log));
+}
+
 impl TransactionInfo {
 	/// Create a new [`TransactionInfo`] from a receipt and a signed transaction.
 	pub fn new(receipt: &ReceiptInfo, transaction_signed: TransactionSigned) -> Self {
@@ -95,17 +177,39 @@ impl ReceiptInfo {
 	}
 
 	/// Calculate receipt logs bloom.
-	fn logs_bloom(logs: &[Log]) -> Bytes256 {
-		let mut bloom = [0u8; 256];
+	pub fn logs_bloom(logs: &[Log]) -> Bytes256 {
+		let mut bloom = Bytes256::default();
 		for log in logs {
-			m3_2048(&mut bloom, &log.address.as_ref());
-			for topic in &log.topics {
-				m3_2048(&mut bloom, topic.as_ref());
-			}
+			bloom.accrue_log(log);
 		}
-		bloom.into()
+		bloom
 	}
 }
+
+impl Bytes256 {
+	/// Add the address and topics of the given log to the bloom.
+	pub fn accrue_log(&mut self, log: &Log) {
+		m3_2048(&mut self.0, log.address.as_ref());
+		for topic in &log.topics {
+			m3_2048(&mut self.0, topic.as_ref());
+		}
+	}
+
+	/// Merge the bits of `other` into this bloom.
+	pub fn accrue_bloom(&mut self, other: &Bytes256) {
+		for (a, b) in self.0.iter_mut().zip(other.0.iter()) {
+			*a |= b;
+		}
+	}
+
+	/// Returns `false` if `bytes` (an address or a topic) was definitely not added to the bloom.
+	pub fn may_contain(&self, bytes: &[u8]) -> bool {
+		let mut input = [0u8; 256];
+		m3_2048(&mut input, bytes);
+		input.iter().zip(self.0.iter()).all(|(i, b)| i & b == *i)
+	}
+}
+
 /// Specialised Bloom filter that sets three bits out of 2048, given an
 /// arbitrary byte sequence.
 ///
@@ -200,6 +304,11 @@ fn logs_bloom_works() {
 	)
 	.unwrap();
 	assert_eq!(receipt.logs_bloom, ReceiptInfo::logs_bloom(&receipt.logs));
+
+	let log = &receipt.logs[0];
+	assert!(receipt.logs_bloom.may_contain(log.address.as_ref()));
+	assert!(log.topics.iter().all(|topic| receipt.logs_bloom.may_contain(topic.as_ref())));
+	assert!(!receipt.logs_bloom.may_contain(H160::from([1u8; 20]).as_ref()));
 }
 
 impl GenericTransaction {
@@ -228,6 +337,26 @@ impl GenericTransaction {
 				gas_price: Some(tx.gas_price),
 				..Default::default()
 			},
+			Transaction7702Unsigned(tx) => GenericTransaction {
+				from,
+				r#type: Some(tx.r#type.as_byte()),
+				chain_id: Some(tx.chain_id),
+				input: tx.input.into(),
+				nonce: Some(tx.nonce),
+				value: Some(tx.value),
+				to: Some(tx.to),
+				gas: Some(tx.gas),
+				gas_price: Some(
+					base_gas_price
+						.saturating_add(tx.max_priority_fee_per_gas)
+						.min(tx.max_fee_per_gas),
+				),
+				access_list: Some(tx.access_list),
+				authorization_list: tx.authorization_list,
+				max_fee_per_gas: Some(tx.max_fee_per_gas),
+				max_priority_fee_per_gas: Some(tx.max_priority_fee_per_gas),
+				..Default::default()
+			},
 			Transaction4844Unsigned(tx) => GenericTransaction {
 				from,
 				r#type: Some(tx.r#type.as_byte()),
@@ -339,6 +468,21 @@ impl GenericTransaction {
 				blob_versioned_hashes: self.blob_versioned_hashes,
 			}
 			.into()),
+			TYPE_EIP7702 => Ok(Transaction7702Unsigned {
+				r#type: TypeEip7702 {},
+				chain_id: self.chain_id.unwrap_or_default(),
+				input: self.input.to_bytes(),
+				nonce: self.nonce.unwrap_or_default(),
+				value: self.value.unwrap_or_default(),
+				to: self.to.unwrap_or_default(),
+				gas: self.gas.unwrap_or_default(),
+				gas_price: self.max_fee_per_gas.unwrap_or_default(),
+				access_list: self.access_list.unwrap_or_default(),
+				authorization_list: self.authorization_list,
+				max_fee_per_gas: self.max_fee_per_gas.unwrap_or_default(),
+				max_priority_fee_per_gas: self.max_priority_fee_per_gas.unwrap_or_default(),
+			}
+			.into()),
 			_ => Err(()),
 		}
 	}
@@ -17,9 +17,9 @@
 //! Generated JSON-RPC types.
 #![allow(missing_docs)]
 
-use super::{byte::*, TypeEip1559, TypeEip2930, TypeEip4844, TypeLegacy};
+use super::{byte::*, TypeEip1559, TypeEip2930, TypeEip4844, TypeEip7702, TypeLegacy};
 use alloc::vec::Vec;
-use codec::{Decode, Encode};
+use codec::{Decode, DecodeWithMemTracking, Encode};
 use derive_more::{From, TryInto};
 pub use ethereum_types::*;
 use scale_info::TypeInfo;
@@ -311,6 +311,13 @@ pub struct GenericTransaction {
 	/// value
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub value: Option<U256>,
+	/// authorizationList
+	/// EIP-7702 authorization list
+	///
+	/// Last field, so that the SCALE encoding of the previous fields is unchanged. See
+	/// `ReviveApi` for calling into runtimes that predate it.
+	#[serde(rename = "authorizationList", default, skip_serializing_if = "Vec::is_empty")]
+	pub authorization_list: AuthorizationList,
 }
 
 /// Receipt information
@@ -420,6 +427,7 @@ pub struct TransactionInfo {
 #[derive(Debug, Clone, Serialize, Deserialize, From, TryInto, Eq, PartialEq)]
 #[serde(untagged)]
 pub enum TransactionUnsigned {
+	Transaction7702Unsigned(Transaction7702Unsigned),
 	Transaction4844Unsigned(Transaction4844Unsigned),
 	Transaction1559Unsigned(Transaction1559Unsigned),
 	Transaction2930Unsigned(Transaction2930Unsigned),
@@ -434,6 +442,9 @@ impl Default for TransactionUnsigned {
 /// Access list
 pub type AccessList = Vec<AccessListEntry>;
 
+/// Authorization list
+pub type AuthorizationList = Vec<AuthorizationListEntry>;
+
 /// Address(es)
 #[derive(Debug, Clone, Serialize, Deserialize, From, TryInto, Eq, PartialEq)]
 #[serde(untagged)]
@@ -495,7 +506,9 @@ impl Default for HashesOrTransactionInfos {
 }
 
 /// log
-#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(
+	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
+)]
 pub struct Log {
 	/// address
 	pub address: Address,
@@ -539,6 +552,50 @@ pub struct SyncingProgress {
 	pub starting_block: Option<U256>,
 }
 
+/// EIP-7702 transaction.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct Transaction7702Unsigned {
+	/// accessList
+	/// EIP-2930 access list
+	#[serde(rename = "accessList")]
+	pub access_list: AccessList,
+	/// authorizationList
+	/// EIP-7702 authorization list
+	#[serde(rename = "authorizationList")]
+	pub authorization_list: AuthorizationList,
+	/// chainId
+	/// Chain ID that this transaction is valid on.
+	#[serde(rename = "chainId")]
+	pub chain_id: U256,
+	/// gas limit
+	pub gas: U256,
+	/// gas price
+	/// The effective gas price paid by the sender in wei. For transactions not yet included in a
+	/// block, this value should be set equal to the max fee per gas. This field is DEPRECATED,
+	/// please transition to using effectiveGasPrice in the receipt object going forward.
+	#[serde(rename = "gasPrice")]
+	pub gas_price: U256,
+	/// input data
+	pub input: Bytes,
+	/// max fee per gas
+	/// The maximum total fee per gas the sender is willing to pay (includes the network / base fee
+	/// and miner / priority fee) in wei
+	#[serde(rename = "maxFeePerGas")]
+	pub max_fee_per_gas: U256,
+	/// max priority fee per gas
+	/// Maximum fee per gas the sender is willing to pay to miners in wei
+	#[serde(rename = "maxPriorityFeePerGas")]
+	pub max_priority_fee_per_gas: U256,
+	/// nonce
+	pub nonce: U256,
+	/// to address
+	pub to: Address,
+	/// type
+	pub r#type: TypeEip7702,
+	/// value
+	pub value: U256,
+}
+
 /// EIP-1559 transaction.
 #[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
 pub struct Transaction1559Unsigned {
@@ -678,6 +735,7 @@ pub struct TransactionLegacyUnsigned {
 #[derive(Debug, Clone, Serialize, Deserialize, From, TryInto, Eq, PartialEq)]
 #[serde(untagged)]
 pub enum TransactionSigned {
+	Transaction7702Signed(Transaction7702Signed),
 	Transaction4844Signed(Transaction4844Signed),
 	Transaction1559Signed(Transaction1559Signed),
 	Transaction2930Signed(Transaction2930Signed),
@@ -703,6 +761,42 @@ pub struct Withdrawal {
 	pub validator_index: U256,
 }
 
+/// Authorization list entry
+/// A signed EIP-7702 authorization, delegating the code of the signer to `address`.
+#[derive(
+	Debug,
+	Default,
+	Clone,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	TypeInfo,
+	Serialize,
+	Deserialize,
+	Eq,
+	PartialEq,
+)]
+pub struct AuthorizationListEntry {
+	/// chainId
+	/// Chain ID that this authorization is valid on, `0` for any chain.
+	#[serde(rename = "chainId")]
+	pub chain_id: U256,
+	/// address
+	/// The address whose code is delegated to.
+	pub address: Address,
+	/// nonce
+	/// The nonce of the signer.
+	pub nonce: U256,
+	/// yParity
+	/// The parity (0 for even, 1 for odd) of the y-value of the secp256k1 signature.
+	#[serde(rename = "yParity")]
+	pub y_parity: U256,
+	/// r
+	pub r: U256,
+	/// s
+	pub s: U256,
+}
+
 /// Access list entry
 #[derive(
 	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
@@ -728,6 +822,26 @@ impl Default for FilterTopic {
 	}
 }
 
+/// Signed 7702 Transaction
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct Transaction7702Signed {
+	#[serde(flatten)]
+	pub transaction_7702_unsigned: Transaction7702Unsigned,
+	/// r
+	pub r: U256,
+	/// s
+	pub s: U256,
+	/// v
+	/// For backwards compatibility, `v` is optionally provided as an alternative to `yParity`.
+	/// This field is DEPRECATED and all use of it should migrate to `yParity`.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub v: Option<U256>,
+	/// yParity
+	/// The parity (0 for even, 1 for odd) of the y-value of the secp256k1 signature.
+	#[serde(rename = "yParity")]
+	pub y_parity: U256,
+}
+
 /// Signed 1559 Transaction
 #[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
 pub struct Transaction1559Signed {
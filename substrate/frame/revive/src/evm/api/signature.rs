@@ -17,6 +17,7 @@
 //! Ethereum signature utilities
 
 use super::*;
+use alloc::vec::Vec;
 use sp_core::{H160, U256};
 use sp_io::{crypto::secp256k1_ecdsa_recover, hashing::keccak_256};
 
@@ -42,6 +43,8 @@ impl TransactionUnsigned {
 		match tx {
 			TransactionSigned::TransactionLegacySigned(signed) =>
 				Self::TransactionLegacyUnsigned(signed.transaction_legacy_unsigned),
+			TransactionSigned::Transaction7702Signed(signed) =>
+				Self::Transaction7702Unsigned(signed.transaction_7702_unsigned),
 			TransactionSigned::Transaction4844Signed(signed) =>
 				Self::Transaction4844Unsigned(signed.transaction_4844_unsigned),
 			TransactionSigned::Transaction1559Signed(signed) =>
@@ -58,6 +61,15 @@ impl TransactionUnsigned {
 		let recovery_id = signature[64];
 
 		match self {
+			TransactionUnsigned::Transaction7702Unsigned(transaction_7702_unsigned) =>
+				Transaction7702Signed {
+					transaction_7702_unsigned,
+					r,
+					s,
+					v: None,
+					y_parity: U256::from(recovery_id),
+				}
+				.into(),
 			TransactionUnsigned::Transaction2930Unsigned(transaction_2930_unsigned) =>
 				Transaction2930Signed {
 					transaction_2930_unsigned,
@@ -108,6 +120,7 @@ impl TransactionSigned {
 		use TransactionSigned::*;
 		let (r, s, v) = match self {
 			TransactionLegacySigned(tx) => (tx.r, tx.s, tx.extract_recovery_id().ok_or(())?),
+			Transaction7702Signed(tx) => (tx.r, tx.s, tx.y_parity.try_into().map_err(|_| ())?),
 			Transaction4844Signed(tx) => (tx.r, tx.s, tx.y_parity.try_into().map_err(|_| ())?),
 			Transaction1559Signed(tx) => (tx.r, tx.s, tx.y_parity.try_into().map_err(|_| ())?),
 			Transaction2930Signed(tx) => (tx.r, tx.s, tx.y_parity.try_into().map_err(|_| ())?),
@@ -129,6 +142,11 @@ impl TransactionSigned {
 				let tx = &tx.transaction_legacy_unsigned;
 				s.append(tx);
 			},
+			Transaction7702Signed(tx) => {
+				let tx = &tx.transaction_7702_unsigned;
+				s.append(&tx.r#type.value());
+				s.append(tx);
+			},
 			Transaction4844Signed(tx) => {
 				let tx = &tx.transaction_4844_unsigned;
 				s.append(&tx.r#type.value());
@@ -156,6 +174,49 @@ impl TransactionSigned {
 	}
 }
 
+impl AuthorizationListEntry {
+	/// The magic byte prepended to the authorization signing payload.
+	/// See https://eips.ethereum.org/EIPS/eip-7702
+	pub const MAGIC: u8 = 0x05;
+
+	/// Half of the secp256k1 curve order, the upper bound for `s` in a valid authorization.
+	const SECP256K1_N_DIV_2: U256 =
+		U256([0xdfe92f46681b20a0, 0x5d576e7357a4501d, 0xffffffffffffffff, 0x7fffffffffffffff]);
+
+	/// Get the payload that is signed by the authority: `MAGIC || rlp([chain_id, address, nonce])`.
+	pub fn signing_payload(chain_id: U256, address: H160, nonce: U256) -> Vec<u8> {
+		let mut s = rlp::RlpStream::new_list(3);
+		s.append(&chain_id);
+		s.append(&address);
+		s.append(&nonce);
+		[&[Self::MAGIC][..], &s.out()].concat()
+	}
+
+	/// Get the raw 65 bytes signature of the authorization.
+	pub fn raw_signature(&self) -> Result<[u8; 65], ()> {
+		let v: u8 = self.y_parity.try_into().map_err(|_| ())?;
+		if v > 1 || self.s > Self::SECP256K1_N_DIV_2 {
+			return Err(());
+		}
+
+		let mut sig = [0u8; 65];
+		self.r.write_as_big_endian(sig[0..32].as_mut());
+		self.s.write_as_big_endian(sig[32..64].as_mut());
+		sig[64] = v;
+		Ok(sig)
+	}
+
+	/// Recover the Ethereum address of the authority that signed this authorization.
+	pub fn recover_authority(&self) -> Result<H160, ()> {
+		let signature = self.raw_signature()?;
+		let hash = keccak_256(&Self::signing_payload(self.chain_id, self.address, self.nonce));
+		let mut addr = H160::default();
+		let pk = secp256k1_ecdsa_recover(&signature, &hash).map_err(|_| ())?;
+		addr.assign_from_slice(&keccak_256(&pk[..])[12..]);
+		Ok(addr)
+	}
+}
+
 #[test]
 fn sign_and_recover_work() {
 	use crate::evm::TransactionUnsigned;
@@ -185,3 +246,23 @@ fn sign_and_recover_work() {
 		assert_eq!(tx, signed);
 	}
 }
+
+#[test]
+fn sign_and_recover_authorization_work() {
+	let account = Account::from_secret_key(hex_literal::hex!(
+		"a872f6cbd25a0e04a08b1e21098017a9e6194d101d75e13111f71410c59cd57f"
+	));
+
+	let auth = account.sign_authorization(1u32.into(), H160::repeat_byte(0x42), 7u32.into());
+	assert_eq!(auth.recover_authority(), Ok(account.address()));
+
+	// Tampering with the signed fields yields a different authority.
+	let tampered = AuthorizationListEntry { nonce: 8u32.into(), ..auth.clone() };
+	assert_ne!(tampered.recover_authority(), Ok(account.address()));
+
+	// Invalid parity and high-s signatures are rejected.
+	let invalid = AuthorizationListEntry { y_parity: 2u32.into(), ..auth.clone() };
+	assert_eq!(invalid.recover_authority(), Err(()));
+	let invalid = AuthorizationListEntry { s: U256::MAX, ..auth };
+	assert_eq!(invalid.recover_authority(), Err(()));
+}
@@ -0,0 +1,191 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::evm::{BlockOverrides, Bytes, GenericTransaction, Log, StateOverride};
+use alloc::{format, string::String, vec::Vec};
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+use sp_core::U256;
+
+/// The parameters of `eth_simulateV1`.
+#[derive(
+	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatePayload {
+	/// The blocks to simulate, in order.
+	pub block_state_calls: Vec<SimulateBlock>,
+	/// Whether to report value transfers as logs.
+	#[serde(default)]
+	pub trace_transfers: bool,
+	/// Whether to reject calls with an invalid nonce, a fee below the base fee, more gas than left
+	/// in the block, or without the funds to pay for their gas and value.
+	#[serde(default)]
+	pub validation: bool,
+}
+
+impl SimulatePayload {
+	/// The maximum number of blocks simulated by a single request, as in geth.
+	pub const MAX_BLOCKS: usize = 256;
+	/// The maximum number of calls simulated by a single request, across all its blocks.
+	///
+	/// The gas used by all the calls of a request is also limited to the block gas limit, this
+	/// only bounds the calls failing without using any gas.
+	pub const MAX_CALLS: usize = 1_000;
+	/// The number of seconds between two blocks without a time override, as in geth.
+	pub const BLOCK_TIME: u64 = 12;
+
+	/// Returns an error if the payload exceeds [`Self::MAX_BLOCKS`] or [`Self::MAX_CALLS`].
+	pub fn check_limits(&self) -> Result<(), String> {
+		let blocks = self.block_state_calls.len();
+		if blocks > Self::MAX_BLOCKS {
+			return Err(format!(
+				"too many blocks: {blocks} exceeds the maximum of {}",
+				Self::MAX_BLOCKS
+			));
+		}
+
+		let calls = self.block_state_calls.iter().map(|block| block.calls.len()).sum::<usize>();
+		if calls > Self::MAX_CALLS {
+			return Err(format!(
+				"too many calls: {calls} exceeds the maximum of {}",
+				Self::MAX_CALLS
+			));
+		}
+
+		Ok(())
+	}
+}
+
+/// A block simulated by `eth_simulateV1`.
+#[derive(
+	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateBlock {
+	/// The block fields to override.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub block_overrides: Option<BlockOverrides>,
+	/// The state overrides applied before executing the calls of the block.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub state_overrides: Option<StateOverride>,
+	/// The calls to execute, in order.
+	#[serde(default)]
+	pub calls: Vec<GenericTransaction>,
+}
+
+/// The result of a block simulated by `eth_simulateV1`.
+///
+/// Simulated blocks are never sealed, hence they have no hash.
+#[derive(
+	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedBlock {
+	/// The block number.
+	pub number: U256,
+	/// The block timestamp, in seconds.
+	pub timestamp: U256,
+	/// The block gas limit.
+	pub gas_limit: U256,
+	/// The gas used by all the calls of the block.
+	pub gas_used: U256,
+	/// The base fee of the block.
+	pub base_fee_per_gas: U256,
+	/// The results of the calls, in order.
+	pub calls: Vec<SimulatedCall>,
+}
+
+/// The result of a call simulated by `eth_simulateV1`.
+#[derive(
+	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedCall {
+	/// The data returned by the call, or the revert data if it reverted.
+	pub return_data: Bytes,
+	/// The logs emitted by the call.
+	pub logs: Vec<Log>,
+	/// The gas used by the call.
+	pub gas_used: U256,
+	/// `1` if the call succeeded, `0` otherwise.
+	pub status: U256,
+	/// The error of the call, if it failed.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<SimulateCallError>,
+}
+
+/// The error of a call simulated by `eth_simulateV1`.
+#[derive(
+	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
+)]
+pub struct SimulateCallError {
+	/// The error code.
+	pub code: i32,
+	/// The error message.
+	pub message: String,
+}
+
+impl SimulateCallError {
+	/// The error code of a reverted call.
+	pub const REVERTED: i32 = 3;
+
+	/// The error code of a call that failed for another reason.
+	pub const FAILED: i32 = -32015;
+	/// The error code of a call with a nonce lower than the nonce of its sender.
+	pub const NONCE_TOO_LOW: i32 = -38010;
+	/// The error code of a call with a nonce higher than the nonce of its sender.
+	pub const NONCE_TOO_HIGH: i32 = -38011;
+	/// The error code of a call whose sender can't pay for its gas and value.
+	pub const INSUFFICIENT_FUNDS: i32 = -38014;
+	/// The error code of a call with more gas than left in its block.
+	pub const BLOCK_GAS_LIMIT_REACHED: i32 = -38015;
+	/// The error code of a call not executed because the request used too much gas.
+	pub const LIMIT_EXCEEDED: i32 = -38026;
+}
+
+#[test]
+fn simulate_payload_deserialization_works() {
+	let json = r#"{
+		"blockStateCalls": [{
+			"blockOverrides": { "number": "0x10" },
+			"calls": [{ "from": "0x0000000000000000000000000000000000000001", "value": "0x1" }]
+		}],
+		"traceTransfers": true
+	}"#;
+
+	let payload: SimulatePayload = serde_json::from_str(json).unwrap();
+	assert_eq!(
+		payload,
+		SimulatePayload {
+			block_state_calls: vec![SimulateBlock {
+				block_overrides: Some(BlockOverrides {
+					number: Some(U256::from(0x10)),
+					..Default::default()
+				}),
+				state_overrides: None,
+				calls: vec![GenericTransaction {
+					from: Some(sp_core::H160::from_low_u64_be(1)),
+					value: Some(U256::from(1)),
+					..Default::default()
+				}],
+			}],
+			trace_transfers: true,
+			validation: false,
+		}
+	);
+}
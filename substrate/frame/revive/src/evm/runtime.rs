@@ -17,7 +17,7 @@
 //! Runtime types for integrating `pallet-revive` with the EVM.
 use crate::{
 	evm::{
-		api::{GenericTransaction, TransactionSigned},
+		api::{AuthorizationList, GenericTransaction, TransactionSigned},
 		GasEncoder,
 	},
 	AccountIdOf, AddressMapper, BalanceOf, Config, ConversionPrecision, MomentOf,
@@ -251,6 +251,8 @@ pub trait EthExtra {
 	/// It should include at least:
 	/// - [`frame_system::CheckNonce`] to ensure that the nonce from the Ethereum transaction is
 	///   correct.
+	/// - [`crate::evm::ApplyAuthorizations`], after [`frame_system::CheckNonce`], built from the
+	///   `authorization_list`, to apply the EIP-7702 authorizations of the Ethereum transaction.
 	type Extension: TransactionExtension<CallOf<Self::Config>>;
 
 	/// Get the transaction extension to apply to an unsigned [`crate::Call::eth_transact`]
@@ -259,9 +261,11 @@ pub trait EthExtra {
 	/// # Parameters
 	/// - `nonce`: The nonce extracted from the Ethereum transaction.
 	/// - `tip`: The transaction tip calculated from the Ethereum transaction.
+	/// - `authorization_list`: The EIP-7702 authorizations of the Ethereum transaction.
 	fn get_eth_extension(
 		nonce: <Self::Config as frame_system::Config>::Nonce,
 		tip: BalanceOf<Self::Config>,
+		authorization_list: AuthorizationList,
 	) -> Self::Extension;
 
 	/// Convert the unsigned [`crate::Call::eth_transact`] into a [`CheckedExtrinsic`].
@@ -300,8 +304,17 @@ pub trait EthExtra {
 		})?;
 
 		let signer = <Self::Config as Config>::AddressMapper::to_fallback_account_id(&signer_addr);
-		let GenericTransaction { nonce, chain_id, to, value, input, gas, gas_price, .. } =
-			GenericTransaction::from_signed(tx, crate::GAS_PRICE.into(), None);
+		let GenericTransaction {
+			nonce,
+			chain_id,
+			to,
+			value,
+			input,
+			gas,
+			gas_price,
+			authorization_list,
+			..
+		} = GenericTransaction::from_signed(tx, crate::GAS_PRICE.into(), None);
 
 		let Some(gas) = gas else {
 			log::debug!(target: LOG_TARGET, "No gas provided");
@@ -390,7 +403,8 @@ pub trait EthExtra {
 			.map_err(|_| InvalidTransaction::Call)?;
 
 		// Fees calculated from the extrinsic, without the tip.
-		info.extension_weight = Self::get_eth_extension(nonce, 0u32.into()).weight(&call);
+		info.extension_weight =
+			Self::get_eth_extension(nonce, 0u32.into(), authorization_list.clone()).weight(&call);
 		let actual_fee: BalanceOf<Self::Config> =
 			pallet_transaction_payment::Pallet::<Self::Config>::compute_fee(
 				encoded_len as u32,
@@ -419,7 +433,10 @@ pub trait EthExtra {
 
 		log::debug!(target: LOG_TARGET, "Created checked Ethereum transaction with nonce: {nonce:?} and tip: {tip:?}");
 		Ok(CheckedExtrinsic {
-			format: ExtrinsicFormat::Signed(signer.into(), Self::get_eth_extension(nonce, tip)),
+			format: ExtrinsicFormat::Signed(
+				signer.into(),
+				Self::get_eth_extension(nonce, tip, authorization_list),
+			),
 			function: call,
 		})
 	}
@@ -444,15 +461,24 @@ mod test {
 
 	#[derive(Clone, PartialEq, Eq, Debug)]
 	pub struct Extra;
-	type SignedExtra = (frame_system::CheckNonce<Test>, ChargeTransactionPayment<Test>);
+	type SignedExtra =
+		(frame_system::CheckNonce<Test>, ApplyAuthorizations<Test>, ChargeTransactionPayment<Test>);
 
 	use pallet_transaction_payment::ChargeTransactionPayment;
 	impl EthExtra for Extra {
 		type Config = Test;
 		type Extension = SignedExtra;
 
-		fn get_eth_extension(nonce: u32, tip: BalanceOf<Test>) -> Self::Extension {
-			(frame_system::CheckNonce::from(nonce), ChargeTransactionPayment::from(tip))
+		fn get_eth_extension(
+			nonce: u32,
+			tip: BalanceOf<Test>,
+			authorization_list: AuthorizationList,
+		) -> Self::Extension {
+			(
+				frame_system::CheckNonce::from(nonce),
+				ApplyAuthorizations::from(authorization_list),
+				ChargeTransactionPayment::from(tip),
+			)
 		}
 	}
 
@@ -503,7 +529,8 @@ mod test {
 				|eth_call, dispatch_call| {
 					let mut info = dispatch_call.get_dispatch_info();
 					info.extension_weight =
-						Extra::get_eth_extension(0, 0u32.into()).weight(&dispatch_call);
+						Extra::get_eth_extension(0, 0u32.into(), Default::default())
+							.weight(&dispatch_call);
 					let uxt: Ex =
 						sp_runtime::generic::UncheckedExtrinsic::new_bare(eth_call).into();
 					pallet_transaction_payment::Pallet::<Test>::compute_fee(
@@ -720,7 +747,7 @@ mod test {
 				.unwrap();
 		let diff = tx.gas_price.unwrap() - U256::from(GAS_PRICE);
 		let expected_tip = crate::Pallet::<Test>::evm_gas_to_fee(tx.gas.unwrap(), diff).unwrap();
-		assert_eq!(extra.1.tip(), expected_tip);
+		assert_eq!(extra.2.tip(), expected_tip);
 	}
 
 	#[test]
@@ -19,14 +19,23 @@ use crate::{
 	tracing::Tracing,
 	BalanceOf, Bounded, Config, MomentOf, Weight,
 };
-use sp_core::{H256, U256};
+use sp_core::{H160, H256, U256};
+
+mod access_list_tracing;
+pub use access_list_tracing::*;
 
 mod call_tracing;
 pub use call_tracing::*;
 
+mod log_tracing;
+pub use log_tracing::*;
+
 mod prestate_tracing;
 pub use prestate_tracing::*;
 
+mod struct_logger_tracing;
+pub use struct_logger_tracing::*;
+
 /// A composite tracer.
 #[derive(derive_more::From, Debug)]
 pub enum Tracer<T> {
@@ -34,6 +43,10 @@ pub enum Tracer<T> {
 	CallTracer(CallTracer<U256, fn(Weight) -> U256>),
 	/// A tracer that traces the prestate.
 	PrestateTracer(PrestateTracer<T>),
+	/// A tracer that records the accessed accounts and storage keys.
+	AccessListTracer(AccessListTracer<fn(&H160) -> bool>),
+	/// A tracer that logs every executed instruction.
+	StructLogger(StructLogger<fn(Weight) -> U256>),
 }
 
 impl<T: Config> Tracer<T>
@@ -48,6 +61,8 @@ where
 		match self {
 			Tracer::CallTracer(_) => CallTrace::default().into(),
 			Tracer::PrestateTracer(tracer) => tracer.empty_trace().into(),
+			Tracer::AccessListTracer(_) => Trace::AccessList(Default::default()),
+			Tracer::StructLogger(_) => Trace::StructLogger(Default::default()),
 		}
 	}
 
@@ -56,6 +71,8 @@ where
 		match self {
 			Tracer::CallTracer(inner) => inner as &mut dyn Tracing,
 			Tracer::PrestateTracer(inner) => inner as &mut dyn Tracing,
+			Tracer::AccessListTracer(inner) => inner as &mut dyn Tracing,
+			Tracer::StructLogger(inner) => inner as &mut dyn Tracing,
 		}
 	}
 
@@ -64,6 +81,8 @@ where
 		match self {
 			Tracer::CallTracer(inner) => inner.collect_trace().map(Trace::Call),
 			Tracer::PrestateTracer(inner) => Some(inner.collect_trace().into()),
+			Tracer::AccessListTracer(inner) => Some(Trace::AccessList(inner.collect_trace())),
+			Tracer::StructLogger(inner) => Some(Trace::StructLogger(inner.collect_trace())),
 		}
 	}
 }
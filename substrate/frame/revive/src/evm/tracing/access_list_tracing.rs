@@ -0,0 +1,127 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{
+	evm::{AccessList, AccessListEntry},
+	primitives::ExecReturnValue,
+	tracing::Tracing,
+	DispatchError, Key, Weight,
+};
+use alloc::{
+	collections::{BTreeMap, BTreeSet},
+	vec::Vec,
+};
+use sp_core::{H160, H256, U256};
+
+/// A tracer that records the accounts and storage keys accessed by a transaction, and reports
+/// them as an [EIP-2930](https://eips.ethereum.org/EIPS/eip-2930) access list.
+///
+/// Like geth's `accessList` tracer, the sender, the recipient and the pre-compiles are only listed
+/// if their storage is accessed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessListTracer<IsPrecompile> {
+	/// Returns true if a pre-compile is deployed at the address.
+	is_precompile: IsPrecompile,
+	/// The sender and recipient of the transaction.
+	excluded: BTreeSet<H160>,
+	/// The accessed accounts, and their accessed storage keys.
+	accessed: BTreeMap<H160, BTreeSet<H256>>,
+	/// Stack of the accounts whose storage is accessed by the active calls.
+	current_stack: Vec<H160>,
+}
+
+impl<IsPrecompile: Fn(&H160) -> bool> AccessListTracer<IsPrecompile> {
+	/// Create a new [`AccessListTracer`] instance.
+	pub fn new(is_precompile: IsPrecompile) -> Self {
+		Self {
+			is_precompile,
+			excluded: Default::default(),
+			accessed: Default::default(),
+			current_stack: Vec::new(),
+		}
+	}
+
+	/// Collect the access list and return it.
+	pub fn collect_trace(&mut self) -> AccessList {
+		let excluded = core::mem::take(&mut self.excluded);
+		self.current_stack.clear();
+		core::mem::take(&mut self.accessed)
+			.into_iter()
+			.filter(|(address, storage_keys)| {
+				!storage_keys.is_empty() ||
+					!(excluded.contains(address) || (self.is_precompile)(address))
+			})
+			.map(|(address, storage_keys)| AccessListEntry {
+				address,
+				storage_keys: storage_keys.into_iter().collect(),
+			})
+			.collect()
+	}
+
+	/// Record a storage key accessed by the current call.
+	fn record_storage_key(&mut self, key: &Key) {
+		// Only fixed sized keys map to Ethereum storage slots.
+		let Key::Fix(key) = key else { return };
+		if let Some(address) = self.current_stack.last() {
+			self.accessed.entry(*address).or_default().insert(H256(*key));
+		}
+	}
+}
+
+impl<IsPrecompile: Fn(&H160) -> bool> Tracing for AccessListTracer<IsPrecompile> {
+	fn enter_child_span(
+		&mut self,
+		from: H160,
+		to: H160,
+		delegate_call: Option<H160>,
+		_is_read_only: bool,
+		_value: U256,
+		_input: &[u8],
+		_gas_left: Weight,
+	) {
+		if self.current_stack.is_empty() {
+			self.excluded.insert(from);
+			self.excluded.insert(to);
+		}
+
+		self.accessed.entry(to).or_default();
+		// The storage of a delegate call is the one of the calling contract.
+		if let Some(code_address) = delegate_call {
+			self.accessed.entry(code_address).or_default();
+		}
+		self.current_stack.push(to);
+	}
+
+	fn balance_read(&mut self, addr: &H160, _value: U256) {
+		self.accessed.entry(*addr).or_default();
+	}
+
+	fn storage_read(&mut self, key: &Key, _value: Option<&[u8]>) {
+		self.record_storage_key(key);
+	}
+
+	fn storage_write(&mut self, key: &Key, _old_value: Option<Vec<u8>>, _new_value: Option<&[u8]>) {
+		self.record_storage_key(key);
+	}
+
+	fn exit_child_span(&mut self, _output: &ExecReturnValue, _gas_left: Weight) {
+		self.current_stack.pop();
+	}
+
+	fn exit_child_span_with_error(&mut self, _error: DispatchError, _gas_left: Weight) {
+		self.current_stack.pop();
+	}
+}
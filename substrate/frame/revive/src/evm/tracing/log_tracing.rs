@@ -0,0 +1,124 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{
+	evm::{Bytes, Log},
+	primitives::ExecReturnValue,
+	tracing::Tracing,
+	DispatchError, Weight,
+};
+use alloc::{vec, vec::Vec};
+use sp_core::{H160, H256, U256};
+
+/// The address emitting the logs that report value transfers.
+pub const TRANSFER_LOG_ADDRESS: H160 =
+	H160(hex_literal::hex!("eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee"));
+
+/// The topic of the logs that report value transfers:
+/// `keccak256("Transfer(address,address,uint256)")`.
+pub const TRANSFER_LOG_TOPIC: H256 =
+	H256(hex_literal::hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"));
+
+/// A tracer that records the logs emitted by a transaction, and the gas it used.
+///
+/// Logs emitted by reverted calls are discarded. Value transfers can optionally be reported as
+/// ERC-20 like `Transfer` logs emitted by [`TRANSFER_LOG_ADDRESS`].
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct LogTracer<GasMapper> {
+	/// Map Weight to Gas equivalent.
+	gas_mapper: GasMapper,
+	/// Whether to report value transfers as logs.
+	trace_transfers: bool,
+	/// The logs emitted so far, only the address, topics and data are set.
+	logs: Vec<Log>,
+	/// Stack of the number of logs emitted before each active call was entered.
+	checkpoints: Vec<usize>,
+	/// The gas used by the transaction.
+	gas_used: U256,
+}
+
+impl<GasMapper> LogTracer<GasMapper> {
+	/// Create a new [`LogTracer`] instance.
+	pub fn new(trace_transfers: bool, gas_mapper: GasMapper) -> Self {
+		Self {
+			gas_mapper,
+			trace_transfers,
+			logs: Vec::new(),
+			checkpoints: Vec::new(),
+			gas_used: U256::zero(),
+		}
+	}
+
+	/// Collect the logs and the gas used by the transaction, and return them.
+	pub fn collect_trace(&mut self) -> (Vec<Log>, U256) {
+		self.checkpoints.clear();
+		(core::mem::take(&mut self.logs), core::mem::take(&mut self.gas_used))
+	}
+
+	/// Pop the current call, discarding its logs if it failed.
+	fn exit_call(&mut self, failed: bool, gas_used: U256) {
+		let checkpoint = self.checkpoints.pop().unwrap_or_default();
+		if failed {
+			self.logs.truncate(checkpoint);
+		}
+		if self.checkpoints.is_empty() {
+			self.gas_used = gas_used;
+		}
+	}
+}
+
+impl<GasMapper: Fn(Weight) -> U256> Tracing for LogTracer<GasMapper> {
+	fn enter_child_span(
+		&mut self,
+		from: H160,
+		to: H160,
+		delegate_call: Option<H160>,
+		_is_read_only: bool,
+		value: U256,
+		_input: &[u8],
+		_gas_left: Weight,
+	) {
+		self.checkpoints.push(self.logs.len());
+
+		if self.trace_transfers && delegate_call.is_none() && !value.is_zero() {
+			self.logs.push(Log {
+				address: TRANSFER_LOG_ADDRESS,
+				topics: vec![TRANSFER_LOG_TOPIC, from.into(), to.into()],
+				data: Some(Bytes(value.to_big_endian().to_vec())),
+				..Default::default()
+			});
+		}
+	}
+
+	fn log_event(&mut self, address: H160, topics: &[H256], data: &[u8]) {
+		self.logs.push(Log {
+			address,
+			topics: topics.to_vec(),
+			data: Some(Bytes(data.to_vec())),
+			..Default::default()
+		});
+	}
+
+	fn exit_child_span(&mut self, output: &ExecReturnValue, gas_used: Weight) {
+		let gas_used = (self.gas_mapper)(gas_used);
+		self.exit_call(output.did_revert(), gas_used);
+	}
+
+	fn exit_child_span_with_error(&mut self, _error: DispatchError, gas_used: Weight) {
+		let gas_used = (self.gas_mapper)(gas_used);
+		self.exit_call(true, gas_used);
+	}
+}
@@ -163,7 +163,7 @@ where
 		&mut self,
 		from: H160,
 		to: H160,
-		is_delegate_call: bool,
+		delegate_call: Option<H160>,
 		_is_read_only: bool,
 		_value: U256,
 		_input: &[u8],
@@ -180,7 +180,7 @@ where
 			});
 		}
 
-		if !is_delegate_call {
+		if delegate_call.is_none() {
 			self.current_addr = to;
 		}
 	}
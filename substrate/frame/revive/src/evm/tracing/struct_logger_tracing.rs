@@ -0,0 +1,211 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{
+	evm::{StructLog, StructLoggerConfig, StructLoggerTrace, MAX_STRUCT_LOGGER_LIMIT},
+	primitives::ExecReturnValue,
+	tracing::Tracing,
+	DispatchError, Key, Weight,
+};
+use alloc::{
+	collections::BTreeMap,
+	format,
+	string::{String, ToString},
+	vec::Vec,
+};
+use sp_core::{H160, H256, U256};
+
+/// The state of an active call.
+#[derive(Default, Debug, Clone, PartialEq)]
+struct Frame {
+	/// The storage slots accessed by the call so far.
+	storage: BTreeMap<H256, H256>,
+	/// The data returned by the last sub call.
+	return_data: Vec<u8>,
+	/// Index of the last step logged for this call.
+	last_step: Option<usize>,
+}
+
+/// A tracer that logs every executed instruction, similar to geth's struct logger.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct StructLogger<GasMapper> {
+	/// Map Weight to Gas equivalent.
+	gas_mapper: GasMapper,
+	/// The tracer configuration.
+	config: StructLoggerConfig,
+	/// The trace being built.
+	trace: StructLoggerTrace,
+	/// Stack of the active calls.
+	frames: Vec<Frame>,
+}
+
+impl<GasMapper> StructLogger<GasMapper> {
+	/// Create a new [`StructLogger`] instance.
+	///
+	/// The runtime never logs an unbounded number of steps: a limit of `0` or above
+	/// [`MAX_STRUCT_LOGGER_LIMIT`] is capped at [`MAX_STRUCT_LOGGER_LIMIT`].
+	pub fn new(mut config: StructLoggerConfig, gas_mapper: GasMapper) -> Self {
+		if config.limit == 0 || config.limit > MAX_STRUCT_LOGGER_LIMIT {
+			config.limit = MAX_STRUCT_LOGGER_LIMIT;
+		}
+		Self { gas_mapper, config, trace: Default::default(), frames: Vec::new() }
+	}
+
+	/// Collect the trace and return it.
+	pub fn collect_trace(&mut self) -> StructLoggerTrace {
+		self.frames.clear();
+		core::mem::take(&mut self.trace)
+	}
+
+	/// Get the last step logged by the current call.
+	fn last_step_mut(&mut self) -> Option<&mut StructLog> {
+		let index = self.frames.last()?.last_step?;
+		self.trace.struct_logs.get_mut(index)
+	}
+
+	/// Record a storage slot accessed by the current call.
+	fn record_storage(&mut self, key: &Key, value: Option<&[u8]>) {
+		// Only fixed sized keys map to Ethereum storage slots.
+		let Key::Fix(key) = key else { return };
+		let Some(frame) = self.frames.last_mut() else { return };
+		frame.storage.insert(H256(*key), to_word(value));
+
+		if self.config.disable_storage {
+			return
+		}
+		let storage = frame.storage.clone();
+		if let Some(step) = self.last_step_mut() {
+			step.storage = Some(storage);
+		}
+	}
+
+	/// Pop the current call, and hand its return data over to its parent.
+	fn exit_frame(&mut self, return_data: &[u8]) {
+		self.frames.pop();
+		if let Some(parent) = self.frames.last_mut() {
+			parent.return_data = return_data.to_vec();
+		}
+	}
+}
+
+/// Convert a storage value to a 32 bytes word, left padding it with zeros.
+fn to_word(value: Option<&[u8]>) -> H256 {
+	let mut word = H256::zero();
+	if let Some(value) = value {
+		let len = value.len().min(32);
+		word.0[32 - len..].copy_from_slice(&value[..len]);
+	}
+	word
+}
+
+impl<GasMapper: Fn(Weight) -> U256> Tracing for StructLogger<GasMapper> {
+	fn is_opcode_tracing_enabled(&self) -> bool {
+		true
+	}
+
+	fn enter_opcode(
+		&mut self,
+		pc: u64,
+		opcode: &str,
+		gas_left: Weight,
+		stack: &dyn Fn() -> Vec<U256>,
+		memory: &dyn Fn() -> Vec<u8>,
+	) {
+		if self.trace.struct_logs.len() as u64 >= self.config.limit {
+			return
+		}
+
+		// Report the gas in the same unit as the gas used by the whole trace.
+		let gas_left = u64::try_from((self.gas_mapper)(gas_left)).unwrap_or(u64::MAX);
+
+		// The cost of the previous step is only known once the next one is reached.
+		if let Some(previous) = self.last_step_mut() {
+			previous.gas_cost = previous.gas.saturating_sub(gas_left);
+		}
+
+		let Some(frame) = self.frames.last() else { return };
+		let step = StructLog {
+			pc,
+			op: opcode.to_string(),
+			gas: gas_left,
+			gas_cost: 0,
+			depth: self.frames.len() as u32,
+			stack: self.config.enable_stack.then(stack),
+			memory: self
+				.config
+				.enable_memory
+				.then(|| memory().chunks(32).map(|word| word.to_vec().into()).collect()),
+			storage: (!self.config.disable_storage && !frame.storage.is_empty())
+				.then(|| frame.storage.clone()),
+			return_data: (self.config.enable_return_data && !frame.return_data.is_empty())
+				.then(|| frame.return_data.clone().into()),
+			error: None,
+		};
+
+		self.trace.struct_logs.push(step);
+		let index = self.trace.struct_logs.len() - 1;
+		if let Some(frame) = self.frames.last_mut() {
+			frame.last_step = Some(index);
+		}
+	}
+
+	fn enter_child_span(
+		&mut self,
+		_from: H160,
+		_to: H160,
+		_delegate_call: Option<H160>,
+		_is_read_only: bool,
+		_value: U256,
+		_input: &[u8],
+		_gas_left: Weight,
+	) {
+		self.frames.push(Frame::default());
+	}
+
+	fn storage_read(&mut self, key: &Key, value: Option<&[u8]>) {
+		self.record_storage(key, value);
+	}
+
+	fn storage_write(&mut self, key: &Key, _old_value: Option<Vec<u8>>, new_value: Option<&[u8]>) {
+		self.record_storage(key, new_value);
+	}
+
+	fn exit_child_span(&mut self, output: &ExecReturnValue, gas_used: Weight) {
+		if self.frames.len() == 1 {
+			self.trace.gas = (self.gas_mapper)(gas_used);
+			self.trace.failed = output.did_revert();
+			self.trace.return_value = output.data.clone().into();
+		}
+		self.exit_frame(&output.data);
+	}
+
+	fn exit_child_span_with_error(&mut self, error: DispatchError, gas_used: Weight) {
+		let error: String = match error {
+			DispatchError::Module(sp_runtime::ModuleError { message, .. }) =>
+				message.unwrap_or_default().to_string(),
+			_ => format!("{:?}", error),
+		};
+		if let Some(step) = self.last_step_mut() {
+			step.error = Some(error);
+		}
+
+		if self.frames.len() == 1 {
+			self.trace.gas = (self.gas_mapper)(gas_used);
+			self.trace.failed = true;
+		}
+		self.exit_frame(&[]);
+	}
+}
@@ -0,0 +1,90 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Transaction extension applying the EIP-7702 authorizations of an Ethereum transaction.
+
+use crate::{evm::AuthorizationList, Config, Pallet, Weight};
+use codec::{Decode, DecodeWithMemTracking, Encode};
+use core::marker::PhantomData;
+use frame_support::DefaultNoBound;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	impl_tx_ext_default,
+	traits::{DispatchInfoOf, TransactionExtension},
+	transaction_validity::TransactionValidityError,
+};
+
+/// Apply the EIP-7702 `authorization_list` of an Ethereum transaction.
+///
+/// EIP-7702 mandates that the authorizations persist even if the call reverts. They are
+/// therefore carried by this extension and applied when the transaction is prepared, outside of
+/// the storage layer of the call, the same way the nonce of the sender is bumped by
+/// [`frame_system::CheckNonce`]. This extension must come after [`frame_system::CheckNonce`], as
+/// the nonce of the sender is bumped before the authorizations are applied.
+///
+/// Every authorization is signed by its authority, so the list does not depend on the origin of
+/// the transaction. Authorizations that fail to validate are skipped.
+#[derive(Encode, Decode, DecodeWithMemTracking, DefaultNoBound, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct ApplyAuthorizations<T> {
+	authorization_list: AuthorizationList,
+	_phantom: PhantomData<T>,
+}
+
+impl<T> core::fmt::Debug for ApplyAuthorizations<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		write!(f, "ApplyAuthorizations({})", self.authorization_list.len())
+	}
+}
+
+impl<T: Config + Send + Sync> ApplyAuthorizations<T> {
+	/// Create a new `ApplyAuthorizations` extension, without authorizations.
+	pub fn new() -> Self {
+		Self::default()
+	}
+}
+
+impl<T> From<AuthorizationList> for ApplyAuthorizations<T> {
+	fn from(authorization_list: AuthorizationList) -> Self {
+		Self { authorization_list, _phantom: PhantomData }
+	}
+}
+
+impl<T: Config + Send + Sync> TransactionExtension<T::RuntimeCall> for ApplyAuthorizations<T> {
+	const IDENTIFIER: &'static str = "ApplyAuthorizations";
+	type Implicit = ();
+	type Val = ();
+	type Pre = ();
+
+	fn weight(&self, _call: &T::RuntimeCall) -> Weight {
+		Pallet::<T>::authorizations_weight(self.authorization_list.len() as u32)
+	}
+
+	fn prepare(
+		self,
+		_val: Self::Val,
+		_origin: &T::RuntimeOrigin,
+		_call: &T::RuntimeCall,
+		_info: &DispatchInfoOf<T::RuntimeCall>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		Pallet::<T>::apply_authorizations(&self.authorization_list);
+		Ok(())
+	}
+
+	impl_tx_ext_default!(T::RuntimeCall; validate);
+}
@@ -25,11 +25,13 @@ use crate::{
 	storage::{self, meter::Diff, WriteOutcome},
 	tracing::if_tracing,
 	transient_storage::TransientStorage,
-	BalanceOf, CodeInfo, CodeInfoOf, Config, ContractInfo, ContractInfoOf, ConversionPrecision,
-	Error, Event, ImmutableData, ImmutableDataOf, Pallet as Contracts, RuntimeCosts,
+	BalanceOf, CodeDelegationOf, CodeInfo, CodeInfoOf, Config, ContractInfo, ContractInfoOf,
+	ConversionPrecision, DelegatedAccountInfoOf, Error, Event, ImmutableData, ImmutableDataOf,
+	Pallet as Contracts, RuntimeCosts,
 };
 use alloc::vec::Vec;
 use core::{fmt::Debug, marker::PhantomData, mem};
+use environmental::environmental;
 use frame_support::{
 	crypto::ecdsa::ECDSAExt,
 	dispatch::DispatchResult,
@@ -51,7 +53,10 @@ use sp_core::{
 	sr25519::{Public as SR25519Public, Signature as SR25519Signature},
 	ConstU32, H160, H256, U256,
 };
-use sp_io::{crypto::secp256k1_ecdsa_recover_compressed, hashing::blake2_256};
+use sp_io::{
+	crypto::secp256k1_ecdsa_recover_compressed,
+	hashing::{blake2_256, keccak_256},
+};
 use sp_runtime::{
 	traits::{BadOrigin, Bounded, Convert, Saturating, Zero},
 	DispatchError, SaturatedConversion,
@@ -64,6 +69,53 @@ pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 pub type MomentOf<T> = <<T as Config>::Time as Time>::Moment;
 pub type ExecResult = Result<ExecReturnValue, ExecError>;
 
+/// The prefix of the code reported for an account that delegates its code via EIP-7702.
+///
+/// The full designator is the prefix followed by the address of the delegation target.
+pub const DELEGATION_DESIGNATOR_PREFIX: [u8; 3] = [0xef, 0x01, 0x00];
+
+/// Returns the code hash that is executed when calling into `address`.
+///
+/// For an account delegating its code via EIP-7702 this is the code hash of the delegation
+/// target. Chains of delegations are not followed. Returns `None` if there is no code to execute.
+fn executed_code_hash<T: Config>(address: &H160, code_hash: H256) -> Option<H256> {
+	let code_hash = match <CodeDelegationOf<T>>::get(address) {
+		Some(target) => <ContractInfoOf<T>>::get(&target)?.code_hash,
+		None => code_hash,
+	};
+	(code_hash != H256::zero()).then_some(code_hash)
+}
+
+/// Returns the designator reported as the code of an account delegating to `target`.
+fn delegation_designator(target: &H160) -> Vec<u8> {
+	[&DELEGATION_DESIGNATOR_PREFIX[..], target.as_bytes()].concat()
+}
+
+// Set up a global reference to the timestamp, in milliseconds, overriding the block timestamp.
+environmental!(timestamp_override: u64);
+
+/// Run `f` with the block timestamp seen by contracts set to `timestamp` milliseconds.
+///
+/// Only meant to be used when dry running calls against a fake block.
+pub(crate) fn with_timestamp_override<R>(mut timestamp: u64, f: impl FnOnce() -> R) -> R {
+	timestamp_override::using_once(&mut timestamp, f)
+}
+
+// Set up a global reference to the base fee overriding the one of the block.
+environmental!(base_fee_override: U256);
+
+/// Run `f` with the base fee seen by contracts set to `base_fee`.
+///
+/// Only meant to be used when dry running calls against a fake block.
+pub(crate) fn with_base_fee_override<R>(mut base_fee: U256, f: impl FnOnce() -> R) -> R {
+	base_fee_override::using_once(&mut base_fee, f)
+}
+
+/// Returns the base fee seen by contracts.
+pub(crate) fn base_fee() -> U256 {
+	base_fee_override::with(|base_fee| *base_fee).unwrap_or_default()
+}
+
 /// Type for variable sized storage key. Used for transparent hashing.
 type VarSizedKey = BoundedVec<u8, ConstU32<{ limits::STORAGE_KEY_BYTES }>>;
 
@@ -714,7 +766,7 @@ impl<T: Config> CachedContract<T> {
 	/// Load the `contract_info` from storage if necessary.
 	fn load(&mut self, account_id: &T::AccountId) {
 		if let CachedContract::Invalidated = self {
-			let contract = <ContractInfoOf<T>>::get(T::AddressMapper::to_address(account_id));
+			let contract = ContractInfo::load(&T::AddressMapper::to_address(account_id));
 			if let Some(contract) = contract {
 				*self = CachedContract::Cached(contract);
 			}
@@ -780,7 +832,7 @@ where
 				t.enter_child_span(
 					origin.account_id().map(T::AddressMapper::to_address).unwrap_or_default(),
 					T::AddressMapper::to_address(&dest),
-					false,
+					None,
 					false,
 					value,
 					&input_data,
@@ -891,7 +943,8 @@ where
 			origin,
 			gas_meter,
 			storage_meter,
-			timestamp: T::Time::now(),
+			timestamp: timestamp_override::with(|timestamp| (*timestamp).saturated_into())
+				.unwrap_or_else(T::Time::now),
 			block_number: <frame_system::Pallet<T>>::block_number(),
 			first_frame,
 			frames: Default::default(),
@@ -929,6 +982,18 @@ where
 					(None, None) =>
 						if let Some(info) = <ContractInfoOf<T>>::get(&address) {
 							CachedContract::Cached(info)
+						} else if <CodeDelegationOf<T>>::contains_key(&address) {
+							// An EOA delegating its code: its storage is held by an info that is
+							// kept apart from the contracts.
+							let info = match <DelegatedAccountInfoOf<T>>::get(&address) {
+								Some(info) => info,
+								None => ContractInfo::new(
+									&address,
+									<System<T>>::account_nonce(&dest),
+									H256::zero(),
+								)?,
+							};
+							CachedContract::Cached(info)
 						} else {
 							return Ok(None)
 						},
@@ -953,10 +1018,14 @@ where
 							_phantom: Default::default(),
 						}
 					} else {
-						let Some(info) = ContractInfoOf::<T>::get(&delegated_call.callee) else {
+						let callee = &delegated_call.callee;
+						let code_hash = ContractInfoOf::<T>::get(callee)
+							.map(|info| info.code_hash)
+							.unwrap_or_default();
+						let Some(code_hash) = executed_code_hash::<T>(callee, code_hash) else {
 							return Ok(None);
 						};
-						let executable = E::from_storage(info.code_hash, gas_meter)?;
+						let executable = E::from_storage(code_hash, gas_meter)?;
 						ExecutableOrPrecompile::Executable(executable)
 					}
 				} else {
@@ -966,13 +1035,14 @@ where
 							_phantom: Default::default(),
 						}
 					} else {
-						let executable = E::from_storage(
-							contract
-								.as_contract()
-								.expect("When not a precompile the contract was loaded above; qed")
-								.code_hash,
-							gas_meter,
-						)?;
+						let code_hash = contract
+							.as_contract()
+							.expect("When not a precompile the contract was loaded above; qed")
+							.code_hash;
+						let Some(code_hash) = executed_code_hash::<T>(&address, code_hash) else {
+							return Ok(None);
+						};
+						let executable = E::from_storage(code_hash, gas_meter)?;
 						ExecutableOrPrecompile::Executable(executable)
 					}
 				};
@@ -1049,10 +1119,7 @@ where
 		if let (CachedContract::Cached(contract), ExportedFunction::Call) =
 			(&frame.contract_info, frame.entry_point)
 		{
-			<ContractInfoOf<T>>::insert(
-				T::AddressMapper::to_address(&frame.account_id),
-				contract.clone(),
-			);
+			contract.store(&T::AddressMapper::to_address(&frame.account_id));
 		}
 
 		let frame = top_frame_mut!(self);
@@ -1091,7 +1158,7 @@ where
 			tracer.enter_child_span(
 				self.caller().account_id().map(T::AddressMapper::to_address).unwrap_or_default(),
 				T::AddressMapper::to_address(&frame.account_id),
-				frame.delegate.is_some(),
+				frame.delegate.as_ref().map(|delegate| delegate.callee),
 				frame.read_only,
 				frame.value_transferred,
 				&input_data,
@@ -1330,7 +1397,7 @@ where
 				// because that case is already handled by the optimization above. Only the first
 				// cache needs to be invalidated because that one will invalidate the next cache
 				// when it is popped from the stack.
-				<ContractInfoOf<T>>::insert(T::AddressMapper::to_address(account_id), contract);
+				contract.store(&T::AddressMapper::to_address(account_id));
 				if let Some(f) = self.frames_mut().skip(1).find(|f| f.account_id == *account_id) {
 					f.contract_info.invalidate();
 				}
@@ -1347,10 +1414,7 @@ where
 				contract.as_deref_mut(),
 			);
 			if let Some(contract) = contract {
-				<ContractInfoOf<T>>::insert(
-					T::AddressMapper::to_address(&self.first_frame.account_id),
-					contract,
-				);
+				contract.store(&T::AddressMapper::to_address(&self.first_frame.account_id));
 			}
 		}
 	}
@@ -1543,6 +1607,9 @@ where
 		if frame.entry_point == ExportedFunction::Constructor {
 			return Err(Error::<T>::TerminatedInConstructor.into());
 		}
+		if <CodeDelegationOf<T>>::contains_key(&T::AddressMapper::to_address(&frame.account_id)) {
+			return Err(Error::<T>::TerminatedDelegatedAccount.into());
+		}
 		let info = frame.terminate();
 		let beneficiary_account = T::AddressMapper::to_account_id(beneficiary);
 		frame.nested_storage.terminate(&info, beneficiary_account);
@@ -1598,7 +1665,16 @@ where
 	}
 
 	fn immutable_data_len(&mut self) -> u32 {
-		self.top_frame_mut().contract_info().immutable_data_len()
+		let frame = self.top_frame_mut();
+		if frame.delegate.is_none() {
+			let address = T::AddressMapper::to_address(&frame.account_id);
+			if let Some(target) = <CodeDelegationOf<T>>::get(&address) {
+				return <ContractInfoOf<T>>::get(&target)
+					.map(|info| info.immutable_data_len())
+					.unwrap_or_default();
+			}
+		}
+		frame.contract_info().immutable_data_len()
 	}
 
 	fn get_immutable_data(&mut self) -> Result<ImmutableData, DispatchError> {
@@ -1613,6 +1689,8 @@ where
 			.as_ref()
 			.map(|d| d.callee)
 			.unwrap_or(T::AddressMapper::to_address(self.account_id()));
+		// Delegated code reads the immutable data of the delegation target.
+		let address = <CodeDelegationOf<T>>::get(&address).unwrap_or(address);
 		Ok(<ImmutableDataOf<T>>::get(address).ok_or_else(|| Error::<T>::InvalidImmutableAccess)?)
 	}
 
@@ -1766,7 +1844,7 @@ where
 					t.enter_child_span(
 						T::AddressMapper::to_address(self.account_id()),
 						T::AddressMapper::to_address(&dest),
-						false,
+						None,
 						is_read_only,
 						value,
 						&input_data,
@@ -1856,6 +1934,9 @@ where
 	}
 
 	fn code_hash(&self, address: &H160) -> H256 {
+		if let Some(target) = <CodeDelegationOf<T>>::get(&address) {
+			return H256(keccak_256(&delegation_designator(&target)));
+		}
 		<ContractInfoOf<T>>::get(&address)
 			.map(|contract| contract.code_hash)
 			.unwrap_or_else(|| {
@@ -1867,6 +1948,9 @@ where
 	}
 
 	fn code_size(&self, address: &H160) -> u64 {
+		if let Some(target) = <CodeDelegationOf<T>>::get(&address) {
+			return delegation_designator(&target).len() as u64;
+		}
 		<ContractInfoOf<T>>::get(&address)
 			.and_then(|contract| CodeInfoOf::<T>::get(contract.code_hash))
 			.map(|info| info.code_len())
@@ -285,6 +285,12 @@ impl<T: Config> GasMeter<T> {
 		self.engine_meter.fuel.try_into().map_err(|_| <Error<T>>::OutOfGas.into())
 	}
 
+	/// Convert an amount of engine gas into the weight it is charged as.
+	pub fn weight_from_engine_fuel(engine_fuel: polkavm::Gas) -> Weight {
+		let fuel = u64::try_from(engine_fuel).unwrap_or_default();
+		Weight::from_parts(fuel.saturating_mul(EngineMeter::<T>::ref_time_per_fuel()), 0)
+	}
+
 	/// Turn this GasMeter into a DispatchResult that contains the actually used gas.
 	pub fn into_dispatch_result<R, E>(
 		self,
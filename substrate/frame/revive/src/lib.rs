@@ -44,11 +44,14 @@ pub mod weights;
 
 use crate::{
 	evm::{
-		runtime::GAS_PRICE, CallTracer, GasEncoder, GenericTransaction, PrestateTracer, Trace,
-		Tracer, TracerType, TYPE_EIP1559,
+		runtime::GAS_PRICE, AccessListTracer, AccountOverride, AuthorizationListEntry,
+		BlockOverrides, CallTracer, DryRunOverrides, GasEncoder, GenericTransaction, LogTracer,
+		PrestateTracer, SimulateCallError, SimulatePayload, SimulatedBlock, SimulatedCall,
+		StateOverride, StructLogger, Trace, Tracer, TracerType, TYPE_EIP1559,
 	},
 	exec::{AccountIdOf, ExecError, Executable, Key, Stack as ExecStack},
 	gas::GasMeter,
+	precompiles::{All as AllPrecompiles, Precompiles},
 	storage::{meter::Meter as StorageMeter, ContractInfo, DeletionQueueManager},
 	tracing::if_tracing,
 	vm::{CodeInfo, ContractBlob, RuntimeCosts},
@@ -80,7 +83,7 @@ use pallet_transaction_payment::OnChargeTransaction;
 use scale_info::TypeInfo;
 use sp_runtime::{
 	traits::{BadOrigin, Bounded, Convert, Dispatchable, Saturating, Zero},
-	AccountId32, DispatchError,
+	AccountId32, DispatchError, SaturatedConversion,
 };
 
 pub use crate::{
@@ -274,6 +277,22 @@ pub mod pallet {
 		/// Only valid value is `()`. See [`GasEncoder`].
 		#[pallet::no_default_bounds]
 		type EthGasEncoder: GasEncoder<BalanceOf<Self>>;
+
+		/// Make the secp256r1 signature verification pre-compile available.
+		///
+		/// Follows [RIP-7212](https://github.com/ethereum/RIPs/blob/master/RIPS/rip-7212.md)
+		/// and lives at address `0x100`. When set to `false` the address behaves like any
+		/// other account without code.
+		#[pallet::constant]
+		type EnableP256Verify: Get<bool>;
+
+		/// Make the BLS12-381 pre-compiles available.
+		///
+		/// Follows [EIP-2537](https://eips.ethereum.org/EIPS/eip-2537) and occupies the
+		/// addresses `0x0b` to `0x11`. When set to `false` those addresses behave like any
+		/// other account without code.
+		#[pallet::constant]
+		type EnableBls12381: Get<bool>;
 	}
 
 	/// Container for different types that implement [`DefaultConfig`]` of this pallet.
@@ -345,6 +364,8 @@ pub mod pallet {
 			type NativeToEthRatio = ConstU32<1>;
 			type EthGasEncoder = ();
 			type FindAuthor = ();
+			type EnableP256Verify = ConstBool<true>;
+			type EnableBls12381 = ConstBool<true>;
 		}
 	}
 
@@ -471,6 +492,8 @@ pub mod pallet {
 		RefcountOverOrUnderflow = 0x2E,
 		/// Unsupported precompile address
 		UnsupportedPrecompileAddress = 0x2F,
+		/// An account executing code delegated to it via EIP-7702 tried to terminate.
+		TerminatedDelegatedAccount = 0x30,
 	}
 
 	/// A reason for the pallet contracts placing a hold on funds.
@@ -513,6 +536,21 @@ pub mod pallet {
 	pub(crate) type DeletionQueueCounter<T: Config> =
 		StorageValue<_, DeletionQueueManager<T>, ValueQuery>;
 
+	/// The EIP-7702 code delegations of externally owned accounts.
+	///
+	/// Calls into an account found in this map execute the code of the delegation target, in
+	/// the context of the account.
+	#[pallet::storage]
+	pub(crate) type CodeDelegationOf<T: Config> = StorageMap<_, Identity, H160, H160>;
+
+	/// The storage info of externally owned accounts delegating their code via EIP-7702.
+	///
+	/// Kept apart from [`ContractInfoOf`], so that those accounts are not treated as contracts.
+	/// It outlives the delegation, the same way the storage of an Ethereum account does.
+	#[pallet::storage]
+	pub(crate) type DelegatedAccountInfoOf<T: Config> =
+		StorageMap<_, Identity, H160, ContractInfo<T>>;
+
 	/// Map a Ethereum address to its original `AccountId32`.
 	///
 	/// When deriving a `H160` from an `AccountId32` we use a hash function. In order to
@@ -1278,6 +1316,9 @@ where
 
 					(result, dispatch_call)
 				} else {
+					// Delegations take effect before the call is executed.
+					Self::apply_authorizations(&tx.authorization_list);
+
 					// Dry run the call.
 					let result = crate::Pallet::<T>::bare_call(
 						T::RuntimeOrigin::signed(origin),
@@ -1394,7 +1435,12 @@ where
 
 		let eth_transact_call =
 			crate::Call::<T>::eth_transact { payload: unsigned_tx.dummy_signed_payload() };
-		let fee = tx_fee(eth_transact_call.into(), dispatch_call);
+		// The authorizations are charged by the `ApplyAuthorizations` transaction extension.
+		let authorizations_fee = T::WeightPrice::convert(Self::authorizations_weight(
+			tx.authorization_list.len() as u32,
+		));
+		let fee =
+			tx_fee(eth_transact_call.into(), dispatch_call).saturating_add(authorizations_fee);
 		let raw_gas = Self::evm_fee_to_gas(fee);
 		let eth_gas =
 			T::EthGasEncoder::encode(raw_gas, result.gas_required, result.storage_deposit);
@@ -1404,6 +1450,359 @@ where
 		Ok(result)
 	}
 
+	/// Dry-run Ethereum calls against a state and block patched with the given `overrides`.
+	///
+	/// The overrides are written straight to storage. Hence this must only be called from a
+	/// runtime API, whose storage changes are discarded.
+	///
+	/// See [`Self::dry_run_eth_transact`] for the other parameters.
+	pub fn dry_run_eth_transact_with_overrides(
+		tx: GenericTransaction,
+		overrides: DryRunOverrides,
+		gas_limit: Weight,
+		tx_fee: impl Fn(<T as Config>::RuntimeCall, <T as Config>::RuntimeCall) -> BalanceOf<T>,
+	) -> Result<EthTransactInfo<BalanceOf<T>>, EthTransactError>
+	where
+		<T as frame_system::Config>::RuntimeCall:
+			Dispatchable<Info = frame_support::dispatch::DispatchInfo>,
+		T: pallet_transaction_payment::Config,
+		OnChargeTransactionBalanceOf<T>: Into<BalanceOf<T>>,
+		<T as Config>::RuntimeCall: From<crate::Call<T>>,
+		<T as Config>::RuntimeCall: Encode,
+		T::Nonce: Into<U256>,
+		T::Hash: frame_support::traits::IsType<H256>,
+	{
+		log::trace!(target: LOG_TARGET, "dry_run_eth_transact_with_overrides: {overrides:?}");
+
+		Self::apply_state_overrides(overrides.state)?;
+
+		let BlockOverrides { number, time, base_fee_per_gas } = overrides.block;
+		if let Some(number) = number {
+			let number = u64::try_from(number)
+				.map_err(|_| EthTransactError::Message("Invalid block number override".into()))?;
+			System::<T>::set_block_number(number.saturated_into());
+		}
+
+		// Like geth, the base fee override is only seen by contracts through `BASEFEE`, the gas
+		// price of the call is left alone.
+		let dry_run = || match time {
+			Some(time) => {
+				let timestamp =
+					u64::try_from(time).ok().and_then(|time| time.checked_mul(1000)).ok_or_else(
+						|| EthTransactError::Message("Invalid block time override".into()),
+					)?;
+				exec::with_timestamp_override(timestamp, || {
+					Self::dry_run_eth_transact(tx, gas_limit, tx_fee)
+				})
+			},
+			None => Self::dry_run_eth_transact(tx, gas_limit, tx_fee),
+		};
+		match base_fee_per_gas {
+			Some(base_fee) => exec::with_base_fee_override(base_fee, dry_run),
+			None => dry_run(),
+		}
+	}
+
+	/// Simulate a sequence of Ethereum calls across one or more blocks, as `eth_simulateV1` does.
+	///
+	/// Every call is dry run against the state left by the previous ones. Like
+	/// [`Self::dry_run_eth_transact_with_overrides`], this must only be called from a runtime API.
+	/// Payloads exceeding [`SimulatePayload::MAX_BLOCKS`] or [`SimulatePayload::MAX_CALLS`] are
+	/// rejected, and calls fail once all calls together used the gas of a whole block.
+	///
+	/// Blocks without a number or time override follow the previous block by one number and
+	/// [`SimulatePayload::BLOCK_TIME`] seconds per number. Overrides must keep both increasing.
+	///
+	/// See [`Self::dry_run_eth_transact`] for the other parameters.
+	pub fn simulate_eth_transacts(
+		payload: SimulatePayload,
+		gas_limit: Weight,
+		tx_fee: impl Fn(<T as Config>::RuntimeCall, <T as Config>::RuntimeCall) -> BalanceOf<T>,
+	) -> Result<Vec<SimulatedBlock>, EthTransactError>
+	where
+		<T as frame_system::Config>::RuntimeCall:
+			Dispatchable<Info = frame_support::dispatch::DispatchInfo>,
+		T: pallet_transaction_payment::Config,
+		OnChargeTransactionBalanceOf<T>: Into<BalanceOf<T>>,
+		<T as Config>::RuntimeCall: From<crate::Call<T>>,
+		<T as Config>::RuntimeCall: Encode,
+		T::Nonce: Into<U256>,
+		T::Hash: frame_support::traits::IsType<H256>,
+	{
+		payload.check_limits().map_err(EthTransactError::Message)?;
+		let SimulatePayload { block_state_calls, trace_transfers, validation } = payload;
+		let block_gas_limit = Self::evm_block_gas_limit();
+		// Bounds the work of a single simulation, whatever the number of calls.
+		let mut gas_budget = block_gas_limit;
+		let mut number = U256::from(System::<T>::block_number().saturated_into::<u64>());
+		let mut timestamp = Into::<U256>::into(T::Time::now()) / 1000;
+
+		let mut blocks = Vec::with_capacity(block_state_calls.len());
+		for block in block_state_calls {
+			let block_overrides = block.block_overrides.unwrap_or_default();
+			let parent_number = number;
+			number = block_overrides.number.unwrap_or_else(|| number.saturating_add(U256::one()));
+			if number <= parent_number {
+				return Err(EthTransactError::Message(format!(
+					"block numbers must be in order: {number} <= {parent_number}"
+				)));
+			}
+			let parent_timestamp = timestamp;
+			timestamp = block_overrides.time.unwrap_or_else(|| {
+				let blocks = number - parent_number;
+				parent_timestamp
+					.saturating_add(blocks.saturating_mul(SimulatePayload::BLOCK_TIME.into()))
+			});
+			if timestamp <= parent_timestamp {
+				return Err(EthTransactError::Message(format!(
+					"block timestamps must be in order: {timestamp} <= {parent_timestamp}"
+				)));
+			}
+			let base_fee_per_gas = block_overrides.base_fee_per_gas.unwrap_or(GAS_PRICE.into());
+			let block_overrides = BlockOverrides {
+				number: Some(number),
+				time: Some(timestamp),
+				base_fee_per_gas: Some(base_fee_per_gas),
+			};
+
+			Self::apply_state_overrides(block.state_overrides.unwrap_or_default())?;
+
+			let mut gas_used = U256::zero();
+			let mut log_index = U256::zero();
+			let mut calls = Vec::with_capacity(block.calls.len());
+			for (index, mut tx) in block.calls.into_iter().enumerate() {
+				let check = if gas_budget.is_zero() {
+					Err(SimulateCallError {
+						code: SimulateCallError::LIMIT_EXCEEDED,
+						message: "the gas of all simulated calls exceeds the block gas limit"
+							.into(),
+					})
+				} else if validation {
+					Self::validate_simulated_call(
+						&mut tx,
+						block_gas_limit.saturating_sub(gas_used),
+						base_fee_per_gas,
+					)
+				} else {
+					Ok(())
+				};
+				if let Err(error) = check {
+					calls.push(SimulatedCall { error: Some(error), ..Default::default() });
+					continue
+				}
+
+				let mut tracer = LogTracer::new(
+					trace_transfers,
+					Self::evm_gas_from_weight as fn(Weight) -> U256,
+				);
+				let overrides =
+					DryRunOverrides { state: Default::default(), block: block_overrides.clone() };
+				let result = crate::tracing::trace(&mut tracer, || {
+					Self::dry_run_eth_transact_with_overrides(tx, overrides, gas_limit, &tx_fee)
+				});
+				let (mut logs, call_gas_used) = tracer.collect_trace();
+				gas_used = gas_used.saturating_add(call_gas_used);
+				gas_budget = gas_budget.saturating_sub(call_gas_used);
+
+				let call = match result {
+					Ok(info) => {
+						for log in logs.iter_mut() {
+							log.block_number = number;
+							log.transaction_index = index.into();
+							log.log_index = log_index;
+							log_index = log_index.saturating_add(U256::one());
+						}
+						SimulatedCall {
+							return_data: info.data.into(),
+							logs,
+							gas_used: call_gas_used,
+							status: U256::one(),
+							error: None,
+						}
+					},
+					Err(EthTransactError::Data(data)) => SimulatedCall {
+						return_data: data.into(),
+						gas_used: call_gas_used,
+						error: Some(SimulateCallError {
+							code: SimulateCallError::REVERTED,
+							message: "execution reverted".into(),
+						}),
+						..Default::default()
+					},
+					Err(EthTransactError::Message(message)) => SimulatedCall {
+						gas_used: call_gas_used,
+						error: Some(SimulateCallError { code: SimulateCallError::FAILED, message }),
+						..Default::default()
+					},
+				};
+				calls.push(call);
+			}
+
+			blocks.push(SimulatedBlock {
+				number,
+				timestamp,
+				gas_limit: block_gas_limit,
+				gas_used,
+				base_fee_per_gas,
+				calls,
+			});
+		}
+
+		Ok(blocks)
+	}
+
+	/// Checks that a simulated call could be included in a block, as `eth_simulateV1` does with
+	/// validation enabled: its nonce, its gas against the gas left in the block, its fee against
+	/// the base fee and the balance of the sender against the cost of the call.
+	///
+	/// The gas of the call defaults to the gas left in the block, so that it is paid for.
+	fn validate_simulated_call(
+		tx: &mut GenericTransaction,
+		gas_left: U256,
+		base_fee_per_gas: U256,
+	) -> Result<(), SimulateCallError>
+	where
+		T::Nonce: Into<U256>,
+	{
+		let from = tx.from.unwrap_or_default();
+		let nonce: U256 = System::<T>::account_nonce(T::AddressMapper::to_account_id(&from)).into();
+		match tx.nonce {
+			Some(tx_nonce) if tx_nonce < nonce =>
+				return Err(SimulateCallError {
+					code: SimulateCallError::NONCE_TOO_LOW,
+					message: format!(
+						"nonce too low: address {from:?}, tx: {tx_nonce} state: {nonce}"
+					),
+				}),
+			Some(tx_nonce) if tx_nonce > nonce =>
+				return Err(SimulateCallError {
+					code: SimulateCallError::NONCE_TOO_HIGH,
+					message: format!(
+						"nonce too high: address {from:?}, tx: {tx_nonce} state: {nonce}"
+					),
+				}),
+			_ => (),
+		}
+
+		let gas = *tx.gas.get_or_insert(gas_left);
+		if gas > gas_left {
+			return Err(SimulateCallError {
+				code: SimulateCallError::BLOCK_GAS_LIMIT_REACHED,
+				message: format!("block gas limit reached: gas {gas} exceeds the {gas_left} left"),
+			});
+		}
+
+		let gas_price = tx.max_fee_per_gas.or(tx.gas_price).unwrap_or(base_fee_per_gas);
+		if gas_price < base_fee_per_gas {
+			return Err(SimulateCallError {
+				code: SimulateCallError::FAILED,
+				message: format!(
+					"max fee per gas less than block base fee: address {from:?}, \
+					maxFeePerGas: {gas_price}, baseFee: {base_fee_per_gas}"
+				),
+			});
+		}
+
+		let cost = gas.saturating_mul(gas_price).saturating_add(tx.value.unwrap_or_default());
+		let balance = Self::evm_balance(&from);
+		if balance < cost {
+			return Err(SimulateCallError {
+				code: SimulateCallError::INSUFFICIENT_FUNDS,
+				message: format!(
+					"insufficient funds for gas * price + value: address {from:?} have {balance} \
+					want {cost}"
+				),
+			});
+		}
+
+		Ok(())
+	}
+
+	/// Write the given state overrides to storage.
+	fn apply_state_overrides(overrides: StateOverride) -> Result<(), EthTransactError> {
+		for (address, account_override) in overrides {
+			let AccountOverride { balance, nonce, code, state, state_diff } = account_override;
+			if state.is_some() && state_diff.is_some() {
+				return Err(EthTransactError::Message(format!(
+					"Account {address:?} has both 'state' and 'stateDiff' overrides"
+				)));
+			}
+
+			let account = T::AddressMapper::to_account_id(&address);
+
+			if let Some(balance) = balance {
+				let balance = Self::convert_evm_to_native(balance, ConversionPrecision::RoundUp)
+					.map_err(|_| {
+						EthTransactError::Message(format!(
+							"Invalid balance override for account {address:?}"
+						))
+					})?;
+				T::Currency::set_balance(&account, balance);
+			}
+
+			if let Some(nonce) = nonce {
+				let nonce = u64::try_from(nonce).map_err(|_| {
+					EthTransactError::Message(format!(
+						"Invalid nonce override for account {address:?}"
+					))
+				})?;
+				frame_system::Account::<T>::mutate(&account, |info| {
+					info.nonce = nonce.saturated_into()
+				});
+			}
+
+			if let Some(code) = code {
+				let invalid_code = |err| {
+					EthTransactError::Message(format!(
+						"Invalid code override for account {address:?}: {err:?}"
+					))
+				};
+				let mut module =
+					ContractBlob::<T>::from_code(code.0, account.clone()).map_err(invalid_code)?;
+				module.store_code(true).map_err(|err| invalid_code(err.into()))?;
+				let code_hash = *module.code_hash();
+				let contract = match ContractInfoOf::<T>::get(&address) {
+					Some(mut contract) => {
+						contract.code_hash = code_hash;
+						contract
+					},
+					None =>
+						ContractInfo::new(&address, System::<T>::account_nonce(&account), code_hash)
+							.map_err(invalid_code)?,
+				};
+				ContractInfoOf::<T>::insert(address, contract);
+			}
+
+			if state.is_none() && state_diff.is_none() {
+				continue
+			}
+			let Some(contract) = ContractInfoOf::<T>::get(&address) else {
+				return Err(EthTransactError::Message(format!(
+					"Cannot override the storage of account {address:?} without code"
+				)));
+			};
+
+			if state.is_some() {
+				let _ = frame_support::storage::child::clear_storage(
+					&contract.child_trie_info(),
+					None,
+					None,
+				);
+			}
+
+			for (key, value) in state.into_iter().chain(state_diff).flatten() {
+				let value = (!value.is_zero()).then(|| value.0.to_vec());
+				contract.write(&Key::Fix(key.0), value, None, false).map_err(|err| {
+					EthTransactError::Message(format!(
+						"Failed to override storage of account {address:?}: {err:?}"
+					))
+				})?;
+			}
+		}
+
+		Ok(())
+	}
+
 	/// Get the balance with EVM decimals of the given `address`.
 	pub fn evm_balance(address: &H160) -> U256 {
 		let account = T::AddressMapper::to_account_id(&address);
@@ -1470,6 +1869,12 @@ where
 		GAS_PRICE.into()
 	}
 
+	/// Returns true if a pre-compile is deployed at `address`.
+	fn is_precompile(address: &H160) -> bool {
+		<AllPrecompiles<T>>::get::<ExecStack<'_, T, ContractBlob<T>>>(address.as_fixed_bytes())
+			.is_some()
+	}
+
 	/// Build an EVM tracer from the given tracer type.
 	pub fn evm_tracer(tracer_type: TracerType) -> Tracer<T>
 	where
@@ -1483,6 +1888,13 @@ where
 			.into(),
 			TracerType::PrestateTracer(config) =>
 				PrestateTracer::new(config.unwrap_or_default()).into(),
+			TracerType::AccessListTracer =>
+				AccessListTracer::new(Self::is_precompile as fn(&H160) -> bool).into(),
+			TracerType::StructLogger(config) => StructLogger::new(
+				config.unwrap_or_default(),
+				Self::evm_gas_from_weight as fn(Weight) -> U256,
+			)
+			.into(),
 		}
 	}
 
@@ -1502,7 +1914,7 @@ where
 	/// Query storage of a specified contract under a specified key.
 	pub fn get_storage(address: H160, key: [u8; 32]) -> GetStorageResult {
 		let contract_info =
-			ContractInfoOf::<T>::get(&address).ok_or(ContractAccessError::DoesntExist)?;
+			ContractInfo::<T>::load(&address).ok_or(ContractAccessError::DoesntExist)?;
 
 		let maybe_value = contract_info.read(&Key::from_fixed(key));
 		Ok(maybe_value)
@@ -1511,7 +1923,7 @@ where
 	/// Query storage of a specified contract under a specified variable-sized key.
 	pub fn get_storage_var_key(address: H160, key: Vec<u8>) -> GetStorageResult {
 		let contract_info =
-			ContractInfoOf::<T>::get(&address).ok_or(ContractAccessError::DoesntExist)?;
+			ContractInfo::<T>::load(&address).ok_or(ContractAccessError::DoesntExist)?;
 
 		let maybe_value = contract_info.read(
 			&Key::try_from_var(key)
@@ -1580,6 +1992,52 @@ where
 }
 
 impl<T: Config> Pallet<T> {
+	/// The weight of applying `n` EIP-7702 authorizations.
+	fn authorizations_weight(n: u32) -> Weight {
+		T::WeightInfo::ecdsa_recover()
+			.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+			.saturating_mul(n.into())
+	}
+
+	/// Apply the EIP-7702 `authorizations` of an Ethereum transaction.
+	///
+	/// Every valid authorization sets (or, when delegating to the zero address, clears) the code
+	/// delegation of its authority and bumps the authority's nonce. Invalid authorizations are
+	/// skipped, as mandated by the EIP.
+	fn apply_authorizations(authorizations: &[AuthorizationListEntry]) {
+		for auth in authorizations {
+			if !auth.chain_id.is_zero() && auth.chain_id != T::ChainId::get().into() {
+				log::debug!(target: LOG_TARGET, "Skipping authorization with invalid chain_id {:?}", auth.chain_id);
+				continue;
+			}
+
+			let Ok(authority) = auth.recover_authority() else {
+				log::debug!(target: LOG_TARGET, "Skipping authorization with invalid signature");
+				continue;
+			};
+
+			// Only accounts without code can be authorities.
+			if <ContractInfoOf<T>>::contains_key(&authority) {
+				log::debug!(target: LOG_TARGET, "Skipping authorization of contract {authority:?}");
+				continue;
+			}
+
+			let account_id = T::AddressMapper::to_account_id(&authority);
+			let nonce: u64 = <System<T>>::account_nonce(&account_id).saturated_into();
+			if auth.nonce != nonce.into() {
+				log::debug!(target: LOG_TARGET, "Skipping authorization of {authority:?} with invalid nonce {:?}", auth.nonce);
+				continue;
+			}
+
+			if auth.address.is_zero() {
+				<CodeDelegationOf<T>>::remove(&authority);
+			} else {
+				<CodeDelegationOf<T>>::insert(&authority, auth.address);
+			}
+			<System<T>>::inc_account_nonce(&account_id);
+		}
+	}
+
 	/// Return the existential deposit of [`Config::Currency`].
 	fn min_balance() -> BalanceOf<T> {
 		<T::Currency as Inspect<AccountIdOf<T>>>::minimum_balance()
@@ -1614,7 +2072,16 @@ environmental!(executing_contract: bool);
 
 sp_api::decl_runtime_apis! {
 	/// The API used to dry-run contract interactions.
-	#[api_version(1)]
+	///
+	/// Since version 4, the [`GenericTransaction`] passed to the API ends with the EIP-7702
+	/// `authorization_list`. Runtimes of previous versions only ignore it when the transaction is
+	/// the last parameter, as in the version 1 `eth_transact(tx)`. With any other parameter
+	/// following it, the field must be stripped before calling into such a runtime.
+	///
+	/// Since version 5, the tracing APIs accept [`TracerType::AccessListTracer`] and
+	/// [`TracerType::StructLogger`], returning a [`Trace::AccessList`] and a
+	/// [`Trace::StructLogger`]. Runtimes of previous versions fail to decode them.
+	#[api_version(5)]
 	pub trait ReviveApi<AccountId, Balance, Nonce, BlockNumber> where
 		AccountId: Codec,
 		Balance: Codec,
@@ -1662,8 +2129,23 @@ sp_api::decl_runtime_apis! {
 		/// Perform an Ethereum call.
 		///
 		/// See [`crate::Pallet::dry_run_eth_transact`]
+		#[changed_in(2)]
 		fn eth_transact(tx: GenericTransaction) -> Result<EthTransactInfo<Balance>, EthTransactError>;
 
+		/// Perform an Ethereum call against a state and block patched with the given overrides.
+		///
+		/// See [`crate::Pallet::dry_run_eth_transact_with_overrides`]
+		fn eth_transact(
+			tx: GenericTransaction,
+			overrides: DryRunOverrides,
+		) -> Result<EthTransactInfo<Balance>, EthTransactError>;
+
+		/// Simulate a sequence of Ethereum calls across one or more blocks.
+		///
+		/// See [`crate::Pallet::simulate_eth_transacts`]
+		#[api_version(3)]
+		fn eth_simulate(payload: SimulatePayload) -> Result<Vec<SimulatedBlock>, EthTransactError>;
+
 		/// Upload new code without instantiating a contract from it.
 		///
 		/// See [`crate::Pallet::bare_upload_code`].
@@ -1740,6 +2222,38 @@ sp_api::decl_runtime_apis! {
 macro_rules! impl_runtime_apis_plus_revive {
 	($Runtime: ty, $Executive: ty, $EthExtra: ty, $($rest:tt)*) => {
 
+		/// Compute the fee of the extrinsic wrapping `call`, where `dispatch_call` is the call it
+		/// dispatches. Used to dry run Ethereum transactions.
+		fn revive_eth_tx_fee(
+			call: <$Runtime as $crate::frame_system::Config>::RuntimeCall,
+			dispatch_call: <$Runtime as $crate::frame_system::Config>::RuntimeCall,
+		) -> Balance {
+			use $crate::{
+				codec::Encode, evm::runtime::EthExtra,
+				frame_support::dispatch::GetDispatchInfo,
+				sp_runtime::traits::TransactionExtension,
+				sp_runtime::traits::Block as BlockT
+			};
+
+			// Get the dispatch info of the actual call dispatched. The weight of the EIP-7702
+			// authorizations is added by the caller.
+			let mut dispatch_info = dispatch_call.get_dispatch_info();
+			dispatch_info.extension_weight =
+				<$EthExtra>::get_eth_extension(0, 0u32.into(), Default::default())
+					.weight(&dispatch_call);
+
+			// Build the extrinsic
+			let uxt: <Block as BlockT>::Extrinsic =
+				$crate::sp_runtime::generic::UncheckedExtrinsic::new_bare(call).into();
+
+			// Compute the fee of the extrinsic
+			$crate::pallet_transaction_payment::Pallet::<$Runtime>::compute_fee(
+				uxt.encoded_size() as u32,
+				&dispatch_info,
+				0u32.into(),
+			)
+		}
+
 		impl_runtime_apis! {
 			$($rest)*
 
@@ -1768,36 +2282,32 @@ macro_rules! impl_runtime_apis_plus_revive {
 
 				fn eth_transact(
 					tx: $crate::evm::GenericTransaction,
+					overrides: $crate::evm::DryRunOverrides,
 				) -> Result<$crate::EthTransactInfo<Balance>, $crate::EthTransactError> {
-					use $crate::{
-						codec::Encode, evm::runtime::EthExtra, frame_support::traits::Get,
-						sp_runtime::traits::TransactionExtension,
-						sp_runtime::traits::Block as BlockT
-					};
-
-					let tx_fee = |call: <Self as $crate::frame_system::Config>::RuntimeCall, dispatch_call: <Self as $crate::frame_system::Config>::RuntimeCall| {
-						use $crate::frame_support::dispatch::GetDispatchInfo;
-
-						// Get the dispatch info of the actual call dispatched
-						let mut dispatch_info = dispatch_call.get_dispatch_info();
-						dispatch_info.extension_weight =
-							<$EthExtra>::get_eth_extension(0, 0u32.into()).weight(&dispatch_call);
+					use $crate::frame_support::traits::Get;
 
-						// Build the extrinsic
-						let uxt: <Block as BlockT>::Extrinsic =
-							$crate::sp_runtime::generic::UncheckedExtrinsic::new_bare(call).into();
+					let blockweights: $crate::BlockWeights =
+						<Self as $crate::frame_system::Config>::BlockWeights::get();
+					$crate::Pallet::<Self>::dry_run_eth_transact_with_overrides(
+						tx,
+						overrides,
+						blockweights.max_block,
+						revive_eth_tx_fee,
+					)
+				}
 
-						// Compute the fee of the extrinsic
-						$crate::pallet_transaction_payment::Pallet::<Self>::compute_fee(
-							uxt.encoded_size() as u32,
-							&dispatch_info,
-							0u32.into(),
-						)
-					};
+				fn eth_simulate(
+					payload: $crate::evm::SimulatePayload,
+				) -> Result<Vec<$crate::evm::SimulatedBlock>, $crate::EthTransactError> {
+					use $crate::frame_support::traits::Get;
 
 					let blockweights: $crate::BlockWeights =
 						<Self as $crate::frame_system::Config>::BlockWeights::get();
-					$crate::Pallet::<Self>::dry_run_eth_transact(tx, blockweights.max_block, tx_fee)
+					$crate::Pallet::<Self>::simulate_eth_transacts(
+						payload,
+						blockweights.max_block,
+						revive_eth_tx_fee,
+					)
 				}
 
 				fn call(
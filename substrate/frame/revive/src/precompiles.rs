@@ -257,6 +257,14 @@ pub(crate) trait PrimitivePrecompile {
 	const MATCHER: BuiltinAddressMatcher;
 	const HAS_CONTRACT_INFO: bool;
 
+	/// Whether the pre-compile is currently available.
+	///
+	/// A disabled pre-compile is treated as if it didn't exist: Calls to its address
+	/// behave like calls to any other account without code.
+	fn is_enabled() -> bool {
+		true
+	}
+
 	fn call(
 		_address: &[u8; 20],
 		_input: Vec<u8>,
@@ -404,7 +412,7 @@ impl<T: Config> Precompiles<T> for Tuple {
 		let mut function: Option<fn(&[u8; 20], Vec<u8>, &mut E) -> Result<Vec<u8>, Error>> = None;
 		for_tuples!(
 			#(
-				if Tuple::MATCHER.matches(address) {
+				if Tuple::MATCHER.matches(address) && Tuple::is_enabled() {
 					if Tuple::HAS_CONTRACT_INFO {
 						has_contract_info = true;
 						function = Some(Tuple::call_with_info);
@@ -16,10 +16,12 @@
 // limitations under the License.
 
 mod blake2f;
+mod bls12_381;
 mod bn128;
 mod ecrecover;
 mod identity;
 mod modexp;
+mod p256_verify;
 mod point_eval;
 mod ripemd160;
 mod sha256;
@@ -53,6 +55,14 @@ type Production<T> = (
 	bn128::Bn128Pairing<T>,
 	blake2f::Blake2F<T>,
 	point_eval::PointEval<T>,
+	bls12_381::Bls12381G1Add<T>,
+	bls12_381::Bls12381G1Msm<T>,
+	bls12_381::Bls12381G2Add<T>,
+	bls12_381::Bls12381G2Msm<T>,
+	bls12_381::Bls12381PairingCheck<T>,
+	bls12_381::Bls12381MapFpToG1<T>,
+	bls12_381::Bls12381MapFp2ToG2<T>,
+	p256_verify::P256Verify<T>,
 );
 
 #[cfg(feature = "runtime-benchmarks")]
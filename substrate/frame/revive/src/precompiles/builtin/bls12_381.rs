@@ -0,0 +1,462 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The BLS12-381 pre-compiles as specified in
+//! [EIP-2537](https://eips.ethereum.org/EIPS/eip-2537).
+//!
+//! Field elements are encoded as 64 bytes big endian where the top 16 bytes must be zero.
+//! Points are encoded as their affine coordinates where the point at infinity is all zeros.
+
+use crate::{
+	precompiles::{BuiltinAddressMatcher, Error, Ext, PrimitivePrecompile},
+	vm::RuntimeCosts,
+	Config,
+};
+use alloc::vec::Vec;
+use ark_bls12_381::{
+	g1, g2, Bls12_381, Fq, Fq2, Fr, G1Affine, G1Projective, G2Affine, G2Projective,
+};
+use ark_ec::{
+	hashing::{curve_maps::wb::WBMap, map_to_curve_hasher::MapToCurve},
+	pairing::Pairing,
+	short_weierstrass::{Affine, SWCurveConfig},
+	AffineRepr, CurveGroup, VariableBaseMSM,
+};
+use ark_ff::{BigInteger384, PrimeField, Zero};
+use core::{marker::PhantomData, num::NonZero};
+use frame_support::traits::Get;
+use sp_runtime::DispatchError;
+
+/// Length of an encoded base field element.
+const FP_LEN: usize = 64;
+/// Length of an encoded element of the quadratic extension field.
+const FP2_LEN: usize = 2 * FP_LEN;
+/// Length of an encoded G1 point.
+const G1_LEN: usize = 2 * FP_LEN;
+/// Length of an encoded G2 point.
+const G2_LEN: usize = 2 * FP2_LEN;
+/// Length of an encoded scalar.
+const SCALAR_LEN: usize = 32;
+/// Length of a single pair accepted by the MSM pre-compile in G1.
+const G1_MSM_PAIR_LEN: usize = G1_LEN + SCALAR_LEN;
+/// Length of a single pair accepted by the MSM pre-compile in G2.
+const G2_MSM_PAIR_LEN: usize = G2_LEN + SCALAR_LEN;
+/// Length of a single pair accepted by the pairing check pre-compile.
+const PAIRING_PAIR_LEN: usize = G1_LEN + G2_LEN;
+/// The number of leading bytes of a field element that must be zero.
+const FP_PADDING: usize = FP_LEN - 48;
+
+pub struct Bls12381G1Add<T>(PhantomData<T>);
+
+impl<T: Config> PrimitivePrecompile for Bls12381G1Add<T> {
+	type T = T;
+	const MATCHER: BuiltinAddressMatcher =
+		BuiltinAddressMatcher::Fixed(NonZero::new(0x0b).unwrap());
+	const HAS_CONTRACT_INFO: bool = false;
+
+	fn is_enabled() -> bool {
+		T::EnableBls12381::get()
+	}
+
+	fn call(
+		_address: &[u8; 20],
+		input: Vec<u8>,
+		env: &mut impl Ext<T = Self::T>,
+	) -> Result<Vec<u8>, Error> {
+		env.gas_meter_mut().charge(RuntimeCosts::Bls12381G1Add)?;
+
+		if input.len() != 2 * G1_LEN {
+			Err(DispatchError::from("invalid input length"))?;
+		}
+
+		// no subgroup check required for addition
+		let p1 = read_g1(&input[..G1_LEN], false)?;
+		let p2 = read_g1(&input[G1_LEN..], false)?;
+
+		Ok(encode_g1((p1 + p2).into_affine()))
+	}
+}
+
+pub struct Bls12381G1Msm<T>(PhantomData<T>);
+
+impl<T: Config> PrimitivePrecompile for Bls12381G1Msm<T> {
+	type T = T;
+	const MATCHER: BuiltinAddressMatcher =
+		BuiltinAddressMatcher::Fixed(NonZero::new(0x0c).unwrap());
+	const HAS_CONTRACT_INFO: bool = false;
+
+	fn is_enabled() -> bool {
+		T::EnableBls12381::get()
+	}
+
+	fn call(
+		_address: &[u8; 20],
+		input: Vec<u8>,
+		env: &mut impl Ext<T = Self::T>,
+	) -> Result<Vec<u8>, Error> {
+		if input.is_empty() || input.len() % G1_MSM_PAIR_LEN != 0 {
+			Err(DispatchError::from("invalid input length"))?;
+		}
+
+		let pairs = input.len() / G1_MSM_PAIR_LEN;
+		env.gas_meter_mut().charge(RuntimeCosts::Bls12381G1Msm(pairs as u32))?;
+
+		let mut bases = Vec::with_capacity(pairs);
+		let mut scalars = Vec::with_capacity(pairs);
+		for pair in input.chunks_exact(G1_MSM_PAIR_LEN) {
+			bases.push(read_g1(&pair[..G1_LEN], true)?);
+			scalars.push(read_scalar(&pair[G1_LEN..]));
+		}
+
+		Ok(encode_g1(G1Projective::msm_unchecked(&bases, &scalars).into_affine()))
+	}
+}
+
+pub struct Bls12381G2Add<T>(PhantomData<T>);
+
+impl<T: Config> PrimitivePrecompile for Bls12381G2Add<T> {
+	type T = T;
+	const MATCHER: BuiltinAddressMatcher =
+		BuiltinAddressMatcher::Fixed(NonZero::new(0x0d).unwrap());
+	const HAS_CONTRACT_INFO: bool = false;
+
+	fn is_enabled() -> bool {
+		T::EnableBls12381::get()
+	}
+
+	fn call(
+		_address: &[u8; 20],
+		input: Vec<u8>,
+		env: &mut impl Ext<T = Self::T>,
+	) -> Result<Vec<u8>, Error> {
+		env.gas_meter_mut().charge(RuntimeCosts::Bls12381G2Add)?;
+
+		if input.len() != 2 * G2_LEN {
+			Err(DispatchError::from("invalid input length"))?;
+		}
+
+		// no subgroup check required for addition
+		let p1 = read_g2(&input[..G2_LEN], false)?;
+		let p2 = read_g2(&input[G2_LEN..], false)?;
+
+		Ok(encode_g2((p1 + p2).into_affine()))
+	}
+}
+
+pub struct Bls12381G2Msm<T>(PhantomData<T>);
+
+impl<T: Config> PrimitivePrecompile for Bls12381G2Msm<T> {
+	type T = T;
+	const MATCHER: BuiltinAddressMatcher =
+		BuiltinAddressMatcher::Fixed(NonZero::new(0x0e).unwrap());
+	const HAS_CONTRACT_INFO: bool = false;
+
+	fn is_enabled() -> bool {
+		T::EnableBls12381::get()
+	}
+
+	fn call(
+		_address: &[u8; 20],
+		input: Vec<u8>,
+		env: &mut impl Ext<T = Self::T>,
+	) -> Result<Vec<u8>, Error> {
+		if input.is_empty() || input.len() % G2_MSM_PAIR_LEN != 0 {
+			Err(DispatchError::from("invalid input length"))?;
+		}
+
+		let pairs = input.len() / G2_MSM_PAIR_LEN;
+		env.gas_meter_mut().charge(RuntimeCosts::Bls12381G2Msm(pairs as u32))?;
+
+		let mut bases = Vec::with_capacity(pairs);
+		let mut scalars = Vec::with_capacity(pairs);
+		for pair in input.chunks_exact(G2_MSM_PAIR_LEN) {
+			bases.push(read_g2(&pair[..G2_LEN], true)?);
+			scalars.push(read_scalar(&pair[G2_LEN..]));
+		}
+
+		Ok(encode_g2(G2Projective::msm_unchecked(&bases, &scalars).into_affine()))
+	}
+}
+
+pub struct Bls12381PairingCheck<T>(PhantomData<T>);
+
+impl<T: Config> PrimitivePrecompile for Bls12381PairingCheck<T> {
+	type T = T;
+	const MATCHER: BuiltinAddressMatcher =
+		BuiltinAddressMatcher::Fixed(NonZero::new(0x0f).unwrap());
+	const HAS_CONTRACT_INFO: bool = false;
+
+	fn is_enabled() -> bool {
+		T::EnableBls12381::get()
+	}
+
+	fn call(
+		_address: &[u8; 20],
+		input: Vec<u8>,
+		env: &mut impl Ext<T = Self::T>,
+	) -> Result<Vec<u8>, Error> {
+		if input.is_empty() || input.len() % PAIRING_PAIR_LEN != 0 {
+			Err(DispatchError::from("invalid input length"))?;
+		}
+
+		let pairs = input.len() / PAIRING_PAIR_LEN;
+		env.gas_meter_mut().charge(RuntimeCosts::Bls12381PairingCheck(pairs as u32))?;
+
+		let mut g1 = Vec::with_capacity(pairs);
+		let mut g2 = Vec::with_capacity(pairs);
+		for pair in input.chunks_exact(PAIRING_PAIR_LEN) {
+			g1.push(read_g1(&pair[..G1_LEN], true)?);
+			g2.push(read_g2(&pair[G1_LEN..], true)?);
+		}
+
+		let mut buf = [0u8; 32];
+		if Bls12_381::multi_pairing(g1, g2).is_zero() {
+			buf[31] = 1;
+		}
+
+		Ok(buf.to_vec())
+	}
+}
+
+pub struct Bls12381MapFpToG1<T>(PhantomData<T>);
+
+impl<T: Config> PrimitivePrecompile for Bls12381MapFpToG1<T> {
+	type T = T;
+	const MATCHER: BuiltinAddressMatcher =
+		BuiltinAddressMatcher::Fixed(NonZero::new(0x10).unwrap());
+	const HAS_CONTRACT_INFO: bool = false;
+
+	fn is_enabled() -> bool {
+		T::EnableBls12381::get()
+	}
+
+	fn call(
+		_address: &[u8; 20],
+		input: Vec<u8>,
+		env: &mut impl Ext<T = Self::T>,
+	) -> Result<Vec<u8>, Error> {
+		env.gas_meter_mut().charge(RuntimeCosts::Bls12381MapFpToG1)?;
+
+		if input.len() != FP_LEN {
+			Err(DispatchError::from("invalid input length"))?;
+		}
+
+		let fe = read_fp(&input)?;
+		let point = WBMap::<g1::Config>::new()
+			.and_then(|map| map.map_to_curve(fe))
+			.map_err(|_| DispatchError::from("mapping to curve failed"))?;
+
+		Ok(encode_g1(point.clear_cofactor()))
+	}
+}
+
+pub struct Bls12381MapFp2ToG2<T>(PhantomData<T>);
+
+impl<T: Config> PrimitivePrecompile for Bls12381MapFp2ToG2<T> {
+	type T = T;
+	const MATCHER: BuiltinAddressMatcher =
+		BuiltinAddressMatcher::Fixed(NonZero::new(0x11).unwrap());
+	const HAS_CONTRACT_INFO: bool = false;
+
+	fn is_enabled() -> bool {
+		T::EnableBls12381::get()
+	}
+
+	fn call(
+		_address: &[u8; 20],
+		input: Vec<u8>,
+		env: &mut impl Ext<T = Self::T>,
+	) -> Result<Vec<u8>, Error> {
+		env.gas_meter_mut().charge(RuntimeCosts::Bls12381MapFp2ToG2)?;
+
+		if input.len() != FP2_LEN {
+			Err(DispatchError::from("invalid input length"))?;
+		}
+
+		let fe = read_fp2(&input)?;
+		let point = WBMap::<g2::Config>::new()
+			.and_then(|map| map.map_to_curve(fe))
+			.map_err(|_| DispatchError::from("mapping to curve failed"))?;
+
+		Ok(encode_g2(point.clear_cofactor()))
+	}
+}
+
+/// Decode a base field element. `input` must be exactly [`FP_LEN`] bytes.
+fn read_fp(input: &[u8]) -> Result<Fq, Error> {
+	if input[..FP_PADDING].iter().any(|b| *b != 0) {
+		Err(DispatchError::from("invalid field element"))?;
+	}
+
+	let mut limbs = [0u64; 6];
+	for (limb, chunk) in limbs.iter_mut().rev().zip(input[FP_PADDING..].chunks_exact(8)) {
+		*limb = u64::from_be_bytes(chunk.try_into().expect("chunk is 8 bytes; qed"));
+	}
+
+	// fails if the value is not smaller than the modulus
+	Fq::from_bigint(BigInteger384::new(limbs))
+		.ok_or_else(|| DispatchError::from("invalid field element").into())
+}
+
+/// Decode an element of the quadratic extension field. `input` must be exactly [`FP2_LEN`] bytes.
+fn read_fp2(input: &[u8]) -> Result<Fq2, Error> {
+	let c0 = read_fp(&input[..FP_LEN])?;
+	let c1 = read_fp(&input[FP_LEN..])?;
+	Ok(Fq2::new(c0, c1))
+}
+
+/// Decode a scalar. Values which are not smaller than the group order are reduced.
+fn read_scalar(input: &[u8]) -> Fr {
+	Fr::from_be_bytes_mod_order(input)
+}
+
+/// Decode a G1 point. `input` must be exactly [`G1_LEN`] bytes.
+fn read_g1(input: &[u8], subgroup_check: bool) -> Result<G1Affine, Error> {
+	let x = read_fp(&input[..FP_LEN])?;
+	let y = read_fp(&input[FP_LEN..])?;
+	let point = if x.is_zero() && y.is_zero() {
+		G1Affine::identity()
+	} else {
+		G1Affine::new_unchecked(x, y)
+	};
+	check_point(point, subgroup_check)
+}
+
+/// Decode a G2 point. `input` must be exactly [`G2_LEN`] bytes.
+fn read_g2(input: &[u8], subgroup_check: bool) -> Result<G2Affine, Error> {
+	let x = read_fp2(&input[..FP2_LEN])?;
+	let y = read_fp2(&input[FP2_LEN..])?;
+	let point = if x.is_zero() && y.is_zero() {
+		G2Affine::identity()
+	} else {
+		G2Affine::new_unchecked(x, y)
+	};
+	check_point(point, subgroup_check)
+}
+
+/// Make sure that `point` is on the curve and optionally in the prime order subgroup.
+fn check_point<P: SWCurveConfig>(
+	point: Affine<P>,
+	subgroup_check: bool,
+) -> Result<Affine<P>, Error> {
+	if !point.is_on_curve() {
+		Err(DispatchError::from("point is not on curve"))?;
+	}
+	if subgroup_check && !point.is_in_correct_subgroup_assuming_on_curve() {
+		Err(DispatchError::from("point is not in the correct subgroup"))?;
+	}
+	Ok(point)
+}
+
+/// Encode a base field element into `out`, which must be exactly [`FP_LEN`] bytes.
+fn write_fp(fe: &Fq, out: &mut [u8]) {
+	for (limb, chunk) in fe.into_bigint().0.iter().rev().zip(out[FP_PADDING..].chunks_exact_mut(8))
+	{
+		chunk.copy_from_slice(&limb.to_be_bytes());
+	}
+}
+
+fn encode_g1(point: G1Affine) -> Vec<u8> {
+	let mut buf = [0u8; G1_LEN];
+	if let Some((x, y)) = point.xy() {
+		write_fp(x, &mut buf[..FP_LEN]);
+		write_fp(y, &mut buf[FP_LEN..]);
+	}
+	buf.to_vec()
+}
+
+fn encode_g2(point: G2Affine) -> Vec<u8> {
+	let mut buf = [0u8; G2_LEN];
+	if let Some((x, y)) = point.xy() {
+		write_fp(&x.c0, &mut buf[..FP_LEN]);
+		write_fp(&x.c1, &mut buf[FP_LEN..FP2_LEN]);
+		write_fp(&y.c0, &mut buf[FP2_LEN..FP2_LEN + FP_LEN]);
+		write_fp(&y.c1, &mut buf[FP2_LEN + FP_LEN..]);
+	}
+	buf.to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		precompiles::tests::{run_failure_test_vectors, run_test_vectors},
+		tests::Test,
+	};
+
+	#[test]
+	fn test_bls12381_g1_add() {
+		run_test_vectors::<Bls12381G1Add<Test>>(include_str!("./testdata/b-bls12381g1add.json"));
+		run_failure_test_vectors::<Bls12381G1Add<Test>>(include_str!(
+			"./testdata/b-bls12381g1add-failure.json"
+		));
+	}
+
+	#[test]
+	fn test_bls12381_g1_msm() {
+		run_test_vectors::<Bls12381G1Msm<Test>>(include_str!("./testdata/c-bls12381g1msm.json"));
+		run_failure_test_vectors::<Bls12381G1Msm<Test>>(include_str!(
+			"./testdata/c-bls12381g1msm-failure.json"
+		));
+	}
+
+	#[test]
+	fn test_bls12381_g2_add() {
+		run_test_vectors::<Bls12381G2Add<Test>>(include_str!("./testdata/d-bls12381g2add.json"));
+		run_failure_test_vectors::<Bls12381G2Add<Test>>(include_str!(
+			"./testdata/d-bls12381g2add-failure.json"
+		));
+	}
+
+	#[test]
+	fn test_bls12381_g2_msm() {
+		run_test_vectors::<Bls12381G2Msm<Test>>(include_str!("./testdata/e-bls12381g2msm.json"));
+		run_failure_test_vectors::<Bls12381G2Msm<Test>>(include_str!(
+			"./testdata/e-bls12381g2msm-failure.json"
+		));
+	}
+
+	#[test]
+	fn test_bls12381_pairing_check() {
+		run_test_vectors::<Bls12381PairingCheck<Test>>(include_str!(
+			"./testdata/f-bls12381pairing.json"
+		));
+		run_failure_test_vectors::<Bls12381PairingCheck<Test>>(include_str!(
+			"./testdata/f-bls12381pairing-failure.json"
+		));
+	}
+
+	#[test]
+	fn test_bls12381_map_fp_to_g1() {
+		run_test_vectors::<Bls12381MapFpToG1<Test>>(include_str!(
+			"./testdata/10-bls12381mapfp.json"
+		));
+		run_failure_test_vectors::<Bls12381MapFpToG1<Test>>(include_str!(
+			"./testdata/10-bls12381mapfp-failure.json"
+		));
+	}
+
+	#[test]
+	fn test_bls12381_map_fp2_to_g2() {
+		run_test_vectors::<Bls12381MapFp2ToG2<Test>>(include_str!(
+			"./testdata/11-bls12381mapfp2.json"
+		));
+		run_failure_test_vectors::<Bls12381MapFp2ToG2<Test>>(include_str!(
+			"./testdata/11-bls12381mapfp2-failure.json"
+		));
+	}
+}
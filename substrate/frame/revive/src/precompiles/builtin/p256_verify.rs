@@ -0,0 +1,92 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	precompiles::{BuiltinAddressMatcher, Error, Ext, PrimitivePrecompile},
+	vm::RuntimeCosts,
+	Config,
+};
+use alloc::vec::Vec;
+use core::{marker::PhantomData, num::NonZero};
+use frame_support::traits::Get;
+use p256::{
+	ecdsa::{signature::hazmat::PrehashVerifier, Signature, VerifyingKey},
+	EncodedPoint, FieldBytes,
+};
+
+/// Verification of secp256r1 (P-256) signatures as specified in
+/// [RIP-7212](https://github.com/ethereum/RIPs/blob/master/RIPS/rip-7212.md).
+///
+/// The input is `hash || r || s || x || y` with each value being 32 bytes big endian.
+/// Returns `1` as a 32 byte word if the signature is valid and no data otherwise.
+pub struct P256Verify<T>(PhantomData<T>);
+
+impl<T: Config> PrimitivePrecompile for P256Verify<T> {
+	type T = T;
+	const MATCHER: BuiltinAddressMatcher =
+		BuiltinAddressMatcher::Fixed(NonZero::new(0x100).unwrap());
+	const HAS_CONTRACT_INFO: bool = false;
+
+	fn is_enabled() -> bool {
+		T::EnableP256Verify::get()
+	}
+
+	fn call(
+		_address: &[u8; 20],
+		input: Vec<u8>,
+		env: &mut impl Ext<T = Self::T>,
+	) -> Result<Vec<u8>, Error> {
+		env.gas_meter_mut().charge(RuntimeCosts::P256Verify)?;
+
+		if input.len() != 160 || !verify(&input) {
+			return Ok(Vec::new());
+		}
+
+		let mut data = [0u8; 32];
+		data[31] = 1;
+		Ok(data.to_vec())
+	}
+}
+
+/// Verify the signature contained in `input`, which must be exactly 160 bytes.
+fn verify(input: &[u8]) -> bool {
+	let hash = &input[0..32];
+	let r = FieldBytes::clone_from_slice(&input[32..64]);
+	let s = FieldBytes::clone_from_slice(&input[64..96]);
+	let x = FieldBytes::from_slice(&input[96..128]);
+	let y = FieldBytes::from_slice(&input[128..160]);
+
+	// rejects `r` or `s` being zero or not smaller than the curve order
+	let Ok(signature) = Signature::from_scalars(r, s) else { return false };
+
+	// rejects the point at infinity and points which are not on the curve
+	let point = EncodedPoint::from_affine_coordinates(x, y, false);
+	let Ok(key) = VerifyingKey::from_encoded_point(&point) else { return false };
+
+	key.verify_prehash(hash, &signature).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{precompiles::tests::run_test_vectors, tests::Test};
+
+	#[test]
+	fn test_p256_verify() {
+		run_test_vectors::<P256Verify<Test>>(include_str!("./testdata/100-p256Verify.json"));
+	}
+}
@@ -25,8 +25,8 @@ use crate::{
 	storage::meter::Diff,
 	tracing::if_tracing,
 	weights::WeightInfo,
-	BalanceOf, Config, ContractInfoOf, DeletionQueue, DeletionQueueCounter, Error, TrieId,
-	SENTINEL,
+	BalanceOf, CodeDelegationOf, Config, ContractInfoOf, DelegatedAccountInfoOf, DeletionQueue,
+	DeletionQueueCounter, Error, TrieId, SENTINEL,
 };
 use alloc::vec::Vec;
 use codec::{Decode, Encode, MaxEncodedLen};
@@ -106,6 +106,29 @@ impl<T: Config> ContractInfo<T> {
 		Ok(contract)
 	}
 
+	/// Load the info of the account at `address` from storage.
+	///
+	/// For an account delegating its code via EIP-7702 this is the info holding the storage of
+	/// the account, which is not a contract.
+	pub fn load(address: &H160) -> Option<Self> {
+		if <CodeDelegationOf<T>>::contains_key(address) {
+			<DelegatedAccountInfoOf<T>>::get(address)
+		} else {
+			<ContractInfoOf<T>>::get(address)
+		}
+	}
+
+	/// Write the info of the account at `address` to storage.
+	///
+	/// See [`Self::load`] for accounts delegating their code.
+	pub fn store(&self, address: &H160) {
+		if <CodeDelegationOf<T>>::contains_key(address) {
+			<DelegatedAccountInfoOf<T>>::insert(address, self);
+		} else {
+			<ContractInfoOf<T>>::insert(address, self);
+		}
+	}
+
 	/// Associated child trie unique id is built from the hash part of the trie id.
 	pub fn child_trie_info(&self) -> ChildInfo {
 		ChildInfo::new_default(self.trie_id.as_ref())
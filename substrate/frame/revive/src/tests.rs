@@ -3855,6 +3855,330 @@ fn recovery_works() {
 	});
 }
 
+#[test]
+fn dry_run_with_overrides_works() {
+	use crate::evm::*;
+	use alloc::collections::BTreeMap;
+
+	let (code, _) = compile_module("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		<Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		<Test as Config>::Currency::set_balance(&BOB, 0);
+
+		let tx = GenericTransaction {
+			from: Some(BOB_ADDR),
+			input: code.into(),
+			gas: Some(1u32.into()),
+			..Default::default()
+		};
+
+		// bob has no money: fail
+		assert!(Pallet::<Test>::dry_run_eth_transact_with_overrides(
+			tx.clone(),
+			Default::default(),
+			Weight::MAX,
+			|_, _| 0u64,
+		)
+		.is_err());
+
+		// bob can deploy once his balance is overridden
+		let balance_override = AccountOverride {
+			balance: Some(Pallet::<Test>::evm_balance(&ALICE_ADDR)),
+			..Default::default()
+		};
+		let overrides = DryRunOverrides {
+			state: BTreeMap::from([(BOB_ADDR, balance_override)]),
+			..Default::default()
+		};
+		assert_ok!(Pallet::<Test>::dry_run_eth_transact_with_overrides(
+			tx.clone(),
+			overrides,
+			Weight::MAX,
+			|_, _| 0u64,
+		));
+
+		// state and stateDiff are mutually exclusive
+		let storage_override = AccountOverride {
+			state: Some(Default::default()),
+			state_diff: Some(Default::default()),
+			..Default::default()
+		};
+		let overrides = DryRunOverrides {
+			state: BTreeMap::from([(BOB_ADDR, storage_override)]),
+			..Default::default()
+		};
+		assert_err!(
+			Pallet::<Test>::dry_run_eth_transact_with_overrides(
+				tx,
+				overrides,
+				Weight::MAX,
+				|_, _| 0u64,
+			),
+			EthTransactError::Message(format!(
+				"Account {BOB_ADDR:?} has both 'state' and 'stateDiff' overrides"
+			))
+		);
+	});
+}
+
+#[test]
+fn dry_run_with_code_storage_and_block_overrides_works() {
+	use crate::evm::*;
+	use alloc::collections::BTreeMap;
+
+	let (base_fee_code, _) = compile_module("base_fee").unwrap();
+	let (dummy_code, _) = compile_module("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		<Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(dummy_code)).build_and_unwrap_contract();
+		// The overrides are written to the storage of the test externalities, which outlive the
+		// dry runs.
+		let dry_run = |tx: GenericTransaction, overrides: DryRunOverrides| {
+			Pallet::<Test>::dry_run_eth_transact_with_overrides(
+				tx,
+				overrides,
+				Weight::MAX,
+				|_, _| 0u64,
+			)
+		};
+
+		// a contract returning the base fee is deployed by the code override
+		let fake = H160::repeat_byte(0x42);
+		let call =
+			GenericTransaction { from: Some(ALICE_ADDR), to: Some(fake), ..Default::default() };
+		let code_override =
+			AccountOverride { code: Some(Bytes(base_fee_code)), ..Default::default() };
+		let overrides = DryRunOverrides {
+			state: BTreeMap::from([(fake, code_override)]),
+			block: BlockOverrides {
+				number: Some(42u32.into()),
+				base_fee_per_gas: Some(1_000u32.into()),
+				..Default::default()
+			},
+		};
+		let result = dry_run(call.clone(), overrides).unwrap();
+		assert_eq!(U256::from_little_endian(&result.data), U256::from(1_000));
+		assert_eq!(System::block_number(), 42);
+		let result = dry_run(call, Default::default()).unwrap();
+		assert_eq!(U256::from_little_endian(&result.data), U256::zero());
+
+		// stateDiff patches the given slots, state replaces the whole storage
+		let slot = |n: u64| H256::from_low_u64_be(n);
+		let storage = |n: u64| Pallet::<Test>::get_storage(addr, slot(n).0).unwrap();
+		let storage_override = |state, state_diff| DryRunOverrides {
+			state: BTreeMap::from([(
+				addr,
+				AccountOverride { state, state_diff, ..Default::default() },
+			)]),
+			..Default::default()
+		};
+		let call =
+			GenericTransaction { from: Some(ALICE_ADDR), to: Some(addr), ..Default::default() };
+		let state_diff = |n: u64| Some(BTreeMap::from([(slot(n), slot(n))]));
+		assert_ok!(dry_run(call.clone(), storage_override(None, state_diff(1))));
+		assert_ok!(dry_run(call.clone(), storage_override(None, state_diff(2))));
+		assert_eq!(storage(1), Some(slot(1).0.to_vec()));
+		assert_eq!(storage(2), Some(slot(2).0.to_vec()));
+		assert_ok!(dry_run(call, storage_override(state_diff(3), None)));
+		assert_eq!(storage(1), None);
+		assert_eq!(storage(2), None);
+		assert_eq!(storage(3), Some(slot(3).0.to_vec()));
+	});
+}
+
+#[test]
+fn simulate_eth_transacts_works() {
+	use crate::evm::*;
+
+	let (code, _) = compile_module("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		<Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		<Test as Config>::Currency::set_balance(&BOB, 1_000);
+
+		let value = Pallet::<Test>::convert_native_to_evm(500);
+		let transfer = GenericTransaction {
+			from: Some(ALICE_ADDR),
+			to: Some(BOB_ADDR),
+			value: Some(value),
+			..Default::default()
+		};
+		let deploy =
+			GenericTransaction { from: Some(BOB_ADDR), input: code.into(), ..Default::default() };
+		let payload = SimulatePayload {
+			block_state_calls: vec![
+				SimulateBlock { calls: vec![transfer], ..Default::default() },
+				SimulateBlock { calls: vec![deploy], ..Default::default() },
+			],
+			trace_transfers: true,
+			validation: false,
+		};
+
+		let number = U256::from(System::block_number());
+		let blocks =
+			Pallet::<Test>::simulate_eth_transacts(payload, Weight::MAX, |_, _| 0u64).unwrap();
+
+		assert_eq!(blocks.len(), 2);
+		assert_eq!(blocks[0].number, number + 1);
+		assert_eq!(blocks[1].number, number + 2);
+		assert_eq!(blocks[1].timestamp, blocks[0].timestamp + SimulatePayload::BLOCK_TIME);
+
+		// the transfer is reported as a log
+		let call = &blocks[0].calls[0];
+		assert_eq!(call.status, U256::one());
+		assert_eq!(
+			call.logs,
+			vec![Log {
+				address: TRANSFER_LOG_ADDRESS,
+				topics: vec![TRANSFER_LOG_TOPIC, ALICE_ADDR.into(), BOB_ADDR.into()],
+				data: Some(Bytes(value.to_big_endian().to_vec())),
+				block_number: number + 1,
+				..Default::default()
+			}]
+		);
+
+		// the deployment is executed in the next block
+		let call = &blocks[1].calls[0];
+		assert_eq!(call.status, U256::one());
+		assert_eq!(call.error, None);
+		assert!(call.logs.is_empty());
+	});
+}
+
+#[test]
+fn simulate_eth_transacts_validates_calls() {
+	use crate::evm::*;
+
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		<Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		<Test as Config>::Currency::set_balance(&BOB, 1_000);
+
+		let block_gas_limit = Pallet::<Test>::evm_block_gas_limit();
+		let calls = vec![
+			GenericTransaction {
+				from: Some(ALICE_ADDR),
+				to: Some(BOB_ADDR),
+				nonce: Some(1.into()),
+				..Default::default()
+			},
+			GenericTransaction {
+				from: Some(BOB_ADDR),
+				to: Some(ALICE_ADDR),
+				value: Some(Pallet::<Test>::convert_native_to_evm(1_000_000)),
+				..Default::default()
+			},
+			GenericTransaction {
+				from: Some(ALICE_ADDR),
+				to: Some(BOB_ADDR),
+				gas: Some(block_gas_limit + 1),
+				..Default::default()
+			},
+			GenericTransaction {
+				from: Some(ALICE_ADDR),
+				to: Some(BOB_ADDR),
+				gas_price: Some(U256::zero()),
+				..Default::default()
+			},
+		];
+		let payload = SimulatePayload {
+			block_state_calls: vec![SimulateBlock { calls, ..Default::default() }],
+			trace_transfers: false,
+			validation: true,
+		};
+
+		let blocks =
+			Pallet::<Test>::simulate_eth_transacts(payload, Weight::MAX, |_, _| 0u64).unwrap();
+		let codes: Vec<_> =
+			blocks[0].calls.iter().map(|call| call.error.as_ref().map(|e| e.code)).collect();
+		assert_eq!(
+			codes,
+			vec![
+				Some(SimulateCallError::NONCE_TOO_HIGH),
+				Some(SimulateCallError::INSUFFICIENT_FUNDS),
+				Some(SimulateCallError::BLOCK_GAS_LIMIT_REACHED),
+				Some(SimulateCallError::FAILED),
+			]
+		);
+	});
+}
+
+#[test]
+fn simulate_eth_transacts_requires_ordered_blocks() {
+	use crate::evm::*;
+
+	ExtBuilder::default().build().execute_with(|| {
+		let block = |number: u64, time: Option<u64>| SimulateBlock {
+			block_overrides: Some(BlockOverrides {
+				number: Some(number.into()),
+				time: time.map(Into::into),
+				..Default::default()
+			}),
+			..Default::default()
+		};
+
+		let payload = SimulatePayload {
+			block_state_calls: vec![block(10, None), block(10, None)],
+			..Default::default()
+		};
+		assert!(matches!(
+			Pallet::<Test>::simulate_eth_transacts(payload, Weight::MAX, |_, _| 0u64),
+			Err(EthTransactError::Message(message)) if message.starts_with("block numbers")
+		));
+
+		let payload = SimulatePayload {
+			block_state_calls: vec![block(10, Some(100)), block(11, Some(100))],
+			..Default::default()
+		};
+		assert!(matches!(
+			Pallet::<Test>::simulate_eth_transacts(payload, Weight::MAX, |_, _| 0u64),
+			Err(EthTransactError::Message(message)) if message.starts_with("block timestamps")
+		));
+
+		// Blocks skipped by a number override are accounted for in the timestamp.
+		let payload = SimulatePayload {
+			block_state_calls: vec![block(10, None), block(13, None)],
+			..Default::default()
+		};
+		let blocks =
+			Pallet::<Test>::simulate_eth_transacts(payload, Weight::MAX, |_, _| 0u64).unwrap();
+		assert_eq!(blocks[1].timestamp, blocks[0].timestamp + 3 * SimulatePayload::BLOCK_TIME);
+	});
+}
+
+#[test]
+fn simulate_eth_transacts_rejects_oversized_payloads() {
+	use crate::evm::*;
+
+	ExtBuilder::default().build().execute_with(|| {
+		let too_many_blocks = SimulatePayload {
+			block_state_calls: vec![Default::default(); SimulatePayload::MAX_BLOCKS + 1],
+			..Default::default()
+		};
+		assert!(matches!(
+			Pallet::<Test>::simulate_eth_transacts(too_many_blocks, Weight::MAX, |_, _| 0u64),
+			Err(EthTransactError::Message(_))
+		));
+
+		let too_many_calls = SimulatePayload {
+			block_state_calls: vec![
+				SimulateBlock {
+					calls: vec![Default::default(); SimulatePayload::MAX_CALLS / 2 + 1],
+					..Default::default()
+				};
+				2
+			],
+			..Default::default()
+		};
+		assert!(matches!(
+			Pallet::<Test>::simulate_eth_transacts(too_many_calls, Weight::MAX, |_, _| 0u64),
+			Err(EthTransactError::Message(_))
+		));
+	});
+}
+
 #[test]
 fn skip_transfer_works() {
 	let (code_caller, _) = compile_module("call").unwrap();
@@ -4395,6 +4719,104 @@ fn prestate_tracing_works() {
 	});
 }
 
+#[test]
+fn access_list_tracing_works() {
+	use crate::evm::*;
+
+	let (code, _code_hash) = compile_module("storage").unwrap();
+	ExtBuilder::default().existential_deposit(200).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 100_000_000);
+
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+
+		let mut tracer = AccessListTracer::new(|_: &H160| false);
+		trace(&mut tracer, || builder::bare_call(addr).build());
+
+		assert_eq!(
+			tracer.collect_trace(),
+			vec![AccessListEntry { address: addr, storage_keys: vec![H256([1u8; 32])] }]
+		);
+	});
+}
+
+#[test]
+fn access_list_tracer_skips_precompiles_and_records_delegate_calls() {
+	use crate::{evm::*, tracing::Tracing, ExecReturnValue};
+
+	let precompile = H160::from_low_u64_be(1);
+	let mut tracer = AccessListTracer::new(|addr: &H160| *addr == precompile);
+	let output = ExecReturnValue::default();
+
+	tracer.enter_child_span(ALICE_ADDR, BOB_ADDR, None, false, 0.into(), &[], Weight::zero());
+	tracer.enter_child_span(BOB_ADDR, precompile, None, false, 0.into(), &[], Weight::zero());
+	tracer.exit_child_span(&output, Weight::zero());
+	tracer.enter_child_span(
+		BOB_ADDR,
+		BOB_ADDR,
+		Some(CHARLIE_ADDR),
+		false,
+		0.into(),
+		&[],
+		Weight::zero(),
+	);
+	tracer.storage_write(&Key::Fix([2u8; 32]), None, None);
+	tracer.exit_child_span(&output, Weight::zero());
+	tracer.exit_child_span(&output, Weight::zero());
+
+	// The storage of the delegate call is accounted to the caller.
+	assert_eq!(
+		tracer.collect_trace(),
+		vec![
+			AccessListEntry { address: BOB_ADDR, storage_keys: vec![H256([2u8; 32])] },
+			AccessListEntry { address: CHARLIE_ADDR, storage_keys: vec![] },
+		]
+	);
+}
+
+#[test]
+fn struct_logger_tracing_works() {
+	use crate::evm::*;
+
+	let (code, _code_hash) = compile_module("storage").unwrap();
+	ExtBuilder::default().existential_deposit(200).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 100_000_000);
+
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+
+		let config = StructLoggerConfig { enable_stack: true, ..Default::default() };
+		let mut tracer = StructLogger::new(config, |w: Weight| U256::from(w.ref_time()));
+		trace(&mut tracer, || builder::bare_call(addr).build());
+		let trace = tracer.collect_trace();
+
+		assert!(!trace.failed);
+		assert!(!trace.struct_logs.is_empty());
+		assert!(trace.struct_logs.iter().all(|step| step.depth == 1 && step.memory.is_none()));
+		assert!(trace.struct_logs.iter().all(|step| step.stack.as_ref().unwrap().len() == 13));
+		assert!(trace.struct_logs.windows(2).all(|steps| steps[0].gas >= steps[1].gas &&
+			steps[0].gas_cost == steps[0].gas - steps[1].gas));
+		// The gas of the steps is reported in the unit of the gas mapper, not in engine gas.
+		assert!(trace.struct_logs[0].gas > GAS_LIMIT.ref_time() / 2);
+		assert!(trace.struct_logs.iter().any(|step| step
+			.storage
+			.as_ref()
+			.is_some_and(|storage| storage.contains_key(&H256([1u8; 32])))));
+
+		// The registers and the memory are only captured on request.
+		let mut tracer =
+			StructLogger::new(Default::default(), |w: Weight| U256::from(w.ref_time()));
+		crate::tracing::trace(&mut tracer, || builder::bare_call(addr).build());
+		let struct_logs = tracer.collect_trace().struct_logs;
+		assert!(struct_logs.iter().all(|step| step.stack.is_none() && step.memory.is_none()));
+
+		let config = StructLoggerConfig { limit: 3, ..Default::default() };
+		let mut tracer = StructLogger::new(config, |w: Weight| U256::from(w.ref_time()));
+		crate::tracing::trace(&mut tracer, || builder::bare_call(addr).build());
+		assert_eq!(tracer.collect_trace().struct_logs.len(), 3);
+	});
+}
+
 #[test]
 fn unknown_precompiles_revert() {
 	let (code, _code_hash) = compile_module("read_only_call").unwrap();
@@ -4671,3 +5093,82 @@ fn bump_nonce_once_works() {
 		assert_eq!(err, <Error<Test>>::DuplicateContract.into());
 	});
 }
+
+#[test]
+fn eip7702_delegation_works() {
+	use crate::{
+		evm::{Account, ApplyAuthorizations, AuthorizationList},
+		CodeDelegationOf, DelegatedAccountInfoOf,
+	};
+	use frame_support::dispatch::GetDispatchInfo;
+	use sp_runtime::traits::{DispatchTransaction, Dispatchable};
+	let (binary, _code_hash) = compile_module("store_call").unwrap();
+	let (revert_binary, _code_hash) = compile_module("ok_trap_revert").unwrap();
+
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let Contract { addr: target, .. } =
+			builder::bare_instantiate(Code::Upload(binary)).build_and_unwrap_contract();
+
+		let eoa = Account::default();
+		let eoa_account = eoa.substrate_account();
+		let _ = <Test as Config>::Currency::set_balance(&eoa_account, 1_000_000);
+		let chain_id = U256::from(<Test as Config>::ChainId::get());
+
+		// Dispatch the call the way an Ethereum transaction is, through the transaction extension
+		// applying the authorizations.
+		let call_with_authorizations = |data: Vec<u8>, authorization_list: AuthorizationList| {
+			let call = RuntimeCall::Contracts(crate::Call::call {
+				dest: eoa.address(),
+				value: 0,
+				gas_limit: GAS_LIMIT,
+				storage_deposit_limit: deposit_limit::<Test>(),
+				data,
+			});
+			let info = call.get_dispatch_info();
+			ApplyAuthorizations::<Test>::from(authorization_list)
+				.test_run(RuntimeOrigin::signed(ALICE), &call, &info, 0, 0, |origin| {
+					call.clone().dispatch(origin)
+				})
+				.unwrap()
+		};
+
+		// An authorization with a stale nonce is skipped without failing the call.
+		let stale = eoa.sign_authorization(chain_id, target, 1u32.into());
+		assert_ok!(call_with_authorizations(vec![], vec![stale]));
+		assert_eq!(CodeDelegationOf::<Test>::get(eoa.address()), None);
+		assert_eq!(System::account_nonce(&eoa_account), 0);
+
+		// A valid authorization delegates the code and the call executes it in the context of
+		// the EOA.
+		let auth = eoa.sign_authorization(chain_id, target, 0u32.into());
+		assert_ok!(call_with_authorizations(10u32.to_le_bytes().to_vec(), vec![auth]));
+		assert_eq!(CodeDelegationOf::<Test>::get(eoa.address()), Some(target));
+		assert_eq!(System::account_nonce(&eoa_account), 1);
+
+		let mut key = [0u8; 32];
+		key[0] = 1;
+		assert_eq!(Pallet::<Test>::get_storage(eoa.address(), key), Ok(Some(vec![0u8; 10])));
+		assert_eq!(Pallet::<Test>::get_storage(target, key), Ok(None));
+		// The storage of the EOA does not turn it into a contract.
+		assert!(!ContractInfoOf::<Test>::contains_key(&eoa.address()));
+		assert!(DelegatedAccountInfoOf::<Test>::contains_key(&eoa.address()));
+
+		// Delegating to the zero address clears the delegation.
+		let auth = eoa.sign_authorization(0u32.into(), H160::zero(), 1u32.into());
+		assert_ok!(call_with_authorizations(vec![], vec![auth]));
+		assert_eq!(CodeDelegationOf::<Test>::get(eoa.address()), None);
+		assert_eq!(System::account_nonce(&eoa_account), 2);
+
+		// The authorizations persist even if the call reverts.
+		let Contract { addr: reverting, .. } =
+			builder::bare_instantiate(Code::Upload(revert_binary)).build_and_unwrap_contract();
+		let auth = eoa.sign_authorization(chain_id, reverting, 2u32.into());
+		assert_err_ignore_postinfo!(
+			call_with_authorizations(vec![1], vec![auth]),
+			<Error<Test>>::ContractReverted,
+		);
+		assert_eq!(CodeDelegationOf::<Test>::get(eoa.address()), Some(reverting));
+		assert_eq!(System::account_nonce(&eoa_account), 3);
+	});
+}
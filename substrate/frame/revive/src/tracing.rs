@@ -46,11 +46,14 @@ pub trait Tracing {
 	fn watch_address(&mut self, _addr: &H160) {}
 
 	/// Called before a contract call is executed
+	///
+	/// For a delegate call, `delegate_call` holds the address of the executed code while `to` is
+	/// the contract whose storage is used.
 	fn enter_child_span(
 		&mut self,
 		_from: H160,
 		_to: H160,
-		_is_delegate_call: bool,
+		_delegate_call: Option<H160>,
 		_is_read_only: bool,
 		_value: U256,
 		_input: &[u8],
@@ -76,6 +79,28 @@ pub trait Tracing {
 	) {
 	}
 
+	/// Whether [`Self::enter_opcode`] should be called for every executed instruction.
+	///
+	/// Instruction level tracing slows down execution considerably, hence it is only enabled
+	/// for the tracers asking for it.
+	fn is_opcode_tracing_enabled(&self) -> bool {
+		false
+	}
+
+	/// Called before an instruction is executed.
+	///
+	/// The `stack` and `memory` closures are only evaluated on demand, since reading them is
+	/// expensive.
+	fn enter_opcode(
+		&mut self,
+		_pc: u64,
+		_opcode: &str,
+		_gas_left: Weight,
+		_stack: &dyn Fn() -> Vec<U256>,
+		_memory: &dyn Fn() -> Vec<u8>,
+	) {
+	}
+
 	/// Record a log event
 	fn log_event(&mut self, _event: H160, _topics: &[H256], _data: &[u8]) {}
 
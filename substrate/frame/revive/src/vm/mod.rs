@@ -33,11 +33,12 @@ use crate::{
 	gas::{GasMeter, Token},
 	limits,
 	storage::meter::Diff,
+	tracing::if_tracing,
 	weights::WeightInfo,
 	AccountIdOf, BadOrigin, BalanceOf, CodeInfoOf, CodeVec, Config, Error, ExecError, HoldReason,
 	PristineCode, Weight, LOG_TARGET,
 };
-use alloc::vec::Vec;
+use alloc::{collections::BTreeMap, format, vec::Vec};
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
 	dispatch::DispatchResult,
@@ -288,6 +289,8 @@ pub struct PreparedCall<'a, E: Ext> {
 	module: polkavm::Module,
 	instance: polkavm::RawInstance,
 	runtime: Runtime<'a, E, polkavm::RawInstance>,
+	/// The opcodes of the program by offset, only present when instructions are traced.
+	opcodes: Option<BTreeMap<u32, polkavm::program::Opcode>>,
 }
 
 impl<'a, E: Ext> PreparedCall<'a, E>
@@ -298,6 +301,9 @@ where
 	pub fn call(mut self) -> ExecResult {
 		let exec_result = loop {
 			let interrupt = self.instance.run();
+			if let (Ok(polkavm::InterruptKind::Step), Some(opcodes)) = (&interrupt, &self.opcodes) {
+				Self::trace_opcode(&self.instance, opcodes);
+			}
 			if let Some(exec_result) =
 				self.runtime.handle_interrupt(interrupt, &self.module, &mut self.instance)
 			{
@@ -308,6 +314,26 @@ where
 		exec_result
 	}
 
+	/// Report the instruction about to be executed to the tracer.
+	fn trace_opcode(
+		instance: &polkavm::RawInstance,
+		opcodes: &BTreeMap<u32, polkavm::program::Opcode>,
+	) {
+		let Some(pc) = instance.program_counter() else { return };
+		let opcode = opcodes.get(&pc.0).map(|opcode| format!("{opcode:?}")).unwrap_or_default();
+		let gas_left = GasMeter::<E::T>::weight_from_engine_fuel(instance.gas());
+		let stack = || polkavm::Reg::ALL.iter().map(|reg| U256::from(instance.reg(*reg))).collect();
+		// The used part of the guest stack is the closest equivalent to the EVM memory.
+		let memory = || {
+			let stack_pointer = instance.reg(polkavm::Reg::SP) as u32;
+			let stack_high = instance.module().memory_map().stack_address_high();
+			instance
+				.read_memory(stack_pointer, stack_high.saturating_sub(stack_pointer))
+				.unwrap_or_default()
+		};
+		if_tracing(|tracer| tracer.enter_opcode(pc.0.into(), &opcode, gas_left, &stack, &memory));
+	}
+
 	/// The guest memory address at which the aux data is located.
 	#[cfg(feature = "runtime-benchmarks")]
 	pub fn aux_data_base(&self) -> u32 {
@@ -358,13 +384,32 @@ impl<T: Config> ContractBlob<T> {
 				interpreter is available on all platforms; qed",
 		);
 
+		let mut opcode_tracing = false;
+		if_tracing(|tracer| opcode_tracing = tracer.is_opcode_tracing_enabled());
+
+		let code = self.code.into_inner();
+		let opcodes = if opcode_tracing {
+			let program = polkavm::ProgramBlob::parse(code.as_slice().into()).map_err(|err| {
+				log::debug!(target: LOG_TARGET, "failed to parse polkavm blob: {err:?}");
+				Error::<T>::CodeRejected
+			})?;
+			Some(
+				program
+					.instructions(polkavm::program::ISA64_V1)
+					.map(|inst| (inst.offset.0, inst.kind.opcode()))
+					.collect(),
+			)
+		} else {
+			None
+		};
+
 		let mut module_config = polkavm::ModuleConfig::new();
 		module_config.set_page_size(limits::PAGE_SIZE);
 		module_config.set_gas_metering(Some(polkavm::GasMeteringKind::Sync));
 		module_config.set_allow_sbrk(false);
 		module_config.set_aux_data_size(aux_data_size);
-		let module = polkavm::Module::new(&engine, &module_config, self.code.into_inner().into())
-			.map_err(|err| {
+		module_config.set_step_tracing(opcode_tracing);
+		let module = polkavm::Module::new(&engine, &module_config, code.into()).map_err(|err| {
 			log::debug!(target: LOG_TARGET, "failed to create polkavm module: {err:?}");
 			Error::<T>::CodeRejected
 		})?;
@@ -385,7 +430,7 @@ impl<T: Config> ContractBlob<T> {
 		instance.set_gas(gas_limit_polkavm);
 		instance.prepare_call_untyped(entry_program_counter, &[]);
 
-		Ok(PreparedCall { module, instance, runtime })
+		Ok(PreparedCall { module, instance, runtime, opcodes })
 	}
 }
 
@@ -384,6 +384,22 @@ pub enum RuntimeCosts {
 	Blake2F(u32),
 	/// Weight of calling `Modexp` precompile
 	Modexp(u64),
+	/// Weight of calling `P256Verify` precompile.
+	P256Verify,
+	/// Weight of calling `Bls12381G1Add` precompile.
+	Bls12381G1Add,
+	/// Weight of calling `Bls12381G1Msm` precompile for the given number of input pairs.
+	Bls12381G1Msm(u32),
+	/// Weight of calling `Bls12381G2Add` precompile.
+	Bls12381G2Add,
+	/// Weight of calling `Bls12381G2Msm` precompile for the given number of input pairs.
+	Bls12381G2Msm(u32),
+	/// Weight of calling `Bls12381PairingCheck` precompile for the given number of input pairs.
+	Bls12381PairingCheck(u32),
+	/// Weight of calling `Bls12381MapFpToG1` precompile.
+	Bls12381MapFpToG1,
+	/// Weight of calling `Bls12381MapFp2ToG2` precompile.
+	Bls12381MapFp2ToG2,
 }
 
 /// For functions that modify storage, benchmarks are performed with one item in the
@@ -518,6 +534,14 @@ impl<T: Config> Token<T> for RuntimeCosts {
 			Bn128Pairing(len) => T::WeightInfo::bn128_pairing(len),
 			Identity(len) => T::WeightInfo::identity(len),
 			Blake2F(rounds) => T::WeightInfo::blake2f(rounds),
+			P256Verify => T::WeightInfo::p256_verify(),
+			Bls12381G1Add => T::WeightInfo::bls12_381_g1_add(),
+			Bls12381G1Msm(n) => T::WeightInfo::bls12_381_g1_msm(n),
+			Bls12381G2Add => T::WeightInfo::bls12_381_g2_add(),
+			Bls12381G2Msm(n) => T::WeightInfo::bls12_381_g2_msm(n),
+			Bls12381PairingCheck(n) => T::WeightInfo::bls12_381_pairing_check(n),
+			Bls12381MapFpToG1 => T::WeightInfo::bls12_381_map_fp_to_g1(),
+			Bls12381MapFp2ToG2 => T::WeightInfo::bls12_381_map_fp2_to_g2(),
 			Modexp(gas) => {
 				use frame_support::weights::constants::WEIGHT_REF_TIME_PER_SECOND;
 				/// Current approximation of the gas/s consumption considering
@@ -1709,7 +1733,7 @@ pub mod env {
 		Ok(self.write_fixed_sandbox_output(
 			memory,
 			out_ptr,
-			&U256::zero().to_little_endian(),
+			&crate::exec::base_fee().to_little_endian(),
 			false,
 			already_charged,
 		)?)
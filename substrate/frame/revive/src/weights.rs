@@ -154,6 +154,14 @@ pub trait WeightInfo {
 	fn bn128_mul() -> Weight;
 	fn bn128_pairing(n: u32, ) -> Weight;
 	fn blake2f(n: u32, ) -> Weight;
+	fn p256_verify() -> Weight;
+	fn bls12_381_g1_add() -> Weight;
+	fn bls12_381_g1_msm(n: u32, ) -> Weight;
+	fn bls12_381_g2_add() -> Weight;
+	fn bls12_381_g2_msm(n: u32, ) -> Weight;
+	fn bls12_381_pairing_check(n: u32, ) -> Weight;
+	fn bls12_381_map_fp_to_g1() -> Weight;
+	fn bls12_381_map_fp2_to_g2() -> Weight;
 	fn seal_ecdsa_to_eth_address() -> Weight;
 	fn seal_set_code_hash() -> Weight;
 	fn instr(r: u32, ) -> Weight;
@@ -1108,6 +1116,71 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			// Standard Error: 6
 			.saturating_add(Weight::from_parts(22_679, 0).saturating_mul(n.into()))
 	}
+	fn p256_verify() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 688_412_000 picoseconds.
+		Weight::from_parts(694_936_000, 0)
+	}
+	fn bls12_381_g1_add() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 22_906_000 picoseconds.
+		Weight::from_parts(23_517_000, 0)
+	}
+	/// The range of component `n` is `[1, 20]`.
+	fn bls12_381_g1_msm(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_184_317_000 picoseconds.
+		Weight::from_parts(257_834_512, 0)
+			// Standard Error: 9_813_224
+			.saturating_add(Weight::from_parts(902_617_408, 0).saturating_mul(n.into()))
+	}
+	fn bls12_381_g2_add() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 61_452_000 picoseconds.
+		Weight::from_parts(62_880_000, 0)
+	}
+	/// The range of component `n` is `[1, 20]`.
+	fn bls12_381_g2_msm(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 3_471_885_000 picoseconds.
+		Weight::from_parts(662_914_827, 0)
+			// Standard Error: 27_440_318
+			.saturating_add(Weight::from_parts(2_745_209_553, 0).saturating_mul(n.into()))
+	}
+	/// The range of component `n` is `[1, 20]`.
+	fn bls12_381_pairing_check(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 16_388_150_000 picoseconds.
+		Weight::from_parts(9_862_473_112, 0)
+			// Standard Error: 41_338_806
+			.saturating_add(Weight::from_parts(6_518_731_460, 0).saturating_mul(n.into()))
+	}
+	fn bls12_381_map_fp_to_g1() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 651_047_000 picoseconds.
+		Weight::from_parts(655_311_000, 0)
+	}
+	fn bls12_381_map_fp2_to_g2() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 2_294_563_000 picoseconds.
+		Weight::from_parts(2_301_447_000, 0)
+	}
 	fn seal_ecdsa_to_eth_address() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
@@ -2095,6 +2168,71 @@ impl WeightInfo for () {
 			// Standard Error: 6
 			.saturating_add(Weight::from_parts(22_679, 0).saturating_mul(n.into()))
 	}
+	fn p256_verify() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 688_412_000 picoseconds.
+		Weight::from_parts(694_936_000, 0)
+	}
+	fn bls12_381_g1_add() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 22_906_000 picoseconds.
+		Weight::from_parts(23_517_000, 0)
+	}
+	/// The range of component `n` is `[1, 20]`.
+	fn bls12_381_g1_msm(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_184_317_000 picoseconds.
+		Weight::from_parts(257_834_512, 0)
+			// Standard Error: 9_813_224
+			.saturating_add(Weight::from_parts(902_617_408, 0).saturating_mul(n.into()))
+	}
+	fn bls12_381_g2_add() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 61_452_000 picoseconds.
+		Weight::from_parts(62_880_000, 0)
+	}
+	/// The range of component `n` is `[1, 20]`.
+	fn bls12_381_g2_msm(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 3_471_885_000 picoseconds.
+		Weight::from_parts(662_914_827, 0)
+			// Standard Error: 27_440_318
+			.saturating_add(Weight::from_parts(2_745_209_553, 0).saturating_mul(n.into()))
+	}
+	/// The range of component `n` is `[1, 20]`.
+	fn bls12_381_pairing_check(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 16_388_150_000 picoseconds.
+		Weight::from_parts(9_862_473_112, 0)
+			// Standard Error: 41_338_806
+			.saturating_add(Weight::from_parts(6_518_731_460, 0).saturating_mul(n.into()))
+	}
+	fn bls12_381_map_fp_to_g1() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 651_047_000 picoseconds.
+		Weight::from_parts(655_311_000, 0)
+	}
+	fn bls12_381_map_fp2_to_g2() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 2_294_563_000 picoseconds.
+		Weight::from_parts(2_301_447_000, 0)
+	}
 	fn seal_ecdsa_to_eth_address() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
@@ -0,0 +1,41 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for the Statement Pallet
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+
+#[benchmarks(where <T as frame_system::Config>::AccountId: From<sp_statement_store::AccountId>)]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn set_store_limits() {
+		let limits = Some(StoreLimits { max_count: u32::MAX, max_size: u32::MAX });
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, limits);
+
+		assert_eq!(Limits::<T>::get(), limits);
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -28,6 +28,10 @@
 //! `max_count`: Maximum number of statements allowed for the author (signer) of this statement.
 //! `max_size`: Maximum total size of statements allowed for the author (signer) of this statement.
 //!
+//! The limits of the whole statement store may be configured on chain with
+//! [`Pallet::set_store_limits`]. They are exposed to the nodes with [`Pallet::store_limits`], which
+//! override the limits configured locally on the nodes.
+//!
 //! This pallet also contains an offchain worker that turns on-chain statement events into
 //! statements. These statements are placed in the store and propagated over the network.
 
@@ -40,18 +44,21 @@ use frame_support::{
 };
 use frame_system::pallet_prelude::*;
 use sp_statement_store::{
-	runtime_api::{InvalidStatement, StatementSource, ValidStatement},
+	runtime_api::{InvalidStatement, StatementSource, StoreLimits, ValidStatement},
 	Proof, SignatureVerificationResult, Statement,
 };
 
+mod benchmarking;
 #[cfg(test)]
 // We do not declare all features used by `construct_runtime`
 #[allow(unexpected_cfgs)]
 mod mock;
 #[cfg(test)]
 mod tests;
+pub mod weights;
 
 pub use pallet::*;
+pub use weights::WeightInfo;
 
 const LOG_TARGET: &str = "runtime::statement";
 
@@ -92,11 +99,17 @@ pub mod pallet {
 		/// Maximum data bytes allowed per account.
 		#[pallet::constant]
 		type MaxAllowedBytes: Get<u32>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(core::marker::PhantomData<T>);
 
+	/// Limits of the whole statement store. When not set, the nodes apply their own limits.
+	#[pallet::storage]
+	pub type Limits<T: Config> = StorageValue<_, StoreLimits, OptionQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config>
@@ -105,6 +118,47 @@ pub mod pallet {
 	{
 		/// A new statement is submitted
 		NewStatement { account: T::AccountId, statement: Statement },
+		/// The limits of the statement store were changed.
+		StoreLimitsSet { limits: Option<StoreLimits> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The limits of the statement store are below the limits allowed per account.
+		InvalidStoreLimits,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T>
+	where
+		<T as frame_system::Config>::AccountId: From<sp_statement_store::AccountId>,
+	{
+		/// Set the limits of the whole statement store, or clear them with `None` to let the nodes
+		/// apply their own limits.
+		///
+		/// The store must at least hold the statements allowed for a single account, that is
+		/// [`Config::MaxAllowedStatements`] and [`Config::MaxAllowedBytes`], and can't be empty.
+		///
+		/// Dispatch origin must be root.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_store_limits())]
+		pub fn set_store_limits(
+			origin: OriginFor<T>,
+			limits: Option<StoreLimits>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			if let Some(StoreLimits { max_count, max_size }) = limits {
+				ensure!(max_count > 0 && max_size > 0, Error::<T>::InvalidStoreLimits);
+				ensure!(
+					max_count >= T::MaxAllowedStatements::get() &&
+						max_size >= T::MaxAllowedBytes::get(),
+					Error::<T>::InvalidStoreLimits
+				);
+			}
+			Limits::<T>::set(limits);
+			Self::deposit_event(Event::StoreLimitsSet { limits });
+			Ok(())
+		}
 	}
 
 	#[pallet::hooks]
@@ -199,6 +253,12 @@ where
 		Ok(ValidStatement { max_count, max_size })
 	}
 
+	/// Limits of the whole statement store. This is supposed to be called by the statement store
+	/// on the host side.
+	pub fn store_limits() -> Option<StoreLimits> {
+		Limits::<T>::get()
+	}
+
 	/// Submit a statement event. The statement will be picked up by the offchain worker and
 	/// broadcast to the network.
 	pub fn submit_statement(account: T::AccountId, statement: Statement) {
@@ -70,6 +70,7 @@ impl Config for Test {
 	type MaxAllowedStatements = ConstU32<MAX_ALLOWED_STATEMENTS>;
 	type MinAllowedBytes = ConstU32<MIN_ALLOWED_BYTES>;
 	type MaxAllowedBytes = ConstU32<MAX_ALLOWED_BYTES>;
+	type WeightInfo = ();
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
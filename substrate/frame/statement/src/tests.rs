@@ -21,6 +21,7 @@
 
 use super::*;
 use crate::mock::*;
+use frame_support::{assert_noop, assert_ok};
 use sp_core::Pair;
 use sp_runtime::AccountId32;
 use sp_statement_store::{
@@ -157,3 +158,47 @@ fn validate_no_event_fails() {
 		assert_eq!(Err(InvalidStatement::BadProof), result);
 	});
 }
+
+#[test]
+fn store_limits_are_set_by_root() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let limits = StoreLimits { max_count: 100, max_size: 1024 * 1024 };
+		assert_eq!(Pallet::<Test>::store_limits(), None);
+
+		let account: AccountId32 =
+			sp_core::sr25519::Pair::from_string("//Alice", None).unwrap().public().into();
+		assert_noop!(
+			Pallet::<Test>::set_store_limits(RuntimeOrigin::signed(account), Some(limits)),
+			DispatchError::BadOrigin,
+		);
+
+		assert_ok!(Pallet::<Test>::set_store_limits(RuntimeOrigin::root(), Some(limits)));
+		assert_eq!(Pallet::<Test>::store_limits(), Some(limits));
+		System::assert_last_event(Event::StoreLimitsSet { limits: Some(limits) }.into());
+
+		assert_ok!(Pallet::<Test>::set_store_limits(RuntimeOrigin::root(), None));
+		assert_eq!(Pallet::<Test>::store_limits(), None);
+	});
+}
+
+#[test]
+fn store_limits_must_hold_an_account() {
+	new_test_ext().execute_with(|| {
+		for limits in [
+			StoreLimits { max_count: 0, max_size: 1024 * 1024 },
+			StoreLimits { max_count: 100, max_size: 0 },
+			StoreLimits { max_count: MAX_ALLOWED_STATEMENTS - 1, max_size: 1024 * 1024 },
+			StoreLimits { max_count: 100, max_size: MAX_ALLOWED_BYTES - 1 },
+		] {
+			assert_noop!(
+				Pallet::<Test>::set_store_limits(RuntimeOrigin::root(), Some(limits)),
+				Error::<Test>::InvalidStoreLimits,
+			);
+		}
+
+		let limits = StoreLimits { max_count: MAX_ALLOWED_STATEMENTS, max_size: MAX_ALLOWED_BYTES };
+		assert_ok!(Pallet::<Test>::set_store_limits(RuntimeOrigin::root(), Some(limits)));
+		assert_eq!(Pallet::<Test>::store_limits(), Some(limits));
+	});
+}
@@ -248,6 +248,15 @@ pub trait Backend<Block: BlockT>:
 
 	fn block_indexed_body(&self, hash: Block::Hash) -> Result<Option<Vec<Vec<u8>>>>;
 
+	/// Get the blocks containing the extrinsic with the given hash, together with the position of
+	/// the extrinsic in their body.
+	///
+	/// Blocks of the finalized chain and unfinalized blocks are returned, blocks of abandoned forks
+	/// are not. Returns `None` if the backend doesn't maintain an index of the extrinsics.
+	fn extrinsic_locations(&self, _hash: Block::Hash) -> Result<Option<Vec<(Block::Hash, u32)>>> {
+		Ok(None)
+	}
+
 	/// Returns all leaves that will be displaced after the block finalization.
 	fn displaced_leaves_after_finalizing(
 		&self,
@@ -19,7 +19,7 @@
 
 use crate::{Hash, Statement, Topic};
 use alloc::vec::Vec;
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 use sp_runtime::RuntimeDebug;
 use sp_runtime_interface::{
@@ -42,6 +42,15 @@ pub struct ValidStatement {
 	pub max_size: u32,
 }
 
+/// Limits of the whole statement store, as configured on chain.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct StoreLimits {
+	/// Max number of statements in the store.
+	pub max_count: u32,
+	/// Max total data size of the statements in the store.
+	pub max_size: u32,
+}
+
 /// An reason for an invalid statement.
 #[derive(Clone, PartialEq, Eq, Encode, Decode, Copy, RuntimeDebug, TypeInfo)]
 pub enum InvalidStatement {
@@ -85,6 +94,11 @@ sp_api::decl_runtime_apis! {
 			source: StatementSource,
 			statement: Statement,
 		) -> Result<ValidStatement, InvalidStatement>;
+
+		/// Limits of the whole store, if configured on chain. When `None`, the store applies the
+		/// limits configured on the node.
+		#[api_version(2)]
+		fn store_limits() -> Option<StoreLimits>;
 	}
 }
 
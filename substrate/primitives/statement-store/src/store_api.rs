@@ -16,7 +16,8 @@
 // limitations under the License.
 
 pub use crate::runtime_api::StatementSource;
-use crate::{Hash, Statement, Topic};
+use crate::{DecryptionKey, Hash, Statement, Topic};
+use futures::stream::BoxStream;
 
 /// Statement store error.
 #[derive(Debug, Eq, PartialEq, thiserror::Error)]
@@ -30,6 +31,9 @@ pub enum Error {
 	/// Error making runtime call.
 	#[error("Error calling into the runtime")]
 	Runtime,
+	/// The subscription can't be created.
+	#[error("Subscription refused: {0}")]
+	Subscription(String),
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -61,6 +65,29 @@ pub enum SubmitResult {
 /// Result type for `Error`
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Filter of the statements of a subscription.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatementFilter {
+	/// Topics which must all be included in the statements.
+	pub match_all_topics: Vec<Topic>,
+	/// Decryption key of the statements, statements with any decryption key or without one
+	/// match if `None`.
+	pub decryption_key: Option<DecryptionKey>,
+}
+
+impl StatementFilter {
+	/// Returns whether `statement` matches the filter.
+	pub fn matches(&self, statement: &Statement) -> bool {
+		self.decryption_key.map_or(true, |key| statement.decryption_key() == Some(key)) &&
+			self.match_all_topics
+				.iter()
+				.all(|topic| (0..crate::MAX_TOPICS).any(|i| statement.topic(i) == Some(*topic)))
+	}
+}
+
+/// Stream of the statements of a subscription.
+pub type StatementStream = BoxStream<'static, Statement>;
+
 /// Statement store API.
 pub trait StatementStore: Send + Sync {
 	/// Return all statements.
@@ -104,4 +131,10 @@ pub trait StatementStore: Send + Sync {
 
 	/// Remove a statement from the store.
 	fn remove(&self, hash: &Hash) -> Result<()>;
+
+	/// Subscribe to the new statements matching `filter`.
+	///
+	/// The stream yields the statements accepted by the store after the subscription. It ends if
+	/// the subscriber doesn't keep up with the new statements.
+	fn subscribe(&self, filter: StatementFilter) -> Result<StatementStream>;
 }
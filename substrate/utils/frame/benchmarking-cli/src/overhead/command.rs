@@ -489,6 +489,7 @@ impl OverheadCmd {
 			trie_cache_maximum_size: self.trie_cache_maximum_size()?,
 			state_pruning: None,
 			blocks_pruning: BlocksPruning::KeepAll,
+			extrinsic_index: false,
 			source: database_source,
 			metrics_registry: None,
 		})?;
@@ -245,6 +245,7 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 				block_import: client.clone(),
 				env: proposer,
 				client,
+				backend: None,
 				pool: transaction_pool,
 				select_chain,
 				commands_stream: Box::pin(commands_stream),